@@ -0,0 +1,43 @@
+#![allow(dead_code)]
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Big {
+    name: String,
+    items: Vec<Item>,
+}
+
+#[derive(IBuilder)]
+struct Item {
+    name: String,
+    value: i32,
+}
+
+/// Make a builder of `Big` with `len` complete items, leaving the user in the main menu.
+fn big_builder(len: usize) -> Builder<Big> {
+    let mut builder = Big::builder();
+    builder.choose(Input::choice("items")).unwrap();
+    for i in 0..len {
+        builder.choose(Input::choice("__new")).unwrap();
+        builder.choose(Input::choice("name")).unwrap();
+        builder.choose(Input::text(format!("item {}", i))).unwrap();
+        builder.choose(Input::choice("value")).unwrap();
+        builder.choose(Input::text(i.to_string())).unwrap();
+        builder.choose(Input::choice(BACK_ID)).unwrap();
+    }
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder
+}
+
+fn main_menu(c: &mut Criterion) {
+    let builder = big_builder(10_000);
+    c.bench_function("main menu with 10k items", |b| {
+        b.iter(|| black_box(builder.get_options()))
+    });
+}
+
+criterion_group!(benches, main_menu);
+criterion_main!(benches);
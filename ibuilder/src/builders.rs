@@ -1,41 +1,148 @@
 //! Module with the implementors of `BuildableValue` for the various standard types.
 
 use std::any::Any;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::hash::Hash;
+use std::iter::FromIterator;
 use std::marker::PhantomData;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use std::ops::{Range, RangeInclusive};
 use std::path::PathBuf;
+use std::rc::Rc;
 use std::str::FromStr;
 
-use crate::nodes::{Field, FieldKind, Node};
+use slab::Slab;
+
+use crate::charset::{self, Charset};
+use crate::nodes::{Field, FieldKind, Node, NodeVisitor};
+use crate::redaction::{RedactingVisitor, Redaction};
+use crate::schema::{Property, Schema, SchemaKind};
+use crate::types::{Confirm, HttpMethod, MimeType, TimeOfDay};
 use crate::{
-    BuildableValue, BuildableValueConfig, Choice, ChooseError, Input, NewBuildableValue, Options,
+    BuildableValue, BuildableValueConfig, Choice, ChooseError, Id, Input, LeafBuildableValue,
+    MenuEntry, NewBuildableValue, Options, Preprocessor, TypedBuildableValue, Validation,
+    Validator,
 };
 
+/// Run all the validators on a value, returning the warnings (if any) or the first error.
+fn validate<T>(validators: &[Validator<T>], value: &T) -> Result<Option<String>, ChooseError> {
+    let mut warnings = Vec::new();
+    for validator in validators {
+        match validator(value) {
+            Validation::Valid => {}
+            Validation::Warning(warning) => warnings.push(warning),
+            Validation::Invalid(error) => return Err(ChooseError::InvalidText { error }),
+        }
+    }
+    if warnings.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(warnings.join("\n")))
+    }
+}
+
+/// Apply the preprocessors in order to the text inserted by the user.
+fn preprocess(preprocessors: &[Preprocessor], text: &str) -> String {
+    preprocessors
+        .iter()
+        .fold(text.to_string(), |text, preprocessor| preprocessor(&text))
+}
+
+/// Make the `Node` of a leaf value, given its textual representation and its validation warning.
+fn leaf_node(value: Option<String>, warning: &Option<String>) -> Node {
+    match (value, warning) {
+        (Some(value), Some(warning)) => Node::Leaf(Field::Warning(value, warning.clone())),
+        (Some(value), None) => Node::Leaf(Field::String(value)),
+        (None, _) => Node::Leaf(Field::Missing),
+    }
+}
+
+/// The kind of the schema of a primitive type, given its name.
+fn primitive_kind(name: &str) -> SchemaKind {
+    match name {
+        "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "isize" | "usize" => {
+            SchemaKind::Integer
+        }
+        "f32" | "f64" => SchemaKind::Number,
+        _ => SchemaKind::String,
+    }
+}
+
 macro_rules! type_builder_boilerplate {
     (normal) => {
-        fn get_subfields(&self, _: &[String]) -> Vec<String> {
+        fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
+            vec![]
+        }
+
+        fn is_complete(&self) -> bool {
+            self.value.is_some()
+        }
+
+        fn to_node(&self) -> Node {
+            leaf_node(self.value.as_ref().map(|v| v.to_string()), &self.warning)
+        }
+
+        fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+            Some(Box::new(self.cloned()))
+        }
+
+        fn validation_error(&self) -> Option<String> {
+            match validate(&self.validators, self.value.as_ref()?) {
+                Err(ChooseError::InvalidText { error }) => Some(error),
+                _ => None,
+            }
+        }
+    };
+    ((format $format:path)) => {
+        fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
             vec![]
         }
 
+        fn is_complete(&self) -> bool {
+            self.value.is_some()
+        }
+
         fn to_node(&self) -> Node {
-            if let Some(value) = &self.value {
-                Node::Leaf(Field::String(value.to_string()))
-            } else {
-                Node::Leaf(Field::Missing)
+            leaf_node(self.value.as_ref().map($format), &self.warning)
+        }
+
+        fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+            Some(Box::new(self.cloned()))
+        }
+
+        fn validation_error(&self) -> Option<String> {
+            match validate(&self.validators, self.value.as_ref()?) {
+                Err(ChooseError::InvalidText { error }) => Some(error),
+                _ => None,
             }
         }
     };
     (path) => {
-        fn get_subfields(&self, _: &[String]) -> Vec<String> {
+        fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
             vec![]
         }
 
+        fn is_complete(&self) -> bool {
+            self.value.is_some()
+        }
+
         fn to_node(&self) -> Node {
-            if let Some(value) = &self.value {
-                Node::Leaf(Field::String(
-                    value.as_os_str().to_string_lossy().to_string(),
-                ))
-            } else {
-                Node::Leaf(Field::Missing)
+            leaf_node(
+                self.value
+                    .as_ref()
+                    .map(|v| v.as_os_str().to_string_lossy().to_string()),
+                &self.warning,
+            )
+        }
+
+        fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+            Some(Box::new(self.cloned()))
+        }
+
+        fn validation_error(&self) -> Option<String> {
+            match validate(&self.validators, self.value.as_ref()?) {
+                Err(ChooseError::InvalidText { error }) => Some(error),
+                _ => None,
             }
         }
     };
@@ -52,12 +159,21 @@ macro_rules! type_builder_struct {
     };
     ($base:ty, $name:ident, $query:expr, $docstring:expr) => {
         #[doc = $docstring]
-        #[derive(Debug)]
         pub struct $name {
             /// The current value.
             pub value: Option<$base>,
             /// The message to show to the user.
             pub prompt: String,
+            /// The functions that check the value inserted by the user.
+            pub validators: Vec<Validator<$base>>,
+            /// The warning of the last validation of the value, if any.
+            pub warning: Option<String>,
+            /// The functions applied to the text inserted by the user before parsing it.
+            pub preprocessors: Vec<Preprocessor>,
+            /// The description of the accepted values, if any.
+            pub hint: Option<String>,
+            /// The characters accepted in the text inserted by the user, if limited.
+            pub charset: Option<Charset>,
         }
 
         impl $name {
@@ -66,9 +182,58 @@ macro_rules! type_builder_struct {
                 Self {
                     value: config.default,
                     prompt: config.prompt.unwrap_or_else(|| $query.to_string()),
+                    validators: config.validators,
+                    warning: None,
+                    preprocessors: config.preprocessors,
+                    hint: config.hint,
+                    charset: config.charset,
+                }
+            }
+
+            /// A copy of the builder, with the same value and configuration.
+            fn cloned(&self) -> Self {
+                Self {
+                    value: self.value.clone(),
+                    prompt: self.prompt.clone(),
+                    validators: self.validators.clone(),
+                    warning: self.warning.clone(),
+                    preprocessors: self.preprocessors.clone(),
+                    hint: self.hint.clone(),
+                    charset: self.charset.clone(),
+                }
+            }
+
+            /// Validate and store the new value.
+            fn set_value(&mut self, value: $base) -> Result<(), ChooseError> {
+                self.warning = validate(&self.validators, &value)?;
+                self.value = Some(value);
+                Ok(())
+            }
+
+            /// Validate and store the new value, if it has the right type.
+            fn set_value_boxed(&mut self, value: Box<dyn Any>) -> bool {
+                match value.downcast::<$base>() {
+                    Ok(value) => self.set_value(*value).is_ok(),
+                    Err(_) => false,
                 }
             }
         }
+
+        impl std::fmt::Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!($name))
+                    .field("value", &self.value)
+                    .field("prompt", &self.prompt)
+                    .field("warning", &self.warning)
+                    .finish()
+            }
+        }
+
+        impl TypedBuildableValue<$base> for $name {
+            fn get_value(&self) -> Option<$base> {
+                self.value.clone()
+            }
+        }
     };
 }
 
@@ -97,7 +262,7 @@ macro_rules! type_builder {
         impl BuildableValue for $name {
             type_builder_boilerplate!($variant);
 
-            fn apply(&mut self, data: Input, current_fields: &[String]) -> Result<(), ChooseError> {
+            fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
                 if !current_fields.is_empty() {
                     panic!(
                         "{}.apply() called with non empty fields: {:?}",
@@ -107,18 +272,21 @@ macro_rules! type_builder {
                 }
                 match data {
                     Input::Text(data) => {
-                        self.value = Some(<$base>::from_str(&data).map_err(|e| {
+                        let data = preprocess(&self.preprocessors, data);
+                        charset::check(&self.charset, &data)?;
+                        let value = <$base>::from_str(&data).map_err(|e| {
                             ChooseError::InvalidText {
                                 error: e.to_string(),
                             }
-                        })?);
+                        })?;
+                        self.set_value(value)?;
                     }
                     _ => return Err(ChooseError::UnexpectedChoice),
                 }
                 Ok(())
             }
 
-            fn get_options(&self, current_fields: &[String]) -> Options {
+            fn get_options(&self, current_fields: &[Id]) -> Options {
                 if !current_fields.is_empty() {
                     panic!(
                         "{}.get_options() called with non empty fields: {:?}",
@@ -130,11 +298,20 @@ macro_rules! type_builder {
                     query: self.prompt.clone(),
                     text_input: true,
                     choices: vec![],
+                    warning: self.warning.clone(),
+                    hint: self.hint.clone(),
+                    ..Default::default()
                 }
             }
 
-            fn get_value_any(&self) -> Option<Box<dyn Any>> {
-                self.value.clone().map(|x| Box::new(x) as Box<dyn Any>)
+            crate::typed_get_value_any!($base);
+
+            fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+                self.set_value_boxed(value)
+            }
+
+            fn schema(&self) -> Schema {
+                Schema::new(primitive_kind(stringify!($base))).with_default_from(self)
             }
         }
 
@@ -143,6 +320,7 @@ macro_rules! type_builder {
                 Box::new($name::new(BuildableValueConfig {
                     default: None,
                     prompt: config.prompt,
+                    ..Default::default()
                 }))
             }
         }
@@ -164,13 +342,369 @@ type_builder!(f64, F64Builder, "Type an integer");
 type_builder!(String, StringBuilder, "Type a string");
 type_builder!(char, CharBuilder, "Type a char");
 type_builder!(PathBuf, PathBufBuilder, "Type a path", path);
+type_builder!(TimeOfDay, TimeOfDayBuilder, "Type a time (HH:MM)");
+type_builder!(IpAddr, IpAddrBuilder, "Type an IP address");
+type_builder!(Ipv4Addr, Ipv4AddrBuilder, "Type an IPv4 address");
+type_builder!(Ipv6Addr, Ipv6AddrBuilder, "Type an IPv6 address");
+type_builder!(SocketAddr, SocketAddrBuilder, "Type an address (IP:port)");
+#[cfg(feature = "cron")]
+type_builder!(
+    @,
+    cron::Schedule,
+    CronBuilder,
+    "Type a cron expression",
+    "Builder for the type `cron::Schedule`, available with the `cron` feature.",
+    normal
+);
+
+/// The formats of the dates accepted by `DateBuilder`, tried in order.
+#[cfg(feature = "chrono")]
+const DATE_FORMATS: &[&str] = &[
+    "%Y-%m-%d",
+    "%Y/%m/%d",
+    "%d/%m/%Y",
+    "%d.%m.%Y",
+    "%d %B %Y",
+    "%B %d %Y",
+    "%B %d, %Y",
+];
+
+/// The formats of the dates with the time accepted by `DateTimeBuilder`, tried in order after
+/// RFC 3339. The time is in UTC.
+#[cfg(feature = "chrono")]
+const DATETIME_FORMATS: &[&str] = &[
+    "%Y-%m-%d %H:%M:%S",
+    "%Y-%m-%d %H:%M",
+    "%Y-%m-%dT%H:%M:%S",
+    "%Y-%m-%dT%H:%M",
+    "%Y-%m-%d %H:%M:%S UTC",
+];
+
+/// The dates accepted by `parse_date` relative to the current day.
+#[cfg(feature = "chrono")]
+const RELATIVE_DATES: &[&str] = &["today", "tomorrow", "yesterday"];
+
+/// Parse a date in one of `DATE_FORMATS`, or one of `RELATIVE_DATES` (in the local timezone).
+#[cfg(feature = "chrono")]
+fn parse_date(text: &str) -> Result<chrono::NaiveDate, String> {
+    let text = text.trim();
+    let today = chrono::DateTime::<chrono::Local>::from(crate::system_time()).date_naive();
+    let relative = match text.to_lowercase().as_str() {
+        "today" => Some(today),
+        "tomorrow" => today.succ_opt(),
+        "yesterday" => today.pred_opt(),
+        _ => None,
+    };
+    relative
+        .or_else(|| {
+            DATE_FORMATS
+                .iter()
+                .find_map(|format| chrono::NaiveDate::parse_from_str(text, format).ok())
+        })
+        .ok_or_else(|| "Invalid date, expecting YYYY-MM-DD".to_string())
+}
+
+/// Parse a date with the time in RFC 3339 or in one of `DATETIME_FORMATS`, a date (at midnight)
+/// accepted by `parse_date`, or `now`.
+#[cfg(feature = "chrono")]
+fn parse_datetime(text: &str) -> Result<chrono::DateTime<chrono::Utc>, String> {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("now") {
+        return Ok(crate::system_time().into());
+    }
+    if let Ok(value) = chrono::DateTime::parse_from_rfc3339(text) {
+        return Ok(value.with_timezone(&chrono::Utc));
+    }
+    DATETIME_FORMATS
+        .iter()
+        .find_map(|format| chrono::NaiveDateTime::parse_from_str(text, format).ok())
+        .or_else(|| {
+            parse_date(text)
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|value| value.and_utc())
+        .ok_or_else(|| "Invalid date and time, expecting YYYY-MM-DD HH:MM (UTC)".to_string())
+}
+
+/// Parse a duration made of numbers followed by their unit, like `1h30m`, `90s` or `2 days`.
+#[cfg(feature = "chrono")]
+fn parse_duration(text: &str) -> Result<chrono::Duration, String> {
+    let mut rest = text.trim();
+    if rest.is_empty() {
+        return Err("Invalid duration, expecting something like 1h30m".to_string());
+    }
+    let mut total = chrono::Duration::zero();
+    while !rest.is_empty() {
+        let digits = rest
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let amount: i64 = rest[..digits]
+            .parse()
+            .map_err(|_| "Invalid duration, expecting something like 1h30m".to_string())?;
+        rest = rest[digits..].trim_start();
+        let letters = rest
+            .find(|c: char| !c.is_alphabetic())
+            .unwrap_or(rest.len());
+        let unit = rest[..letters].to_lowercase();
+        rest = rest[letters..].trim_start();
+        let part = match unit.as_str() {
+            "w" | "week" | "weeks" => chrono::Duration::try_weeks(amount),
+            "d" | "day" | "days" => chrono::Duration::try_days(amount),
+            "h" | "hour" | "hours" => chrono::Duration::try_hours(amount),
+            "m" | "min" | "mins" | "minute" | "minutes" => chrono::Duration::try_minutes(amount),
+            "s" | "sec" | "secs" | "second" | "seconds" => chrono::Duration::try_seconds(amount),
+            "ms" => chrono::Duration::try_milliseconds(amount),
+            "" => return Err(format!("Missing the unit after {}", amount)),
+            _ => {
+                return Err(format!(
+                    "Unknown unit {:?}, expecting w, d, h, m, s or ms",
+                    unit
+                ))
+            }
+        };
+        total = part
+            .and_then(|part| total.checked_add(&part))
+            .ok_or_else(|| "The duration is too long".to_string())?;
+    }
+    Ok(total)
+}
+
+/// Format a duration the way `parse_duration` accepts it, like `1h 30m`.
+#[cfg(feature = "chrono")]
+fn format_duration(value: &chrono::Duration) -> String {
+    const UNITS: &[(i64, &str)] = &[
+        (7 * 24 * 3_600_000, "w"),
+        (24 * 3_600_000, "d"),
+        (3_600_000, "h"),
+        (60_000, "m"),
+        (1_000, "s"),
+        (1, "ms"),
+    ];
+    let mut millis = value.abs().num_milliseconds();
+    let mut parts = vec![];
+    for (size, unit) in UNITS {
+        if millis >= *size {
+            parts.push(format!("{}{}", millis / size, unit));
+            millis %= size;
+        }
+    }
+    if parts.is_empty() {
+        return "0s".to_string();
+    }
+    let sign = if *value < chrono::Duration::zero() {
+        "-"
+    } else {
+        ""
+    };
+    format!("{}{}", sign, parts.join(" "))
+}
+
+/// Make a builder for a type that is parsed from the text by a function, offering some choices
+/// (with the text of the choice that is parsed as well) as shortcuts.
+///
+/// The optional `$volatile => $render` lists the keywords whose value changes over time (e.g.
+/// `now`): the value they resolved to is recorded in the history instead, rendered as a text
+/// accepted by `$parse`, so that a replay builds the same value.
+#[cfg(any(feature = "chrono", feature = "url", feature = "uuid"))]
+macro_rules! parsed_type_builder {
+    ($base:ty, $name:ident, $query:expr, $docstring:expr, $parse:path, $variant:tt, [$(($id:expr, $text:expr)),*] $(, $volatile:expr => $render:expr)?) => {
+        type_builder_struct!($base, $name, $query, $docstring);
+
+        impl BuildableValue for $name {
+            type_builder_boilerplate!($variant);
+
+            fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+                if !current_fields.is_empty() {
+                    panic!(
+                        "{}.apply() called with non empty fields: {:?}",
+                        stringify!($name),
+                        current_fields
+                    );
+                }
+                let shortcuts: &[&str] = &[$($id),*];
+                let text = match data {
+                    Input::Text(data) => {
+                        let data = preprocess(&self.preprocessors, data);
+                        charset::check(&self.charset, &data)?;
+                        data
+                    }
+                    Input::Choice(data) if shortcuts.contains(&data.as_str()) => data.clone(),
+                    Input::Choice(_) => return Err(ChooseError::UnexpectedChoice),
+                };
+                let value = $parse(&text).map_err(|error| ChooseError::InvalidText { error })?;
+                self.set_value(value)
+            }
+
+            $(
+            fn recorded_input(&self, data: &Input, _current_fields: &[Id]) -> Option<Input> {
+                let text = match data {
+                    Input::Text(data) => preprocess(&self.preprocessors, data),
+                    Input::Choice(data) => data.clone(),
+                };
+                let volatile: &[&str] = $volatile;
+                if !volatile.iter().any(|kw| text.trim().eq_ignore_ascii_case(kw)) {
+                    return None;
+                }
+                self.value.as_ref().map(|value| Input::Text(($render)(value)))
+            }
+            )?
+
+            fn get_options(&self, current_fields: &[Id]) -> Options {
+                if !current_fields.is_empty() {
+                    panic!(
+                        "{}.get_options() called with non empty fields: {:?}",
+                        stringify!($name),
+                        current_fields
+                    );
+                }
+                Options {
+                    query: self.prompt.clone(),
+                    text_input: true,
+                    choices: vec![$(Choice {
+                        choice_id: $id.into(),
+                        text: $text.to_string(),
+                        needs_action: false,
+                        ..Default::default()
+                    }),*],
+                    warning: self.warning.clone(),
+                    hint: self.hint.clone(),
+                    ..Default::default()
+                }
+            }
+
+            crate::typed_get_value_any!($base);
+
+            fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+                self.set_value_boxed(value)
+            }
+
+            fn schema(&self) -> Schema {
+                Schema::new(SchemaKind::String).with_default_from(self)
+            }
+        }
+
+        impl NewBuildableValue for $base {
+            fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+                Box::new($name::new(BuildableValueConfig {
+                    default: None,
+                    prompt: config.prompt,
+                    ..Default::default()
+                }))
+            }
+        }
+    };
+}
+
+#[cfg(feature = "chrono")]
+parsed_type_builder!(
+    chrono::NaiveDate,
+    DateBuilder,
+    "Type a date (YYYY-MM-DD)",
+    "Builder for the type `chrono::NaiveDate`, available with the `chrono` feature.\n\nThe date \
+     can be typed as `YYYY-MM-DD`, `YYYY/MM/DD`, `DD/MM/YYYY`, `DD.MM.YYYY`, `5 March 2024`, \
+     `March 5, 2024` or as `today`, `tomorrow` and `yesterday`.",
+    parse_date,
+    normal,
+    [("today", "Today")],
+    RELATIVE_DATES => |value: &chrono::NaiveDate| value.to_string()
+);
+#[cfg(feature = "chrono")]
+parsed_type_builder!(
+    chrono::DateTime<chrono::Utc>,
+    DateTimeBuilder,
+    "Type a date and time (YYYY-MM-DD HH:MM, UTC)",
+    "Builder for the type `chrono::DateTime<Utc>`, available with the `chrono` feature.\n\nThe \
+     value can be typed in RFC 3339 (e.g. `2024-03-05T10:30:00+01:00`), as `YYYY-MM-DD HH:MM` \
+     (optionally with the seconds) in UTC, as a date accepted by `DateBuilder` (at midnight) or \
+     as `now`.",
+    parse_datetime,
+    normal,
+    [("now", "Now")],
+    &["now", "today", "tomorrow", "yesterday"] =>
+        |value: &chrono::DateTime<chrono::Utc>| value.to_rfc3339()
+);
+#[cfg(feature = "chrono")]
+parsed_type_builder!(
+    chrono::Duration,
+    DurationBuilder,
+    "Type a duration (e.g. 1h30m)",
+    "Builder for the type `chrono::Duration`, available with the `chrono` feature.\n\nThe \
+     duration is made of numbers followed by their unit (`w`, `d`, `h`, `m`, `s` or `ms`, also \
+     spelled out like `2 days`), for example `1h30m` or `90s`.",
+    parse_duration,
+    (format format_duration),
+    []
+);
+
+/// Parse a URL, with the errors explained for the user.
+#[cfg(feature = "url")]
+fn parse_url(text: &str) -> Result<url::Url, String> {
+    url::Url::parse(text.trim()).map_err(|e| {
+        match e {
+            url::ParseError::RelativeUrlWithoutBase => {
+                "Missing the scheme, e.g. https://example.com"
+            }
+            url::ParseError::EmptyHost => "Missing the host, e.g. https://example.com",
+            url::ParseError::InvalidPort => "Invalid port, it must be a number up to 65535",
+            url::ParseError::InvalidIpv4Address => "Invalid IPv4 address in the host",
+            url::ParseError::InvalidIpv6Address => "Invalid IPv6 address in the host",
+            url::ParseError::InvalidDomainCharacter | url::ParseError::IdnaError => {
+                "Invalid character in the domain"
+            }
+            _ => return format!("Invalid URL: {}", e),
+        }
+        .to_string()
+    })
+}
+
+/// Parse a UUID in any of its textual forms, or make a new random one with `random`.
+#[cfg(feature = "uuid")]
+fn parse_uuid(text: &str) -> Result<uuid::Uuid, String> {
+    let text = text.trim();
+    if text.eq_ignore_ascii_case("random") {
+        let mut bytes = [0; 16];
+        if crate::random_bytes(&mut bytes) {
+            return Ok(uuid::Builder::from_random_bytes(bytes).into_uuid());
+        }
+        return Ok(uuid::Uuid::new_v4());
+    }
+    uuid::Uuid::parse_str(text).map_err(|_| {
+        "Invalid UUID, expecting 32 hexadecimal digits like 67e55044-10b1-426f-9247-bb680e5fe0c8"
+            .to_string()
+    })
+}
+
+#[cfg(feature = "url")]
+parsed_type_builder!(
+    url::Url,
+    UrlBuilder,
+    "Type a URL",
+    "Builder for the type `url::Url`, available with the `url` feature.",
+    parse_url,
+    normal,
+    []
+);
+#[cfg(feature = "uuid")]
+parsed_type_builder!(
+    uuid::Uuid,
+    UuidBuilder,
+    "Type a UUID",
+    "Builder for the type `uuid::Uuid`, available with the `uuid` feature.\n\nThe UUID can be \
+     typed in any of its textual forms, or a new random one can be generated choosing (or \
+     typing) `random`.",
+    parse_uuid,
+    normal,
+    [("random", "Generate a random UUID")],
+    &["random"] => |value: &uuid::Uuid| value.to_string()
+);
 
 type_builder_struct!(bool, BoolBuilder, "True or false?");
 
 impl BuildableValue for BoolBuilder {
     type_builder_boilerplate!(normal);
 
-    fn apply(&mut self, data: Input, current_fields: &[String]) -> Result<(), ChooseError> {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
         if !current_fields.is_empty() {
             panic!(
                 "BoolBuilder.apply() called with non empty fields: {:?}",
@@ -179,8 +713,8 @@ impl BuildableValue for BoolBuilder {
         }
         match data {
             Input::Choice(data) => match data.as_str() {
-                "true" => self.value = Some(true),
-                "false" => self.value = Some(false),
+                "true" => self.set_value(true)?,
+                "false" => self.set_value(false)?,
                 _ => return Err(ChooseError::UnexpectedChoice),
             },
             Input::Text(_) => return Err(ChooseError::UnexpectedText),
@@ -188,7 +722,7 @@ impl BuildableValue for BoolBuilder {
         Ok(())
     }
 
-    fn get_options(&self, current_fields: &[String]) -> Options {
+    fn get_options(&self, current_fields: &[Id]) -> Options {
         if !current_fields.is_empty() {
             panic!(
                 "BoolBuilder.get_options() called with non empty fields: {:?}",
@@ -200,451 +734,3763 @@ impl BuildableValue for BoolBuilder {
             text_input: false,
             choices: vec![
                 Choice {
-                    choice_id: "true".to_string(),
+                    choice_id: "true".into(),
                     text: "true".to_string(),
                     needs_action: false,
+                    ..Default::default()
                 },
                 Choice {
-                    choice_id: "false".to_string(),
+                    choice_id: "false".into(),
                     text: "false".to_string(),
                     needs_action: false,
+                    ..Default::default()
                 },
             ],
+            warning: self.warning.clone(),
+            default_choice: self
+                .value
+                .map(|value| Id::from(if value { "true" } else { "false" })),
+            ..Default::default()
         }
     }
 
-    fn get_value_any(&self) -> Option<Box<dyn Any>> {
-        self.value.map(|x| Box::new(x) as Box<dyn Any>)
+    crate::typed_get_value_any!(bool);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        self.set_value_boxed(value)
+    }
+
+    fn schema(&self) -> Schema {
+        Schema::new(SchemaKind::Boolean).with_default_from(self)
     }
 }
 
-/// Builder for the type `Vec<T>`.
-///
-/// The type parameters are:
-/// - `B`: the type of the builder that produces `T`
-/// - `T`: the type of the items of the final `Vec`
-///
-/// The state machine that this builder implements is a bit complex since it has to handle
-/// insertions, deletions and updates and it looks like this:
-///
-/// ```text
-///                 |
-///                 v
-///            +-------------+    __new    +-------------+  <B> specific
-///  +-------> |  empty      | ----------> | not empty   | ------>>>
-///  |         |  main       |             | edit        |
-///  |         +-------------+             +-------------+
-///  |                                         ^    |
-///  |                                         |    |
-///  |         +-------------+  index / __new  |    | __back
-///  +-------> |  not empty  | ----------------+    |
-///  |         |  main       | <--------------------+
-///  | index   +-------------+
-///  |            ^    |
-///  |     __back |    | __remove
-///  |            |    v
-///  |         +-------------+
-///  +-------- |  not empty  |
-///            |  remove     |
-///            +-------------+
-/// ```
-///
-/// When `__new` is applied a new item is pushed at the back of the `Vec` and when `__new` is to
-/// be considered as an index it refers to the last element of the `Vec`.
-pub struct VecBuilder<T>
-where
-    T: NewBuildableValue + 'static,
-{
-    items: Vec<Box<dyn BuildableValue>>,
-    inner_type: PhantomData<T>,
-    prompt: String,
+type_builder_struct!(HttpMethod, HttpMethodBuilder, "Choose the HTTP method");
+
+impl BuildableValue for HttpMethodBuilder {
+    type_builder_boilerplate!(normal);
+
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            panic!(
+                "HttpMethodBuilder.apply() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        match data {
+            Input::Choice(data) => {
+                let method =
+                    HttpMethod::from_str(data).map_err(|_| ChooseError::UnexpectedChoice)?;
+                self.set_value(method)?;
+            }
+            Input::Text(_) => return Err(ChooseError::UnexpectedText),
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if !current_fields.is_empty() {
+            panic!(
+                "HttpMethodBuilder.get_options() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        Options {
+            query: self.prompt.clone(),
+            text_input: false,
+            choices: HttpMethod::ALL
+                .iter()
+                .map(|method| Choice {
+                    choice_id: method.as_str().into(),
+                    text: method.to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                })
+                .collect(),
+            default_choice: self.value.map(|method| Id::from(method.as_str())),
+            warning: self.warning.clone(),
+            ..Default::default()
+        }
+    }
+
+    crate::typed_get_value_any!(HttpMethod);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        self.set_value_boxed(value)
+    }
 }
 
-impl<T> std::fmt::Debug for VecBuilder<T>
-where
-    T: NewBuildableValue + 'static,
-{
+impl NewBuildableValue for HttpMethod {
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(HttpMethodBuilder::new(BuildableValueConfig {
+            default: None,
+            prompt: config.prompt,
+            ..Default::default()
+        }))
+    }
+}
+
+/// Builder for the type `Confirm`, with a choice for each answer. The labels of the choices can be
+/// changed with `with_labels`.
+pub struct ConfirmBuilder {
+    /// The current value.
+    pub value: Option<Confirm>,
+    /// The message to show to the user.
+    pub prompt: String,
+    /// The text of the `Confirm::Yes` choice.
+    pub yes_label: String,
+    /// The text of the `Confirm::No` choice.
+    pub no_label: String,
+}
+
+impl ConfirmBuilder {
+    /// Make a new instance of the builder, with the "Yes" and "No" labels.
+    pub fn new(config: BuildableValueConfig<Confirm>) -> Self {
+        Self {
+            value: config.default,
+            prompt: config.prompt.unwrap_or_else(|| "Are you sure?".to_string()),
+            yes_label: "Yes".to_string(),
+            no_label: "No".to_string(),
+        }
+    }
+
+    /// Change the labels of the choices.
+    pub fn with_labels<Y: Into<String>, N: Into<String>>(mut self, yes: Y, no: N) -> Self {
+        self.yes_label = yes.into();
+        self.no_label = no.into();
+        self
+    }
+}
+
+impl std::fmt::Debug for ConfirmBuilder {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("VecBuilder")
-            .field("items", &self.items)
+        f.debug_struct("ConfirmBuilder")
+            .field("value", &self.value)
+            .field("prompt", &self.prompt)
             .finish()
     }
 }
 
-impl<T> NewBuildableValue for Vec<T>
-where
-    T: NewBuildableValue + 'static,
-{
-    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
-        Box::new(VecBuilder::<T> {
-            items: Vec::new(),
-            inner_type: Default::default(),
-            prompt: config
-                .prompt
-                .unwrap_or_else(|| "Select an action".to_string()),
-        })
+impl TypedBuildableValue<Confirm> for ConfirmBuilder {
+    fn get_value(&self) -> Option<Confirm> {
+        self.value
     }
 }
 
-impl<T> BuildableValue for VecBuilder<T>
-where
-    T: NewBuildableValue + 'static,
-{
-    fn apply(&mut self, data: Input, current_fields: &[String]) -> Result<(), ChooseError> {
-        // vec main menu
-        if current_fields.is_empty() {
-            match data {
-                Input::Choice(data) if data == "__new" => {
-                    self.items.push(T::new_buildable_value(Default::default()));
+impl BuildableValue for ConfirmBuilder {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            panic!(
+                "ConfirmBuilder.apply() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        match data {
+            Input::Choice(data) => {
+                let value = Confirm::from_str(data).map_err(|_| ChooseError::UnexpectedChoice)?;
+                self.value = Some(value);
+            }
+            Input::Text(_) => return Err(ChooseError::UnexpectedText),
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if !current_fields.is_empty() {
+            panic!(
+                "ConfirmBuilder.get_options() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        let choice = |value: Confirm, label: &str| Choice {
+            choice_id: value.as_str().into(),
+            text: label.to_string(),
+            needs_action: false,
+            ..Default::default()
+        };
+        Options {
+            query: self.prompt.clone(),
+            text_input: false,
+            choices: vec![
+                choice(Confirm::Yes, &self.yes_label),
+                choice(Confirm::No, &self.no_label),
+            ],
+            ..Default::default()
+        }
+    }
+
+    fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
+        vec![]
+    }
+
+    fn to_node(&self) -> Node {
+        leaf_node(self.value.map(|v| v.to_string()), &None)
+    }
+
+    crate::typed_get_value_any!(Confirm);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        match value.downcast::<Confirm>() {
+            Ok(value) => {
+                self.value = Some(*value);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(ConfirmBuilder {
+            value: self.value,
+            prompt: self.prompt.clone(),
+            yes_label: self.yes_label.clone(),
+            no_label: self.no_label.clone(),
+        }))
+    }
+}
+
+impl NewBuildableValue for Confirm {
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(ConfirmBuilder::new(BuildableValueConfig {
+            default: None,
+            prompt: config.prompt,
+            ..Default::default()
+        }))
+    }
+}
+
+/// Builder for an enum without fields, presented as a list of choices, one for each variant. This
+/// is useful for the enums that cannot derive `IBuilder`, for example because they are defined in
+/// another crate, see `ibuilder_for_fieldless_enum!`.
+pub struct FieldlessEnumBuilder<T> {
+    /// The variants of the enum, with their names.
+    variants: Vec<(&'static str, T)>,
+    /// The index of the selected variant.
+    value: Option<usize>,
+    prompt: String,
+}
+
+impl<T> std::fmt::Debug for FieldlessEnumBuilder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FieldlessEnumBuilder")
+            .field("value", &self.value.map(|i| self.variants[i].0))
+            .field("prompt", &self.prompt)
+            .finish()
+    }
+}
+
+impl<T> FieldlessEnumBuilder<T> {
+    /// Make a new instance of the builder, with the variants of the enum and their names.
+    pub fn new(config: BuildableValueConfig<()>, variants: Vec<(&'static str, T)>) -> Self {
+        Self {
+            variants,
+            value: None,
+            prompt: config
+                .prompt
+                .unwrap_or_else(|| "Select a variant".to_string()),
+        }
+    }
+}
+
+impl<T: Clone + 'static> TypedBuildableValue<T> for FieldlessEnumBuilder<T> {
+    fn get_value(&self) -> Option<T> {
+        let (_, variant) = &self.variants[self.value?];
+        Some(variant.clone())
+    }
+}
+
+impl<T: Clone + 'static> BuildableValue for FieldlessEnumBuilder<T> {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            panic!(
+                "FieldlessEnumBuilder.apply() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        match data {
+            Input::Choice(data) => {
+                let index = self
+                    .variants
+                    .iter()
+                    .position(|(name, _)| *name == data)
+                    .ok_or(ChooseError::UnexpectedChoice)?;
+                self.value = Some(index);
+            }
+            Input::Text(_) => return Err(ChooseError::UnexpectedText),
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if !current_fields.is_empty() {
+            panic!(
+                "FieldlessEnumBuilder.get_options() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        Options {
+            query: self.prompt.clone(),
+            text_input: false,
+            choices: self
+                .variants
+                .iter()
+                .map(|(name, _)| Choice {
+                    choice_id: (*name).into(),
+                    text: name.to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                })
+                .collect(),
+            default_choice: self.value.map(|index| Id::from(self.variants[index].0)),
+            ..Default::default()
+        }
+    }
+
+    fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
+        vec![]
+    }
+
+    fn to_node(&self) -> Node {
+        match self.value {
+            Some(index) => Node::Leaf(Field::String(self.variants[index].0.to_string())),
+            None => Node::Leaf(Field::Missing),
+        }
+    }
+
+    crate::typed_get_value_any!(T);
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(FieldlessEnumBuilder {
+            variants: self.variants.clone(),
+            value: self.value,
+            prompt: self.prompt.clone(),
+        }))
+    }
+}
+
+/// Make a builder function for an enum without fields that cannot derive `IBuilder` (e.g. because
+/// it's defined in another crate), listing the variants to show to the user. The function can be
+/// used in a derived struct with `#[ibuilder(builder = "...")]`. The enum must implement `Clone`.
+///
+/// ```
+/// use ibuilder::*;
+///
+/// // imagine this enum is defined in another crate
+/// #[derive(Clone, Debug, PartialEq)]
+/// pub enum Ordering {
+///     Less,
+///     Equal,
+///     Greater,
+/// }
+///
+/// const ORDERING: BuilderFn = ibuilder_for_fieldless_enum!(Ordering, [Less, Equal, Greater]);
+///
+/// #[derive(IBuilder)]
+/// struct Config {
+///     #[ibuilder(builder = "ORDERING")]
+///     ordering: Ordering,
+/// }
+///
+/// let mut builder = Config::builder();
+/// builder.choose(Input::choice("ordering")).unwrap();
+/// builder.choose(Input::choice("Greater")).unwrap();
+/// let config = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+/// assert_eq!(config.ordering, Ordering::Greater);
+/// ```
+#[macro_export]
+macro_rules! ibuilder_for_fieldless_enum {
+    ($ty:ty, [$($variant:ident),* $(,)?]) => {
+        |config: $crate::BuildableValueConfig<()>| -> Box<dyn $crate::BuildableValue> {
+            Box::new($crate::builders::FieldlessEnumBuilder::<$ty>::new(
+                config,
+                vec![$((stringify!($variant), <$ty>::$variant),)*],
+            ))
+        }
+    };
+}
+
+type_builder_struct!(MimeType, MimeTypeBuilder, "Type the MIME type");
+
+impl BuildableValue for MimeTypeBuilder {
+    type_builder_boilerplate!(normal);
+
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            panic!(
+                "MimeTypeBuilder.apply() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        let value = match data {
+            Input::Choice(data) => {
+                if !MimeType::COMMON.contains(&data.as_str()) {
+                    return Err(ChooseError::UnexpectedChoice);
+                }
+                MimeType::from_str(data).expect("Invalid common MIME type")
+            }
+            Input::Text(data) => {
+                let data = preprocess(&self.preprocessors, data);
+                charset::check(&self.charset, &data)?;
+                MimeType::from_str(&data).map_err(|e| ChooseError::InvalidText {
+                    error: e.to_string(),
+                })?
+            }
+        };
+        self.set_value(value)
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if !current_fields.is_empty() {
+            panic!(
+                "MimeTypeBuilder.get_options() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        Options {
+            query: self.prompt.clone(),
+            text_input: true,
+            choices: MimeType::COMMON
+                .iter()
+                .map(|mime| Choice {
+                    choice_id: (*mime).into(),
+                    text: mime.to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                })
+                .collect(),
+            warning: self.warning.clone(),
+            ..Default::default()
+        }
+    }
+
+    crate::typed_get_value_any!(MimeType);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        self.set_value_boxed(value)
+    }
+}
+
+impl NewBuildableValue for MimeType {
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(MimeTypeBuilder::new(BuildableValueConfig {
+            default: None,
+            prompt: config.prompt,
+            ..Default::default()
+        }))
+    }
+}
+
+/// Builder for the types implementing `LeafBuildableValue`: the user types the value as a text,
+/// that is parsed with `LeafBuildableValue::parse`.
+///
+/// ```
+/// use ibuilder::builders::LeafBuilder;
+/// use ibuilder::*;
+///
+/// #[derive(Debug, Clone, PartialEq)]
+/// struct Rgb(u8, u8, u8);
+///
+/// impl LeafBuildableValue for Rgb {
+///     const PROMPT: &'static str = "Type the color (#rrggbb)";
+///
+///     fn parse(text: &str) -> Result<Self, String> {
+///         let hex = text.strip_prefix('#').ok_or("Missing the leading #")?;
+///         let channel = |i: usize| {
+///             hex.get(i..i + 2)
+///                 .and_then(|c| u8::from_str_radix(c, 16).ok())
+///                 .ok_or_else(|| format!("Invalid color: {}", text))
+///         };
+///         if hex.len() != 6 {
+///             return Err(format!("Invalid color: {}", text));
+///         }
+///         Ok(Rgb(channel(0)?, channel(2)?, channel(4)?))
+///     }
+///
+///     fn render(&self) -> String {
+///         format!("#{:02x}{:02x}{:02x}", self.0, self.1, self.2)
+///     }
+/// }
+///
+/// impl NewBuildableValue for Rgb {
+///     fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+///         Box::new(LeafBuilder::<Rgb>::new(config))
+///     }
+/// }
+///
+/// #[derive(IBuilder)]
+/// struct Theme {
+///     background: Rgb,
+/// }
+///
+/// let mut builder = Theme::builder();
+/// builder.choose(Input::choice("background")).unwrap();
+/// assert!(builder.choose(Input::text("red")).is_err());
+/// builder.choose(Input::text("#ff8000")).unwrap();
+/// let theme = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+/// assert_eq!(theme.background, Rgb(255, 128, 0));
+/// ```
+pub struct LeafBuilder<T> {
+    /// The current value.
+    pub value: Option<T>,
+    /// The message to show to the user.
+    pub prompt: String,
+    /// The functions that check the value inserted by the user.
+    pub validators: Vec<Validator<T>>,
+    /// The warning of the last validation of the value, if any.
+    pub warning: Option<String>,
+}
+
+impl<T: LeafBuildableValue> LeafBuilder<T> {
+    /// Make a new instance of the builder, without a value.
+    pub fn new(config: BuildableValueConfig<()>) -> Self {
+        Self {
+            value: None,
+            prompt: config.prompt.unwrap_or_else(|| T::PROMPT.to_string()),
+            validators: vec![],
+            warning: None,
+        }
+    }
+}
+
+impl<T: LeafBuildableValue> std::fmt::Debug for LeafBuilder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LeafBuilder")
+            .field("value", &self.value.as_ref().map(T::render))
+            .field("prompt", &self.prompt)
+            .field("warning", &self.warning)
+            .finish()
+    }
+}
+
+impl<T: LeafBuildableValue> TypedBuildableValue<T> for LeafBuilder<T> {
+    fn get_value(&self) -> Option<T> {
+        self.value.clone()
+    }
+}
+
+impl<T: LeafBuildableValue> BuildableValue for LeafBuilder<T> {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            panic!(
+                "LeafBuilder.apply() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        match data {
+            Input::Text(data) => {
+                let value = T::parse(data).map_err(|error| ChooseError::InvalidText { error })?;
+                self.warning = validate(&self.validators, &value)?;
+                self.value = Some(value);
+            }
+            Input::Choice(_) => return Err(ChooseError::UnexpectedChoice),
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if !current_fields.is_empty() {
+            panic!(
+                "LeafBuilder.get_options() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        Options {
+            query: self.prompt.clone(),
+            text_input: true,
+            choices: vec![],
+            warning: self.warning.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
+        vec![]
+    }
+
+    fn is_complete(&self) -> bool {
+        self.value.is_some()
+    }
+
+    fn to_node(&self) -> Node {
+        leaf_node(self.value.as_ref().map(T::render), &self.warning)
+    }
+
+    crate::typed_get_value_any!(T);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        let value = match value.downcast::<T>() {
+            Ok(value) => *value,
+            Err(_) => return false,
+        };
+        match validate(&self.validators, &value) {
+            Ok(warning) => {
+                self.warning = warning;
+                self.value = Some(value);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(LeafBuilder {
+            value: self.value.clone(),
+            prompt: self.prompt.clone(),
+            validators: self.validators.clone(),
+            warning: self.warning.clone(),
+        }))
+    }
+}
+
+/// An encoding of binary data into text, used by `BinaryBuilder`.
+pub trait BinaryEncoding: 'static {
+    /// The default prompt of the builder.
+    const PROMPT: &'static str;
+
+    /// Decode the text inserted by the user, returning a message if it's not valid.
+    fn decode(text: &str) -> Result<Vec<u8>, String>;
+}
+
+/// The base64 encoding, both with the standard and the URL-safe alphabets and with optional
+/// padding.
+#[derive(Debug)]
+pub struct Base64;
+
+impl BinaryEncoding for Base64 {
+    const PROMPT: &'static str = "Paste the base64 data";
+
+    fn decode(text: &str) -> Result<Vec<u8>, String> {
+        let text: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let data = match text.iter().position(|&c| c == '=') {
+            Some(pad) => {
+                if !text.len().is_multiple_of(4)
+                    || text.len() - pad > 2
+                    || text[pad..].iter().any(|&c| c != '=')
+                {
+                    return Err("Invalid base64 padding".into());
+                }
+                &text[..pad]
+            }
+            None => &text[..],
+        };
+        if data.len() % 4 == 1 {
+            return Err("Invalid base64 length".into());
+        }
+        let mut res = Vec::with_capacity(data.len() * 3 / 4);
+        let mut buffer = 0u32;
+        let mut bits = 0;
+        for &c in data {
+            let value = match c {
+                'A'..='Z' => c as u32 - 'A' as u32,
+                'a'..='z' => c as u32 - 'a' as u32 + 26,
+                '0'..='9' => c as u32 - '0' as u32 + 52,
+                '+' | '-' => 62,
+                '/' | '_' => 63,
+                _ => return Err(format!("Invalid base64 character: {}", c)),
+            };
+            buffer = (buffer << 6) | value;
+            bits += 6;
+            if bits >= 8 {
+                bits -= 8;
+                res.push((buffer >> bits) as u8);
+                buffer &= (1 << bits) - 1;
+            }
+        }
+        Ok(res)
+    }
+}
+
+/// The hexadecimal encoding, with an optional `0x` prefix.
+#[derive(Debug)]
+pub struct Hex;
+
+impl BinaryEncoding for Hex {
+    const PROMPT: &'static str = "Paste the hex data";
+
+    fn decode(text: &str) -> Result<Vec<u8>, String> {
+        let text: String = text.chars().filter(|c| !c.is_whitespace()).collect();
+        let text = text.strip_prefix("0x").unwrap_or(&text);
+        if !text.len().is_multiple_of(2) {
+            return Err("Invalid hex length".into());
+        }
+        (0..text.len())
+            .step_by(2)
+            .map(|i| {
+                text.get(i..i + 2)
+                    .and_then(|byte| u8::from_str_radix(byte, 16).ok())
+                    .ok_or_else(|| "Invalid hex character".to_string())
+            })
+            .collect()
+    }
+}
+
+/// Builder for the type `Vec<u8>`, inserted as text in the provided encoding. The node of the
+/// value only shows the length of the data.
+pub struct BinaryBuilder<E: BinaryEncoding> {
+    /// The current value.
+    pub value: Option<Vec<u8>>,
+    /// The message to show to the user.
+    pub prompt: String,
+    /// The functions that check the value inserted by the user.
+    pub validators: Vec<Validator<Vec<u8>>>,
+    /// The warning of the last validation of the value, if any.
+    pub warning: Option<String>,
+    /// The functions applied to the text inserted by the user before decoding it.
+    pub preprocessors: Vec<Preprocessor>,
+    /// The characters accepted in the text inserted by the user, if limited.
+    pub charset: Option<Charset>,
+    encoding: PhantomData<E>,
+}
+
+impl<E: BinaryEncoding> BinaryBuilder<E> {
+    /// Make a new instance of the builder.
+    pub fn new(config: BuildableValueConfig<Vec<u8>>) -> Self {
+        Self {
+            value: config.default,
+            prompt: config.prompt.unwrap_or_else(|| E::PROMPT.to_string()),
+            validators: config.validators,
+            warning: None,
+            preprocessors: config.preprocessors,
+            charset: config.charset,
+            encoding: Default::default(),
+        }
+    }
+}
+
+impl<E: BinaryEncoding> std::fmt::Debug for BinaryBuilder<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BinaryBuilder")
+            .field("value", &self.value)
+            .field("prompt", &self.prompt)
+            .field("warning", &self.warning)
+            .finish()
+    }
+}
+
+impl<E: BinaryEncoding> TypedBuildableValue<Vec<u8>> for BinaryBuilder<E> {
+    fn get_value(&self) -> Option<Vec<u8>> {
+        self.value.clone()
+    }
+}
+
+impl<E: BinaryEncoding> BuildableValue for BinaryBuilder<E> {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            panic!(
+                "BinaryBuilder.apply() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        match data {
+            Input::Text(data) => {
+                let data = preprocess(&self.preprocessors, data);
+                charset::check(&self.charset, &data)?;
+                let value = E::decode(&data).map_err(|error| ChooseError::InvalidText { error })?;
+                self.warning = validate(&self.validators, &value)?;
+                self.value = Some(value);
+            }
+            _ => return Err(ChooseError::UnexpectedChoice),
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if !current_fields.is_empty() {
+            panic!(
+                "BinaryBuilder.get_options() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        Options {
+            query: self.prompt.clone(),
+            text_input: true,
+            choices: vec![],
+            warning: self.warning.clone(),
+            ..Default::default()
+        }
+    }
+
+    fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
+        vec![]
+    }
+
+    fn to_node(&self) -> Node {
+        leaf_node(
+            self.value.as_ref().map(|v| format!("{} bytes", v.len())),
+            &self.warning,
+        )
+    }
+
+    crate::typed_get_value_any!(Vec<u8>);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        let value = match value.downcast::<Vec<u8>>() {
+            Ok(value) => *value,
+            Err(_) => return false,
+        };
+        match validate(&self.validators, &value) {
+            Ok(warning) => {
+                self.warning = warning;
+                self.value = Some(value);
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(BinaryBuilder::<E> {
+            value: self.value.clone(),
+            prompt: self.prompt.clone(),
+            validators: self.validators.clone(),
+            warning: self.warning.clone(),
+            preprocessors: self.preprocessors.clone(),
+            charset: self.charset.clone(),
+            encoding: PhantomData,
+        }))
+    }
+}
+
+/// Builder for the type `Vec<T>`.
+///
+/// The type parameters are:
+/// - `B`: the type of the builder that produces `T`
+/// - `T`: the type of the items of the final `Vec`
+///
+/// The state machine that this builder implements is a bit complex since it has to handle
+/// insertions, deletions and updates and it looks like this:
+///
+/// ```text
+///                 |
+///                 v
+///            +-------------+    __new    +-------------+  <B> specific
+///  +-------> |  empty      | ----------> | not empty   | ------>>>
+///  |         |  main       |             | edit        |
+///  |         +-------------+             +-------------+
+///  |                                         ^    |
+///  |                                         |    |
+///  |         +-------------+  index / __new  |    | __back
+///  +-------> |  not empty  | ----------------+    |
+///  |         |  main       | <--------------------+
+///  | index   +-------------+
+///  |            ^    |
+///  |     __back |    | __remove
+///  |            |    v
+///  |         +-------------+
+///  +-------- |  not empty  |
+///            |  remove     |
+///            +-------------+
+/// ```
+///
+/// When `__new` is applied a new item is pushed at the back of the `Vec` and when `__new` is to
+/// be considered as an index it refers to the last element of the `Vec`.
+///
+/// `__duplicate` works like `__remove`: the user selects an item, and a copy of it (see
+/// `BuildableValue::clone_boxed`) is pushed at the back of the `Vec`. The choice is shown only if
+/// the builder of the items can be copied.
+///
+/// The items are stored in a `Slab` and they are identified by their key instead of their
+/// position, so removing an item doesn't change the identifier of the following ones. The key of
+/// a removed item may be reused by a new one.
+pub struct VecBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    items: Slab<Box<dyn BuildableValue>>,
+    /// The keys of the items, in the order of the `Vec`.
+    order: Vec<usize>,
+    /// The number of items that are not complete, kept up to date by `apply`.
+    incomplete: usize,
+    inner_type: PhantomData<T>,
+    prompt: String,
+}
+
+impl<T> std::fmt::Debug for VecBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VecBuilder")
+            .field("items", &self.iter_items().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<T> NewBuildableValue for Vec<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(VecBuilder::<T>::new(config))
+    }
+}
+
+impl<T> VecBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    /// Make a new builder for a `Vec<T>`, without items.
+    pub fn new(config: BuildableValueConfig<()>) -> VecBuilder<T> {
+        VecBuilder {
+            items: Slab::new(),
+            order: Vec::new(),
+            incomplete: 0,
+            inner_type: Default::default(),
+            prompt: config
+                .prompt
+                .unwrap_or_else(|| "Select an action".to_string()),
+        }
+    }
+
+    /// A copy of the builder with all its items, `None` if an item cannot be copied.
+    fn try_clone(&self) -> Option<VecBuilder<T>> {
+        let items = self
+            .items
+            .iter()
+            .map(|(key, item)| Some((key, item.clone_boxed()?)))
+            .collect::<Option<Slab<_>>>()?;
+        Some(VecBuilder {
+            items,
+            order: self.order.clone(),
+            incomplete: self.incomplete,
+            inner_type: PhantomData,
+            prompt: self.prompt.clone(),
+        })
+    }
+
+    /// The key of the item referred by the field, `__new` refers to the last one.
+    fn item_key(&self, field: &str) -> usize {
+        match field {
+            "__new" => *self.order.last().expect("Vec __new didn't push"),
+            key => {
+                let key =
+                    usize::from_str(key).unwrap_or_else(|_| panic!("Invalid key for vec: {}", key));
+                assert!(self.items.contains(key), "Invalid key for vec: {}", key);
+                key
+            }
+        }
+    }
+
+    /// The key of the item selected by the user, if valid.
+    fn parse_key(&self, choice: &str) -> Result<usize, ChooseError> {
+        let key = usize::from_str(choice).map_err(|_| ChooseError::UnexpectedChoice)?;
+        if self.items.contains(key) {
+            Ok(key)
+        } else {
+            Err(ChooseError::UnexpectedChoice)
+        }
+    }
+
+    /// Whether the items can be duplicated, i.e. their builder supports `clone_boxed`.
+    fn can_duplicate(&self) -> bool {
+        T::new_buildable_value(Default::default())
+            .clone_boxed()
+            .is_some()
+    }
+
+    /// The items in the order of the `Vec`.
+    fn iter_items(&self) -> impl Iterator<Item = &Box<dyn BuildableValue>> {
+        self.order.iter().map(move |key| &self.items[*key])
+    }
+
+    /// Apply the input to an item, keeping the count of the incomplete items up to date.
+    fn apply_to_item(
+        &mut self,
+        key: usize,
+        data: &Input,
+        current_fields: &[Id],
+    ) -> Result<(), ChooseError> {
+        let item = &mut self.items[key];
+        let was_complete = item.is_complete();
+        let res = item.apply(data, current_fields);
+        self.incomplete = self.incomplete + was_complete as usize - item.is_complete() as usize;
+        res
+    }
+}
+
+impl<T> TypedBuildableValue<Vec<T>> for VecBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn get_value(&self) -> Option<Vec<T>> {
+        let mut results: Vec<T> = Vec::with_capacity(self.items.len());
+        for item in self.iter_items() {
+            results.push(*item.get_value_any()?.downcast::<T>().unwrap());
+        }
+        Some(results)
+    }
+}
+
+impl<T> BuildableValue for VecBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        // vec main menu
+        if current_fields.is_empty() {
+            match data {
+                Input::Choice(data) if data == "__new" => {
+                    let item = T::new_buildable_value(Default::default());
+                    if !item.is_complete() {
+                        self.incomplete += 1;
+                    }
+                    let key = self.items.insert(item);
+                    self.order.push(key);
+                }
+                Input::Choice(data) => {
+                    if data != "__remove" && data != "__duplicate" {
+                        // check that the inserted key is valid
+                        self.parse_key(data)?;
+                    }
+                }
+                _ => return Err(ChooseError::UnexpectedText),
+            }
+        // remove item or apply to element
+        } else {
+            let field = &current_fields[0];
+            let rest = &current_fields[1..];
+            match field.as_str() {
+                "__remove" => match data {
+                    Input::Choice(choice) => {
+                        let key = self.parse_key(choice)?;
+                        self.order.retain(|k| *k != key);
+                        if !self.items.remove(key).is_complete() {
+                            self.incomplete -= 1;
+                        }
+                    }
+                    Input::Text(_) => return Err(ChooseError::UnexpectedText),
+                },
+                "__duplicate" => match data {
+                    Input::Choice(choice) => {
+                        let key = self.parse_key(choice)?;
+                        let item = self.items[key]
+                            .clone_boxed()
+                            .ok_or(ChooseError::UnexpectedChoice)?;
+                        if !item.is_complete() {
+                            self.incomplete += 1;
+                        }
+                        let key = self.items.insert(item);
+                        self.order.push(key);
+                    }
+                    Input::Text(_) => return Err(ChooseError::UnexpectedText),
+                },
+                field => {
+                    let key = self.item_key(field);
+                    self.apply_to_item(key, data, rest)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        // vec main manu
+        if current_fields.is_empty() {
+            let mut choices = vec![Choice {
+                choice_id: "__new".into(),
+                text: "New element".to_string(),
+                needs_action: false,
+                ..Default::default()
+            }];
+            if !self.items.is_empty() {
+                choices.push(Choice {
+                    choice_id: "__remove".into(),
+                    text: "Remove element".to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                });
+                if self.can_duplicate() {
+                    choices.push(Choice {
+                        choice_id: "__duplicate".into(),
+                        text: "Duplicate element".to_string(),
+                        needs_action: false,
+                        ..Default::default()
+                    });
+                }
+                for (i, key) in self.order.iter().enumerate() {
+                    choices.push(Choice {
+                        choice_id: key.to_string().into(),
+                        text: format!("Edit item {}", i),
+                        needs_action: !self.items[*key].is_complete(),
+                        ..Default::default()
+                    });
+                }
+            }
+            Options {
+                query: self.prompt.clone(),
+                text_input: false,
+                choices,
+                ..Default::default()
+            }
+        // item menu
+        } else {
+            let field = &current_fields[0];
+            let rest = &current_fields[1..];
+            match field.as_str() {
+                // select the item to remove
+                "__remove" => {
+                    let mut choices = Vec::new();
+                    for (i, key) in self.order.iter().enumerate() {
+                        choices.push(Choice {
+                            choice_id: key.to_string().into(),
+                            text: format!("Remove item {}", i),
+                            needs_action: false,
+                            ..Default::default()
+                        });
+                    }
+                    Options {
+                        query: "Select the item to remove".to_string(),
+                        text_input: false,
+                        choices,
+                        ..Default::default()
+                    }
+                }
+                // select the item to copy at the end of the vec
+                "__duplicate" => {
+                    let choices = self
+                        .order
+                        .iter()
+                        .enumerate()
+                        .map(|(i, key)| Choice {
+                            choice_id: key.to_string().into(),
+                            text: format!("Duplicate item {}", i),
+                            needs_action: false,
+                            ..Default::default()
+                        })
+                        .collect();
+                    Options {
+                        query: "Select the item to duplicate".to_string(),
+                        text_input: false,
+                        choices,
+                        ..Default::default()
+                    }
+                }
+                // edit one of the items, __new is the last one
+                field => self.items[self.item_key(field)].get_options(rest),
+            }
+        }
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        // main manu
+        if current_fields.is_empty() {
+            if self.items.is_empty() {
+                vec!["__new".into()]
+            } else {
+                let mut res = vec!["__new".into(), "__remove".into()];
+                if self.can_duplicate() {
+                    res.push("__duplicate".into());
+                }
+                res.extend(self.order.iter().map(|key| key.to_string().into()));
+                res
+            }
+        } else {
+            let field = &current_fields[0];
+            let rest = &current_fields[1..];
+            match field.as_str() {
+                // just select the item to remove or to duplicate
+                "__remove" | "__duplicate" => vec![],
+                field => self.items[self.item_key(field)].get_subfields(rest),
+            }
+        }
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        if current_fields.is_empty() {
+            return self
+                .get_subfields(current_fields)
+                .into_iter()
+                .find(|f| f == name);
+        }
+        let field = &current_fields[0];
+        let rest = &current_fields[1..];
+        match field.as_str() {
+            "__remove" | "__duplicate" => None,
+            field => self.items[self.item_key(field)].subfield(rest, name),
+        }
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        if current_fields.is_empty() {
+            return None;
+        }
+        let field = &current_fields[0];
+        let rest = &current_fields[1..];
+        match field.as_str() {
+            "__remove" | "__duplicate" => None,
+            field => self.items[self.item_key(field)].auto_enter(rest, entry),
+        }
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        if current_fields.is_empty() {
+            return false;
+        }
+        let field = &current_fields[0];
+        let rest = &current_fields[1..];
+        match field.as_str() {
+            "__remove" | "__duplicate" => false,
+            field => self.items[self.item_key(field)].stay_after_apply(rest),
+        }
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        let field = current_fields.first()?;
+        let rest = &current_fields[1..];
+        match field.as_str() {
+            "__remove" | "__duplicate" => None,
+            field => self.items[self.item_key(field)].recorded_input(data, rest),
+        }
+    }
+
+    fn to_node(&self) -> Node {
+        self.to_node_pruned(usize::MAX)
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        if depth == 0 {
+            return Node::Leaf(Field::Elided);
+        }
+        let items = self
+            .iter_items()
+            .map(|i| FieldKind::Unnamed(i.to_node_pruned(depth - 1)))
+            .collect();
+        // Vec has no name
+        Node::Composite("".into(), items)
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        if !visitor.enter_composite("") {
+            return;
+        }
+        for item in self.iter_items() {
+            if visitor.enter_field(None) {
+                item.visit_nodes(visitor);
+            }
+        }
+        visitor.leave_composite();
+    }
+
+    crate::typed_get_value_any!(Vec<T>);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        let value = match value.downcast::<Vec<T>>() {
+            Ok(value) => *value,
+            Err(_) => return false,
+        };
+        self.items.clear();
+        self.order.clear();
+        let mut res = true;
+        for item in value {
+            let mut builder = T::new_buildable_value(Default::default());
+            res &= builder.set_value_any(Box::new(item));
+            let key = self.items.insert(builder);
+            self.order.push(key);
+        }
+        self.refresh();
+        res
+    }
+
+    fn is_complete(&self) -> bool {
+        self.incomplete == 0
+    }
+
+    fn refresh(&mut self) {
+        self.incomplete = self.iter_items().filter(|i| !i.is_complete()).count();
+    }
+
+    fn schema(&self) -> Schema {
+        let items = T::new_buildable_value(Default::default()).schema();
+        Schema::new(SchemaKind::Array(Box::new(items)))
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(self.try_clone()?))
+    }
+}
+
+/// Builder for the types `HashSet<T>` and `BTreeSet<T>`, where `S` is the type of the set.
+///
+/// The menu is the one of `VecBuilder`, without the "Duplicate element" choice. An input that
+/// makes an item equal to another one is rejected with `ChooseError::DuplicateValue`, leaving the
+/// item as it was. If the builder of the items cannot be copied (see `BuildableValue::clone_boxed`)
+/// the input is kept instead, and the set is not complete until the duplicated items are changed
+/// or removed.
+pub struct SetBuilder<T, S>
+where
+    T: NewBuildableValue + 'static,
+{
+    items: VecBuilder<T>,
+    set_type: PhantomData<S>,
+}
+
+impl<T, S> std::fmt::Debug for SetBuilder<T, S>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SetBuilder")
+            .field("items", &self.items.iter_items().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<T, S> SetBuilder<T, S>
+where
+    T: NewBuildableValue + PartialEq + 'static,
+{
+    /// Make a new builder for a set, without items.
+    pub fn new(config: BuildableValueConfig<()>) -> Self {
+        Self {
+            items: VecBuilder::new(config),
+            set_type: Default::default(),
+        }
+    }
+
+    /// The value of a complete item.
+    fn item_value(item: &dyn BuildableValue) -> Option<T> {
+        Some(*item.get_value_any()?.downcast::<T>().unwrap())
+    }
+
+    /// Whether the item with this key is complete and equal to another item.
+    fn is_duplicated(&self, key: usize) -> bool {
+        let value = match Self::item_value(self.items.items[key].as_ref()) {
+            Some(value) => value,
+            None => return false,
+        };
+        self.items
+            .items
+            .iter()
+            .filter(|(k, _)| *k != key)
+            .any(|(_, item)| Self::item_value(item.as_ref()).as_ref() == Some(&value))
+    }
+
+    /// Whether some complete items are equal.
+    fn has_duplicates(&self) -> bool {
+        self.items
+            .items
+            .iter()
+            .any(|(key, _)| self.is_duplicated(key))
+    }
+}
+
+impl<T> NewBuildableValue for HashSet<T>
+where
+    T: NewBuildableValue + Eq + Hash + 'static,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(SetBuilder::<T, HashSet<T>>::new(config))
+    }
+}
+
+impl<T> NewBuildableValue for BTreeSet<T>
+where
+    T: NewBuildableValue + Ord + 'static,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(SetBuilder::<T, BTreeSet<T>>::new(config))
+    }
+}
+
+impl<T, S> TypedBuildableValue<S> for SetBuilder<T, S>
+where
+    T: NewBuildableValue + PartialEq + 'static,
+    S: FromIterator<T> + IntoIterator<Item = T> + 'static,
+{
+    fn get_value(&self) -> Option<S> {
+        if self.has_duplicates() {
+            return None;
+        }
+        Some(self.items.get_value()?.into_iter().collect())
+    }
+}
+
+impl<T, S> BuildableValue for SetBuilder<T, S>
+where
+    T: NewBuildableValue + PartialEq + 'static,
+    S: FromIterator<T> + IntoIterator<Item = T> + 'static,
+{
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        let field = match current_fields.first() {
+            Some(field) => field.as_str(),
+            None if *data == Input::choice("__duplicate") => {
+                return Err(ChooseError::UnexpectedChoice)
+            }
+            None => return self.items.apply(data, current_fields),
+        };
+        match field {
+            "__duplicate" => Err(ChooseError::UnexpectedChoice),
+            "__remove" => self.items.apply(data, current_fields),
+            field => {
+                let key = self.items.item_key(field);
+                let previous = self.items.items[key].clone_boxed();
+                self.items.apply(data, current_fields)?;
+                match previous {
+                    Some(previous) if self.is_duplicated(key) => {
+                        self.items.items[key] = previous;
+                        self.items.refresh();
+                        Err(ChooseError::DuplicateValue)
+                    }
+                    _ => Ok(()),
+                }
+            }
+        }
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        let mut options = self.items.get_options(current_fields);
+        if current_fields.is_empty() {
+            options.choices.retain(|c| c.choice_id != "__duplicate");
+            for choice in &mut options.choices {
+                if let Ok(key) = self.items.parse_key(&choice.choice_id) {
+                    choice.needs_action |= self.is_duplicated(key);
+                }
+            }
+        }
+        options
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        let mut subfields = self.items.get_subfields(current_fields);
+        if current_fields.is_empty() {
+            subfields.retain(|f| f != "__duplicate");
+        }
+        subfields
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        if current_fields.is_empty() && name == "__duplicate" {
+            return None;
+        }
+        self.items.subfield(current_fields, name)
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        self.items.auto_enter(current_fields, entry)
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        self.items.stay_after_apply(current_fields)
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        self.items.recorded_input(data, current_fields)
+    }
+
+    fn to_node(&self) -> Node {
+        self.items.to_node()
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        self.items.to_node_pruned(depth)
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        self.items.visit_nodes(visitor)
+    }
+
+    crate::typed_get_value_any!(S);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        match value.downcast::<S>() {
+            Ok(value) => {
+                let items: Vec<T> = value.into_iter().collect();
+                self.items.set_value_any(Box::new(items))
+            }
+            Err(_) => false,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.items.is_complete() && !self.has_duplicates()
+    }
+
+    fn refresh(&mut self) {
+        self.items.refresh()
+    }
+
+    fn schema(&self) -> Schema {
+        self.items.schema()
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(SetBuilder::<T, S> {
+            items: self.items.try_clone()?,
+            set_type: PhantomData,
+        }))
+    }
+}
+
+/// An entry of a `MapBuilder`.
+struct MapEntry<K> {
+    /// The key, with the text it has been parsed from.
+    key: Option<(K, String)>,
+    /// The builder of the value.
+    value: Box<dyn BuildableValue>,
+}
+
+/// Builder for the types `HashMap<K, V>` and `BTreeMap<K, V>`, where `M` is the type of the map.
+/// The keys are parsed from the text of the user with `FromStr`, the values have their own
+/// builders.
+///
+/// The menu works like the one of `VecBuilder`: entries can be added with `__new`, removed with
+/// `__remove` or edited by index. Inside an entry the user is asked for the key and then for the
+/// value.
+pub struct MapBuilder<K, V, M> {
+    entries: Vec<MapEntry<K>>,
+    prompt: String,
+    map_type: PhantomData<(V, M)>,
+}
+
+/// Builder for the type `HashMap<String, String>`, for free-form sections like labels or
+/// annotations.
+pub type KeyValueBuilder = MapBuilder<String, String, HashMap<String, String>>;
+
+impl<K, V, M> MapBuilder<K, V, M>
+where
+    K: FromStr + Clone + PartialEq + 'static,
+    K::Err: std::fmt::Display,
+    V: NewBuildableValue + 'static,
+    M: FromIterator<(K, V)> + 'static,
+{
+    /// Make a new instance of the builder, without entries.
+    pub fn new(config: BuildableValueConfig<()>) -> Self {
+        Self {
+            entries: Vec::new(),
+            prompt: config
+                .prompt
+                .unwrap_or_else(|| "Select an action".to_string()),
+            map_type: Default::default(),
+        }
+    }
+
+    /// The index of the entry referred by the field, `__new` refers to the last one.
+    fn entry_index(&self, field: &str) -> usize {
+        match field {
+            "__new" => self.entries.len() - 1,
+            index => usize::from_str(index)
+                .unwrap_or_else(|_| panic!("Invalid index for map: {}", index)),
+        }
+    }
+
+    /// The text describing the entry in the menus.
+    fn entry_name(&self, index: usize) -> String {
+        match &self.entries[index].key {
+            Some((_, key)) => key.clone(),
+            None => format!("entry {}", index),
+        }
+    }
+
+    /// Whether both the key and the value of the entry have been inserted.
+    fn is_entry_complete(entry: &MapEntry<K>) -> bool {
+        entry.key.is_some() && entry.value.is_complete()
+    }
+}
+
+impl<K, V, M> std::fmt::Debug for MapBuilder<K, V, M> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let entries: Vec<_> = self
+            .entries
+            .iter()
+            .map(|e| (e.key.as_ref().map(|(_, key)| key), &e.value))
+            .collect();
+        f.debug_struct("MapBuilder")
+            .field("entries", &entries)
+            .field("prompt", &self.prompt)
+            .finish()
+    }
+}
+
+impl<K, V> NewBuildableValue for HashMap<K, V>
+where
+    K: FromStr + std::fmt::Display + Clone + Eq + Hash + 'static,
+    K::Err: std::fmt::Display,
+    V: NewBuildableValue + 'static,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(MapBuilder::<K, V, HashMap<K, V>>::new(config))
+    }
+}
+
+impl<K, V> NewBuildableValue for BTreeMap<K, V>
+where
+    K: FromStr + std::fmt::Display + Clone + Ord + 'static,
+    K::Err: std::fmt::Display,
+    V: NewBuildableValue + 'static,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(MapBuilder::<K, V, BTreeMap<K, V>>::new(config))
+    }
+}
+
+impl<K, V, M> TypedBuildableValue<M> for MapBuilder<K, V, M>
+where
+    K: FromStr + std::fmt::Display + Clone + PartialEq + 'static,
+    K::Err: std::fmt::Display,
+    V: NewBuildableValue + 'static,
+    M: FromIterator<(K, V)> + IntoIterator<Item = (K, V)> + 'static,
+{
+    fn get_value(&self) -> Option<M> {
+        let mut results = Vec::with_capacity(self.entries.len());
+        for entry in &self.entries {
+            let (key, _) = entry.key.as_ref()?;
+            let value = *entry.value.get_value_any()?.downcast::<V>().unwrap();
+            results.push((key.clone(), value));
+        }
+        Some(results.into_iter().collect())
+    }
+}
+
+impl<K, V, M> BuildableValue for MapBuilder<K, V, M>
+where
+    K: FromStr + std::fmt::Display + Clone + PartialEq + 'static,
+    K::Err: std::fmt::Display,
+    V: NewBuildableValue + 'static,
+    M: FromIterator<(K, V)> + IntoIterator<Item = (K, V)> + 'static,
+{
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        // map main menu
+        if current_fields.is_empty() {
+            match data {
+                Input::Choice(data) if data == "__new" => {
+                    self.entries.push(MapEntry {
+                        key: None,
+                        value: V::new_buildable_value(Default::default()),
+                    });
+                }
+                Input::Choice(data) => {
+                    if data != "__remove" {
+                        let index =
+                            usize::from_str(data).map_err(|_| ChooseError::UnexpectedChoice)?;
+                        if index >= self.entries.len() {
+                            return Err(ChooseError::UnexpectedChoice);
+                        }
+                    }
+                }
+                _ => return Err(ChooseError::UnexpectedText),
+            }
+            return Ok(());
+        }
+        let field = &current_fields[0];
+        if field == "__remove" {
+            return match data {
+                Input::Choice(choice) => {
+                    let index =
+                        usize::from_str(choice).map_err(|_| ChooseError::UnexpectedChoice)?;
+                    if index >= self.entries.len() {
+                        return Err(ChooseError::UnexpectedChoice);
+                    }
+                    self.entries.remove(index);
+                    Ok(())
+                }
+                Input::Text(_) => Err(ChooseError::UnexpectedText),
+            };
+        }
+        let index = self.entry_index(field);
+        match (current_fields.get(1).map(|f| f.as_str()), data) {
+            // entry menu
+            (None, Input::Choice(data)) if data == "key" || data == "value" => {}
+            (None, Input::Choice(_)) => return Err(ChooseError::UnexpectedChoice),
+            (None, Input::Text(_)) => return Err(ChooseError::UnexpectedText),
+            (Some("key"), Input::Text(text)) => {
+                let key = K::from_str(text).map_err(|e| ChooseError::InvalidText {
+                    error: e.to_string(),
+                })?;
+                let duplicated = self
+                    .entries
+                    .iter()
+                    .enumerate()
+                    .any(|(i, e)| i != index && e.key.as_ref().map(|(k, _)| k) == Some(&key));
+                if duplicated {
+                    return Err(ChooseError::InvalidText {
+                        error: format!("Duplicated key: {}", text),
+                    });
+                }
+                self.entries[index].key = Some((key, text.clone()));
+            }
+            (Some("key"), Input::Choice(_)) => return Err(ChooseError::UnexpectedChoice),
+            (Some(_), _) => self.entries[index]
+                .value
+                .apply(data, &current_fields[2..])?,
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        // map main menu
+        if current_fields.is_empty() {
+            let mut choices = vec![Choice {
+                choice_id: "__new".into(),
+                text: "New entry".to_string(),
+                needs_action: false,
+                ..Default::default()
+            }];
+            if !self.entries.is_empty() {
+                choices.push(Choice {
+                    choice_id: "__remove".into(),
+                    text: "Remove entry".to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                });
+                for (i, entry) in self.entries.iter().enumerate() {
+                    choices.push(Choice {
+                        choice_id: i.to_string().into(),
+                        text: format!("Edit {}", self.entry_name(i)),
+                        needs_action: !Self::is_entry_complete(entry),
+                        ..Default::default()
+                    });
+                }
+            }
+            return Options {
+                query: self.prompt.clone(),
+                text_input: false,
+                choices,
+                ..Default::default()
+            };
+        }
+        let field = &current_fields[0];
+        if field == "__remove" {
+            let choices = (0..self.entries.len())
+                .map(|i| Choice {
+                    choice_id: i.to_string().into(),
+                    text: format!("Remove {}", self.entry_name(i)),
+                    needs_action: false,
+                    ..Default::default()
+                })
+                .collect();
+            return Options {
+                query: "Select the entry to remove".to_string(),
+                text_input: false,
+                choices,
+                ..Default::default()
+            };
+        }
+        let entry = &self.entries[self.entry_index(field)];
+        match current_fields.get(1).map(|f| f.as_str()) {
+            None => Options {
+                query: "Edit the entry".to_string(),
+                text_input: false,
+                choices: vec![
+                    Choice {
+                        choice_id: "key".into(),
+                        text: "Edit key".to_string(),
+                        needs_action: entry.key.is_none(),
+                        ..Default::default()
+                    },
+                    Choice {
+                        choice_id: "value".into(),
+                        text: "Edit value".to_string(),
+                        needs_action: !entry.value.is_complete(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            },
+            Some("key") => Options {
+                query: "Type the key".to_string(),
+                text_input: true,
+                choices: vec![],
+                ..Default::default()
+            },
+            Some(_) => {
+                let rest = &current_fields[2..];
+                let mut options = entry.value.get_options(rest);
+                // the textual values are asked like the keys
+                if rest.is_empty() && options.text_input {
+                    options.query = "Type the value".to_string();
+                }
+                options
+            }
+        }
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        match current_fields.len() {
+            0 => {
+                if self.entries.is_empty() {
+                    vec!["__new".into()]
+                } else {
+                    let mut res = vec!["__new".into(), "__remove".into()];
+                    for i in 0..self.entries.len() {
+                        res.push(i.to_string().into());
+                    }
+                    res
+                }
+            }
+            1 if current_fields[0] != "__remove" => vec!["key".into(), "value".into()],
+            1 => vec![],
+            _ if current_fields[1] == "value" => {
+                let entry = &self.entries[self.entry_index(&current_fields[0])];
+                entry.value.get_subfields(&current_fields[2..])
+            }
+            _ => vec![],
+        }
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        if current_fields.len() >= 2 && current_fields[1] == "value" {
+            let entry = &self.entries[self.entry_index(&current_fields[0])];
+            return entry.value.subfield(&current_fields[2..], name);
+        }
+        self.get_subfields(current_fields)
+            .into_iter()
+            .find(|f| f == name)
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        match current_fields.get(1).map(|f| f.as_str()) {
+            Some("value") => {
+                let entry = &self.entries[self.entry_index(&current_fields[0])];
+                entry.value.stay_after_apply(&current_fields[2..])
+            }
+            _ => false,
+        }
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        match current_fields.get(1).map(|f| f.as_str()) {
+            Some("value") => {
+                let entry = &self.entries[self.entry_index(&current_fields[0])];
+                entry.value.recorded_input(data, &current_fields[2..])
+            }
+            _ => None,
+        }
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        if current_fields.is_empty() || current_fields[0] == "__remove" {
+            return None;
+        }
+        let map_entry = &self.entries[self.entry_index(&current_fields[0])];
+        if current_fields.len() > 1 {
+            return match current_fields[1].as_str() {
+                "value" => map_entry.value.auto_enter(&current_fields[2..], entry),
+                _ => None,
+            };
+        }
+        if let MenuEntry::WentBack(_) = entry {
+            return None;
+        }
+        // ask for the key and then for the value
+        if map_entry.key.is_none() {
+            Some("key".into())
+        } else if !map_entry.value.is_complete() {
+            Some("value".into())
+        } else {
+            None
+        }
+    }
+
+    fn to_node(&self) -> Node {
+        self.to_node_pruned(usize::MAX)
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        if depth == 0 {
+            return Node::Leaf(Field::Elided);
+        }
+        let entries = self
+            .entries
+            .iter()
+            .map(|e| {
+                let key = e.key.as_ref().map(|(_, key)| key.clone());
+                FieldKind::Named(key.unwrap_or_default(), e.value.to_node_pruned(depth - 1))
+            })
+            .collect();
+        // the maps have no name
+        Node::Composite("".into(), entries)
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        if !visitor.enter_composite("") {
+            return;
+        }
+        for entry in &self.entries {
+            let key = entry.key.as_ref().map(|(_, key)| key.as_str());
+            if visitor.enter_field(Some(key.unwrap_or_default())) {
+                entry.value.visit_nodes(visitor);
+            }
+        }
+        visitor.leave_composite();
+    }
+
+    crate::typed_get_value_any!(M);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        let value = match value.downcast::<M>() {
+            Ok(value) => *value,
+            Err(_) => return false,
+        };
+        self.entries.clear();
+        let mut res = true;
+        for (key, value) in value {
+            let mut builder = V::new_buildable_value(Default::default());
+            res &= builder.set_value_any(Box::new(value));
+            let text = key.to_string();
+            self.entries.push(MapEntry {
+                key: Some((key, text)),
+                value: builder,
+            });
+        }
+        res
+    }
+
+    fn is_complete(&self) -> bool {
+        self.entries.iter().all(Self::is_entry_complete)
+    }
+
+    fn schema(&self) -> Schema {
+        let values = V::new_buildable_value(Default::default()).schema();
+        Schema::new(SchemaKind::Map(Box::new(values)))
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        let entries = self
+            .entries
+            .iter()
+            .map(|entry| {
+                Some(MapEntry {
+                    key: entry.key.clone(),
+                    value: entry.value.clone_boxed()?,
+                })
+            })
+            .collect::<Option<_>>()?;
+        Some(Box::new(MapBuilder::<K, V, M> {
+            entries,
+            prompt: self.prompt.clone(),
+            map_type: PhantomData,
+        }))
+    }
+}
+
+/// Builder for the type `Box<T>`.
+pub struct BoxBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    value: Box<dyn BuildableValue>,
+    inner_type: PhantomData<T>,
+}
+
+impl<T> std::fmt::Debug for BoxBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BoxBuilder")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T> NewBuildableValue for Box<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(BoxBuilder::<T> {
+            value: T::new_buildable_value(config),
+            inner_type: Default::default(),
+        })
+    }
+}
+
+impl<T> TypedBuildableValue<Box<T>> for BoxBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn get_value(&self) -> Option<Box<T>> {
+        self.value.get_value_any()?.downcast::<T>().ok()
+    }
+}
+
+impl<T> BuildableValue for BoxBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        self.value.apply(data, current_fields)
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        self.value.get_options(current_fields)
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        self.value.get_subfields(current_fields)
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        self.value.subfield(current_fields, name)
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        self.value.stay_after_apply(current_fields)
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        self.value.recorded_input(data, current_fields)
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        self.value.auto_enter(current_fields, entry)
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn BuildableValue> {
+        self.value.get_child(name)
+    }
+
+    fn get_child_mut(&mut self, name: &str) -> Option<&mut Box<dyn BuildableValue>> {
+        self.value.get_child_mut(name)
+    }
+
+    fn to_node(&self) -> Node {
+        self.value.to_node()
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        self.value.to_node_pruned(depth)
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        self.value.visit_nodes(visitor)
+    }
+
+    crate::typed_get_value_any!(Box<T>);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        match value.downcast::<Box<T>>() {
+            Ok(value) => self.value.set_value_any(Box::new(*value)),
+            Err(_) => false,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        self.value.is_complete()
+    }
+
+    fn refresh(&mut self) {
+        self.value.refresh()
+    }
+
+    fn schema(&self) -> Schema {
+        self.value.schema()
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(BoxBuilder::<T> {
+            value: self.value.clone_boxed()?,
+            inner_type: PhantomData,
+        }))
+    }
+}
+
+/// Wrapper of a builder that shows the value parsed from the text of the user before saving it,
+/// asking to confirm it or to insert the text again. Used by the fields with
+/// `#[ibuilder(preview)]`.
+///
+/// Only the text inserted in the menu of the field is previewed, the inputs for the inner menus
+/// (e.g. the ones of a `Vec`) are forwarded to the wrapped builder.
+pub struct PreviewBuilder {
+    /// The builder with the confirmed value.
+    value: Box<dyn BuildableValue>,
+    /// The builder with the parsed value waiting to be confirmed, if any.
+    pending: Option<Box<dyn BuildableValue>>,
+    /// The function that makes a fresh builder for parsing the text of the user.
+    make: Rc<dyn Fn() -> Box<dyn BuildableValue>>,
+    /// Whether the user should stay in the menu of the field after the last input.
+    stay: bool,
+}
+
+impl PreviewBuilder {
+    /// Make a new `PreviewBuilder` wrapping the builders made by the provided function.
+    pub fn new(make: Box<dyn Fn() -> Box<dyn BuildableValue>>) -> PreviewBuilder {
+        PreviewBuilder {
+            value: make(),
+            pending: None,
+            make: make.into(),
+            stay: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for PreviewBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PreviewBuilder")
+            .field("value", &self.value)
+            .field("pending", &self.pending)
+            .finish()
+    }
+}
+
+impl BuildableValue for PreviewBuilder {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            self.value.apply(data, current_fields)?;
+            self.stay = self.value.stay_after_apply(current_fields);
+            return Ok(());
+        }
+        match (data, &self.pending) {
+            (Input::Choice(data), Some(_)) => match data.as_str() {
+                "__confirm" => {
+                    self.value = self.pending.take().unwrap();
+                    self.value.refresh();
+                    self.stay = false;
+                }
+                "__reenter" => {
+                    self.pending = None;
+                    self.stay = true;
+                }
+                _ => return Err(ChooseError::UnexpectedChoice),
+            },
+            (Input::Text(_), None) if self.value.get_options(&[]).text_input => {
+                let mut pending = (self.make)();
+                pending.apply(data, &[])?;
+                self.pending = Some(pending);
+                self.stay = true;
+            }
+            (_, Some(_)) => return Err(ChooseError::UnexpectedText),
+            (_, None) => {
+                self.value.apply(data, &[])?;
+                self.stay = self.value.stay_after_apply(&[]);
+            }
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        let pending = match &self.pending {
+            Some(pending) if current_fields.is_empty() => pending,
+            _ => return self.value.get_options(current_fields),
+        };
+        let (parsed, warning) = match pending.to_node() {
+            Node::Leaf(Field::String(value)) => (value, None),
+            Node::Leaf(Field::Warning(value, warning)) => (value, Some(warning)),
+            node => (format!("{:?}", node), None),
+        };
+        Options {
+            query: format!("Parsed as {}", parsed),
+            text_input: false,
+            choices: vec![
+                Choice {
+                    choice_id: "__confirm".into(),
+                    text: "Confirm".to_string(),
+                    ..Default::default()
+                },
+                Choice {
+                    choice_id: "__reenter".into(),
+                    text: "Re-enter".to_string(),
+                    ..Default::default()
+                },
+            ],
+            warning,
+            ..Default::default()
+        }
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        if self.pending.is_some() && current_fields.is_empty() {
+            return vec![];
+        }
+        self.value.get_subfields(current_fields)
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        if self.pending.is_some() && current_fields.is_empty() {
+            return None;
+        }
+        self.value.subfield(current_fields, name)
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        if current_fields.is_empty() {
+            self.stay
+        } else {
+            self.value.stay_after_apply(current_fields)
+        }
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        // the text just parsed is waiting in the preview
+        match &self.pending {
+            Some(pending) if current_fields.is_empty() => pending.recorded_input(data, &[]),
+            _ => self.value.recorded_input(data, current_fields),
+        }
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        if self.pending.is_some() && current_fields.is_empty() {
+            return None;
+        }
+        self.value.auto_enter(current_fields, entry)
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn BuildableValue> {
+        self.value.get_child(name)
+    }
+
+    fn get_child_mut(&mut self, name: &str) -> Option<&mut Box<dyn BuildableValue>> {
+        self.value.get_child_mut(name)
+    }
+
+    fn to_node(&self) -> Node {
+        self.value.to_node()
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        self.value.to_node_pruned(depth)
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        self.value.visit_nodes(visitor)
+    }
+
+    fn get_value_any(&self) -> Option<Box<dyn Any>> {
+        self.value.get_value_any()
+    }
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        self.pending = None;
+        self.value.set_value_any(value)
+    }
+
+    fn is_complete(&self) -> bool {
+        self.value.is_complete()
+    }
+
+    fn refresh(&mut self) {
+        self.value.refresh()
+    }
+
+    fn schema(&self) -> Schema {
+        self.value.schema()
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        let pending = match &self.pending {
+            Some(pending) => Some(pending.clone_boxed()?),
+            None => None,
+        };
+        Some(Box::new(PreviewBuilder {
+            value: self.value.clone_boxed()?,
+            pending,
+            make: self.make.clone(),
+            stay: self.stay,
+        }))
+    }
+}
+
+/// The identifier of the input recorded in the history of the `Builder` in place of the invalid
+/// text that made a `FallbackBuilder` reset its value, so that applying the history again resets it
+/// too.
+pub const FALLBACK_ID: &str = "__fallback";
+
+/// Wrapper of a builder that resets the value to its default after too many invalid texts in a
+/// row, so the user doesn't get stuck on a field. Used by the fields with
+/// `#[ibuilder(max_attempts = N, fallback_default)]`.
+///
+/// The attempts are counted in the menu of the field and in the ones directly inside it (e.g. the
+/// value of an `Option`), the user is moved back to the parent of the menu of the failed attempt.
+/// The reset is recorded in the history as the `FALLBACK_ID` choice.
+pub struct FallbackBuilder {
+    /// The wrapped builder.
+    value: Box<dyn BuildableValue>,
+    /// The function that makes the builder with the default value.
+    make: Rc<dyn Fn() -> Box<dyn BuildableValue>>,
+    /// The number of invalid texts in a row that triggers the fallback.
+    max_attempts: usize,
+    /// The number of invalid texts in a row inserted so far.
+    failures: usize,
+    /// Whether the last input triggered the fallback.
+    fell_back: bool,
+}
+
+impl FallbackBuilder {
+    /// Make a new `FallbackBuilder` wrapping the builders made by the provided function, falling
+    /// back to a new one after `max_attempts` invalid texts in a row.
+    pub fn new(
+        make: Box<dyn Fn() -> Box<dyn BuildableValue>>,
+        max_attempts: usize,
+    ) -> FallbackBuilder {
+        FallbackBuilder {
+            value: make(),
+            make: make.into(),
+            max_attempts,
+            failures: 0,
+            fell_back: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for FallbackBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FallbackBuilder")
+            .field("value", &self.value)
+            .field("max_attempts", &self.max_attempts)
+            .field("failures", &self.failures)
+            .finish()
+    }
+}
+
+impl BuildableValue for FallbackBuilder {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        self.fell_back = false;
+        if current_fields.len() <= 1 && matches!(data, Input::Choice(c) if c == FALLBACK_ID) {
+            self.value = (self.make)();
+            self.failures = 0;
+            self.fell_back = true;
+            return Ok(());
+        }
+        match self.value.apply(data, current_fields) {
+            Err(ChooseError::InvalidText { error }) if current_fields.len() <= 1 => {
+                self.failures += 1;
+                if self.failures < self.max_attempts {
+                    return Err(ChooseError::InvalidText { error });
+                }
+                self.value = (self.make)();
+                self.failures = 0;
+                self.fell_back = true;
+                Ok(())
+            }
+            Err(e) => Err(e),
+            Ok(()) => {
+                if let Input::Text(_) = data {
+                    self.failures = 0;
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        self.value.get_options(current_fields)
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        self.value.get_subfields(current_fields)
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        self.value.subfield(current_fields, name)
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        !self.fell_back && self.value.stay_after_apply(current_fields)
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        if self.fell_back {
+            return Some(Input::choice(FALLBACK_ID));
+        }
+        self.value.recorded_input(data, current_fields)
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        self.value.auto_enter(current_fields, entry)
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn BuildableValue> {
+        self.value.get_child(name)
+    }
+
+    fn get_child_mut(&mut self, name: &str) -> Option<&mut Box<dyn BuildableValue>> {
+        self.value.get_child_mut(name)
+    }
+
+    fn to_node(&self) -> Node {
+        self.value.to_node()
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        self.value.to_node_pruned(depth)
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        self.value.visit_nodes(visitor)
+    }
+
+    fn get_value_any(&self) -> Option<Box<dyn Any>> {
+        self.value.get_value_any()
+    }
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        self.failures = 0;
+        self.value.set_value_any(value)
+    }
+
+    fn is_complete(&self) -> bool {
+        self.value.is_complete()
+    }
+
+    fn refresh(&mut self) {
+        self.value.refresh()
+    }
+
+    fn schema(&self) -> Schema {
+        self.value.schema()
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(FallbackBuilder {
+            value: self.value.clone_boxed()?,
+            make: self.make.clone(),
+            max_attempts: self.max_attempts,
+            failures: self.failures,
+            fell_back: self.fell_back,
+        }))
+    }
+}
+
+/// Wrapper of a builder whose value is secret (e.g. a password), used by the fields with
+/// `#[ibuilder(secret)]`. Its menus are marked as `Options::sensitive`, and the value is replaced
+/// with `redaction::PLACEHOLDER` in the `Node`s and in the `Debug` output. The texts inserted in
+/// its menus are replaced as well in `Builder::history`, in `Builder::export` and in the inputs
+/// sent to the `MetricsSink`, but not in the snapshots of `Builder::save_state` (and so in the
+/// storages of `persistence`), which are needed for restoring the state.
+pub struct SecretBuilder {
+    /// The wrapped builder.
+    value: Box<dyn BuildableValue>,
+}
+
+impl SecretBuilder {
+    /// Make a new `SecretBuilder` wrapping the builder of the secret value.
+    pub fn new(value: Box<dyn BuildableValue>) -> SecretBuilder {
+        SecretBuilder { value }
+    }
+}
+
+impl std::fmt::Debug for SecretBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SecretBuilder")
+            .field("value", &self.to_node())
+            .finish()
+    }
+}
+
+impl BuildableValue for SecretBuilder {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        self.value.apply(data, current_fields)
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        let mut options = self.value.get_options(current_fields);
+        options.sensitive = true;
+        options
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        self.value.get_subfields(current_fields)
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        self.value.subfield(current_fields, name)
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        self.value.stay_after_apply(current_fields)
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        self.value.recorded_input(data, current_fields)
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        self.value.auto_enter(current_fields, entry)
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn BuildableValue> {
+        self.value.get_child(name)
+    }
+
+    fn get_child_mut(&mut self, name: &str) -> Option<&mut Box<dyn BuildableValue>> {
+        self.value.get_child_mut(name)
+    }
+
+    fn to_node(&self) -> Node {
+        Redaction::Placeholder.redact_node(self.value.to_node())
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        Redaction::Placeholder.redact_node(self.value.to_node_pruned(depth))
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        self.value.visit_nodes(&mut RedactingVisitor {
+            redaction: Redaction::Placeholder,
+            inner: visitor,
+        })
+    }
+
+    fn get_value_any(&self) -> Option<Box<dyn Any>> {
+        self.value.get_value_any()
+    }
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        self.value.set_value_any(value)
+    }
+
+    fn is_complete(&self) -> bool {
+        self.value.is_complete()
+    }
+
+    fn validation_error(&self) -> Option<String> {
+        self.value.validation_error()
+    }
+
+    fn refresh(&mut self) {
+        self.value.refresh()
+    }
+
+    fn schema(&self) -> Schema {
+        self.value.schema()
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(SecretBuilder {
+            value: self.value.clone_boxed()?,
+        }))
+    }
+}
+
+/// The identifier of the choice that confirms the previous value of a field invalidated with
+/// `Builder::invalidate`.
+pub const KEEP_ID: &str = "__keep";
+
+/// Wrapper of a builder whose value has to be confirmed again by the user, made by
+/// `Builder::invalidate`. The value is kept, but it's not complete until the user selects "Keep"
+/// (`KEEP_ID`) or inserts a new value. The previous value is the first of the `suggestions` of the
+/// text inputs.
+pub struct StaleBuilder {
+    /// The wrapped builder, with the previous value.
+    value: Box<dyn BuildableValue>,
+    /// Whether the value still has to be confirmed.
+    stale: bool,
+}
+
+impl StaleBuilder {
+    /// Make a new `StaleBuilder` wrapping the builder with the value to confirm.
+    pub fn new(value: Box<dyn BuildableValue>) -> StaleBuilder {
+        StaleBuilder { value, stale: true }
+    }
+
+    /// Whether the value still has to be confirmed by the user.
+    pub fn is_stale(&self) -> bool {
+        self.stale
+    }
+
+    /// Add the warning about the confirmation to the node of a single value, while it's stale.
+    fn mark_stale(&self, node: Node) -> Node {
+        match node {
+            Node::Leaf(Field::String(value)) | Node::Leaf(Field::Warning(value, _))
+                if self.stale =>
+            {
+                Node::Leaf(Field::Warning(value, "To be confirmed".to_string()))
+            }
+            node => node,
+        }
+    }
+
+    /// The textual representation of the previous value, if it's a single value.
+    fn previous(&self) -> Option<String> {
+        match self.value.to_node() {
+            Node::Leaf(Field::String(value)) | Node::Leaf(Field::Warning(value, _)) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Debug for StaleBuilder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StaleBuilder")
+            .field("value", &self.value)
+            .field("stale", &self.stale)
+            .finish()
+    }
+}
+
+impl BuildableValue for StaleBuilder {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        match data {
+            Input::Choice(choice)
+                if self.stale && current_fields.is_empty() && choice == KEEP_ID =>
+            {
+                self.stale = false;
+                return Ok(());
+            }
+            // selecting a subfield is just a movement, it doesn't confirm the value
+            Input::Choice(choice)
+                if self
+                    .value
+                    .get_subfields(current_fields)
+                    .iter()
+                    .any(|f| f == choice) =>
+            {
+                return self.value.apply(data, current_fields);
+            }
+            _ => {}
+        }
+        self.value.apply(data, current_fields)?;
+        self.stale = false;
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        let mut options = self.value.get_options(current_fields);
+        if self.stale && current_fields.is_empty() {
+            let previous = self.previous();
+            if let Some(previous) = &previous {
+                if options.text_input && !options.sensitive {
+                    options.suggestions.retain(|s| s != previous);
+                    options.suggestions.insert(0, previous.clone());
+                }
+            }
+            options.choices.insert(
+                0,
+                Choice {
+                    choice_id: KEEP_ID.into(),
+                    text: match previous {
+                        Some(previous) => format!("Keep {}", previous),
+                        None => "Keep the current value".to_string(),
+                    },
+                    needs_action: true,
+                    ..Default::default()
+                },
+            );
+        }
+        options
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        self.value.get_subfields(current_fields)
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        self.value.subfield(current_fields, name)
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        self.value.stay_after_apply(current_fields)
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        self.value.recorded_input(data, current_fields)
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        self.value.auto_enter(current_fields, entry)
+    }
+
+    fn get_child(&self, name: &str) -> Option<&dyn BuildableValue> {
+        self.value.get_child(name)
+    }
+
+    fn get_child_mut(&mut self, name: &str) -> Option<&mut Box<dyn BuildableValue>> {
+        self.value.get_child_mut(name)
+    }
+
+    fn to_node(&self) -> Node {
+        self.mark_stale(self.value.to_node())
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        self.mark_stale(self.value.to_node_pruned(depth))
+    }
+
+    fn get_value_any(&self) -> Option<Box<dyn Any>> {
+        if self.stale {
+            return None;
+        }
+        self.value.get_value_any()
+    }
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        self.stale = false;
+        self.value.set_value_any(value)
+    }
+
+    fn is_complete(&self) -> bool {
+        !self.stale && self.value.is_complete()
+    }
+
+    fn refresh(&mut self) {
+        self.value.refresh()
+    }
+
+    fn schema(&self) -> Schema {
+        self.value.schema()
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(StaleBuilder {
+            value: self.value.clone_boxed()?,
+            stale: self.stale,
+        }))
+    }
+}
+
+/// Builder for the type `Option<T>`.
+pub struct OptionBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    value: Option<Box<dyn BuildableValue>>,
+    inner_type: PhantomData<T>,
+    prompt: String,
+    /// Makes the builder of the value when the user sets it.
+    new_inner: Rc<dyn Fn() -> Box<dyn BuildableValue>>,
+    /// Whether a value that has been set but not completed is considered `None`.
+    default_none: bool,
+}
+
+impl<T> std::fmt::Debug for OptionBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OptionBuilder")
+            .field("value", &self.value)
+            .field("default_none", &self.default_none)
+            .finish()
+    }
+}
+
+impl<T> OptionBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    /// Make a new builder for an `Option<T>`, initially `None`.
+    pub fn new(config: BuildableValueConfig<()>) -> OptionBuilder<T> {
+        OptionBuilder {
+            value: None,
+            inner_type: Default::default(),
+            prompt: config
+                .prompt
+                .unwrap_or_else(|| "Choose an option".to_string()),
+            new_inner: Rc::new(|| T::new_buildable_value(Default::default())),
+            default_none: false,
+        }
+    }
+
+    /// Start with the value set, using the builder made by `new_inner` (e.g. a builder with a
+    /// default value). The same function makes the builder when the user sets the value again
+    /// after removing it. This is used by `#[ibuilder(default = ...)]` on the optional fields.
+    pub fn with_default<F>(mut self, new_inner: F) -> OptionBuilder<T>
+    where
+        F: Fn() -> Box<dyn BuildableValue> + 'static,
+    {
+        self.value = Some(new_inner());
+        self.new_inner = Rc::new(new_inner);
+        self
+    }
+
+    /// Consider the value `None` when the user has set it but not completed it, instead of
+    /// considering the whole option incomplete. This is used by `#[ibuilder(default_none)]`.
+    pub fn with_default_none(mut self) -> OptionBuilder<T> {
+        self.default_none = true;
+        self
+    }
+}
+
+impl<T> NewBuildableValue for Option<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(OptionBuilder::<T>::new(config))
+    }
+}
+
+impl<T> TypedBuildableValue<Option<T>> for OptionBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn get_value(&self) -> Option<Option<T>> {
+        match &self.value {
+            Some(inner) => match inner.get_value_any() {
+                Some(value) => Some(Some(*value.downcast::<T>().unwrap())),
+                None if self.default_none => Some(None),
+                None => None,
+            },
+            None => Some(None),
+        }
+    }
+}
+
+impl<T> BuildableValue for OptionBuilder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if current_fields.is_empty() {
+            match data {
+                Input::Choice(data) => match data.as_str() {
+                    "__remove" => self.value = None,
+                    "__edit" => {}
+                    "__set" => self.value = Some((self.new_inner)()),
+                    _ => return Err(ChooseError::UnexpectedChoice),
+                },
+                Input::Text(_) => return Err(ChooseError::UnexpectedText),
+            }
+            Ok(())
+        } else {
+            let field = &current_fields[0];
+            let rest = &current_fields[1..];
+            if field == "__edit" || field == "__set" {
+                self.value.as_mut().unwrap().apply(data, rest)
+            } else {
+                unreachable!("Unexpected field: {}", field);
+            }
+        }
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if current_fields.is_empty() {
+            let choices = match self.value {
+                Some(_) => vec![
+                    Choice {
+                        choice_id: "__remove".into(),
+                        text: "Remove value".to_string(),
+                        needs_action: false,
+                        ..Default::default()
+                    },
+                    Choice {
+                        choice_id: "__edit".into(),
+                        text: "Edit value".to_string(),
+                        needs_action: false,
+                        ..Default::default()
+                    },
+                ],
+                None => vec![Choice {
+                    choice_id: "__set".into(),
+                    text: "Set value".to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                }],
+            };
+            Options {
+                query: self.prompt.clone(),
+                text_input: false,
+                choices,
+                ..Default::default()
+            }
+        } else {
+            let field = &current_fields[0];
+            let rest = &current_fields[1..];
+            if field == "__edit" || field == "__set" {
+                self.value.as_ref().unwrap().get_options(rest)
+            } else {
+                unreachable!("Unexpected field: {}", field);
+            }
+        }
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        if current_fields.is_empty() {
+            match self.value {
+                Some(_) => vec!["__edit".into()],
+                None => vec!["__set".into()],
+            }
+        } else {
+            let field = &current_fields[0];
+            let rest = &current_fields[1..];
+            if field == "__edit" || field == "__set" {
+                self.value.as_ref().unwrap().get_subfields(rest)
+            } else {
+                unreachable!("Unexpected field: {}", field);
+            }
+        }
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        if current_fields.is_empty() {
+            let field = match self.value {
+                Some(_) => "__edit",
+                None => "__set",
+            };
+            return (name == field).then(|| field.into());
+        }
+        let field = &current_fields[0];
+        let rest = &current_fields[1..];
+        if field == "__edit" || field == "__set" {
+            self.value.as_ref().unwrap().subfield(rest, name)
+        } else {
+            unreachable!("Unexpected field: {}", field);
+        }
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        if current_fields.is_empty() {
+            return None;
+        }
+        let field = &current_fields[0];
+        let rest = &current_fields[1..];
+        if field == "__edit" || field == "__set" {
+            self.value.as_ref().unwrap().auto_enter(rest, entry)
+        } else {
+            unreachable!("Unexpected field: {}", field);
+        }
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        if current_fields.is_empty() {
+            return false;
+        }
+        let field = &current_fields[0];
+        let rest = &current_fields[1..];
+        if field == "__edit" || field == "__set" {
+            self.value.as_ref().unwrap().stay_after_apply(rest)
+        } else {
+            unreachable!("Unexpected field: {}", field);
+        }
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        let field = current_fields.first()?;
+        let rest = &current_fields[1..];
+        if field == "__edit" || field == "__set" {
+            self.value.as_ref()?.recorded_input(data, rest)
+        } else {
+            unreachable!("Unexpected field: {}", field);
+        }
+    }
+
+    fn to_node(&self) -> Node {
+        self.to_node_pruned(usize::MAX)
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        match &self.value {
+            Some(inner) => inner.to_node_pruned(depth),
+            None => Node::Leaf(Field::String("None".into())),
+        }
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        match &self.value {
+            Some(inner) => inner.visit_nodes(visitor),
+            None => visitor.leaf(&Field::String("None".into())),
+        }
+    }
+
+    crate::typed_get_value_any!(Option<T>);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        match value.downcast::<Option<T>>() {
+            Ok(value) => match *value {
+                Some(value) => {
+                    let mut inner = (self.new_inner)();
+                    let res = inner.set_value_any(Box::new(value));
+                    self.value = Some(inner);
+                    res
+                }
+                None => {
+                    self.value = None;
+                    true
                 }
+            },
+            Err(_) => false,
+        }
+    }
+
+    fn is_complete(&self) -> bool {
+        match &self.value {
+            Some(inner) => self.default_none || inner.is_complete(),
+            None => true,
+        }
+    }
+
+    fn schema(&self) -> Schema {
+        let inner = match &self.value {
+            Some(inner) => inner.schema(),
+            None => (self.new_inner)().schema(),
+        };
+        Schema::new(SchemaKind::Nullable(Box::new(inner)))
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        let value = match &self.value {
+            Some(inner) => Some(inner.clone_boxed()?),
+            None => None,
+        };
+        Some(Box::new(OptionBuilder::<T> {
+            value,
+            inner_type: PhantomData,
+            prompt: self.prompt.clone(),
+            new_inner: self.new_inner.clone(),
+            default_none: self.default_none,
+        }))
+    }
+}
+
+/// Builder for the type `Result<T, E>`.
+pub struct ResultBuilder<T, E>
+where
+    T: NewBuildableValue + 'static,
+    E: NewBuildableValue + 'static,
+{
+    /// The selected variant (`"Ok"` or `"Err"`) with the builder of its content.
+    value: Option<(String, Box<dyn BuildableValue>)>,
+    inner_type: PhantomData<(T, E)>,
+    prompt: String,
+}
+
+impl<T, E> std::fmt::Debug for ResultBuilder<T, E>
+where
+    T: NewBuildableValue + 'static,
+    E: NewBuildableValue + 'static,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResultBuilder")
+            .field("value", &self.value)
+            .finish()
+    }
+}
+
+impl<T, E> NewBuildableValue for Result<T, E>
+where
+    T: NewBuildableValue + 'static,
+    E: NewBuildableValue + 'static,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(ResultBuilder::<T, E> {
+            value: None,
+            inner_type: Default::default(),
+            prompt: config
+                .prompt
+                .unwrap_or_else(|| "Choose a variant".to_string()),
+        })
+    }
+}
+
+impl<T, E> ResultBuilder<T, E>
+where
+    T: NewBuildableValue + 'static,
+    E: NewBuildableValue + 'static,
+{
+    /// Get the builder of the content of the variant, checking that `field` is the selected one.
+    fn inner(&self, field: &str) -> &dyn BuildableValue {
+        match &self.value {
+            Some((variant, inner)) if variant == field => inner.as_ref(),
+            _ => unreachable!("Unexpected field: {}", field),
+        }
+    }
+}
+
+impl<T, E> TypedBuildableValue<Result<T, E>> for ResultBuilder<T, E>
+where
+    T: NewBuildableValue + 'static,
+    E: NewBuildableValue + 'static,
+{
+    fn get_value(&self) -> Option<Result<T, E>> {
+        let (variant, inner) = self.value.as_ref()?;
+        let inner = inner.get_value_any()?;
+        if variant == "Ok" {
+            Some(Ok(*inner.downcast::<T>().unwrap()))
+        } else {
+            Some(Err(*inner.downcast::<E>().unwrap()))
+        }
+    }
+}
+
+impl<T, E> BuildableValue for ResultBuilder<T, E>
+where
+    T: NewBuildableValue + 'static,
+    E: NewBuildableValue + 'static,
+{
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if current_fields.is_empty() {
+            match data {
                 Input::Choice(data) => {
-                    if data != "__remove" {
-                        // check that the inserted index is valid
-                        let index =
-                            usize::from_str(&data).map_err(|_| ChooseError::UnexpectedChoice)?;
-                        if index >= self.items.len() {
-                            return Err(ChooseError::UnexpectedChoice);
+                    if let Some((variant, _)) = &self.value {
+                        if variant == data {
+                            return Ok(());
                         }
                     }
+                    let inner = match data.as_str() {
+                        "Ok" => T::new_buildable_value(Default::default()),
+                        "Err" => E::new_buildable_value(Default::default()),
+                        _ => return Err(ChooseError::UnexpectedChoice),
+                    };
+                    self.value = Some((data.clone(), inner));
                 }
-                _ => return Err(ChooseError::UnexpectedText),
+                Input::Text(_) => return Err(ChooseError::UnexpectedText),
             }
-        // remove item or apply to element
+            Ok(())
         } else {
             let field = &current_fields[0];
             let rest = &current_fields[1..];
-            match field.as_str() {
-                "__remove" => match data {
-                    Input::Choice(choice) => {
-                        let index =
-                            usize::from_str(&choice).map_err(|_| ChooseError::UnexpectedChoice)?;
-                        if index >= self.items.len() {
-                            return Err(ChooseError::UnexpectedChoice);
-                        }
-                        self.items.remove(index);
-                    }
-                    Input::Text(_) => return Err(ChooseError::UnexpectedText),
-                },
-                "__new" => {
-                    self.items
-                        .last_mut()
-                        .expect("Vec __new didn't push")
-                        .apply(data, rest)?;
-                }
-                index => {
-                    let index = usize::from_str(index)
-                        .unwrap_or_else(|_| panic!("Invalid index for vec: {}", index));
-                    self.items[index].apply(data, rest)?;
-                }
+            match &mut self.value {
+                Some((variant, inner)) if variant == field => inner.apply(data, rest),
+                _ => unreachable!("Unexpected field: {}", field),
             }
         }
-        Ok(())
     }
 
-    fn get_options(&self, current_fields: &[String]) -> Options {
-        // vec main manu
+    fn get_options(&self, current_fields: &[Id]) -> Options {
         if current_fields.is_empty() {
-            let mut choices = vec![Choice {
-                choice_id: "__new".to_string(),
-                text: "New element".to_string(),
-                needs_action: false,
-            }];
-            if !self.items.is_empty() {
-                choices.push(Choice {
-                    choice_id: "__remove".to_string(),
-                    text: "Remove element".to_string(),
-                    needs_action: false,
-                });
-                for i in 0..self.items.len() {
-                    choices.push(Choice {
-                        choice_id: i.to_string(),
-                        text: format!("Edit item {}", i),
-                        needs_action: self.items[i].get_value_any().is_none(),
-                    });
-                }
-            }
+            let choices = ["Ok", "Err"]
+                .iter()
+                .map(|variant| Choice {
+                    choice_id: (*variant).into(),
+                    text: variant.to_string(),
+                    needs_action: match &self.value {
+                        Some((current, inner)) if current == variant => !inner.is_complete(),
+                        _ => false,
+                    },
+                    ..Default::default()
+                })
+                .collect();
             Options {
                 query: self.prompt.clone(),
                 text_input: false,
                 choices,
+                ..Default::default()
             }
-        // item menu
         } else {
-            let field = &current_fields[0];
-            let rest = &current_fields[1..];
-            match field.as_str() {
-                // select the item to remove
-                "__remove" => {
-                    let mut choices = Vec::new();
-                    for i in 0..self.items.len() {
-                        choices.push(Choice {
-                            choice_id: i.to_string(),
-                            text: format!("Remove item {}", i),
-                            needs_action: false,
-                        });
-                    }
-                    Options {
-                        query: "Select the item to remove".to_string(),
-                        text_input: false,
-                        choices,
-                    }
+            self.inner(&current_fields[0])
+                .get_options(&current_fields[1..])
+        }
+    }
+
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        if current_fields.is_empty() {
+            vec!["Ok".into(), "Err".into()]
+        } else {
+            self.inner(&current_fields[0])
+                .get_subfields(&current_fields[1..])
+        }
+    }
+
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        if current_fields.is_empty() {
+            match name {
+                "Ok" => Some("Ok".into()),
+                "Err" => Some("Err".into()),
+                _ => None,
+            }
+        } else {
+            self.inner(&current_fields[0])
+                .subfield(&current_fields[1..], name)
+        }
+    }
+
+    fn auto_enter(&self, current_fields: &[Id], entry: MenuEntry) -> Option<Id> {
+        if current_fields.is_empty() {
+            return None;
+        }
+        self.inner(&current_fields[0])
+            .auto_enter(&current_fields[1..], entry)
+    }
+
+    fn stay_after_apply(&self, current_fields: &[Id]) -> bool {
+        if current_fields.is_empty() {
+            return false;
+        }
+        self.inner(&current_fields[0])
+            .stay_after_apply(&current_fields[1..])
+    }
+
+    fn recorded_input(&self, data: &Input, current_fields: &[Id]) -> Option<Input> {
+        let field = current_fields.first()?;
+        self.inner(field).recorded_input(data, &current_fields[1..])
+    }
+
+    fn to_node(&self) -> Node {
+        self.to_node_pruned(usize::MAX)
+    }
+
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        match &self.value {
+            Some(_) if depth == 0 => Node::Leaf(Field::Elided),
+            Some((variant, inner)) => Node::Composite(
+                variant.clone(),
+                vec![FieldKind::Unnamed(inner.to_node_pruned(depth - 1))],
+            ),
+            None => Node::Leaf(Field::Missing),
+        }
+    }
+
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        match &self.value {
+            Some((variant, inner)) => {
+                if !visitor.enter_composite(variant) {
+                    return;
                 }
-                // last action was __new, now inside the last item menu
-                "__new" => self
-                    .items
-                    .last()
-                    .expect("Vec __new didn't push")
-                    .get_options(rest),
-                // edit one of the items
-                index => {
-                    let index = usize::from_str(index)
-                        .unwrap_or_else(|_| panic!("Invalid index for vec: {}", index));
-                    self.items[index].get_options(rest)
+                if visitor.enter_field(None) {
+                    inner.visit_nodes(visitor);
                 }
+                visitor.leave_composite();
             }
+            None => visitor.leaf(&Field::Missing),
+        }
+    }
+
+    crate::typed_get_value_any!(Result<T, E>);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        let value = match value.downcast::<Result<T, E>>() {
+            Ok(value) => *value,
+            Err(_) => return false,
+        };
+        let (variant, mut inner, value) = match value {
+            Ok(value) => (
+                "Ok",
+                T::new_buildable_value(Default::default()),
+                Box::new(value) as Box<dyn Any>,
+            ),
+            Err(value) => (
+                "Err",
+                E::new_buildable_value(Default::default()),
+                Box::new(value) as Box<dyn Any>,
+            ),
+        };
+        let res = inner.set_value_any(value);
+        self.value = Some((variant.to_string(), inner));
+        res
+    }
+
+    fn is_complete(&self) -> bool {
+        match &self.value {
+            Some((_, inner)) => inner.is_complete(),
+            None => false,
+        }
+    }
+
+    fn schema(&self) -> Schema {
+        let variant = |name: &str, new: fn(BuildableValueConfig<()>) -> Box<dyn BuildableValue>| {
+            let schema = match &self.value {
+                Some((variant, inner)) if variant == name => inner.schema(),
+                _ => new(Default::default()).schema(),
+            };
+            Schema::new(SchemaKind::Object(vec![Property {
+                name: name.to_string(),
+                schema,
+                required: true,
+            }]))
+        };
+        Schema::new(SchemaKind::OneOf(vec![
+            variant("Ok", T::new_buildable_value),
+            variant("Err", E::new_buildable_value),
+        ]))
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        let value = match &self.value {
+            Some((variant, inner)) => Some((variant.clone(), inner.clone_boxed()?)),
+            None => None,
+        };
+        Some(Box::new(ResultBuilder::<T, E> {
+            value,
+            inner_type: PhantomData,
+            prompt: self.prompt.clone(),
+        }))
+    }
+}
+
+/// Builder for the types `Range<T>` and `RangeInclusive<T>`.
+///
+/// The start and the end of the range are inserted separately, as text, and the builder makes
+/// sure that the start is never after the end.
+pub struct RangeBuilder<T>
+where
+    T: FromStr + PartialOrd + ToString + Clone + 'static,
+    T::Err: ToString,
+{
+    start: Option<T>,
+    end: Option<T>,
+    inclusive: bool,
+    prompt: String,
+}
+
+impl<T> std::fmt::Debug for RangeBuilder<T>
+where
+    T: FromStr + PartialOrd + ToString + Clone + 'static,
+    T::Err: ToString,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RangeBuilder")
+            .field("start", &self.start.as_ref().map(|v| v.to_string()))
+            .field("end", &self.end.as_ref().map(|v| v.to_string()))
+            .field("inclusive", &self.inclusive)
+            .finish()
+    }
+}
+
+impl<T> RangeBuilder<T>
+where
+    T: FromStr + PartialOrd + ToString + Clone + 'static,
+    T::Err: ToString,
+{
+    /// Make a new instance of the builder, for an inclusive range or not.
+    pub fn new(config: BuildableValueConfig<()>, inclusive: bool) -> Self {
+        Self {
+            start: None,
+            end: None,
+            inclusive,
+            prompt: config
+                .prompt
+                .unwrap_or_else(|| "Edit the range".to_string()),
         }
     }
 
-    fn get_subfields(&self, current_fields: &[String]) -> Vec<String> {
-        // main manu
-        if current_fields.is_empty() {
-            if self.items.is_empty() {
-                vec!["__new".into()]
-            } else {
-                let mut res = vec!["__new".into(), "__remove".into()];
-                for i in 0..self.items.len() {
-                    res.push(i.to_string());
-                }
-                res
-            }
+    /// The separator between the start and the end of the range.
+    fn separator(&self) -> &'static str {
+        if self.inclusive {
+            "..="
         } else {
-            let field = &current_fields[0];
-            let rest = &current_fields[1..];
-            match field.as_str() {
-                // just select the item to remove
-                "__remove" => vec![],
-                "__new" => self
-                    .items
-                    .last()
-                    .expect("Vec __new didn't push")
-                    .get_subfields(rest),
-                index => {
-                    let index = usize::from_str(index)
-                        .unwrap_or_else(|_| panic!("Invalid index for vec: {}", index));
-                    self.items[index].get_subfields(rest)
-                }
-            }
+            ".."
         }
     }
+}
 
-    fn to_node(&self) -> Node {
-        let items = self
-            .items
-            .iter()
-            .map(|i| FieldKind::Unnamed(i.to_node()))
-            .collect();
-        // Vec has no name
-        Node::Composite("".into(), items)
-    }
-
-    fn get_value_any(&self) -> Option<Box<dyn Any>> {
-        let mut results: Vec<T> = Vec::with_capacity(self.items.len());
-        for item in &self.items {
-            results.push(*item.get_value_any()?.downcast::<T>().unwrap());
-        }
-        Some(Box::new(results))
+impl<T> NewBuildableValue for Range<T>
+where
+    T: FromStr + PartialOrd + ToString + Clone + 'static,
+    T::Err: ToString,
+{
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(RangeBuilder::<T>::new(config, false))
     }
 }
 
-/// Builder for the type `Box<T>`.
-pub struct BoxBuilder<T>
+impl<T> NewBuildableValue for RangeInclusive<T>
 where
-    T: NewBuildableValue + 'static,
+    T: FromStr + PartialOrd + ToString + Clone + 'static,
+    T::Err: ToString,
 {
-    value: Box<dyn BuildableValue>,
-    inner_type: PhantomData<T>,
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(RangeBuilder::<T>::new(config, true))
+    }
 }
 
-impl<T> std::fmt::Debug for BoxBuilder<T>
+/// The value of the builders of `Range<T>`, `None` for the ones of `RangeInclusive<T>`.
+impl<T> TypedBuildableValue<Range<T>> for RangeBuilder<T>
 where
-    T: NewBuildableValue + 'static,
+    T: FromStr + PartialOrd + ToString + Clone + 'static,
+    T::Err: ToString,
 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("BoxBuilder")
-            .field("value", &self.value)
-            .finish()
+    fn get_value(&self) -> Option<Range<T>> {
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) if !self.inclusive => Some(start.clone()..end.clone()),
+            _ => None,
+        }
     }
 }
 
-impl<T> NewBuildableValue for Box<T>
+/// The value of the builders of `RangeInclusive<T>`, `None` for the ones of `Range<T>`.
+impl<T> TypedBuildableValue<RangeInclusive<T>> for RangeBuilder<T>
 where
-    T: NewBuildableValue + 'static,
+    T: FromStr + PartialOrd + ToString + Clone + 'static,
+    T::Err: ToString,
 {
-    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
-        Box::new(BoxBuilder::<T> {
-            value: T::new_buildable_value(config),
-            inner_type: Default::default(),
-        })
+    fn get_value(&self) -> Option<RangeInclusive<T>> {
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) if self.inclusive => Some(start.clone()..=end.clone()),
+            _ => None,
+        }
     }
 }
 
-impl<T> BuildableValue for BoxBuilder<T>
+impl<T> BuildableValue for RangeBuilder<T>
 where
-    T: NewBuildableValue + 'static,
+    T: FromStr + PartialOrd + ToString + Clone + 'static,
+    T::Err: ToString,
 {
-    fn apply(&mut self, data: Input, current_fields: &[String]) -> Result<(), ChooseError> {
-        self.value.apply(data, current_fields)
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if current_fields.is_empty() {
+            return match data {
+                Input::Choice(data) if data == "start" || data == "end" => Ok(()),
+                Input::Choice(_) => Err(ChooseError::UnexpectedChoice),
+                Input::Text(_) => Err(ChooseError::UnexpectedText),
+            };
+        }
+        let value = match data {
+            Input::Text(data) => {
+                T::from_str(data.trim()).map_err(|e| ChooseError::InvalidText {
+                    error: e.to_string(),
+                })?
+            }
+            Input::Choice(_) => return Err(ChooseError::UnexpectedChoice),
+        };
+        let (start, end) = match current_fields[0].as_str() {
+            "start" => (Some(&value), self.end.as_ref()),
+            "end" => (self.start.as_ref(), Some(&value)),
+            field => unreachable!("Unexpected field: {}", field),
+        };
+        if let (Some(start), Some(end)) = (start, end) {
+            if start > end {
+                return Err(ChooseError::InvalidText {
+                    error: "The start of the range cannot be after its end".to_string(),
+                });
+            }
+        }
+        if current_fields[0] == "start" {
+            self.start = Some(value);
+        } else {
+            self.end = Some(value);
+        }
+        Ok(())
     }
 
-    fn get_options(&self, current_fields: &[String]) -> Options {
-        self.value.get_options(current_fields)
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if current_fields.is_empty() {
+            Options {
+                query: self.prompt.clone(),
+                text_input: false,
+                choices: vec![
+                    Choice {
+                        choice_id: "start".into(),
+                        text: "Edit start".to_string(),
+                        needs_action: self.start.is_none(),
+                        ..Default::default()
+                    },
+                    Choice {
+                        choice_id: "end".into(),
+                        text: "Edit end".to_string(),
+                        needs_action: self.end.is_none(),
+                        ..Default::default()
+                    },
+                ],
+                ..Default::default()
+            }
+        } else {
+            let query = match current_fields[0].as_str() {
+                "start" => "Type the start of the range",
+                "end" => "Type the end of the range",
+                field => unreachable!("Unexpected field: {}", field),
+            };
+            Options {
+                query: query.to_string(),
+                text_input: true,
+                choices: vec![],
+                ..Default::default()
+            }
+        }
     }
 
-    fn get_subfields(&self, current_fields: &[String]) -> Vec<String> {
-        self.value.get_subfields(current_fields)
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id> {
+        if current_fields.is_empty() {
+            vec!["start".into(), "end".into()]
+        } else {
+            vec![]
+        }
     }
 
     fn to_node(&self) -> Node {
-        self.value.to_node()
+        match (&self.start, &self.end) {
+            (Some(start), Some(end)) => Node::Leaf(Field::String(format!(
+                "{}{}{}",
+                start.to_string(),
+                self.separator(),
+                end.to_string()
+            ))),
+            _ => Node::Leaf(Field::Missing),
+        }
     }
 
     fn get_value_any(&self) -> Option<Box<dyn Any>> {
-        Some(Box::new(Box::new(
-            *self.value.get_value_any()?.downcast::<T>().unwrap(),
-        )))
+        // the type of the value depends on the kind of range, chosen at runtime
+        if self.inclusive {
+            crate::typed_value_any::<RangeInclusive<T>, _>(self)
+        } else {
+            crate::typed_value_any::<Range<T>, _>(self)
+        }
+    }
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        let (start, end) = if self.inclusive {
+            match value.downcast::<RangeInclusive<T>>() {
+                Ok(range) => range.into_inner(),
+                Err(_) => return false,
+            }
+        } else {
+            match value.downcast::<Range<T>>() {
+                Ok(range) => (range.start, range.end),
+                Err(_) => return false,
+            }
+        };
+        if start > end {
+            return false;
+        }
+        self.start = Some(start);
+        self.end = Some(end);
+        true
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(RangeBuilder::<T> {
+            start: self.start.clone(),
+            end: self.end.clone(),
+            inclusive: self.inclusive,
+            prompt: self.prompt.clone(),
+        }))
     }
 }
 
-/// Builder for the type `Option<T>`.
-pub struct OptionBuilder<T>
+/// Builder for the types generated by the `bitflags!` macro, available with the `bitflags`
+/// feature.
+///
+/// The menu shows a toggle for each named flag, the user stays in the menu after toggling a flag.
+/// It's also possible to set all the flags at once writing their names separated by `|`.
+///
+/// The types generated by `bitflags!` cannot derive `IBuilder`, so `NewBuildableValue` should be
+/// implemented manually using this builder:
+///
+/// ```
+/// use ibuilder::builders::FlagsBuilder;
+/// use ibuilder::{BuildableValue, BuildableValueConfig, NewBuildableValue};
+///
+/// bitflags::bitflags! {
+///     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+///     struct Permissions: u8 {
+///         const READ = 0b001;
+///         const WRITE = 0b010;
+///         const EXECUTE = 0b100;
+///     }
+/// }
+///
+/// impl NewBuildableValue for Permissions {
+///     fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+///         Box::new(FlagsBuilder::<Permissions>::new(config))
+///     }
+/// }
+/// ```
+#[cfg(feature = "bitflags")]
+pub struct FlagsBuilder<T>
 where
-    T: NewBuildableValue + 'static,
+    T: bitflags::Flags + 'static,
 {
-    value: Option<Box<dyn BuildableValue>>,
-    inner_type: PhantomData<T>,
+    value: T,
     prompt: String,
 }
 
-impl<T> std::fmt::Debug for OptionBuilder<T>
+#[cfg(feature = "bitflags")]
+impl<T> FlagsBuilder<T>
 where
-    T: NewBuildableValue + 'static,
+    T: bitflags::Flags + 'static,
+{
+    /// Make a new instance of the builder, with all the flags disabled.
+    pub fn new(config: BuildableValueConfig<()>) -> Self {
+        Self {
+            value: T::empty(),
+            prompt: config
+                .prompt
+                .unwrap_or_else(|| "Toggle the flags".to_string()),
+        }
+    }
+
+    /// The named flags of the type, with their value.
+    fn flags() -> impl Iterator<Item = (&'static str, T)> {
+        T::FLAGS
+            .iter()
+            .filter(|flag| flag.is_named())
+            .map(|flag| (flag.name(), T::from_bits_retain(flag.value().bits())))
+    }
+
+    /// The names of the enabled flags, separated by `|`.
+    fn enabled_names(&self) -> String {
+        Self::flags()
+            .filter(|(_, flag)| self.value.contains(T::from_bits_retain(flag.bits())))
+            .map(|(name, _)| name)
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+}
+
+#[cfg(feature = "bitflags")]
+impl<T> std::fmt::Debug for FlagsBuilder<T>
+where
+    T: bitflags::Flags + 'static,
 {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("OptionBuilder")
-            .field("value", &self.value)
+        f.debug_struct("FlagsBuilder")
+            .field("value", &self.enabled_names())
+            .field("prompt", &self.prompt)
             .finish()
     }
 }
 
-impl<T> NewBuildableValue for Option<T>
+#[cfg(feature = "bitflags")]
+impl<T> TypedBuildableValue<T> for FlagsBuilder<T>
 where
-    T: NewBuildableValue + 'static,
+    T: bitflags::Flags + 'static,
 {
-    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
-        Box::new(OptionBuilder::<T> {
-            value: None,
-            inner_type: Default::default(),
-            prompt: config
-                .prompt
-                .unwrap_or_else(|| "Choose an option".to_string()),
-        })
+    fn get_value(&self) -> Option<T> {
+        Some(T::from_bits_retain(self.value.bits()))
     }
 }
 
-impl<T> BuildableValue for OptionBuilder<T>
+#[cfg(feature = "bitflags")]
+impl<T> BuildableValue for FlagsBuilder<T>
 where
-    T: NewBuildableValue + 'static,
+    T: bitflags::Flags + 'static,
 {
-    fn apply(&mut self, data: Input, current_fields: &[String]) -> Result<(), ChooseError> {
-        if current_fields.is_empty() {
-            match data {
-                Input::Choice(data) => match data.as_str() {
-                    "__remove" => self.value = None,
-                    "__edit" => {}
-                    "__set" => self.value = Some(T::new_buildable_value(Default::default())),
-                    _ => return Err(ChooseError::UnexpectedChoice),
-                },
-                Input::Text(_) => return Err(ChooseError::UnexpectedText),
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            unreachable!("flags don't have subfields");
+        }
+        match data {
+            Input::Choice(choice) => {
+                let (_, flag) = Self::flags()
+                    .find(|(name, _)| *name == choice)
+                    .ok_or(ChooseError::UnexpectedChoice)?;
+                self.value.toggle(flag);
             }
-            Ok(())
-        } else {
-            let field = &current_fields[0];
-            let rest = &current_fields[1..];
-            if field == "__edit" || field == "__set" {
-                self.value.as_mut().unwrap().apply(data, rest)
-            } else {
-                unreachable!("Unexpected field: {}", field);
+            Input::Text(text) => {
+                let mut value = T::empty();
+                for name in text.split('|').map(str::trim).filter(|n| !n.is_empty()) {
+                    let (_, flag) = Self::flags().find(|(n, _)| *n == name).ok_or_else(|| {
+                        ChooseError::InvalidText {
+                            error: format!("Unknown flag: {}", name),
+                        }
+                    })?;
+                    value.insert(flag);
+                }
+                self.value = value;
             }
         }
+        Ok(())
     }
 
-    fn get_options(&self, current_fields: &[String]) -> Options {
-        if current_fields.is_empty() {
-            let choices = match self.value {
-                Some(_) => vec![
-                    Choice {
-                        choice_id: "__remove".to_string(),
-                        text: "Remove value".to_string(),
-                        needs_action: false,
-                    },
-                    Choice {
-                        choice_id: "__edit".to_string(),
-                        text: "Edit value".to_string(),
-                        needs_action: false,
-                    },
-                ],
-                None => vec![Choice {
-                    choice_id: "__set".to_string(),
-                    text: "Set value".to_string(),
+    fn get_options(&self, _current_fields: &[Id]) -> Options {
+        let choices = Self::flags()
+            .map(|(name, flag)| {
+                let enabled = self.value.contains(flag);
+                Choice {
+                    choice_id: name.into(),
+                    text: format!("[{}] {}", if enabled { "x" } else { " " }, name),
                     needs_action: false,
-                }],
-            };
-            Options {
-                query: self.prompt.clone(),
-                text_input: false,
-                choices,
-            }
-        } else {
-            let field = &current_fields[0];
-            let rest = &current_fields[1..];
-            if field == "__edit" || field == "__set" {
-                self.value.as_ref().unwrap().get_options(rest)
-            } else {
-                unreachable!("Unexpected field: {}", field);
-            }
+                    ..Default::default()
+                }
+            })
+            .collect();
+        Options {
+            query: self.prompt.clone(),
+            text_input: true,
+            choices,
+            ..Default::default()
         }
     }
 
-    fn get_subfields(&self, current_fields: &[String]) -> Vec<String> {
-        if current_fields.is_empty() {
-            match self.value {
-                Some(_) => vec!["__edit".to_string()],
-                None => vec!["__set".to_string()],
-            }
-        } else {
-            let field = &current_fields[0];
-            let rest = &current_fields[1..];
-            if field == "__edit" || field == "__set" {
-                self.value.as_ref().unwrap().get_subfields(rest)
-            } else {
-                unreachable!("Unexpected field: {}", field);
+    fn get_subfields(&self, _current_fields: &[Id]) -> Vec<Id> {
+        vec![]
+    }
+
+    fn to_node(&self) -> Node {
+        Node::Leaf(Field::String(self.enabled_names()))
+    }
+
+    crate::typed_get_value_any!(T);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        match value.downcast::<T>() {
+            Ok(value) => {
+                self.value = *value;
+                true
             }
+            Err(_) => false,
         }
     }
 
-    fn to_node(&self) -> Node {
-        match &self.value {
-            Some(inner) => inner.to_node(),
-            None => Node::Leaf(Field::String("None".into())),
+    fn stay_after_apply(&self, _current_fields: &[Id]) -> bool {
+        true
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(FlagsBuilder::<T> {
+            value: T::from_bits_retain(self.value.bits()),
+            prompt: self.prompt.clone(),
+        }))
+    }
+}
+
+/// The maximum number of chars of a JSON value shown in its node.
+#[cfg(feature = "serde_json")]
+const JSON_NODE_MAX_LEN: usize = 200;
+
+#[cfg(feature = "serde_json")]
+type_builder_struct!(
+    serde_json::Value,
+    JsonBuilder,
+    "Type a JSON value",
+    "Builder for the type `serde_json::Value`, available with the `serde_json` feature."
+);
+
+#[cfg(feature = "serde_json")]
+impl BuildableValue for JsonBuilder {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            panic!(
+                "JsonBuilder.apply() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        match data {
+            Input::Text(data) => {
+                let value = serde_json::from_str(data).map_err(|e| ChooseError::InvalidText {
+                    error: e.to_string(),
+                })?;
+                self.set_value(value)?;
+            }
+            _ => return Err(ChooseError::UnexpectedChoice),
         }
+        Ok(())
     }
 
-    fn get_value_any(&self) -> Option<Box<dyn Any>> {
-        match &self.value {
-            Some(inner) => Some(Box::new(Some(
-                *inner.get_value_any()?.downcast::<T>().unwrap(),
-            ))),
-            None => Some(Box::new(None::<T>)),
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if !current_fields.is_empty() {
+            panic!(
+                "JsonBuilder.get_options() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        Options {
+            query: self.prompt.clone(),
+            text_input: true,
+            choices: vec![],
+            warning: self.warning.clone(),
+            ..Default::default()
         }
     }
+
+    fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
+        vec![]
+    }
+
+    fn to_node(&self) -> Node {
+        let value = self.value.as_ref().map(|value| {
+            let pretty = serde_json::to_string_pretty(value).expect("Invalid JSON value");
+            if pretty.chars().count() > JSON_NODE_MAX_LEN {
+                pretty.chars().take(JSON_NODE_MAX_LEN).collect::<String>() + "..."
+            } else {
+                pretty
+            }
+        });
+        leaf_node(value, &self.warning)
+    }
+
+    crate::typed_get_value_any!(serde_json::Value);
+
+    fn set_value_any(&mut self, value: Box<dyn Any>) -> bool {
+        self.set_value_boxed(value)
+    }
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(self.cloned()))
+    }
+}
+
+#[cfg(feature = "serde_json")]
+impl NewBuildableValue for serde_json::Value {
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(JsonBuilder::new(BuildableValueConfig {
+            default: None,
+            prompt: config.prompt,
+            ..Default::default()
+        }))
+    }
 }
@@ -0,0 +1,84 @@
+//! Module with the sets of characters accepted by the text inputs, see `Charset`.
+
+use std::ops::RangeInclusive;
+
+use crate::ChooseError;
+
+/// The set of characters accepted by a text input, set with `#[ibuilder(charset = "...")]` or with
+/// `BuildableValueConfig::charset`. The text inserted by the user (after the preprocessors) is
+/// rejected before being parsed if it contains a character outside the set.
+///
+/// The set is written like the character classes of the regular expressions, without the
+/// brackets: the characters are listed one after the other, and `a-z` is the range of the
+/// characters from `a` to `z`. A `-` at the start or at the end of the set is the character
+/// itself. For example `a-zA-Z0-9_-` accepts the letters, the digits, `_` and `-`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Charset {
+    /// The set as it has been written, shown in the error messages.
+    spec: String,
+    /// The ranges of the accepted characters.
+    ranges: Vec<RangeInclusive<char>>,
+}
+
+impl Charset {
+    /// Make a new set of characters from its description, see `Charset`. Returns an error if a
+    /// range is reversed (e.g. `z-a`) or the set is empty.
+    pub fn parse(spec: &str) -> Result<Charset, String> {
+        let chars: Vec<char> = spec.chars().collect();
+        let mut ranges = vec![];
+        let mut i = 0;
+        while i < chars.len() {
+            if i + 2 < chars.len() && chars[i + 1] == '-' {
+                let (start, end) = (chars[i], chars[i + 2]);
+                if start > end {
+                    return Err(format!("Invalid range {}-{}", start, end));
+                }
+                ranges.push(start..=end);
+                i += 3;
+            } else {
+                ranges.push(chars[i]..=chars[i]);
+                i += 1;
+            }
+        }
+        if ranges.is_empty() {
+            return Err("Empty charset".to_string());
+        }
+        Ok(Charset {
+            spec: spec.to_string(),
+            ranges,
+        })
+    }
+
+    /// Make a new set of characters from its description, like `parse`.
+    ///
+    /// Panics if the description is not valid.
+    pub fn new(spec: &str) -> Charset {
+        Charset::parse(spec).unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    /// Whether the character is in the set.
+    pub fn contains(&self, c: char) -> bool {
+        self.ranges.iter().any(|range| range.contains(&c))
+    }
+
+    /// Check that all the characters of the text are in the set.
+    pub fn check(&self, text: &str) -> Result<(), ChooseError> {
+        match text.chars().find(|c| !self.contains(*c)) {
+            Some(c) => Err(ChooseError::InvalidText {
+                error: format!(
+                    "The character {:?} is not allowed, only [{}] are",
+                    c, self.spec
+                ),
+            }),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Check the text against the set of characters, if any.
+pub(crate) fn check(charset: &Option<Charset>, text: &str) -> Result<(), ChooseError> {
+    match charset {
+        Some(charset) => charset.check(text),
+        None => Ok(()),
+    }
+}
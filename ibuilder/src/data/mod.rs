@@ -0,0 +1,266 @@
+//! Ready-made builders for the values picked from the standard lists that are too big to be
+//! modelled as enums: ISO 3166 countries, ISO 4217 currencies and IANA timezones. This module is
+//! available with the `data` feature.
+//!
+//! The user can type the code or part of the name of the value: if the text matches a single
+//! entry it is selected, otherwise the matching entries are shown as choices.
+//!
+//! ```
+//! use ibuilder::data::{CountryCode, CurrencyCode, TimeZone};
+//! use ibuilder::*;
+//!
+//! #[derive(IBuilder)]
+//! struct Shop {
+//!     country: CountryCode,
+//!     currency: CurrencyCode,
+//!     timezone: TimeZone,
+//! }
+//!
+//! let mut builder = Shop::builder();
+//! builder.choose(Input::choice("currency")).unwrap();
+//! // more than one currency matches, the user has to pick one of them
+//! builder.choose(Input::text("dollar")).unwrap();
+//! builder.choose(Input::choice("USD")).unwrap();
+//! ```
+
+use std::fmt::{Display, Formatter};
+use std::marker::PhantomData;
+
+use crate::nodes::{Field, Node};
+use crate::{
+    BuildableValue, BuildableValueConfig, Choice, ChooseError, Id, Input, NewBuildableValue,
+    Options, TypedBuildableValue,
+};
+
+mod tables;
+
+pub use tables::{COUNTRIES, CURRENCIES, TIMEZONES};
+
+/// The maximum number of matching entries shown as choices.
+const MAX_CHOICES: usize = 20;
+
+/// A set of values, each identified by a code and described by a name.
+pub trait DataSet: Sized + 'static {
+    /// All the entries of the set, as `(code, name)` pairs.
+    fn entries() -> &'static [(&'static str, &'static str)];
+
+    /// The default prompt of the builder of the values of the set.
+    fn prompt() -> &'static str;
+
+    /// Make a value from the code of one of the entries.
+    fn from_code(code: &str) -> Self;
+}
+
+macro_rules! data_type {
+    ($name:ident, $entries:expr, $prompt:expr, $docstring:expr) => {
+        #[doc = $docstring]
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub struct $name(pub String);
+
+        impl DataSet for $name {
+            fn entries() -> &'static [(&'static str, &'static str)] {
+                $entries
+            }
+
+            fn prompt() -> &'static str {
+                $prompt
+            }
+
+            fn from_code(code: &str) -> Self {
+                $name(code.to_string())
+            }
+        }
+
+        impl Display for $name {
+            fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+                write!(f, "{}", self.0)
+            }
+        }
+
+        impl NewBuildableValue for $name {
+            fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+                Box::new(DataBuilder::<$name>::new(config))
+            }
+        }
+    };
+}
+
+data_type!(
+    CountryCode,
+    COUNTRIES,
+    "Type the name or the code of the country",
+    "An ISO 3166-1 alpha-2 country code (e.g. `IT`)."
+);
+data_type!(
+    CurrencyCode,
+    CURRENCIES,
+    "Type the name or the code of the currency",
+    "An ISO 4217 currency code (e.g. `EUR`)."
+);
+data_type!(
+    TimeZone,
+    TIMEZONES,
+    "Type the name of the timezone or its country",
+    "The name of an IANA timezone (e.g. `Europe/Rome`)."
+);
+
+/// Builder for the values of a `DataSet`, with a searchable list of choices.
+pub struct DataBuilder<T: DataSet> {
+    /// The selected entry.
+    value: Option<&'static (&'static str, &'static str)>,
+    /// The text of the last search that matched more than one entry.
+    search: Option<String>,
+    prompt: String,
+    data_type: PhantomData<T>,
+}
+
+impl<T: DataSet> std::fmt::Debug for DataBuilder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataBuilder")
+            .field("value", &self.value)
+            .field("search", &self.search)
+            .finish()
+    }
+}
+
+impl<T: DataSet> DataBuilder<T> {
+    /// Make a new instance of the builder.
+    pub fn new(config: BuildableValueConfig<()>) -> Self {
+        Self {
+            value: None,
+            search: None,
+            prompt: config.prompt.unwrap_or_else(|| T::prompt().to_string()),
+            data_type: Default::default(),
+        }
+    }
+
+    /// Find all the entries whose code or name contain the text, ignoring the case.
+    fn find(text: &str) -> Vec<&'static (&'static str, &'static str)> {
+        let text = text.to_lowercase();
+        T::entries()
+            .iter()
+            .filter(|(code, name)| {
+                code.to_lowercase().contains(&text) || name.to_lowercase().contains(&text)
+            })
+            .collect()
+    }
+
+    /// Select an entry, forgetting the last search.
+    fn select(&mut self, entry: &'static (&'static str, &'static str)) {
+        self.value = Some(entry);
+        self.search = None;
+    }
+}
+
+impl<T: DataSet> TypedBuildableValue<T> for DataBuilder<T> {
+    fn get_value(&self) -> Option<T> {
+        let (code, _) = self.value?;
+        Some(T::from_code(code))
+    }
+}
+
+impl<T: DataSet> BuildableValue for DataBuilder<T> {
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError> {
+        if !current_fields.is_empty() {
+            panic!(
+                "DataBuilder.apply() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        match data {
+            Input::Choice(data) => {
+                let entry = T::entries()
+                    .iter()
+                    .find(|(code, _)| *code == data)
+                    .ok_or(ChooseError::UnexpectedChoice)?;
+                self.select(entry);
+            }
+            Input::Text(data) => {
+                let text = data.trim();
+                let exact = T::entries().iter().find(|(code, name)| {
+                    code.eq_ignore_ascii_case(text) || name.to_lowercase() == text.to_lowercase()
+                });
+                if let Some(entry) = exact {
+                    self.select(entry);
+                    return Ok(());
+                }
+                let matches = Self::find(text);
+                match matches.len() {
+                    0 => {
+                        return Err(ChooseError::InvalidText {
+                            error: format!("Nothing matches \"{}\"", text),
+                        })
+                    }
+                    1 => self.select(matches[0]),
+                    _ => self.search = Some(text.to_string()),
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn get_options(&self, current_fields: &[Id]) -> Options {
+        if !current_fields.is_empty() {
+            panic!(
+                "DataBuilder.get_options() called with non empty fields: {:?}",
+                current_fields
+            );
+        }
+        let matches = match &self.search {
+            Some(search) => Self::find(search),
+            None => vec![],
+        };
+        let notice = if matches.len() > MAX_CHOICES {
+            Some(format!(
+                "Showing {} of {} matches, refine the search",
+                MAX_CHOICES,
+                matches.len()
+            ))
+        } else {
+            None
+        };
+        Options {
+            query: self.prompt.clone(),
+            text_input: true,
+            choices: matches
+                .iter()
+                .take(MAX_CHOICES)
+                .map(|(code, name)| Choice {
+                    choice_id: (*code).into(),
+                    text: format!("{} ({})", name, code),
+                    needs_action: false,
+                    ..Default::default()
+                })
+                .collect(),
+            notice,
+            ..Default::default()
+        }
+    }
+
+    fn get_subfields(&self, _: &[Id]) -> Vec<Id> {
+        vec![]
+    }
+
+    fn stay_after_apply(&self, _: &[Id]) -> bool {
+        // let the user pick one of the entries matched by the search
+        self.search.is_some()
+    }
+
+    fn to_node(&self) -> Node {
+        match self.value {
+            Some((code, _)) => Node::Leaf(Field::String(code.to_string())),
+            None => Node::Leaf(Field::Missing),
+        }
+    }
+
+    crate::typed_get_value_any!(T);
+
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        Some(Box::new(DataBuilder::<T> {
+            value: self.value,
+            search: self.search.clone(),
+            prompt: self.prompt.clone(),
+            data_type: PhantomData,
+        }))
+    }
+}
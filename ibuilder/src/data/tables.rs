@@ -0,0 +1,839 @@
+//! The tables with the data of the builders in the `data` module.
+
+/// The ISO 3166-1 alpha-2 country codes, with the name of the country.
+pub static COUNTRIES: &[(&str, &str)] = &[
+    ("AD", "Andorra"),
+    ("AE", "United Arab Emirates"),
+    ("AF", "Afghanistan"),
+    ("AG", "Antigua & Barbuda"),
+    ("AI", "Anguilla"),
+    ("AL", "Albania"),
+    ("AM", "Armenia"),
+    ("AO", "Angola"),
+    ("AQ", "Antarctica"),
+    ("AR", "Argentina"),
+    ("AS", "Samoa (American)"),
+    ("AT", "Austria"),
+    ("AU", "Australia"),
+    ("AW", "Aruba"),
+    ("AX", "Åland Islands"),
+    ("AZ", "Azerbaijan"),
+    ("BA", "Bosnia & Herzegovina"),
+    ("BB", "Barbados"),
+    ("BD", "Bangladesh"),
+    ("BE", "Belgium"),
+    ("BF", "Burkina Faso"),
+    ("BG", "Bulgaria"),
+    ("BH", "Bahrain"),
+    ("BI", "Burundi"),
+    ("BJ", "Benin"),
+    ("BL", "St Barthelemy"),
+    ("BM", "Bermuda"),
+    ("BN", "Brunei"),
+    ("BO", "Bolivia"),
+    ("BQ", "Caribbean NL"),
+    ("BR", "Brazil"),
+    ("BS", "Bahamas"),
+    ("BT", "Bhutan"),
+    ("BV", "Bouvet Island"),
+    ("BW", "Botswana"),
+    ("BY", "Belarus"),
+    ("BZ", "Belize"),
+    ("CA", "Canada"),
+    ("CC", "Cocos (Keeling) Islands"),
+    ("CD", "Congo (Dem. Rep.)"),
+    ("CF", "Central African Rep."),
+    ("CG", "Congo (Rep.)"),
+    ("CH", "Switzerland"),
+    ("CI", "Côte d'Ivoire"),
+    ("CK", "Cook Islands"),
+    ("CL", "Chile"),
+    ("CM", "Cameroon"),
+    ("CN", "China"),
+    ("CO", "Colombia"),
+    ("CR", "Costa Rica"),
+    ("CU", "Cuba"),
+    ("CV", "Cape Verde"),
+    ("CW", "Curaçao"),
+    ("CX", "Christmas Island"),
+    ("CY", "Cyprus"),
+    ("CZ", "Czech Republic"),
+    ("DE", "Germany"),
+    ("DJ", "Djibouti"),
+    ("DK", "Denmark"),
+    ("DM", "Dominica"),
+    ("DO", "Dominican Republic"),
+    ("DZ", "Algeria"),
+    ("EC", "Ecuador"),
+    ("EE", "Estonia"),
+    ("EG", "Egypt"),
+    ("EH", "Western Sahara"),
+    ("ER", "Eritrea"),
+    ("ES", "Spain"),
+    ("ET", "Ethiopia"),
+    ("FI", "Finland"),
+    ("FJ", "Fiji"),
+    ("FK", "Falkland Islands"),
+    ("FM", "Micronesia"),
+    ("FO", "Faroe Islands"),
+    ("FR", "France"),
+    ("GA", "Gabon"),
+    ("GB", "Britain (UK)"),
+    ("GD", "Grenada"),
+    ("GE", "Georgia"),
+    ("GF", "French Guiana"),
+    ("GG", "Guernsey"),
+    ("GH", "Ghana"),
+    ("GI", "Gibraltar"),
+    ("GL", "Greenland"),
+    ("GM", "Gambia"),
+    ("GN", "Guinea"),
+    ("GP", "Guadeloupe"),
+    ("GQ", "Equatorial Guinea"),
+    ("GR", "Greece"),
+    ("GS", "South Georgia & the South Sandwich Islands"),
+    ("GT", "Guatemala"),
+    ("GU", "Guam"),
+    ("GW", "Guinea-Bissau"),
+    ("GY", "Guyana"),
+    ("HK", "Hong Kong"),
+    ("HM", "Heard Island & McDonald Islands"),
+    ("HN", "Honduras"),
+    ("HR", "Croatia"),
+    ("HT", "Haiti"),
+    ("HU", "Hungary"),
+    ("ID", "Indonesia"),
+    ("IE", "Ireland"),
+    ("IL", "Israel"),
+    ("IM", "Isle of Man"),
+    ("IN", "India"),
+    ("IO", "British Indian Ocean Territory"),
+    ("IQ", "Iraq"),
+    ("IR", "Iran"),
+    ("IS", "Iceland"),
+    ("IT", "Italy"),
+    ("JE", "Jersey"),
+    ("JM", "Jamaica"),
+    ("JO", "Jordan"),
+    ("JP", "Japan"),
+    ("KE", "Kenya"),
+    ("KG", "Kyrgyzstan"),
+    ("KH", "Cambodia"),
+    ("KI", "Kiribati"),
+    ("KM", "Comoros"),
+    ("KN", "St Kitts & Nevis"),
+    ("KP", "Korea (North)"),
+    ("KR", "Korea (South)"),
+    ("KW", "Kuwait"),
+    ("KY", "Cayman Islands"),
+    ("KZ", "Kazakhstan"),
+    ("LA", "Laos"),
+    ("LB", "Lebanon"),
+    ("LC", "St Lucia"),
+    ("LI", "Liechtenstein"),
+    ("LK", "Sri Lanka"),
+    ("LR", "Liberia"),
+    ("LS", "Lesotho"),
+    ("LT", "Lithuania"),
+    ("LU", "Luxembourg"),
+    ("LV", "Latvia"),
+    ("LY", "Libya"),
+    ("MA", "Morocco"),
+    ("MC", "Monaco"),
+    ("MD", "Moldova"),
+    ("ME", "Montenegro"),
+    ("MF", "St Martin (French)"),
+    ("MG", "Madagascar"),
+    ("MH", "Marshall Islands"),
+    ("MK", "North Macedonia"),
+    ("ML", "Mali"),
+    ("MM", "Myanmar (Burma)"),
+    ("MN", "Mongolia"),
+    ("MO", "Macau"),
+    ("MP", "Northern Mariana Islands"),
+    ("MQ", "Martinique"),
+    ("MR", "Mauritania"),
+    ("MS", "Montserrat"),
+    ("MT", "Malta"),
+    ("MU", "Mauritius"),
+    ("MV", "Maldives"),
+    ("MW", "Malawi"),
+    ("MX", "Mexico"),
+    ("MY", "Malaysia"),
+    ("MZ", "Mozambique"),
+    ("NA", "Namibia"),
+    ("NC", "New Caledonia"),
+    ("NE", "Niger"),
+    ("NF", "Norfolk Island"),
+    ("NG", "Nigeria"),
+    ("NI", "Nicaragua"),
+    ("NL", "Netherlands"),
+    ("NO", "Norway"),
+    ("NP", "Nepal"),
+    ("NR", "Nauru"),
+    ("NU", "Niue"),
+    ("NZ", "New Zealand"),
+    ("OM", "Oman"),
+    ("PA", "Panama"),
+    ("PE", "Peru"),
+    ("PF", "French Polynesia"),
+    ("PG", "Papua New Guinea"),
+    ("PH", "Philippines"),
+    ("PK", "Pakistan"),
+    ("PL", "Poland"),
+    ("PM", "St Pierre & Miquelon"),
+    ("PN", "Pitcairn"),
+    ("PR", "Puerto Rico"),
+    ("PS", "Palestine"),
+    ("PT", "Portugal"),
+    ("PW", "Palau"),
+    ("PY", "Paraguay"),
+    ("QA", "Qatar"),
+    ("RE", "Réunion"),
+    ("RO", "Romania"),
+    ("RS", "Serbia"),
+    ("RU", "Russia"),
+    ("RW", "Rwanda"),
+    ("SA", "Saudi Arabia"),
+    ("SB", "Solomon Islands"),
+    ("SC", "Seychelles"),
+    ("SD", "Sudan"),
+    ("SE", "Sweden"),
+    ("SG", "Singapore"),
+    ("SH", "St Helena"),
+    ("SI", "Slovenia"),
+    ("SJ", "Svalbard & Jan Mayen"),
+    ("SK", "Slovakia"),
+    ("SL", "Sierra Leone"),
+    ("SM", "San Marino"),
+    ("SN", "Senegal"),
+    ("SO", "Somalia"),
+    ("SR", "Suriname"),
+    ("SS", "South Sudan"),
+    ("ST", "Sao Tome & Principe"),
+    ("SV", "El Salvador"),
+    ("SX", "St Maarten (Dutch)"),
+    ("SY", "Syria"),
+    ("SZ", "Eswatini (Swaziland)"),
+    ("TC", "Turks & Caicos Is"),
+    ("TD", "Chad"),
+    ("TF", "French S. Terr."),
+    ("TG", "Togo"),
+    ("TH", "Thailand"),
+    ("TJ", "Tajikistan"),
+    ("TK", "Tokelau"),
+    ("TL", "East Timor"),
+    ("TM", "Turkmenistan"),
+    ("TN", "Tunisia"),
+    ("TO", "Tonga"),
+    ("TR", "Turkey"),
+    ("TT", "Trinidad & Tobago"),
+    ("TV", "Tuvalu"),
+    ("TW", "Taiwan"),
+    ("TZ", "Tanzania"),
+    ("UA", "Ukraine"),
+    ("UG", "Uganda"),
+    ("UM", "US minor outlying islands"),
+    ("US", "United States"),
+    ("UY", "Uruguay"),
+    ("UZ", "Uzbekistan"),
+    ("VA", "Vatican City"),
+    ("VC", "St Vincent"),
+    ("VE", "Venezuela"),
+    ("VG", "Virgin Islands (UK)"),
+    ("VI", "Virgin Islands (US)"),
+    ("VN", "Vietnam"),
+    ("VU", "Vanuatu"),
+    ("WF", "Wallis & Futuna"),
+    ("WS", "Samoa (western)"),
+    ("YE", "Yemen"),
+    ("YT", "Mayotte"),
+    ("ZA", "South Africa"),
+    ("ZM", "Zambia"),
+    ("ZW", "Zimbabwe"),
+];
+
+/// The active ISO 4217 currency codes, with the name of the currency.
+pub static CURRENCIES: &[(&str, &str)] = &[
+    ("AED", "UAE Dirham"),
+    ("AFN", "Afghani"),
+    ("ALL", "Lek"),
+    ("AMD", "Armenian Dram"),
+    ("ANG", "Netherlands Antillean Guilder"),
+    ("AOA", "Kwanza"),
+    ("ARS", "Argentine Peso"),
+    ("AUD", "Australian Dollar"),
+    ("AWG", "Aruban Florin"),
+    ("AZN", "Azerbaijan Manat"),
+    ("BAM", "Convertible Mark"),
+    ("BBD", "Barbados Dollar"),
+    ("BDT", "Taka"),
+    ("BGN", "Bulgarian Lev"),
+    ("BHD", "Bahraini Dinar"),
+    ("BIF", "Burundi Franc"),
+    ("BMD", "Bermudian Dollar"),
+    ("BND", "Brunei Dollar"),
+    ("BOB", "Boliviano"),
+    ("BRL", "Brazilian Real"),
+    ("BSD", "Bahamian Dollar"),
+    ("BTN", "Ngultrum"),
+    ("BWP", "Pula"),
+    ("BYN", "Belarusian Ruble"),
+    ("BZD", "Belize Dollar"),
+    ("CAD", "Canadian Dollar"),
+    ("CDF", "Congolese Franc"),
+    ("CHF", "Swiss Franc"),
+    ("CLP", "Chilean Peso"),
+    ("CNY", "Yuan Renminbi"),
+    ("COP", "Colombian Peso"),
+    ("CRC", "Costa Rican Colon"),
+    ("CUP", "Cuban Peso"),
+    ("CVE", "Cabo Verde Escudo"),
+    ("CZK", "Czech Koruna"),
+    ("DJF", "Djibouti Franc"),
+    ("DKK", "Danish Krone"),
+    ("DOP", "Dominican Peso"),
+    ("DZD", "Algerian Dinar"),
+    ("EGP", "Egyptian Pound"),
+    ("ERN", "Nakfa"),
+    ("ETB", "Ethiopian Birr"),
+    ("EUR", "Euro"),
+    ("FJD", "Fiji Dollar"),
+    ("FKP", "Falkland Islands Pound"),
+    ("GBP", "Pound Sterling"),
+    ("GEL", "Lari"),
+    ("GHS", "Ghana Cedi"),
+    ("GIP", "Gibraltar Pound"),
+    ("GMD", "Dalasi"),
+    ("GNF", "Guinean Franc"),
+    ("GTQ", "Quetzal"),
+    ("GYD", "Guyana Dollar"),
+    ("HKD", "Hong Kong Dollar"),
+    ("HNL", "Lempira"),
+    ("HTG", "Gourde"),
+    ("HUF", "Forint"),
+    ("IDR", "Rupiah"),
+    ("ILS", "New Israeli Sheqel"),
+    ("INR", "Indian Rupee"),
+    ("IQD", "Iraqi Dinar"),
+    ("IRR", "Iranian Rial"),
+    ("ISK", "Iceland Krona"),
+    ("JMD", "Jamaican Dollar"),
+    ("JOD", "Jordanian Dinar"),
+    ("JPY", "Yen"),
+    ("KES", "Kenyan Shilling"),
+    ("KGS", "Som"),
+    ("KHR", "Riel"),
+    ("KMF", "Comorian Franc"),
+    ("KPW", "North Korean Won"),
+    ("KRW", "Won"),
+    ("KWD", "Kuwaiti Dinar"),
+    ("KYD", "Cayman Islands Dollar"),
+    ("KZT", "Tenge"),
+    ("LAK", "Lao Kip"),
+    ("LBP", "Lebanese Pound"),
+    ("LKR", "Sri Lanka Rupee"),
+    ("LRD", "Liberian Dollar"),
+    ("LSL", "Loti"),
+    ("LYD", "Libyan Dinar"),
+    ("MAD", "Moroccan Dirham"),
+    ("MDL", "Moldovan Leu"),
+    ("MGA", "Malagasy Ariary"),
+    ("MKD", "Denar"),
+    ("MMK", "Kyat"),
+    ("MNT", "Tugrik"),
+    ("MOP", "Pataca"),
+    ("MRU", "Ouguiya"),
+    ("MUR", "Mauritius Rupee"),
+    ("MVR", "Rufiyaa"),
+    ("MWK", "Malawi Kwacha"),
+    ("MXN", "Mexican Peso"),
+    ("MYR", "Malaysian Ringgit"),
+    ("MZN", "Mozambique Metical"),
+    ("NAD", "Namibia Dollar"),
+    ("NGN", "Naira"),
+    ("NIO", "Cordoba Oro"),
+    ("NOK", "Norwegian Krone"),
+    ("NPR", "Nepalese Rupee"),
+    ("NZD", "New Zealand Dollar"),
+    ("OMR", "Rial Omani"),
+    ("PAB", "Balboa"),
+    ("PEN", "Sol"),
+    ("PGK", "Kina"),
+    ("PHP", "Philippine Peso"),
+    ("PKR", "Pakistan Rupee"),
+    ("PLN", "Zloty"),
+    ("PYG", "Guarani"),
+    ("QAR", "Qatari Rial"),
+    ("RON", "Romanian Leu"),
+    ("RSD", "Serbian Dinar"),
+    ("RUB", "Russian Ruble"),
+    ("RWF", "Rwanda Franc"),
+    ("SAR", "Saudi Riyal"),
+    ("SBD", "Solomon Islands Dollar"),
+    ("SCR", "Seychelles Rupee"),
+    ("SDG", "Sudanese Pound"),
+    ("SEK", "Swedish Krona"),
+    ("SGD", "Singapore Dollar"),
+    ("SHP", "Saint Helena Pound"),
+    ("SLE", "Leone"),
+    ("SOS", "Somali Shilling"),
+    ("SRD", "Surinam Dollar"),
+    ("SSP", "South Sudanese Pound"),
+    ("STN", "Dobra"),
+    ("SVC", "El Salvador Colon"),
+    ("SYP", "Syrian Pound"),
+    ("SZL", "Lilangeni"),
+    ("THB", "Baht"),
+    ("TJS", "Somoni"),
+    ("TMT", "Turkmenistan New Manat"),
+    ("TND", "Tunisian Dinar"),
+    ("TOP", "Pa'anga"),
+    ("TRY", "Turkish Lira"),
+    ("TTD", "Trinidad and Tobago Dollar"),
+    ("TWD", "New Taiwan Dollar"),
+    ("TZS", "Tanzanian Shilling"),
+    ("UAH", "Hryvnia"),
+    ("UGX", "Uganda Shilling"),
+    ("USD", "US Dollar"),
+    ("UYU", "Peso Uruguayo"),
+    ("UZS", "Uzbekistan Sum"),
+    ("VES", "Bolivar Soberano"),
+    ("VND", "Dong"),
+    ("VUV", "Vatu"),
+    ("WST", "Tala"),
+    ("XAF", "CFA Franc BEAC"),
+    ("XCD", "East Caribbean Dollar"),
+    ("XOF", "CFA Franc BCEAO"),
+    ("XPF", "CFP Franc"),
+    ("YER", "Yemeni Rial"),
+    ("ZAR", "Rand"),
+    ("ZMW", "Zambian Kwacha"),
+    ("ZWL", "Zimbabwe Dollar"),
+];
+
+/// The canonical IANA timezones, with the name of the country they are in.
+pub static TIMEZONES: &[(&str, &str)] = &[
+    ("Africa/Abidjan", "Côte d'Ivoire"),
+    ("Africa/Accra", "Ghana"),
+    ("Africa/Addis_Ababa", "Ethiopia"),
+    ("Africa/Algiers", "Algeria"),
+    ("Africa/Asmara", "Eritrea"),
+    ("Africa/Bamako", "Mali"),
+    ("Africa/Bangui", "Central African Rep."),
+    ("Africa/Banjul", "Gambia"),
+    ("Africa/Bissau", "Guinea-Bissau"),
+    ("Africa/Blantyre", "Malawi"),
+    ("Africa/Brazzaville", "Congo (Rep.)"),
+    ("Africa/Bujumbura", "Burundi"),
+    ("Africa/Cairo", "Egypt"),
+    ("Africa/Casablanca", "Morocco"),
+    ("Africa/Ceuta", "Spain"),
+    ("Africa/Conakry", "Guinea"),
+    ("Africa/Dakar", "Senegal"),
+    ("Africa/Dar_es_Salaam", "Tanzania"),
+    ("Africa/Djibouti", "Djibouti"),
+    ("Africa/Douala", "Cameroon"),
+    ("Africa/El_Aaiun", "Western Sahara"),
+    ("Africa/Freetown", "Sierra Leone"),
+    ("Africa/Gaborone", "Botswana"),
+    ("Africa/Harare", "Zimbabwe"),
+    ("Africa/Johannesburg", "South Africa"),
+    ("Africa/Juba", "South Sudan"),
+    ("Africa/Kampala", "Uganda"),
+    ("Africa/Khartoum", "Sudan"),
+    ("Africa/Kigali", "Rwanda"),
+    ("Africa/Kinshasa", "Congo (Dem. Rep.)"),
+    ("Africa/Lagos", "Nigeria"),
+    ("Africa/Libreville", "Gabon"),
+    ("Africa/Lome", "Togo"),
+    ("Africa/Luanda", "Angola"),
+    ("Africa/Lubumbashi", "Congo (Dem. Rep.)"),
+    ("Africa/Lusaka", "Zambia"),
+    ("Africa/Malabo", "Equatorial Guinea"),
+    ("Africa/Maputo", "Mozambique"),
+    ("Africa/Maseru", "Lesotho"),
+    ("Africa/Mbabane", "Eswatini (Swaziland)"),
+    ("Africa/Mogadishu", "Somalia"),
+    ("Africa/Monrovia", "Liberia"),
+    ("Africa/Nairobi", "Kenya"),
+    ("Africa/Ndjamena", "Chad"),
+    ("Africa/Niamey", "Niger"),
+    ("Africa/Nouakchott", "Mauritania"),
+    ("Africa/Ouagadougou", "Burkina Faso"),
+    ("Africa/Porto-Novo", "Benin"),
+    ("Africa/Sao_Tome", "Sao Tome & Principe"),
+    ("Africa/Tripoli", "Libya"),
+    ("Africa/Tunis", "Tunisia"),
+    ("Africa/Windhoek", "Namibia"),
+    ("America/Adak", "United States"),
+    ("America/Anchorage", "United States"),
+    ("America/Anguilla", "Anguilla"),
+    ("America/Antigua", "Antigua & Barbuda"),
+    ("America/Araguaina", "Brazil"),
+    ("America/Argentina/Buenos_Aires", "Argentina"),
+    ("America/Argentina/Catamarca", "Argentina"),
+    ("America/Argentina/Cordoba", "Argentina"),
+    ("America/Argentina/Jujuy", "Argentina"),
+    ("America/Argentina/La_Rioja", "Argentina"),
+    ("America/Argentina/Mendoza", "Argentina"),
+    ("America/Argentina/Rio_Gallegos", "Argentina"),
+    ("America/Argentina/Salta", "Argentina"),
+    ("America/Argentina/San_Juan", "Argentina"),
+    ("America/Argentina/San_Luis", "Argentina"),
+    ("America/Argentina/Tucuman", "Argentina"),
+    ("America/Argentina/Ushuaia", "Argentina"),
+    ("America/Aruba", "Aruba"),
+    ("America/Asuncion", "Paraguay"),
+    ("America/Atikokan", "Canada"),
+    ("America/Bahia", "Brazil"),
+    ("America/Bahia_Banderas", "Mexico"),
+    ("America/Barbados", "Barbados"),
+    ("America/Belem", "Brazil"),
+    ("America/Belize", "Belize"),
+    ("America/Blanc-Sablon", "Canada"),
+    ("America/Boa_Vista", "Brazil"),
+    ("America/Bogota", "Colombia"),
+    ("America/Boise", "United States"),
+    ("America/Cambridge_Bay", "Canada"),
+    ("America/Campo_Grande", "Brazil"),
+    ("America/Cancun", "Mexico"),
+    ("America/Caracas", "Venezuela"),
+    ("America/Cayenne", "French Guiana"),
+    ("America/Cayman", "Cayman Islands"),
+    ("America/Chicago", "United States"),
+    ("America/Chihuahua", "Mexico"),
+    ("America/Ciudad_Juarez", "Mexico"),
+    ("America/Costa_Rica", "Costa Rica"),
+    ("America/Coyhaique", "Chile"),
+    ("America/Creston", "Canada"),
+    ("America/Cuiaba", "Brazil"),
+    ("America/Curacao", "Curaçao"),
+    ("America/Danmarkshavn", "Greenland"),
+    ("America/Dawson", "Canada"),
+    ("America/Dawson_Creek", "Canada"),
+    ("America/Denver", "United States"),
+    ("America/Detroit", "United States"),
+    ("America/Dominica", "Dominica"),
+    ("America/Edmonton", "Canada"),
+    ("America/Eirunepe", "Brazil"),
+    ("America/El_Salvador", "El Salvador"),
+    ("America/Fort_Nelson", "Canada"),
+    ("America/Fortaleza", "Brazil"),
+    ("America/Glace_Bay", "Canada"),
+    ("America/Goose_Bay", "Canada"),
+    ("America/Grand_Turk", "Turks & Caicos Is"),
+    ("America/Grenada", "Grenada"),
+    ("America/Guadeloupe", "Guadeloupe"),
+    ("America/Guatemala", "Guatemala"),
+    ("America/Guayaquil", "Ecuador"),
+    ("America/Guyana", "Guyana"),
+    ("America/Halifax", "Canada"),
+    ("America/Havana", "Cuba"),
+    ("America/Hermosillo", "Mexico"),
+    ("America/Indiana/Indianapolis", "United States"),
+    ("America/Indiana/Knox", "United States"),
+    ("America/Indiana/Marengo", "United States"),
+    ("America/Indiana/Petersburg", "United States"),
+    ("America/Indiana/Tell_City", "United States"),
+    ("America/Indiana/Vevay", "United States"),
+    ("America/Indiana/Vincennes", "United States"),
+    ("America/Indiana/Winamac", "United States"),
+    ("America/Inuvik", "Canada"),
+    ("America/Iqaluit", "Canada"),
+    ("America/Jamaica", "Jamaica"),
+    ("America/Juneau", "United States"),
+    ("America/Kentucky/Louisville", "United States"),
+    ("America/Kentucky/Monticello", "United States"),
+    ("America/Kralendijk", "Caribbean NL"),
+    ("America/La_Paz", "Bolivia"),
+    ("America/Lima", "Peru"),
+    ("America/Los_Angeles", "United States"),
+    ("America/Lower_Princes", "St Maarten (Dutch)"),
+    ("America/Maceio", "Brazil"),
+    ("America/Managua", "Nicaragua"),
+    ("America/Manaus", "Brazil"),
+    ("America/Marigot", "St Martin (French)"),
+    ("America/Martinique", "Martinique"),
+    ("America/Matamoros", "Mexico"),
+    ("America/Mazatlan", "Mexico"),
+    ("America/Menominee", "United States"),
+    ("America/Merida", "Mexico"),
+    ("America/Metlakatla", "United States"),
+    ("America/Mexico_City", "Mexico"),
+    ("America/Miquelon", "St Pierre & Miquelon"),
+    ("America/Moncton", "Canada"),
+    ("America/Monterrey", "Mexico"),
+    ("America/Montevideo", "Uruguay"),
+    ("America/Montserrat", "Montserrat"),
+    ("America/Nassau", "Bahamas"),
+    ("America/New_York", "United States"),
+    ("America/Nome", "United States"),
+    ("America/Noronha", "Brazil"),
+    ("America/North_Dakota/Beulah", "United States"),
+    ("America/North_Dakota/Center", "United States"),
+    ("America/North_Dakota/New_Salem", "United States"),
+    ("America/Nuuk", "Greenland"),
+    ("America/Ojinaga", "Mexico"),
+    ("America/Panama", "Panama"),
+    ("America/Paramaribo", "Suriname"),
+    ("America/Phoenix", "United States"),
+    ("America/Port-au-Prince", "Haiti"),
+    ("America/Port_of_Spain", "Trinidad & Tobago"),
+    ("America/Porto_Velho", "Brazil"),
+    ("America/Puerto_Rico", "Puerto Rico"),
+    ("America/Punta_Arenas", "Chile"),
+    ("America/Rankin_Inlet", "Canada"),
+    ("America/Recife", "Brazil"),
+    ("America/Regina", "Canada"),
+    ("America/Resolute", "Canada"),
+    ("America/Rio_Branco", "Brazil"),
+    ("America/Santarem", "Brazil"),
+    ("America/Santiago", "Chile"),
+    ("America/Santo_Domingo", "Dominican Republic"),
+    ("America/Sao_Paulo", "Brazil"),
+    ("America/Scoresbysund", "Greenland"),
+    ("America/Sitka", "United States"),
+    ("America/St_Barthelemy", "St Barthelemy"),
+    ("America/St_Johns", "Canada"),
+    ("America/St_Kitts", "St Kitts & Nevis"),
+    ("America/St_Lucia", "St Lucia"),
+    ("America/St_Thomas", "Virgin Islands (US)"),
+    ("America/St_Vincent", "St Vincent"),
+    ("America/Swift_Current", "Canada"),
+    ("America/Tegucigalpa", "Honduras"),
+    ("America/Thule", "Greenland"),
+    ("America/Tijuana", "Mexico"),
+    ("America/Toronto", "Canada"),
+    ("America/Tortola", "Virgin Islands (UK)"),
+    ("America/Vancouver", "Canada"),
+    ("America/Whitehorse", "Canada"),
+    ("America/Winnipeg", "Canada"),
+    ("America/Yakutat", "United States"),
+    ("Antarctica/Casey", "Antarctica"),
+    ("Antarctica/Davis", "Antarctica"),
+    ("Antarctica/DumontDUrville", "Antarctica"),
+    ("Antarctica/Macquarie", "Australia"),
+    ("Antarctica/Mawson", "Antarctica"),
+    ("Antarctica/McMurdo", "Antarctica"),
+    ("Antarctica/Palmer", "Antarctica"),
+    ("Antarctica/Rothera", "Antarctica"),
+    ("Antarctica/Syowa", "Antarctica"),
+    ("Antarctica/Troll", "Antarctica"),
+    ("Antarctica/Vostok", "Antarctica"),
+    ("Arctic/Longyearbyen", "Svalbard & Jan Mayen"),
+    ("Asia/Aden", "Yemen"),
+    ("Asia/Almaty", "Kazakhstan"),
+    ("Asia/Amman", "Jordan"),
+    ("Asia/Anadyr", "Russia"),
+    ("Asia/Aqtau", "Kazakhstan"),
+    ("Asia/Aqtobe", "Kazakhstan"),
+    ("Asia/Ashgabat", "Turkmenistan"),
+    ("Asia/Atyrau", "Kazakhstan"),
+    ("Asia/Baghdad", "Iraq"),
+    ("Asia/Bahrain", "Bahrain"),
+    ("Asia/Baku", "Azerbaijan"),
+    ("Asia/Bangkok", "Thailand"),
+    ("Asia/Barnaul", "Russia"),
+    ("Asia/Beirut", "Lebanon"),
+    ("Asia/Bishkek", "Kyrgyzstan"),
+    ("Asia/Brunei", "Brunei"),
+    ("Asia/Chita", "Russia"),
+    ("Asia/Colombo", "Sri Lanka"),
+    ("Asia/Damascus", "Syria"),
+    ("Asia/Dhaka", "Bangladesh"),
+    ("Asia/Dili", "East Timor"),
+    ("Asia/Dubai", "United Arab Emirates"),
+    ("Asia/Dushanbe", "Tajikistan"),
+    ("Asia/Famagusta", "Cyprus"),
+    ("Asia/Gaza", "Palestine"),
+    ("Asia/Hebron", "Palestine"),
+    ("Asia/Ho_Chi_Minh", "Vietnam"),
+    ("Asia/Hong_Kong", "Hong Kong"),
+    ("Asia/Hovd", "Mongolia"),
+    ("Asia/Irkutsk", "Russia"),
+    ("Asia/Jakarta", "Indonesia"),
+    ("Asia/Jayapura", "Indonesia"),
+    ("Asia/Jerusalem", "Israel"),
+    ("Asia/Kabul", "Afghanistan"),
+    ("Asia/Kamchatka", "Russia"),
+    ("Asia/Karachi", "Pakistan"),
+    ("Asia/Kathmandu", "Nepal"),
+    ("Asia/Khandyga", "Russia"),
+    ("Asia/Kolkata", "India"),
+    ("Asia/Krasnoyarsk", "Russia"),
+    ("Asia/Kuala_Lumpur", "Malaysia"),
+    ("Asia/Kuching", "Malaysia"),
+    ("Asia/Kuwait", "Kuwait"),
+    ("Asia/Macau", "Macau"),
+    ("Asia/Magadan", "Russia"),
+    ("Asia/Makassar", "Indonesia"),
+    ("Asia/Manila", "Philippines"),
+    ("Asia/Muscat", "Oman"),
+    ("Asia/Nicosia", "Cyprus"),
+    ("Asia/Novokuznetsk", "Russia"),
+    ("Asia/Novosibirsk", "Russia"),
+    ("Asia/Omsk", "Russia"),
+    ("Asia/Oral", "Kazakhstan"),
+    ("Asia/Phnom_Penh", "Cambodia"),
+    ("Asia/Pontianak", "Indonesia"),
+    ("Asia/Pyongyang", "Korea (North)"),
+    ("Asia/Qatar", "Qatar"),
+    ("Asia/Qostanay", "Kazakhstan"),
+    ("Asia/Qyzylorda", "Kazakhstan"),
+    ("Asia/Riyadh", "Saudi Arabia"),
+    ("Asia/Sakhalin", "Russia"),
+    ("Asia/Samarkand", "Uzbekistan"),
+    ("Asia/Seoul", "Korea (South)"),
+    ("Asia/Shanghai", "China"),
+    ("Asia/Singapore", "Singapore"),
+    ("Asia/Srednekolymsk", "Russia"),
+    ("Asia/Taipei", "Taiwan"),
+    ("Asia/Tashkent", "Uzbekistan"),
+    ("Asia/Tbilisi", "Georgia"),
+    ("Asia/Tehran", "Iran"),
+    ("Asia/Thimphu", "Bhutan"),
+    ("Asia/Tokyo", "Japan"),
+    ("Asia/Tomsk", "Russia"),
+    ("Asia/Ulaanbaatar", "Mongolia"),
+    ("Asia/Urumqi", "China"),
+    ("Asia/Ust-Nera", "Russia"),
+    ("Asia/Vientiane", "Laos"),
+    ("Asia/Vladivostok", "Russia"),
+    ("Asia/Yakutsk", "Russia"),
+    ("Asia/Yangon", "Myanmar (Burma)"),
+    ("Asia/Yekaterinburg", "Russia"),
+    ("Asia/Yerevan", "Armenia"),
+    ("Atlantic/Azores", "Portugal"),
+    ("Atlantic/Bermuda", "Bermuda"),
+    ("Atlantic/Canary", "Spain"),
+    ("Atlantic/Cape_Verde", "Cape Verde"),
+    ("Atlantic/Faroe", "Faroe Islands"),
+    ("Atlantic/Madeira", "Portugal"),
+    ("Atlantic/Reykjavik", "Iceland"),
+    (
+        "Atlantic/South_Georgia",
+        "South Georgia & the South Sandwich Islands",
+    ),
+    ("Atlantic/St_Helena", "St Helena"),
+    ("Atlantic/Stanley", "Falkland Islands"),
+    ("Australia/Adelaide", "Australia"),
+    ("Australia/Brisbane", "Australia"),
+    ("Australia/Broken_Hill", "Australia"),
+    ("Australia/Darwin", "Australia"),
+    ("Australia/Eucla", "Australia"),
+    ("Australia/Hobart", "Australia"),
+    ("Australia/Lindeman", "Australia"),
+    ("Australia/Lord_Howe", "Australia"),
+    ("Australia/Melbourne", "Australia"),
+    ("Australia/Perth", "Australia"),
+    ("Australia/Sydney", "Australia"),
+    ("Europe/Amsterdam", "Netherlands"),
+    ("Europe/Andorra", "Andorra"),
+    ("Europe/Astrakhan", "Russia"),
+    ("Europe/Athens", "Greece"),
+    ("Europe/Belgrade", "Serbia"),
+    ("Europe/Berlin", "Germany"),
+    ("Europe/Bratislava", "Slovakia"),
+    ("Europe/Brussels", "Belgium"),
+    ("Europe/Bucharest", "Romania"),
+    ("Europe/Budapest", "Hungary"),
+    ("Europe/Busingen", "Germany"),
+    ("Europe/Chisinau", "Moldova"),
+    ("Europe/Copenhagen", "Denmark"),
+    ("Europe/Dublin", "Ireland"),
+    ("Europe/Gibraltar", "Gibraltar"),
+    ("Europe/Guernsey", "Guernsey"),
+    ("Europe/Helsinki", "Finland"),
+    ("Europe/Isle_of_Man", "Isle of Man"),
+    ("Europe/Istanbul", "Turkey"),
+    ("Europe/Jersey", "Jersey"),
+    ("Europe/Kaliningrad", "Russia"),
+    ("Europe/Kirov", "Russia"),
+    ("Europe/Kyiv", "Ukraine"),
+    ("Europe/Lisbon", "Portugal"),
+    ("Europe/Ljubljana", "Slovenia"),
+    ("Europe/London", "Britain (UK)"),
+    ("Europe/Luxembourg", "Luxembourg"),
+    ("Europe/Madrid", "Spain"),
+    ("Europe/Malta", "Malta"),
+    ("Europe/Mariehamn", "Åland Islands"),
+    ("Europe/Minsk", "Belarus"),
+    ("Europe/Monaco", "Monaco"),
+    ("Europe/Moscow", "Russia"),
+    ("Europe/Oslo", "Norway"),
+    ("Europe/Paris", "France"),
+    ("Europe/Podgorica", "Montenegro"),
+    ("Europe/Prague", "Czech Republic"),
+    ("Europe/Riga", "Latvia"),
+    ("Europe/Rome", "Italy"),
+    ("Europe/Samara", "Russia"),
+    ("Europe/San_Marino", "San Marino"),
+    ("Europe/Sarajevo", "Bosnia & Herzegovina"),
+    ("Europe/Saratov", "Russia"),
+    ("Europe/Simferopol", "Ukraine"),
+    ("Europe/Skopje", "North Macedonia"),
+    ("Europe/Sofia", "Bulgaria"),
+    ("Europe/Stockholm", "Sweden"),
+    ("Europe/Tallinn", "Estonia"),
+    ("Europe/Tirane", "Albania"),
+    ("Europe/Ulyanovsk", "Russia"),
+    ("Europe/Vaduz", "Liechtenstein"),
+    ("Europe/Vatican", "Vatican City"),
+    ("Europe/Vienna", "Austria"),
+    ("Europe/Vilnius", "Lithuania"),
+    ("Europe/Volgograd", "Russia"),
+    ("Europe/Warsaw", "Poland"),
+    ("Europe/Zagreb", "Croatia"),
+    ("Europe/Zurich", "Switzerland"),
+    ("Indian/Antananarivo", "Madagascar"),
+    ("Indian/Chagos", "British Indian Ocean Territory"),
+    ("Indian/Christmas", "Christmas Island"),
+    ("Indian/Cocos", "Cocos (Keeling) Islands"),
+    ("Indian/Comoro", "Comoros"),
+    ("Indian/Kerguelen", "French S. Terr."),
+    ("Indian/Mahe", "Seychelles"),
+    ("Indian/Maldives", "Maldives"),
+    ("Indian/Mauritius", "Mauritius"),
+    ("Indian/Mayotte", "Mayotte"),
+    ("Indian/Reunion", "Réunion"),
+    ("Pacific/Apia", "Samoa (western)"),
+    ("Pacific/Auckland", "New Zealand"),
+    ("Pacific/Bougainville", "Papua New Guinea"),
+    ("Pacific/Chatham", "New Zealand"),
+    ("Pacific/Chuuk", "Micronesia"),
+    ("Pacific/Easter", "Chile"),
+    ("Pacific/Efate", "Vanuatu"),
+    ("Pacific/Fakaofo", "Tokelau"),
+    ("Pacific/Fiji", "Fiji"),
+    ("Pacific/Funafuti", "Tuvalu"),
+    ("Pacific/Galapagos", "Ecuador"),
+    ("Pacific/Gambier", "French Polynesia"),
+    ("Pacific/Guadalcanal", "Solomon Islands"),
+    ("Pacific/Guam", "Guam"),
+    ("Pacific/Honolulu", "United States"),
+    ("Pacific/Kanton", "Kiribati"),
+    ("Pacific/Kiritimati", "Kiribati"),
+    ("Pacific/Kosrae", "Micronesia"),
+    ("Pacific/Kwajalein", "Marshall Islands"),
+    ("Pacific/Majuro", "Marshall Islands"),
+    ("Pacific/Marquesas", "French Polynesia"),
+    ("Pacific/Midway", "US minor outlying islands"),
+    ("Pacific/Nauru", "Nauru"),
+    ("Pacific/Niue", "Niue"),
+    ("Pacific/Norfolk", "Norfolk Island"),
+    ("Pacific/Noumea", "New Caledonia"),
+    ("Pacific/Pago_Pago", "Samoa (American)"),
+    ("Pacific/Palau", "Palau"),
+    ("Pacific/Pitcairn", "Pitcairn"),
+    ("Pacific/Pohnpei", "Micronesia"),
+    ("Pacific/Port_Moresby", "Papua New Guinea"),
+    ("Pacific/Rarotonga", "Cook Islands"),
+    ("Pacific/Saipan", "Northern Mariana Islands"),
+    ("Pacific/Tahiti", "French Polynesia"),
+    ("Pacific/Tarawa", "Kiribati"),
+    ("Pacific/Tongatapu", "Tonga"),
+    ("Pacific/Wake", "US minor outlying islands"),
+    ("Pacific/Wallis", "Wallis & Futuna"),
+    ("UTC", "Coordinated Universal Time"),
+];
@@ -0,0 +1,210 @@
+//! Chain several builders into a single interaction with the user.
+//!
+//! A `Flow` drives a tuple of `Builder`s, possibly of different types, one after the other: when
+//! the user selects "Done" in the main menu of a builder, the flow moves to the main menu of the
+//! next one. The main menus of all the builders but the first also have a "Go back" (`BACK_ID`)
+//! choice, that returns to the main menu of the previous builder, keeping what the user inserted
+//! so far. When the last builder is done, `Flow::choose` returns the tuple with all the values.
+//!
+//! ```
+//! use ibuilder::flow::Flow;
+//! use ibuilder::*;
+//!
+//! #[derive(IBuilder)]
+//! struct Account {
+//!     username: String,
+//! }
+//!
+//! #[derive(IBuilder)]
+//! struct Preferences {
+//!     #[ibuilder(default = true)]
+//!     newsletter: bool,
+//! }
+//!
+//! let mut flow = Flow::new((Account::builder(), Preferences::builder()));
+//! flow.choose(Input::choice("username")).unwrap();
+//! flow.choose(Input::text("edomora97")).unwrap();
+//! flow.choose(Input::choice(FINALIZE_ID)).unwrap();
+//! assert_eq!(flow.current_step(), 1);
+//! let (account, preferences) = flow.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+//! assert_eq!(account.username, "edomora97");
+//! assert!(preferences.newsletter);
+//! ```
+
+use std::any::Any;
+use std::marker::PhantomData;
+
+use crate::nodes::Node;
+use crate::{Builder, Choice, ChooseError, Input, Options, BACK_ID};
+
+/// A single step of a `Flow`, implemented by all the `Builder`s.
+pub trait FlowStep {
+    /// The options of the current menu of the step.
+    fn get_options(&self) -> Options;
+
+    /// Apply the input to the step, returning whether the user finalized it.
+    fn choose(&mut self, input: Input) -> Result<bool, ChooseError>;
+
+    /// The value built by the step, `None` if some fields are still missing.
+    fn value(&self) -> Option<Box<dyn Any>>;
+
+    /// Whether the user is in the main menu of the step, where "Go back" leaves the step.
+    fn is_at_main_menu(&self) -> bool;
+
+    /// The tree structure of the state of the step.
+    fn to_node(&self) -> Node;
+}
+
+impl<T: 'static> FlowStep for Builder<T> {
+    fn get_options(&self) -> Options {
+        Builder::get_options(self)
+    }
+
+    fn choose(&mut self, input: Input) -> Result<bool, ChooseError> {
+        Ok(Builder::choose(self, input)?.is_some())
+    }
+
+    fn value(&self) -> Option<Box<dyn Any>> {
+        self.finalize()
+            .ok()
+            .map(|value| Box::new(value) as Box<dyn Any>)
+    }
+
+    fn is_at_main_menu(&self) -> bool {
+        Builder::is_at_main_menu(self)
+    }
+
+    fn to_node(&self) -> Node {
+        Builder::to_node(self)
+    }
+}
+
+/// The builders that can be chained in a `Flow`, implemented for the tuples of up to 4
+/// `Builder`s.
+pub trait FlowSteps {
+    /// The tuple with the values built by the steps.
+    type Output;
+
+    /// Split the tuple into its steps.
+    fn into_steps(self) -> Vec<Box<dyn FlowStep>>;
+
+    /// Make the tuple with the values returned by the steps, in the same order.
+    fn output(values: Vec<Box<dyn Any>>) -> Self::Output;
+}
+
+macro_rules! impl_flow_steps {
+    ($($ty:ident $index:tt),+) => {
+        impl<$($ty: 'static),+> FlowSteps for ($(Builder<$ty>,)+) {
+            type Output = ($($ty,)+);
+
+            fn into_steps(self) -> Vec<Box<dyn FlowStep>> {
+                vec![$(Box::new(self.$index)),+]
+            }
+
+            fn output(values: Vec<Box<dyn Any>>) -> Self::Output {
+                let mut values = values.into_iter();
+                ($(*values
+                    .next()
+                    .expect("Missing value of a step")
+                    .downcast::<$ty>()
+                    .expect("Invalid value of a step"),)+)
+            }
+        }
+    };
+}
+
+impl_flow_steps!(A 0);
+impl_flow_steps!(A 0, B 1);
+impl_flow_steps!(A 0, B 1, C 2);
+impl_flow_steps!(A 0, B 1, C 2, D 3);
+
+/// A sequence of builders presented to the user one after the other, see the module
+/// documentation.
+pub struct Flow<S: FlowSteps> {
+    steps: Vec<Box<dyn FlowStep>>,
+    current: usize,
+    steps_type: PhantomData<S>,
+}
+
+impl<S: FlowSteps> std::fmt::Debug for Flow<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Flow")
+            .field("steps", &self.steps.len())
+            .field("current", &self.current)
+            .finish()
+    }
+}
+
+impl<S: FlowSteps> Flow<S> {
+    /// Make a new flow that starts from the first of the provided builders.
+    pub fn new(builders: S) -> Flow<S> {
+        Flow {
+            steps: builders.into_steps(),
+            current: 0,
+            steps_type: PhantomData,
+        }
+    }
+
+    /// The index of the builder the user is interacting with, starting from zero.
+    pub fn current_step(&self) -> usize {
+        self.current
+    }
+
+    /// The number of builders in the flow.
+    pub fn step_count(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Return all the valid options of the current builder, with the "Go back" choice in the main
+    /// menus of the builders after the first.
+    pub fn get_options(&self) -> Options {
+        let step = self.step();
+        let mut options = step.get_options();
+        if self.current > 0 && step.is_at_main_menu() {
+            options.choices.push(Choice {
+                choice_id: BACK_ID.into(),
+                text: "Go back".to_string(),
+                needs_action: false,
+                ..Default::default()
+            });
+        }
+        options
+    }
+
+    /// Apply an input to the current builder. Returns `Ok(Some(...))` with the values of all the
+    /// builders when the user finalizes the last one.
+    pub fn choose(&mut self, input: Input) -> Result<Option<S::Output>, ChooseError> {
+        if self.current > 0 && self.step().is_at_main_menu() {
+            if let Input::Choice(data) = &input {
+                if data == BACK_ID {
+                    self.current -= 1;
+                    return Ok(None);
+                }
+            }
+        }
+        if !self.steps[self.current].choose(input)? {
+            return Ok(None);
+        }
+        // like a `Builder`, the last step stays in its main menu after being finalized
+        if self.current + 1 < self.steps.len() {
+            self.current += 1;
+            return Ok(None);
+        }
+        let values = self
+            .steps
+            .iter()
+            .map(|step| step.value().expect("Finalize failed"))
+            .collect();
+        Ok(Some(S::output(values)))
+    }
+
+    /// Return the tree structure of the state of the current builder.
+    pub fn to_node(&self) -> Node {
+        self.step().to_node()
+    }
+
+    /// The step the user is interacting with.
+    fn step(&self) -> &dyn FlowStep {
+        self.steps[self.current].as_ref()
+    }
+}
@@ -0,0 +1,77 @@
+//! Drivers for the interaction loop between a `Builder` and a user interface.
+//!
+//! A frontend only has to know how to render the `Options` (and optionally the `Node` tree) of a
+//! builder and how to collect the `Input` of the user. The loop that feeds the inputs to the
+//! builder, re-prompts on errors and finalizes the value is provided by this module.
+
+use std::future::Future;
+
+use crate::nodes::Node;
+use crate::{Builder, ChooseError, Input, Options};
+
+/// A user interface that can be driven synchronously, like a console application.
+///
+/// This is the blocking counterpart of `AsyncFrontend`, see `run_builder_sync`.
+pub trait Frontend {
+    /// Show the options and the current state of the builder to the user, and return the input
+    /// they provided.
+    fn present(&mut self, options: Options, node: Node) -> Input;
+
+    /// Show an error to the user after an invalid input. The options will be presented again right
+    /// after. By default the error is ignored.
+    fn present_error(&mut self, _error: ChooseError) {}
+}
+
+/// A user interface that can be driven asynchronously, like a chat bot or a web page.
+///
+/// Implementors only have to render the options and wait for the user's input, `run_builder` will
+/// take care of the rest.
+pub trait AsyncFrontend {
+    /// Show the options and the current state of the builder to the user, and return the input
+    /// they provided.
+    fn present(&mut self, options: Options, node: Node) -> impl Future<Output = Input>;
+
+    /// Show an error to the user after an invalid input. The options will be presented again right
+    /// after. By default the error is ignored.
+    fn present_error(&mut self, _error: ChooseError) -> impl Future<Output = ()> {
+        async {}
+    }
+}
+
+/// Drive the `Builder` using the provided `AsyncFrontend` until the user finalizes the value.
+///
+/// Invalid inputs are reported to the frontend with `present_error` and the user is prompted again.
+pub async fn run_builder<F, T>(frontend: &mut F, mut builder: Builder<T>) -> T
+where
+    F: AsyncFrontend,
+    T: 'static,
+{
+    loop {
+        let input = frontend
+            .present(builder.get_options(), builder.to_node())
+            .await;
+        match builder.choose(input) {
+            Ok(Some(value)) => return value,
+            Ok(None) => {}
+            Err(e) => frontend.present_error(e).await,
+        }
+    }
+}
+
+/// Drive the `Builder` using the provided `Frontend` until the user finalizes the value.
+///
+/// Invalid inputs are reported to the frontend with `present_error` and the user is prompted again.
+pub fn run_builder_sync<F, T>(frontend: &mut F, mut builder: Builder<T>) -> T
+where
+    F: Frontend,
+    T: 'static,
+{
+    loop {
+        let input = frontend.present(builder.get_options(), builder.to_node());
+        match builder.choose(input) {
+            Ok(Some(value)) => return value,
+            Ok(None) => {}
+            Err(e) => frontend.present_error(e),
+        }
+    }
+}
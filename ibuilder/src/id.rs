@@ -0,0 +1,165 @@
+//! Module with the identifiers of the fields and of the choices, see `Id`.
+
+use std::borrow::Borrow;
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::sync::Arc;
+
+/// The identifier of a field or of a choice, the type of `current_fields` and of
+/// `Choice::choice_id`. The ids known at compile time (e.g. the names of the fields of the derived
+/// structs) are borrowed and the others (e.g. the indices of a list) are shared, so the menus can
+/// clone them on every input without copying the strings.
+///
+/// It compares equal to the strings with the same content:
+///
+/// ```
+/// use ibuilder::Id;
+///
+/// let id = Id::from_static("name");
+/// assert_eq!(id, "name");
+/// assert_eq!(id, Id::from("name".to_string()));
+/// assert_eq!(id.as_str(), "name");
+/// ```
+#[derive(Clone)]
+pub struct Id(Repr);
+
+/// The storage of an `Id`.
+#[derive(Clone)]
+enum Repr {
+    /// A string known at compile time.
+    Static(&'static str),
+    /// A string made at runtime, shared by the clones.
+    Shared(Arc<str>),
+}
+
+impl Id {
+    /// Make an id from a string known at compile time, without allocating.
+    pub const fn from_static(id: &'static str) -> Id {
+        Id(Repr::Static(id))
+    }
+
+    /// The content of the id.
+    pub fn as_str(&self) -> &str {
+        match &self.0 {
+            Repr::Static(id) => id,
+            Repr::Shared(id) => id,
+        }
+    }
+}
+
+impl Default for Id {
+    fn default() -> Self {
+        Id::from_static("")
+    }
+}
+
+impl Deref for Id {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl AsRef<str> for Id {
+    fn as_ref(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl Borrow<str> for Id {
+    fn borrow(&self) -> &str {
+        self.as_str()
+    }
+}
+
+impl From<&'static str> for Id {
+    fn from(id: &'static str) -> Self {
+        Id::from_static(id)
+    }
+}
+
+impl From<String> for Id {
+    fn from(id: String) -> Self {
+        Id(Repr::Shared(id.into()))
+    }
+}
+
+impl From<&Id> for Id {
+    fn from(id: &Id) -> Self {
+        id.clone()
+    }
+}
+
+impl From<Id> for String {
+    fn from(id: Id) -> Self {
+        id.as_str().to_string()
+    }
+}
+
+impl From<&Id> for String {
+    fn from(id: &Id) -> Self {
+        id.as_str().to_string()
+    }
+}
+
+impl fmt::Debug for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl PartialEq for Id {
+    fn eq(&self, other: &Id) -> bool {
+        self.as_str() == other.as_str()
+    }
+}
+
+impl Eq for Id {}
+
+impl PartialOrd for Id {
+    fn partial_cmp(&self, other: &Id) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Id {
+    fn cmp(&self, other: &Id) -> Ordering {
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl Hash for Id {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.as_str().hash(state)
+    }
+}
+
+/// Compare an `Id` with the string types, in both directions.
+macro_rules! impl_str_eq {
+    ($($ty:ty),*) => {
+        $(
+            impl PartialEq<$ty> for Id {
+                fn eq(&self, other: &$ty) -> bool {
+                    self.as_str() == &other[..]
+                }
+            }
+
+            impl PartialEq<Id> for $ty {
+                fn eq(&self, other: &Id) -> bool {
+                    &self[..] == other.as_str()
+                }
+            }
+        )*
+    };
+}
+
+impl_str_eq!(str, &str, String);
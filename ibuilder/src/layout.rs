@@ -0,0 +1,78 @@
+//! Hints for the frontends that draw the menus themselves, like the TUIs and the CLIs.
+//!
+//! When `BuilderConfig::layout_hints` is enabled, the `Options` returned by the `Builder` carry a
+//! `Layout` with the direction of the text and the width of the widest choice, measured in
+//! terminal columns. The width takes into account the characters that fill two columns (e.g. the
+//! emojis and the CJK ideographs), so the frontend can pad all the buttons of a menu to the same
+//! size without computing it itself.
+
+use unicode_width::UnicodeWidthStr;
+
+use crate::Options;
+
+/// The layout hints of a menu, see `Options::layout`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Layout {
+    /// The direction of the text of the query.
+    pub direction: TextDirection,
+    /// The display width of the widest text of the choices, in columns. It's zero if the menu has
+    /// no choices.
+    pub button_width: usize,
+}
+
+/// The direction in which a text is written.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum TextDirection {
+    /// Like English or Italian.
+    LeftToRight,
+    /// Like Arabic or Hebrew.
+    RightToLeft,
+}
+
+impl Layout {
+    /// Compute the layout hints of the provided options. The direction is the one of the first
+    /// letter of the query, or of the first choice if the query has no letters.
+    pub fn of(options: &Options) -> Layout {
+        let direction = std::iter::once(options.query.as_str())
+            .chain(options.choices.iter().map(|c| c.text.as_str()))
+            .find_map(text_direction)
+            .unwrap_or(TextDirection::LeftToRight);
+        let button_width = options
+            .choices
+            .iter()
+            .map(|c| display_width(&c.text))
+            .max()
+            .unwrap_or(0);
+        Layout {
+            direction,
+            button_width,
+        }
+    }
+}
+
+/// The number of columns used by the text in a terminal.
+pub fn display_width(text: &str) -> usize {
+    text.width()
+}
+
+/// The direction of the text, according to its first letter. `None` if the text has no letters
+/// (e.g. it contains only digits and punctuation).
+pub fn text_direction(text: &str) -> Option<TextDirection> {
+    text.chars()
+        .find(|c| c.is_alphabetic())
+        .map(|c| match is_right_to_left(c) {
+            true => TextDirection::RightToLeft,
+            false => TextDirection::LeftToRight,
+        })
+}
+
+/// Whether the character belongs to one of the scripts written from right to left.
+fn is_right_to_left(c: char) -> bool {
+    matches!(c,
+        // Hebrew, Arabic, Syriac, Thaana, NKo, Samaritan, Mandaic and the Arabic supplements
+        '\u{0590}'..='\u{08FF}'
+        // Hebrew and Arabic presentation forms
+        | '\u{FB1D}'..='\u{FDFF}'
+        | '\u{FE70}'..='\u{FEFF}'
+    )
+}
@@ -50,8 +50,23 @@
 //! - Hidden fields (that takes the value only from the default)
 //! - Nested structures (i.e. custom types)
 //! - Supported field types: all numeric types from rust, `bool`, `String`, `char`, `Box<T>`,
-//!   `Vec<T>` and `Option<T>`
-//! - Any field type that implementes the `NewBuildableValue` trait
+//!   `Vec<T>`, `Option<T>`, `Result<T, E>`, `Range<T>`, `RangeInclusive<T>`, `HashSet<T>`,
+//!   `BTreeSet<T>`, `HashMap<K, V>` and `BTreeMap<K, V>` (with the keys parsed with `FromStr`),
+//!   `IpAddr`, `Ipv4Addr`, `Ipv6Addr` and `SocketAddr`
+//! - Any field type that implementes the `NewBuildableValue` trait, custom textual types can
+//!   implement the simpler `LeafBuildableValue` trait and use the `LeafBuilder` adapter
+//! - Undo/redo, the history of the inputs (`Builder::history`) and snapshots of the state of the
+//!   builder for resuming a session later (serializable with the `serde` feature), even after the
+//!   type has changed (`Builder::restore_state_lenient`, or with versioned snapshots and
+//!   `BuilderConfig::migrations`)
+//! - Prefilling the builder with the `Default` value of the type (`Builder::from_default`)
+//! - Prefilling the builder from a JSON document (`Builder::load_json`, with the `serde` feature)
+//! - Redaction of the values inserted by the user from the logs of the state of the builder
+//!   (`BuilderConfig::redaction`)
+//! - Exporting the completed value together with the transcript of the session, for archiving it
+//!   (`Builder::export`, serializable with the `serde` feature)
+//! - Automatic persistence of the state of the sessions after every input
+//!   (`Builder::with_persistence`, in memory or in JSON files with the `serde` feature)
 //!
 //! ## Example of Usage
 //!
@@ -116,23 +131,73 @@
 //! assert_eq!(value.num_hands, 2);
 //! ```
 
+// `failure_derive` generates its impls inside an anonymous const.
+#![allow(non_local_definitions)]
+
 #[cfg(feature = "derive")]
 pub use ibuilder_derive::IBuilder;
 
+pub use crate::id::Id;
+
 use std::any::Any;
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::{BTreeMap, VecDeque};
 use std::marker::PhantomData;
+use std::rc::Rc;
+use std::time::{Duration, Instant, SystemTime};
 
 use failure::Fail;
 
-use crate::nodes::Node;
+use crate::builders::{ConfirmBuilder, StaleBuilder, KEEP_ID};
+use crate::charset::Charset;
+use crate::layout::Layout;
+use crate::metrics::MetricsSink;
+use crate::navigation::{Navigator, Transition};
+use crate::nodes::{Field, FieldKind, Node, NodeVisitor};
+use crate::persistence::{PersistenceError, StatePersistence};
+use crate::redaction::{RedactingVisitor, Redaction};
+use crate::schema::Schema;
+use crate::types::Confirm;
 
 pub mod builders;
+pub mod charset;
+#[cfg(feature = "data")]
+pub mod data;
+pub mod flow;
+pub mod frontend;
+pub mod id;
+pub mod layout;
+pub mod metrics;
+pub mod navigation;
 pub mod nodes;
+pub mod persistence;
+pub mod pick;
+pub mod preprocessors;
+pub mod redaction;
+pub mod schema;
+pub mod types;
+pub mod validators;
 
 /// The identifier of the "Done" choice.
 pub const FINALIZE_ID: &str = "__finalize";
 /// The identifier of the "Back" choice.
 pub const BACK_ID: &str = "__back";
+/// The identifier of the "Try again" choice, shown after an error when
+/// `BuilderConfig::error_recovery` is enabled.
+pub const RETRY_ID: &str = "__retry";
+/// The identifier of the "Find field…" choice, shown in the main menu when
+/// `BuilderConfig::field_search` is enabled.
+pub const SEARCH_ID: &str = "__search";
+/// The identifier of the "Back to start" choice, shown in the nested menus when
+/// `BuilderConfig::back_to_start` is enabled.
+pub const ROOT_ID: &str = "__root";
+/// The prefix of the identifiers of the groups of choices, shown when a menu has more choices than
+/// `BuilderConfig::max_choices_per_menu`. It's followed by the index of the group.
+pub const GROUP_ID_PREFIX: &str = "__group_";
+/// The prefix of the identifiers of the inputs recorded in the history by `Builder::goto`. It's
+/// followed by the path of the field, joined by `.` (e.g. `__goto_server.port`).
+pub const GOTO_ID_PREFIX: &str = "__goto_";
 
 /// Interactive builder for creating instances of the struct `T` by communicating. To instantiate a
 /// new `Builder` for the type `T`, make `T` derive from `IBuilder` and call `builder()` on it from
@@ -155,11 +220,335 @@ pub const BACK_ID: &str = "__back";
 ///
 /// When the user has filled all the fields of the builder, he can select the "done" options, which
 /// will make the `choose` method return `Ok(Some(T))`, signaling the end of the communication.
-#[derive(Debug)]
 pub struct Builder<T> {
     builder: Box<dyn BuildableValue>,
-    current_fields: Vec<String>,
+    navigator: Navigator,
     inner_type: PhantomData<T>,
+    config: BuilderConfig,
+    pending_error: Option<ChooseError>,
+    notice: Option<String>,
+    metrics: Option<Box<dyn MetricsSink>>,
+    persistence: Option<(Box<dyn StatePersistence>, String)>,
+    persistence_error: Option<PersistenceError>,
+    started: Instant,
+    menu_since: Instant,
+    recent_inputs: VecDeque<Instant>,
+    last_input: Option<(Input, Instant)>,
+    open_group: Option<usize>,
+    search: Option<Vec<PathInfo>>,
+    initial: Option<ReplayBase>,
+    history: Vec<AppliedInput>,
+    recorded_input: Option<Input>,
+    undone: Vec<Input>,
+    revision: u64,
+}
+
+impl<T> std::fmt::Debug for Builder<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut debug = f.debug_struct("Builder");
+        let redaction = self.config.redaction();
+        if redaction.is_active() {
+            debug.field("builder", &redaction.redact_node(self.builder.to_node()));
+        } else {
+            debug.field("builder", &self.builder);
+        }
+        debug
+            .field("current_fields", &self.navigator.current_fields())
+            .field("config", &self.config)
+            .field("pending_error", &self.pending_error)
+            .field("notice", &self.notice)
+            .finish()
+    }
+}
+
+/// The state the history of a `Builder` is applied to for rebuilding its previous states, see
+/// `Builder::undo`.
+enum ReplayBase {
+    /// A new value, made by the function.
+    New(fn() -> Box<dyn BuildableValue>),
+    /// A copy of the value after a change not made with an input (e.g. `Builder::invalidate`),
+    /// with the menu the user was in.
+    Snapshot(Box<dyn BuildableValue>, Navigator),
+}
+
+/// The configuration of the behaviour of a `Builder`.
+#[derive(Debug, Clone, Default)]
+pub struct BuilderConfig {
+    /// When an input is not valid, instead of just returning the error from `choose`, make the
+    /// next `get_options` show the error message with a "Try again" (`RETRY_ID`) and a "Go back"
+    /// (`BACK_ID`) choice. This is useful for frontends that can only display `Options`.
+    pub error_recovery: bool,
+    /// Reject with `ChooseError::RateLimited` the inputs that exceed this number in the last
+    /// second. This protects the state of the builder from storms of inputs (e.g. a user mashing
+    /// the buttons of a bot).
+    pub max_inputs_per_second: Option<usize>,
+    /// Reject with `ChooseError::RateLimited` an input identical to the previous one if it's
+    /// received within this interval, like a double click on the same button.
+    pub debounce: Option<Duration>,
+    /// When a menu has more choices than this, they are sorted by their text and split into
+    /// groups (e.g. "A–F", "G–M"), each with at most `max_choices_per_menu - 1` choices plus
+    /// "Go back". The menu shows the groups instead, with the `GROUP_ID_PREFIX` identifiers. This
+    /// is useful for the platforms that limit the number of buttons of a message.
+    pub max_choices_per_menu: Option<usize>,
+    /// Add a "Find field…" (`SEARCH_ID`) choice to the main menu. It lets the user type part of
+    /// the name of a field, anywhere in the tree (see `Builder::paths`), and jump directly to it.
+    pub field_search: bool,
+    /// Add a "Back to start" (`ROOT_ID`) choice next to "Go back" in the menus that are at least
+    /// two levels deep, which moves the user directly to the main menu.
+    pub back_to_start: bool,
+    /// Skip the menus of the structs and variants with a single field, moving the user directly
+    /// to the menu of that field and back past them. This avoids the screens with a single button
+    /// produced by the wrapper types (e.g. `struct Port(u16)`).
+    pub skip_single_field_menus: bool,
+    /// The order of the choices of the main menu.
+    pub choice_order: ChoiceOrder,
+    /// The source of the current time, used for the rate limiting, for the timings of the
+    /// metrics and for the dates relative to today (e.g. `now`). It can be replaced for making the
+    /// tests reproducible.
+    pub clock: Clock,
+    /// The source of the randomness, e.g. of the UUIDs generated with `random`. If `None` the
+    /// randomness of the system is used. It can be replaced for making the tests reproducible.
+    pub random: Option<Random>,
+    /// The translations of the prompts marked with `#[ibuilder(prompt_key = "...")]`. When it
+    /// knows the key of the current menu, its translation replaces the query of the `Options`.
+    pub localizer: Option<Localizer>,
+    /// Fill the `layout` of the `Options` with the direction of the text and the width of the
+    /// choices, for the frontends that align the menus themselves (see the `layout` module).
+    pub layout_hints: bool,
+    /// Reject with `ChooseError::TextTooLong` the textual inputs longer than this number of
+    /// characters, before they reach the builders. This protects the memory of the builder, and
+    /// the systems that receive the value, from huge pastes in the chat inputs.
+    pub max_text_len: Option<usize>,
+    /// Hide the values inserted by the user from the `Debug` of the builder, its `to_node`, its
+    /// `history` and the inputs sent to the `MetricsSink`, see the `redaction` module. If `None`
+    /// the global redaction is used (see `redaction::set_global_redaction`).
+    pub redaction: Option<Redaction>,
+    /// The version of the type being built, stamped on the snapshots made with
+    /// `Builder::save_state`. Bump it when the type changes in a way that breaks the old
+    /// snapshots, and register in `migrations` how to convert them.
+    pub state_version: u32,
+    /// The conversions of the snapshots made with an older `state_version`, run by
+    /// `Builder::restore_state` before applying the inputs.
+    pub migrations: Migrations,
+}
+
+impl BuilderConfig {
+    /// The redaction in effect: `redaction`, or the global one if not set.
+    fn redaction(&self) -> Redaction {
+        self.redaction.unwrap_or_else(redaction::global_redaction)
+    }
+}
+
+/// The order of the choices of the main menu, see `BuilderConfig::choice_order`.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ChoiceOrder {
+    /// The order of the fields in the definition of the type.
+    #[default]
+    Declaration,
+    /// The choices that need action (e.g. the missing fields) first, followed by the others, both
+    /// in the order of the definition. This reduces the hunting for the missing fields in the long
+    /// structs.
+    MissingFirst,
+}
+
+/// A source of the current time, see `BuilderConfig::clock`. The default one is the system clock.
+#[derive(Clone)]
+pub struct Clock {
+    /// The monotonic time, for measuring the intervals.
+    now: Rc<dyn Fn() -> Instant>,
+    /// The wall-clock time, for the dates (e.g. `today`).
+    system_time: Rc<dyn Fn() -> SystemTime>,
+}
+
+impl Clock {
+    /// Make a clock that calls the provided function for getting the current time. The wall-clock
+    /// time is the one of the system, replace it with `with_system_time`.
+    pub fn new<F: Fn() -> Instant + 'static>(now: F) -> Clock {
+        Clock {
+            now: Rc::new(now),
+            system_time: Rc::new(SystemTime::now),
+        }
+    }
+
+    /// Use the provided function for getting the wall-clock time, e.g. when the builders resolve
+    /// `today` or `now`.
+    pub fn with_system_time<F: Fn() -> SystemTime + 'static>(self, system_time: F) -> Clock {
+        Clock {
+            system_time: Rc::new(system_time),
+            ..self
+        }
+    }
+
+    /// The current time according to this clock.
+    pub fn now(&self) -> Instant {
+        (self.now)()
+    }
+
+    /// The current wall-clock time according to this clock.
+    pub fn system_time(&self) -> SystemTime {
+        (self.system_time)()
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Clock::new(Instant::now)
+    }
+}
+
+impl std::fmt::Debug for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Clock").finish()
+    }
+}
+
+/// A source of random bytes, see `BuilderConfig::random`.
+#[derive(Clone)]
+pub struct Random(FillFn);
+
+/// The function used by a `Random` for filling a buffer.
+type FillFn = Rc<dyn Fn(&mut [u8])>;
+
+impl Random {
+    /// Make a source that calls the provided function for filling the buffers with random bytes.
+    pub fn new<F: Fn(&mut [u8]) + 'static>(fill: F) -> Random {
+        Random(Rc::new(fill))
+    }
+
+    /// Fill the buffer with random bytes.
+    pub fn fill(&self, bytes: &mut [u8]) {
+        (self.0)(bytes)
+    }
+}
+
+impl std::fmt::Debug for Random {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Random").finish()
+    }
+}
+
+thread_local! {
+    /// The sources of the `Builder` that is processing an input, see `with_sources`.
+    static SOURCES: RefCell<Option<Sources>> = const { RefCell::new(None) };
+}
+
+/// The clock and the randomness of a `BuilderConfig`.
+type Sources = (Clock, Option<Random>);
+
+/// Run `f` making the clock and the randomness of the config available to the builders, which
+/// don't have access to the `BuilderConfig`.
+fn with_sources<R>(clock: Clock, random: Option<Random>, f: impl FnOnce() -> R) -> R {
+    /// Put back the previous sources, even if `f` panics.
+    struct Restore(Option<Sources>);
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            let previous = self.0.take();
+            SOURCES.with(|sources| *sources.borrow_mut() = previous);
+        }
+    }
+    let _restore = Restore(SOURCES.with(|sources| sources.replace(Some((clock, random)))));
+    f()
+}
+
+/// The current wall-clock time, according to the `BuilderConfig::clock` of the builder processing
+/// the input, or the system one.
+#[cfg(feature = "chrono")]
+pub(crate) fn system_time() -> SystemTime {
+    let clock = SOURCES.with(|sources| sources.borrow().as_ref().map(|(clock, _)| clock.clone()));
+    clock.map_or_else(SystemTime::now, |clock| clock.system_time())
+}
+
+/// Fill the buffer using the `BuilderConfig::random` of the builder processing the input. Returns
+/// `false`, leaving the buffer untouched, if the system randomness should be used.
+#[cfg(feature = "uuid")]
+pub(crate) fn random_bytes(bytes: &mut [u8]) -> bool {
+    let random = SOURCES.with(|sources| sources.borrow().as_ref().and_then(|(_, r)| r.clone()));
+    match random {
+        Some(random) => {
+            random.fill(bytes);
+            true
+        }
+        None => false,
+    }
+}
+
+/// The source of the translations of the prompts, see `BuilderConfig::localizer`.
+#[derive(Clone)]
+pub struct Localizer(LocalizeFn);
+
+/// The function used by a `Localizer` for translating a key.
+type LocalizeFn = Rc<dyn Fn(&str) -> Option<String>>;
+
+impl Localizer {
+    /// Make a localizer that calls the provided function with the key of the prompt. The function
+    /// returns `None` if the key has no translation, and the original prompt is kept.
+    pub fn new<F: Fn(&str) -> Option<String> + 'static>(localize: F) -> Localizer {
+        Localizer(Rc::new(localize))
+    }
+
+    /// The translation of the prompt with the provided key, if any.
+    pub fn localize(&self, key: &str) -> Option<String> {
+        (self.0)(key)
+    }
+}
+
+impl std::fmt::Debug for Localizer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("Localizer").finish()
+    }
+}
+
+/// The conversions of the old snapshots to the current `BuilderConfig::state_version`. Each
+/// migration converts a snapshot from a version to the next one, and they are chained for the
+/// snapshots older than that.
+#[derive(Clone, Default)]
+pub struct Migrations(BTreeMap<u32, MigrateFn>);
+
+/// The function used by `Migrations` for converting a snapshot to the next version.
+type MigrateFn = Rc<dyn Fn(SavedState) -> SavedState>;
+
+impl Migrations {
+    /// Make an empty set of migrations.
+    pub fn new() -> Migrations {
+        Migrations::default()
+    }
+
+    /// Register the function that converts a snapshot made with the version `from` to the version
+    /// `from + 1`, for example renaming the choices of a renamed field. The version of the
+    /// snapshot is updated automatically. A migration already registered for `from` is replaced.
+    pub fn add<F: Fn(SavedState) -> SavedState + 'static>(
+        mut self,
+        from: u32,
+        migrate: F,
+    ) -> Migrations {
+        self.0.insert(from, Rc::new(migrate));
+        self
+    }
+
+    /// Convert a snapshot to the version `to`, running the migrations from its version onwards.
+    pub fn migrate(&self, mut state: SavedState, to: u32) -> Result<SavedState, RestoreError> {
+        if state.version > to {
+            return Err(RestoreError::NewerVersion {
+                version: state.version,
+            });
+        }
+        while state.version < to {
+            let from = state.version;
+            let migrate = self
+                .0
+                .get(&from)
+                .ok_or(RestoreError::MissingMigration { from })?;
+            state = migrate(state);
+            state.version = from + 1;
+        }
+        Ok(state)
+    }
+}
+
+impl std::fmt::Debug for Migrations {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
 }
 
 /// A type that supports being built using a `Builder`. Deriving `IBuilder` an auto-generated
@@ -167,6 +556,229 @@ pub struct Builder<T> {
 pub trait Buildable<T> {
     /// Create a new `Builder<T>` for the current type.
     fn builder() -> Builder<T>;
+
+    /// Create a new `Builder<T>` prefilled with this value, see `Builder::from_value`.
+    fn builder_from(self) -> Builder<T>;
+}
+
+impl<T> Builder<T>
+where
+    T: NewBuildableValue + 'static,
+{
+    /// Make a new builder with the default configuration and the state of a snapshot made with
+    /// `Builder::save_state`. For a builder with another configuration use `restore_state`.
+    pub fn from_saved_state(state: &SavedState) -> Result<Builder<T>, RestoreError> {
+        let mut builder = T::builder();
+        builder.restore_state(state)?;
+        Ok(builder)
+    }
+
+    /// Make a new builder prefilled with an existing value, so the user edits it instead of
+    /// creating a new one from scratch. Like in `from_default`, the parts of the value that cannot
+    /// be prefilled (see `BuildableValue::set_value_any`) are left to be inserted.
+    ///
+    /// The builder cannot `undo`, since it cannot rebuild the initial state without the value.
+    pub fn from_value(value: T) -> Builder<T> {
+        let mut inner = T::new_buildable_value(Default::default());
+        inner.set_value_any(Box::new(value));
+        Builder::<T>::from_buildable_value(inner)
+    }
+
+    /// Make a new builder prefilled with the fields of a JSON document, for example an existing
+    /// configuration file. The document is walked like the user would walk the menus: the objects
+    /// fill the fields of the structs (and select the variants of the enums), the arrays and the
+    /// objects fill the lists and the maps, and the other values are inserted as text. The fields
+    /// missing from the document, or whose value is not valid, are left to be inserted
+    /// interactively.
+    ///
+    /// Available with the `serde` feature. An error is returned only if the text is not valid
+    /// JSON. Like `from_value`, the builder cannot `undo`.
+    #[cfg(feature = "serde")]
+    pub fn load_json(json: &str) -> Result<Builder<T>, serde_json::Error> {
+        let json: serde_json::Value = serde_json::from_str(json)?;
+        let mut inner = T::new_buildable_value(Default::default());
+        load_json_value(inner.as_mut(), &mut vec![], &json);
+        Ok(Builder::<T>::from_buildable_value(inner))
+    }
+}
+
+impl<T> Builder<T>
+where
+    T: NewBuildableValue + Default + 'static,
+{
+    /// Make a new builder prefilled with `T::default()`, so the user edits the default value
+    /// instead of starting from scratch. The parts of the value that cannot be prefilled (see
+    /// `BuildableValue::set_value_any`) are left to be inserted.
+    pub fn from_default() -> Builder<T> {
+        fn prefilled<T: NewBuildableValue + Default + 'static>() -> Box<dyn BuildableValue> {
+            let mut value = T::new_buildable_value(Default::default());
+            value.set_value_any(Box::new(T::default()));
+            value
+        }
+        let mut builder = Builder::<T>::from_buildable_value(prefilled::<T>());
+        builder.initial = Some(ReplayBase::New(prefilled::<T>));
+        builder
+    }
+}
+
+impl Builder<Confirm> {
+    /// Make a builder for a stand-alone yes/no question, with the "Yes" and "No" labels. Use
+    /// `answer` for getting the answer as soon as the user selects it, without "Done".
+    pub fn confirm<S: Into<String>>(prompt: S) -> Builder<Confirm> {
+        Builder::confirm_with_labels(prompt, "Yes", "No")
+    }
+
+    /// Make a builder for a stand-alone yes/no question, with custom labels for the two choices.
+    pub fn confirm_with_labels<S, Y, N>(prompt: S, yes: Y, no: N) -> Builder<Confirm>
+    where
+        S: Into<String>,
+        Y: Into<String>,
+        N: Into<String>,
+    {
+        let builder = ConfirmBuilder::new(BuildableValueConfig {
+            prompt: Some(prompt.into()),
+            ..Default::default()
+        })
+        .with_labels(yes, no);
+        Builder::from_buildable_value(Box::new(builder))
+    }
+
+    /// Apply the input to the question, returning the answer of the user as soon as it's
+    /// selected. The options to show to the user are the ones of `get_options`.
+    pub fn answer(&mut self, input: Input) -> Result<Option<Confirm>, ChooseError> {
+        if let Some(value) = self.choose(input)? {
+            return Ok(Some(value));
+        }
+        Ok(self.finalize().ok())
+    }
+}
+
+/// An input received by a `Builder`, see `Builder::history`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct AppliedInput {
+    /// When the input has been received, according to `BuilderConfig::clock`.
+    pub at: Instant,
+    /// The menu the user was in after the input.
+    pub path: Vec<Id>,
+    /// The input itself.
+    pub input: Input,
+    /// What the input did.
+    pub outcome: InputOutcome,
+    /// Whether the input is the text of a secret (see `Options::sensitive`), which is always
+    /// redacted in `Builder::history`.
+    pub sensitive: bool,
+}
+
+/// The outcome of an input received by a `Builder`, see `AppliedInput`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputOutcome {
+    /// The input changed the state of the builder.
+    Applied,
+    /// The input finalized the builder.
+    Finalized,
+    /// The input has been rejected with this error, the state of the builder is unchanged.
+    Rejected(ChooseError),
+}
+
+/// A completed value bundled with how it has been collected, made with `Builder::export`.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Export<T> {
+    /// The built value.
+    pub value: T,
+    /// The inputs received by the builder, in order, like `Builder::history`.
+    pub transcript: Vec<ExportedInput>,
+    /// The timing statistics of the session.
+    pub stats: ExportStats,
+    /// The final tree structure of the state, like `Builder::to_node`.
+    pub node: Node,
+}
+
+/// An input of the transcript of an `Export`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExportedInput {
+    /// When the input has been received, since the creation of the builder.
+    pub elapsed: Duration,
+    /// The menu the user was in after the input.
+    pub path: Vec<String>,
+    /// The input itself.
+    pub input: Input,
+    /// The message of the error, if the input has been rejected.
+    pub error: Option<String>,
+}
+
+/// The timing statistics of an `Export`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExportStats {
+    /// The time from the creation of the builder to the export, according to
+    /// `BuilderConfig::clock`.
+    pub duration: Duration,
+    /// The number of inputs received, including the rejected ones.
+    pub inputs: usize,
+    /// The number of inputs rejected with an error.
+    pub rejected: usize,
+}
+
+/// A snapshot of the state of a `Builder`, made with `Builder::save_state`. It's made of the
+/// inputs applied to the builder, which can be applied again to a new builder for restoring its
+/// state.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SavedState {
+    /// The valid inputs applied to the builder, in order.
+    pub inputs: Vec<Input>,
+    /// The menu the user was in after each input, used by `Builder::restore_state_lenient` for
+    /// recognizing the inputs meant for the fields that don't exist anymore. It may be empty, for
+    /// example in the snapshots made by older versions.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub paths: Vec<Vec<String>>,
+    /// The `BuilderConfig::state_version` of the builder that made the snapshot.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub version: u32,
+}
+
+/// The outcome of `Builder::restore_state_lenient`: how many inputs of the snapshot have been
+/// applied again, and the ones that have been dropped because they don't fit the type anymore.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct RestoreReport {
+    /// The number of inputs applied again.
+    pub applied: usize,
+    /// The inputs of the snapshot that have not been applied, in order.
+    pub dropped: Vec<DroppedInput>,
+}
+
+impl RestoreReport {
+    /// Whether all the inputs of the snapshot have been applied again.
+    pub fn is_complete(&self) -> bool {
+        self.dropped.is_empty()
+    }
+}
+
+/// An input of a `SavedState` dropped by `Builder::restore_state_lenient`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct DroppedInput {
+    /// The index of the input in the snapshot.
+    pub index: usize,
+    /// The menu the user was in when the input should have been applied.
+    pub path: Vec<Id>,
+    /// The input itself.
+    pub input: Input,
+    /// Why the input has been dropped.
+    pub reason: DropReason,
+}
+
+/// Why an input of a `SavedState` has been dropped, see `DroppedInput`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum DropReason {
+    /// The input was meant for a menu that doesn't exist anymore, e.g. the one of a removed field.
+    UnknownMenu,
+    /// The input is not valid anymore, e.g. it selects a removed field or its text cannot be
+    /// parsed by the new type of the field.
+    Rejected(ChooseError),
+    /// The input would finalize the builder.
+    Finalized,
 }
 
 impl<T> Buildable<T> for T
@@ -174,30 +786,280 @@ where
     T: NewBuildableValue + 'static,
 {
     fn builder() -> Builder<T> {
-        Builder::<T>::from_buildable_value(T::new_buildable_value(Default::default()))
+        let mut builder =
+            Builder::<T>::from_buildable_value(T::new_buildable_value(Default::default()));
+        builder.initial = Some(ReplayBase::New(|| {
+            T::new_buildable_value(Default::default())
+        }));
+        builder
+    }
+
+    fn builder_from(self) -> Builder<T> {
+        Builder::from_value(self)
     }
 }
 
 /// The interactive builder for a base type.
 pub trait BuildableValue: std::fmt::Debug {
-    /// Try to change the inner value using the provided input.
-    fn apply(&mut self, data: Input, current_fields: &[String]) -> Result<(), ChooseError>;
+    /// Try to change the inner value using the provided input. The input is borrowed, so that the
+    /// containers can forward the same input to their subfields.
+    fn apply(&mut self, data: &Input, current_fields: &[Id]) -> Result<(), ChooseError>;
 
     /// The options to show to the user for setting this value.
-    fn get_options(&self, current_fields: &[String]) -> Options;
+    fn get_options(&self, current_fields: &[Id]) -> Options;
 
     /// Whether this value contains itself other values (i.e. it's a struct).
-    fn get_subfields(&self, current_fields: &[String]) -> Vec<String>;
+    fn get_subfields(&self, current_fields: &[Id]) -> Vec<Id>;
+
+    /// The id of the subfield of the menu at `current_fields` named `name`, if it's one of the
+    /// `get_subfields`. It's checked on every input, so the values with many subfields should
+    /// override it without building the whole list, and the values that contain other values
+    /// should forward the call to the inner ones.
+    fn subfield(&self, current_fields: &[Id], name: &str) -> Option<Id> {
+        self.get_subfields(current_fields)
+            .into_iter()
+            .find(|f| f == name)
+    }
 
     /// Create the tree structure of this value.
     fn to_node(&self) -> Node;
 
+    /// Create the tree structure of this value, replacing the composite nodes deeper than `depth`
+    /// with `Field::Elided` (see `Node::pruned`).
+    ///
+    /// The values that contain other values should override it, without building the omitted
+    /// nodes.
+    fn to_node_pruned(&self, depth: usize) -> Node {
+        self.to_node().pruned(depth)
+    }
+
+    /// Walk the tree structure of this value calling the `visitor`, as if it was called on the
+    /// result of `to_node`.
+    ///
+    /// The values that contain other values should override it, without building the skipped
+    /// nodes.
+    fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        self.to_node().visit(visitor)
+    }
+
     /// Get the inner value, if present, as an `Any`.
     ///
     /// It's **very important** that the returned `Any` internal type matches the type that this
     /// builder is used for. The `Builder` will downcast this `Any` to the types it's expecting,
-    /// panicking in case of mismatched type.
+    /// panicking in case of mismatched type. For this reason it should not be implemented by hand:
+    /// implement `TypedBuildableValue` and use `typed_get_value_any!` instead.
     fn get_value_any(&self) -> Option<Box<dyn Any>>;
+
+    /// Replace the inner value with the provided one, which has the same type returned by
+    /// `get_value_any`. This is used for prefilling the builders (e.g. `Builder::from_default`).
+    ///
+    /// Return whether the whole value has been set: `false` if the type doesn't match, if the
+    /// value (or part of it) is rejected by the validators, or if the builder doesn't support being
+    /// prefilled, which is the default.
+    fn set_value_any(&mut self, _value: Box<dyn Any>) -> bool {
+        false
+    }
+
+    /// Whether all the required data has been inserted, i.e. `get_value_any` would return a value.
+    ///
+    /// The values that contain other values should override it, without building the inner values.
+    fn is_complete(&self) -> bool {
+        self.get_value_any().is_some()
+    }
+
+    /// The error of the validators on the current value, if it doesn't pass them anymore (e.g. a
+    /// default value that has never been validated). It's checked by `Builder::finalize`, the
+    /// default is `None`.
+    fn validation_error(&self) -> Option<String> {
+        None
+    }
+
+    /// Recompute the state that this value caches about its subfields (e.g. which of them are
+    /// complete). It's called after a subfield has been changed without `apply`, for example by
+    /// `Builder::replace_field`.
+    fn refresh(&mut self) {}
+
+    /// Get the builder of a direct subfield of this value (e.g. a field of a struct), if any.
+    fn get_child(&self, _name: &str) -> Option<&dyn BuildableValue> {
+        None
+    }
+
+    /// Get a mutable reference to the builder of a direct subfield of this value, if any. This is
+    /// used by `Builder::replace_field` for swapping the builder of a field at runtime.
+    fn get_child_mut(&mut self, _name: &str) -> Option<&mut Box<dyn BuildableValue>> {
+        None
+    }
+
+    /// Whether the user should stay in the menu at `current_fields` after an input that doesn't
+    /// select a subfield has been applied, instead of going back to the parent menu. This is useful
+    /// for menus that accept many inputs in a row, like a list of toggles.
+    ///
+    /// The values that contain other values should forward the call to the inner ones.
+    fn stay_after_apply(&self, _current_fields: &[Id]) -> bool {
+        false
+    }
+
+    /// The input to record in the history of the `Builder` in place of `data`, which has just been
+    /// applied to the menu at `current_fields`, if applying `data` again wouldn't give the same
+    /// result (e.g. it made a random value). The history is applied again by `Builder::undo` and
+    /// by `Builder::restore_state`, so the recorded input must bring the value to the same state.
+    /// The default is `None`, which records `data` itself.
+    ///
+    /// The values that contain other values should forward the call to the inner ones.
+    fn recorded_input(&self, _data: &Input, _current_fields: &[Id]) -> Option<Input> {
+        None
+    }
+
+    /// The subfield of the menu at `current_fields` the user should be moved into as soon as they
+    /// reach that menu, if any. `entry` tells how the user reached the menu, for example the values
+    /// should not send the user back into a subfield they just left with `BACK_ID`, otherwise they
+    /// would be trapped inside it.
+    ///
+    /// The values that contain other values should forward the call to the inner ones.
+    fn auto_enter(&self, _current_fields: &[Id], _entry: MenuEntry) -> Option<Id> {
+        None
+    }
+
+    /// The JSON Schema of the value, see the `schema` module. By default it's guessed from the
+    /// options of the main menu (see `Schema::guess`).
+    fn schema(&self) -> Schema {
+        Schema::guess(self)
+    }
+
+    /// Make a copy of this builder, with all its state (e.g. for duplicating an item of a `Vec`).
+    /// Return `None` if the builder doesn't support being copied, which is the default.
+    ///
+    /// The values that contain other values should return `None` if any of the inner ones can't
+    /// be copied.
+    fn clone_boxed(&self) -> Option<Box<dyn BuildableValue>> {
+        None
+    }
+}
+
+/// The typed access to the value of a `BuildableValue` that builds a `T`, implemented by the
+/// builders of concrete types of this crate and by the ones generated by the derive macro. The
+/// wrappers that erase the type of the inner builder (e.g. `builders::SecretBuilder`) don't
+/// implement it.
+///
+/// `BuildableValue` has to be object-safe, so its `get_value_any` returns an `Any` which must have
+/// the right type. The custom builders should implement this trait instead, and make
+/// `get_value_any` an adapter of `get_value` with `typed_get_value_any!`: this way the type of
+/// the value is checked by the compiler.
+///
+/// ```
+/// use ibuilder::nodes::{Field, Node};
+/// use ibuilder::*;
+///
+/// /// A builder that counts the inputs of the user.
+/// #[derive(Debug, Default)]
+/// struct Counter(u32);
+///
+/// impl TypedBuildableValue<u32> for Counter {
+///     fn get_value(&self) -> Option<u32> {
+///         Some(self.0)
+///     }
+/// }
+///
+/// impl BuildableValue for Counter {
+///     typed_get_value_any!(u32);
+///
+///     fn apply(&mut self, _data: &Input, _current_fields: &[Id]) -> Result<(), ChooseError> {
+///         self.0 += 1;
+///         Ok(())
+///     }
+///
+///     fn get_options(&self, _current_fields: &[Id]) -> Options {
+///         Options {
+///             query: "Type anything".to_string(),
+///             text_input: true,
+///             ..Default::default()
+///         }
+///     }
+///
+///     fn get_subfields(&self, _current_fields: &[Id]) -> Vec<Id> {
+///         vec![]
+///     }
+///
+///     fn to_node(&self) -> Node {
+///         Node::Leaf(Field::String(self.0.to_string()))
+///     }
+/// }
+///
+/// let mut counter = Counter::default();
+/// counter.apply(&Input::text("hello"), &[]).unwrap();
+/// assert_eq!(counter.get_value(), Some(1));
+/// ```
+pub trait TypedBuildableValue<T: 'static>: BuildableValue {
+    /// Get the inner value, if present.
+    fn get_value(&self) -> Option<T>;
+}
+
+/// Implement `BuildableValue::get_value_any` with `TypedBuildableValue::get_value`, for the type
+/// provided as argument. It's used inside the `impl BuildableValue` block, see
+/// `TypedBuildableValue`.
+#[macro_export]
+macro_rules! typed_get_value_any {
+    ($ty:ty) => {
+        fn get_value_any(&self) -> Option<Box<dyn std::any::Any>> {
+            $crate::typed_value_any::<$ty, Self>(self)
+        }
+    };
+}
+
+/// The value of `TypedBuildableValue::get_value` as an `Any`, used by `typed_get_value_any!` and by
+/// the builders whose type of value is chosen at runtime (e.g. `builders::RangeBuilder`).
+#[doc(hidden)]
+pub fn typed_value_any<V: 'static, B: TypedBuildableValue<V> + ?Sized>(
+    builder: &B,
+) -> Option<Box<dyn Any>> {
+    builder
+        .get_value()
+        .map(|value| Box::new(value) as Box<dyn Any>)
+}
+
+/// How the user reached a menu, see `BuildableValue::auto_enter`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum MenuEntry<'a> {
+    /// The menu has been entered from its parent, or it's the first menu of the `Builder`.
+    Entered,
+    /// The user returned to the menu after an input has been applied to this subfield.
+    Returned(&'a str),
+    /// The user went back to the menu from this subfield using `BACK_ID`.
+    WentBack(&'a str),
+}
+
+/// A read-only view over the state of a partially built value, handed to the user-provided
+/// callbacks (e.g. the handlers of the actions).
+pub struct PartialState<'a> {
+    inner: &'a dyn BuildableValue,
+}
+
+impl<'a> PartialState<'a> {
+    /// Wrap a `BuildableValue` for inspecting its state.
+    pub fn new(inner: &'a dyn BuildableValue) -> PartialState<'a> {
+        PartialState { inner }
+    }
+
+    /// Get the current value of a field, if it has been set. `T` must be the type of the field,
+    /// `None` is returned if the field doesn't exist or the type doesn't match.
+    pub fn get<T: 'static>(&self, field: &str) -> Option<T> {
+        self.inner
+            .get_child(field)?
+            .get_value_any()?
+            .downcast::<T>()
+            .ok()
+            .map(|v| *v)
+    }
+
+    /// Whether all the fields of the value have been set.
+    pub fn is_done(&self) -> bool {
+        self.inner.is_complete()
+    }
+
+    /// Return the tree structure of the partial state.
+    pub fn to_node(&self) -> Node {
+        self.inner.to_node()
+    }
 }
 
 /// A type that can be built with a `BuildableValue` inside a `Builder`. Keep in mind that the
@@ -209,6 +1071,118 @@ pub trait NewBuildableValue {
     fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue>;
 }
 
+/// The fields asked by the builder of a struct with named fields, implemented deriving
+/// `IBuilder`. The hidden fields are not listed.
+///
+/// The applications that mirror the form elsewhere (e.g. in the documentation, or in the columns of
+/// a table) can check at compile time that they cover all the fields with `assert_fields!`.
+///
+/// ```
+/// use ibuilder::*;
+///
+/// #[derive(IBuilder)]
+/// struct Person {
+///     name: String,
+///     age: u8,
+///     #[ibuilder(hidden)]
+///     id: u64,
+/// }
+///
+/// assert_eq!(Person::FIELD_COUNT, 2);
+/// assert_eq!(Person::FIELD_NAMES, &["name", "age"]);
+/// ```
+pub trait FieldList {
+    /// The number of fields, i.e. the length of `FIELD_NAMES`.
+    const FIELD_COUNT: usize;
+    /// The identifiers of the fields, in the order of declaration. They are the ones used in the
+    /// paths of the `Builder`, the renames are not applied.
+    const FIELD_NAMES: &'static [&'static str];
+}
+
+/// Whether the two lists contain the same field names, in any order. It's usable in the constant
+/// expressions, see `assert_fields!`.
+pub const fn fields_match(fields: &[&str], other: &[&str]) -> bool {
+    const fn contains(list: &[&str], name: &str) -> bool {
+        let mut i = 0;
+        while i < list.len() {
+            let (a, b) = (list[i].as_bytes(), name.as_bytes());
+            let mut equal = a.len() == b.len();
+            let mut j = 0;
+            while equal && j < a.len() {
+                equal = a[j] == b[j];
+                j += 1;
+            }
+            if equal {
+                return true;
+            }
+            i += 1;
+        }
+        false
+    }
+    let mut i = 0;
+    while i < fields.len() {
+        if !contains(other, fields[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    let mut i = 0;
+    while i < other.len() {
+        if !contains(fields, other[i]) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+/// Assert at compile time that the list contains all the fields of a type implementing
+/// `FieldList`, and nothing else. Adding a field to the struct without updating the list breaks
+/// the build.
+///
+/// ```
+/// use ibuilder::*;
+///
+/// #[derive(IBuilder)]
+/// struct Person {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// assert_fields!(Person, ["age", "name"]);
+/// ```
+#[macro_export]
+macro_rules! assert_fields {
+    ($ty:ty, [$($field:expr),* $(,)?]) => {
+        const _: () = assert!(
+            $crate::fields_match(<$ty as $crate::FieldList>::FIELD_NAMES, &[$($field),*]),
+            "the list doesn't match the fields of the type"
+        );
+    };
+}
+
+/// A type whose value is inserted by the user as a single text, like the numbers or the strings.
+///
+/// Implementing `BuildableValue` requires handling the menus and the navigation of the user, while
+/// for these simple types it's enough to tell how to parse and show the value: the
+/// `builders::LeafBuilder` adapter implements `BuildableValue` on top of this trait. The type still
+/// needs to implement `NewBuildableValue` making a `LeafBuilder`, see its documentation for an
+/// example.
+pub trait LeafBuildableValue: Clone + 'static {
+    /// The prompt message shown when the field doesn't specify one.
+    const PROMPT: &'static str = "Type a value";
+
+    /// Parse the text inserted by the user, returning the message to show if it's not valid.
+    fn parse(text: &str) -> Result<Self, String>;
+
+    /// The textual representation of the value, shown in the tree structure of the builder.
+    fn render(&self) -> String;
+}
+
+/// A function that makes a new `BuildableValue`, like `NewBuildableValue::new_buildable_value`.
+/// It's used for the fields with `#[ibuilder(builder = "...")]`.
+pub type BuilderFn = fn(BuildableValueConfig<()>) -> Box<dyn BuildableValue>;
+
 /// The configuration for customizing the aspect of a `BuildableValue` that produces a value of type
 /// `T`.
 pub struct BuildableValueConfig<T> {
@@ -217,6 +1191,16 @@ pub struct BuildableValueConfig<T> {
     pub default: Option<T>,
     /// The prompt message to show to the user, if `None` a default message is shown.
     pub prompt: Option<String>,
+    /// The functions used to check the value after it has been parsed.
+    pub validators: Vec<Validator<T>>,
+    /// The functions applied in order to the text inserted by the user, before parsing it.
+    pub preprocessors: Vec<Preprocessor>,
+    /// A short description of the accepted values, exposed in the `Options` (see
+    /// `Options::hint`).
+    pub hint: Option<String>,
+    /// The characters accepted in the text inserted by the user, checked after the preprocessors
+    /// and before parsing it. If `None` all the characters are accepted.
+    pub charset: Option<Charset>,
 }
 
 impl<T> Default for BuildableValueConfig<T> {
@@ -224,6 +1208,39 @@ impl<T> Default for BuildableValueConfig<T> {
         Self {
             default: None,
             prompt: None,
+            validators: vec![],
+            preprocessors: vec![],
+            hint: None,
+            charset: None,
+        }
+    }
+}
+
+/// A function that checks a value inserted by the user, see `Validation`.
+pub type Validator<T> = Rc<dyn Fn(&T) -> Validation>;
+
+/// A function that normalizes the text inserted by the user before it's parsed (e.g. removing the
+/// surrounding whitespace), see the `preprocessors` module.
+pub type Preprocessor = Rc<dyn Fn(&str) -> String>;
+
+/// The outcome of the validation of a value inserted by the user.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Validation {
+    /// The value is valid.
+    Valid,
+    /// The value is accepted, but the user should be warned with this message. The warning is
+    /// exposed in the `Options` and in the `Node` of the value.
+    Warning(String),
+    /// The value is not valid and it's rejected with this message.
+    Invalid(String),
+}
+
+impl From<Result<(), String>> for Validation {
+    /// The validators can also return a `Result`, where the error rejects the value.
+    fn from(result: Result<(), String>) -> Self {
+        match result {
+            Ok(()) => Validation::Valid,
+            Err(error) => Validation::Invalid(error),
         }
     }
 }
@@ -238,34 +1255,202 @@ impl<T: 'static> Builder<T> {
     /// Create a new builder from a `BuildableValue`. Note that the inner type of the
     /// `BuildableValue` must match `T`, otherwise a panic is very likely.
     pub fn from_buildable_value(inner: Box<dyn BuildableValue>) -> Builder<T> {
-        Self {
+        let mut builder = Self {
             builder: inner,
-            current_fields: vec![],
+            navigator: Navigator::new(),
             inner_type: Default::default(),
+            config: Default::default(),
+            pending_error: None,
+            notice: None,
+            metrics: None,
+            persistence: None,
+            persistence_error: None,
+            started: Instant::now(),
+            menu_since: Instant::now(),
+            recent_inputs: VecDeque::new(),
+            last_input: None,
+            open_group: None,
+            search: None,
+            initial: None,
+            history: vec![],
+            recorded_input: None,
+            undone: vec![],
+            revision: 0,
+        };
+        builder.auto_enter(MenuEntry::Entered);
+        builder
+    }
+
+    /// Change the configuration of this builder.
+    pub fn with_config(mut self, config: BuilderConfig) -> Builder<T> {
+        self.config = config;
+        self.started = self.config.clock.now();
+        self.menu_since = self.started;
+        self
+    }
+
+    /// Send the analytics about the interaction with this builder to the provided sink.
+    pub fn with_metrics<M: MetricsSink + 'static>(mut self, metrics: M) -> Builder<T> {
+        self.metrics = Some(Box::new(metrics));
+        self
+    }
+
+    /// Save the state of this builder to the provided storage, with the key of the session, after
+    /// every successful `choose`, and remove it when the value is finalized. If the storage already
+    /// has a snapshot with this key it's restored like with `restore_state_lenient`, so the session
+    /// continues where it was left. See the `persistence` module.
+    ///
+    /// The errors of the automatic saves don't make `choose` fail, they are kept until they are
+    /// taken with `take_persistence_error`.
+    ///
+    /// Fails with `PersistenceError::Invalid` if the snapshots of this builder cannot be restored,
+    /// i.e. it's made with `Builder::from_value` or `Builder::load_json`, or its state has been
+    /// changed with `replace_field` or `invalidate`.
+    pub fn with_persistence<P: StatePersistence + 'static>(
+        mut self,
+        mut persistence: P,
+        key: &str,
+    ) -> Result<Builder<T>, PersistenceError> {
+        if !self.is_restorable() {
+            return Err(PersistenceError::Invalid(
+                "the state of this builder cannot be rebuilt from its inputs".to_string(),
+            ));
         }
+        if let Some(state) = persistence.load(key)? {
+            self.restore_state_lenient(&state)
+                .map_err(|e| PersistenceError::Invalid(e.to_string()))?;
+        }
+        self.persistence = Some((Box::new(persistence), key.to_string()));
+        Ok(self)
     }
 
-    /// Return all the valid options that this builder accepts in the current state.
-    pub fn get_options(&self) -> Options {
-        let mut options = self.builder.get_options(&self.current_fields);
-        // main menu
-        if self.current_fields.is_empty() {
-            if self.is_done() {
-                options.choices.push(Choice {
-                    choice_id: FINALIZE_ID.to_string(),
-                    text: "Done".to_string(),
-                    needs_action: false,
+    /// Save the current state to the storage set with `with_persistence`, for example after the
+    /// changes not made with `choose` (like `undo`). Without a storage nothing is done.
+    pub fn persist(&mut self) -> Result<(), PersistenceError> {
+        if self.persistence.is_some() && !self.is_restorable() {
+            return Err(PersistenceError::Invalid(
+                "the state cannot be rebuilt from its inputs after replace_field or invalidate"
+                    .to_string(),
+            ));
+        }
+        let state = self.save_state();
+        match &mut self.persistence {
+            Some((persistence, key)) => persistence.save(key, &state),
+            None => Ok(()),
+        }
+    }
+
+    /// Take the error of the last automatic save that failed, if any, see `with_persistence`.
+    pub fn take_persistence_error(&mut self) -> Option<PersistenceError> {
+        self.persistence_error.take()
+    }
+
+    /// Save the state after an input of `choose`, or remove it if the value has been finalized.
+    fn autosave(&mut self, finalized: bool) {
+        let res = match &mut self.persistence {
+            Some((persistence, key)) if finalized => persistence.remove(key),
+            Some(_) => self.persist(),
+            None => return,
+        };
+        if let Err(e) = res {
+            self.persistence_error = Some(e);
+        }
+    }
+
+    /// Return all the valid options that this builder accepts in the current state.
+    pub fn get_options(&self) -> Options {
+        let mut options = self.menu_options();
+        if options.choices.iter().any(|c| c.choice_id == FINALIZE_ID) {
+            options.default_choice = Some(FINALIZE_ID.into());
+        }
+        if self.config.layout_hints {
+            options.layout = Some(Layout::of(&options));
+        }
+        options
+    }
+
+    /// The options of the current menu, without the layout hints.
+    fn menu_options(&self) -> Options {
+        if let Some(error) = &self.pending_error {
+            return self.error_recovery_options(error);
+        }
+        let mut options = self.builder.get_options(self.navigator.current_fields());
+        if let (Some(localizer), Some(key)) = (&self.config.localizer, &options.prompt_key) {
+            if let Some(query) = localizer.localize(key) {
+                options.query = query;
+            }
+        }
+        if options.notice.is_none() {
+            options.notice = self.notice.clone();
+        }
+        if let Some(search) = &self.search {
+            return self.search_options(search);
+        }
+        if self.config.choice_order == ChoiceOrder::MissingFirst && self.navigator.is_main_menu() {
+            // the sort is stable, so the order of the definition is kept
+            options.choices.sort_by_key(|c| !c.needs_action);
+        }
+        if let Some(groups) = self.group_choices(&mut options) {
+            return self.group_options(options, groups);
+        }
+        options.choices.extend(self.special_choices());
+        options
+    }
+
+    /// The choices added by the `Builder` to the current menu: "Find field…" and "Done" in the
+    /// main menu, "Go back" (and "Back to start" in the nested ones) in the others.
+    fn special_choices(&self) -> Vec<Choice> {
+        let mut choices = vec![];
+        // main menu
+        if self.navigator.is_main_menu() {
+            if self.config.field_search {
+                choices.push(Choice {
+                    choice_id: SEARCH_ID.into(),
+                    text: "Find field…".to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                });
+            }
+            if self.is_done() {
+                choices.push(Choice {
+                    choice_id: FINALIZE_ID.into(),
+                    text: "Done".to_string(),
+                    needs_action: false,
+                    ..Default::default()
                 });
             }
         // field menu
         } else {
-            options.choices.push(Choice {
-                choice_id: BACK_ID.to_string(),
+            choices.push(Choice {
+                choice_id: BACK_ID.into(),
                 text: "Go back".to_string(),
                 needs_action: false,
+                ..Default::default()
             });
+            if self.can_go_to_root() {
+                choices.push(Choice {
+                    choice_id: ROOT_ID.into(),
+                    text: "Back to start".to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                });
+            }
         }
-        options
+        choices
+    }
+
+    /// Whether the "Back to start" choice is available in the current menu.
+    fn can_go_to_root(&self) -> bool {
+        self.config.back_to_start && self.navigator.current_fields().len() >= 2
+    }
+
+    /// Whether the user is looking at the main menu, not at the error recovery menu, at the search
+    /// or at a group of choices.
+    fn is_at_main_menu(&self) -> bool {
+        self.navigator.is_main_menu()
+            && self.pending_error.is_none()
+            && self.search.is_none()
+            && self.open_group.is_none()
     }
 
     /// Apply an input to the builder, making it change state. Call again `get_options()` for the
@@ -274,64 +1459,1413 @@ impl<T: 'static> Builder<T> {
     /// Returns `Ok(None)` if the process is not done yet, `Ok(Some(T))` when the user choose to
     /// finish the builder.
     pub fn choose(&mut self, input: Input) -> Result<Option<T>, ChooseError> {
+        // the text is dropped right away, without recording it in the history or in the metrics
+        self.check_text_len(&input)?;
+        self.notice = None;
+        let sensitive = self.is_sensitive(&input);
+        // the menu and the input are needed only for the metrics, avoid cloning them otherwise
+        let metrics_input = self
+            .metrics
+            .as_ref()
+            .map(|_| (self.navigator.current_fields().to_vec(), input.clone()));
+        let recovery = self.pending_error.is_some();
+        let res = if let Err(e) = self.check_rate_limit(&input) {
+            Err(e)
+        } else if recovery {
+            self.choose_error_recovery(&input)
+        } else {
+            let res = self.choose_inner(&input);
+            if let (Err(e), true) = (&res, self.config.error_recovery) {
+                self.pending_error = Some(e.clone());
+            }
+            res
+        };
+        match &res {
+            // the error recovery records the inputs that change the state by itself
+            Ok(_) if recovery => {}
+            Ok(None) => {
+                let input = self.recorded(input);
+                self.record(input, sensitive)
+            }
+            Ok(Some(_)) => self.log(input, InputOutcome::Finalized, sensitive),
+            // a flood of inputs would fill the history, and they were not even looked at
+            Err(ChooseError::RateLimited) => {}
+            Err(e) => self.log(input, InputOutcome::Rejected(e.clone()), sensitive),
+        }
+        if let Ok(finalized) = &res {
+            self.revision += 1;
+            self.autosave(finalized.is_some());
+        }
+        if let Some((fields, input)) = metrics_input {
+            self.send_metrics(&fields, input, sensitive, &res);
+        }
+        res
+    }
+
+    /// Like `choose`, but apply the input only if the state of the builder is still at the provided
+    /// `revision`, otherwise fail with `ChooseError::Conflict`. This is useful when many users edit
+    /// the same builder (e.g. two admins of a bot): each of them sends the revision of the options
+    /// they have seen, and the input of the slower one is rejected instead of being applied to a
+    /// menu they have never seen.
+    pub fn choose_if(&mut self, revision: u64, input: Input) -> Result<Option<T>, ChooseError> {
+        if revision != self.revision {
+            return Err(ChooseError::Conflict {
+                revision: self.revision,
+            });
+        }
+        self.choose(input)
+    }
+
+    /// The number of changes of the state of the builder: it's incremented by every successful
+    /// `choose`, and by the other methods that change the state (e.g. `undo` or `goto`). It never
+    /// decreases, see `choose_if`.
+    pub fn revision(&self) -> u64 {
+        self.revision
+    }
+
+    /// Check that the text of the input is not longer than `BuilderConfig::max_text_len`.
+    fn check_text_len(&self, input: &Input) -> Result<(), ChooseError> {
+        match (input, self.config.max_text_len) {
+            // count at most `max + 1` characters, the text may be huge
+            (Input::Text(text), Some(max))
+                if text.len() > max && text.chars().nth(max).is_some() =>
+            {
+                Err(ChooseError::TextTooLong { max })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Check that the input doesn't exceed the limits set in the `BuilderConfig`, and record it.
+    fn check_rate_limit(&mut self, input: &Input) -> Result<(), ChooseError> {
+        let now = self.config.clock.now();
+        if let (Some(debounce), Some((last, when))) = (self.config.debounce, &self.last_input) {
+            if last == input && now.duration_since(*when) < debounce {
+                return Err(ChooseError::RateLimited);
+            }
+        }
+        if let Some(max) = self.config.max_inputs_per_second {
+            while let Some(when) = self.recent_inputs.front() {
+                if now.duration_since(*when) < Duration::from_secs(1) {
+                    break;
+                }
+                self.recent_inputs.pop_front();
+            }
+            if self.recent_inputs.len() >= max {
+                return Err(ChooseError::RateLimited);
+            }
+            self.recent_inputs.push_back(now);
+        }
+        if self.config.debounce.is_some() {
+            self.last_input = Some((input.clone(), now));
+        }
+        Ok(())
+    }
+
+    /// Notify the `MetricsSink` (if any) about the outcome of an input in the menu at `fields`.
+    fn send_metrics(
+        &mut self,
+        fields: &[Id],
+        input: Input,
+        sensitive: bool,
+        res: &Result<Option<T>, ChooseError>,
+    ) {
+        let redaction = if sensitive {
+            Redaction::Placeholder
+        } else {
+            self.redaction()
+        };
+        let input = redaction.redact_input(&input);
+        let metrics = match &mut self.metrics {
+            Some(metrics) => {
+                match res {
+                    Ok(_) => metrics.input(fields, &input),
+                    Err(e) => metrics.error(fields, e),
+                }
+                metrics
+            }
+            None => return,
+        };
+        let finalized = matches!(res, Ok(Some(_)));
+        if finalized || fields != self.navigator.current_fields() {
+            let now = self.config.clock.now();
+            metrics.dwell(fields, now.duration_since(self.menu_since));
+            self.menu_since = now;
+        }
+        if finalized {
+            let now = self.config.clock.now();
+            metrics.finalized(now.duration_since(self.started));
+        }
+    }
+
+    /// Apply the input to the builder, without handling the error recovery.
+    fn choose_inner(&mut self, input: &Input) -> Result<Option<T>, ChooseError> {
+        let (clock, random) = (self.config.clock.clone(), self.config.random.clone());
+        with_sources(clock, random, || self.choose_sourced(input))
+    }
+
+    /// Process the input, with the sources of the config available to the builders.
+    fn choose_sourced(&mut self, input: &Input) -> Result<Option<T>, ChooseError> {
+        // recorded by `goto`, it's valid in any menu
+        if let Input::Choice(data) = input {
+            if let Some(path) = data.strip_prefix(GOTO_ID_PREFIX) {
+                let path: Vec<_> = path.split('.').filter(|field| !field.is_empty()).collect();
+                return match self.goto_path(&path) {
+                    Ok(()) => Ok(None),
+                    Err(_) => Err(ChooseError::UnexpectedChoice),
+                };
+            }
+        }
+        if self.search.is_some() {
+            return self.choose_search(input);
+        }
+        if let Input::Choice(data) = input {
+            if data == SEARCH_ID && self.config.field_search && self.navigator.is_main_menu() {
+                self.search = Some(vec![]);
+                return Ok(None);
+            }
+            if self.open_group.is_some() && data == BACK_ID {
+                self.open_group = None;
+                return Ok(None);
+            }
+            if let Some(index) = data.strip_prefix(GROUP_ID_PREFIX) {
+                let mut options = self.builder.get_options(self.navigator.current_fields());
+                let groups = self.group_choices(&mut options).unwrap_or_default();
+                return match index.parse::<usize>() {
+                    Ok(index) if self.open_group.is_none() && index < groups.len() => {
+                        self.open_group = Some(index);
+                        Ok(None)
+                    }
+                    _ => Err(ChooseError::UnexpectedChoice),
+                };
+            }
+        }
+        // a group stays open only while the user stays in its menu
+        let fields = self
+            .open_group
+            .map(|_| self.navigator.current_fields().to_vec());
+        let res = self.choose_menu(input);
+        if fields.is_some_and(|fields| fields != self.navigator.current_fields()) {
+            self.open_group = None;
+        }
+        res
+    }
+
+    /// Apply the input to the current menu of the builder.
+    fn choose_menu(&mut self, input: &Input) -> Result<Option<T>, ChooseError> {
         // main menu
-        if self.current_fields.is_empty() {
-            if let Input::Choice(data) = &input {
+        if self.navigator.is_main_menu() {
+            if let Input::Choice(data) = input {
                 if data == FINALIZE_ID && self.is_done() {
-                    return Ok(Some(self.finalize().expect("Finalize failed")));
+                    // the fields are complete, but their values may not pass the validators
+                    return match self.finalize() {
+                        Ok(value) => Ok(Some(value)),
+                        Err(FinalizeError::Incomplete { issues }) => {
+                            let issues: Vec<_> = issues.iter().map(|i| i.to_string()).collect();
+                            Err(ChooseError::InvalidText {
+                                error: issues.join("\n"),
+                            })
+                        }
+                        Err(e) => Err(ChooseError::InvalidText {
+                            error: e.to_string(),
+                        }),
+                    };
                 }
             }
 
         // field menu
         } else {
-            match &input {
+            match input {
                 Input::Choice(data) if data == BACK_ID => {
-                    self.current_fields.pop();
+                    self.go_back();
+                    return Ok(None);
+                }
+                Input::Choice(data) if data == ROOT_ID && self.can_go_to_root() => {
+                    self.go_to_root();
                     return Ok(None);
                 }
                 _ => {}
             }
         };
-        let subfields = self.builder.get_subfields(&self.current_fields);
-        for subfield in subfields {
-            match &input {
-                Input::Choice(data) => {
-                    if subfield == data.as_str() {
-                        self.builder.apply(input, &self.current_fields)?;
-                        self.current_fields.push(subfield);
-                        return Ok(None);
-                    }
+        let (transition, recorded) = self
+            .navigator
+            .apply_recorded(self.builder.as_mut(), input)?;
+        self.recorded_input = recorded;
+        match transition {
+            Transition::Entered => self.auto_enter(MenuEntry::Entered),
+            Transition::Returned(field) => {
+                let field = self.leave_skipped_menus(field);
+                self.auto_enter(MenuEntry::Returned(&field))
+            }
+            Transition::Stayed => {}
+        }
+        Ok(None)
+    }
+
+    /// Split the choices of the options into groups, if they exceed
+    /// `BuilderConfig::max_choices_per_menu`. The choices are moved out of the options.
+    fn group_choices(&self, options: &mut Options) -> Option<Vec<Vec<Choice>>> {
+        let max = self.config.max_choices_per_menu?;
+        if options.choices.len() + self.special_choices().len() <= max {
+            return None;
+        }
+        let mut choices = std::mem::take(&mut options.choices);
+        choices.sort_by_key(|c| c.text.to_lowercase());
+        let size = max.saturating_sub(1).max(1);
+        let mut groups = vec![];
+        while !choices.is_empty() {
+            let rest = choices.split_off(size.min(choices.len()));
+            groups.push(choices);
+            choices = rest;
+        }
+        Some(groups)
+    }
+
+    /// The options of a menu whose choices have been split into groups: either the list of the
+    /// groups or the content of the open group.
+    fn group_options(&self, mut options: Options, mut groups: Vec<Vec<Choice>>) -> Options {
+        if let Some(index) = self.open_group {
+            if index < groups.len() {
+                options.choices = groups.swap_remove(index);
+                options.choices.push(Choice {
+                    choice_id: BACK_ID.into(),
+                    text: "Go back".to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                });
+                return options;
+            }
+        }
+        options.choices = groups
+            .iter()
+            .enumerate()
+            .map(|(index, group)| Choice {
+                choice_id: format!("{}{}", GROUP_ID_PREFIX, index).into(),
+                text: group_label(group),
+                needs_action: group.iter().any(|c| c.needs_action),
+                ..Default::default()
+            })
+            .collect();
+        options.choices.extend(self.special_choices());
+        options
+    }
+
+    /// The options of the field search: the fields matching the last search, if any.
+    fn search_options(&self, search: &[PathInfo]) -> Options {
+        let mut choices: Vec<_> = search
+            .iter()
+            .take(MAX_SEARCH_RESULTS)
+            .map(|info| Choice {
+                choice_id: info.path.join(".").into(),
+                text: format!("{} ({})", info.name, info.path.join(" > ")),
+                needs_action: info.required,
+                ..Default::default()
+            })
+            .collect();
+        choices.push(Choice {
+            choice_id: BACK_ID.into(),
+            text: "Go back".to_string(),
+            needs_action: false,
+            ..Default::default()
+        });
+        let notice = if search.len() > MAX_SEARCH_RESULTS {
+            Some(format!(
+                "Showing {} of {} fields, refine the search",
+                MAX_SEARCH_RESULTS,
+                search.len()
+            ))
+        } else {
+            self.notice.clone()
+        };
+        Options {
+            query: "Type part of the name of the field".to_string(),
+            text_input: true,
+            choices,
+            notice,
+            ..Default::default()
+        }
+    }
+
+    /// Apply the input to the field search.
+    fn choose_search(&mut self, input: &Input) -> Result<Option<T>, ChooseError> {
+        match input {
+            Input::Choice(data) if data == BACK_ID => self.search = None,
+            Input::Choice(data) => {
+                let path = self
+                    .search
+                    .iter()
+                    .flatten()
+                    .take(MAX_SEARCH_RESULTS)
+                    .find(|info| info.path.join(".") == *data)
+                    .map(|info| info.path.clone())
+                    .ok_or(ChooseError::UnexpectedChoice)?;
+                self.search = None;
+                self.enter_path(&path)?;
+            }
+            Input::Text(text) => {
+                let found = search_paths(self.paths(), text);
+                if found.is_empty() {
+                    return Err(ChooseError::InvalidText {
+                        error: format!("No field matches \"{}\"", text.trim()),
+                    });
                 }
-                Input::Text(_) => {}
+                self.search = Some(found);
+            }
+        }
+        Ok(None)
+    }
+
+    /// Move the user from the main menu to the menu of the field at `path`, selecting all the
+    /// fields along the way.
+    fn enter_path(&mut self, path: &[String]) -> Result<(), ChooseError> {
+        for field in path {
+            self.navigator.enter(self.builder.as_mut(), field)?;
+        }
+        Ok(())
+    }
+
+    /// Move the user back to the parent menu.
+    fn go_back(&mut self) {
+        self.open_group = None;
+        if let Some(field) = self.navigator.back() {
+            let field = self.leave_skipped_menus(field);
+            self.auto_enter(MenuEntry::WentBack(&field));
+        }
+    }
+
+    /// The only field of the current menu, if the menu should be skipped according to
+    /// `BuilderConfig::skip_single_field_menus`. The menus whose only choice is one of the
+    /// builtin actions (e.g. "Set value" of an `Option`) are not skipped, since entering them
+    /// changes the value.
+    fn single_field(&self) -> Option<Id> {
+        if !self.config.skip_single_field_menus || self.navigator.is_main_menu() {
+            return None;
+        }
+        let fields = self.navigator.current_fields();
+        let options = self.builder.get_options(fields);
+        let subfields = self.builder.get_subfields(fields);
+        match (options.choices.as_slice(), subfields.as_slice()) {
+            ([choice], [subfield])
+                if !options.text_input
+                    && &choice.choice_id == subfield
+                    && !subfield.starts_with("__") =>
+            {
+                Some(subfield.clone())
+            }
+            _ => None,
+        }
+    }
+
+    /// Move the user out of the skipped menus they returned to, returning the name of the last
+    /// field that has been left.
+    fn leave_skipped_menus(&mut self, field: Id) -> Id {
+        let mut field = field;
+        while self.single_field().is_some() {
+            match self.navigator.back() {
+                Some(parent) => field = parent,
+                None => break,
+            }
+        }
+        field
+    }
+
+    /// Move the user back to the main menu.
+    fn go_to_root(&mut self) {
+        self.open_group = None;
+        if let Some(field) = self.navigator.current_fields().first().cloned() {
+            self.navigator.truncate(0);
+            self.auto_enter(MenuEntry::WentBack(&field));
+        }
+    }
+
+    /// Move the user inside the subfields the current menu asks to enter first, if any, and past
+    /// the skipped menus.
+    fn auto_enter(&mut self, entry: MenuEntry) {
+        self.navigator.auto_enter(self.builder.as_ref(), entry);
+        while let Some(field) = self.single_field() {
+            if self.navigator.enter(self.builder.as_mut(), &field).is_err() {
+                break;
+            }
+            self.navigator
+                .auto_enter(self.builder.as_ref(), MenuEntry::Entered);
+        }
+    }
+
+    /// Show a transient message to the user in the `notice` of the next `Options`. The message is
+    /// discarded after the next call to `choose`.
+    pub fn notify<S: Into<String>>(&mut self, message: S) {
+        self.notice = Some(message.into());
+    }
+
+    /// The options to send again to a user that has been inactive for a while: the same of
+    /// `get_options`, with a reminder in the `notice` that highlights the suggested answer of the
+    /// current menu, if any.
+    ///
+    /// For a text input the suggested answer is the current value of the field (e.g. its default)
+    /// or its first suggestion, and it's moved to the first position of the `suggestions`. In the
+    /// other menus it's the first choice that needs action, moved to the first position of the
+    /// `choices`.
+    pub fn nudge(&self) -> Options {
+        let mut options = self.get_options();
+        let suggested = if options.text_input {
+            let current =
+                if self.pending_error.is_none() && self.search.is_none() && !options.sensitive {
+                    self.current_value()
+                } else {
+                    None
+                };
+            let suggested = current.or_else(|| options.suggestions.first().cloned());
+            if let Some(suggested) = &suggested {
+                options.suggestions.retain(|s| s != suggested);
+                options.suggestions.insert(0, suggested.clone());
+            }
+            suggested
+        } else {
+            options
+                .choices
+                .iter()
+                .position(|c| c.needs_action)
+                .map(|index| {
+                    let choice = options.choices.remove(index);
+                    let text = choice.text.clone();
+                    options.choices.insert(0, choice);
+                    text
+                })
+        };
+        options.notice = Some(match suggested {
+            Some(suggested) => format!("Still there? The suggested answer is \"{}\"", suggested),
+            None => "Still there?".to_string(),
+        });
+        options
+    }
+
+    /// The textual value of the field of the current menu, if it's a leaf reachable with
+    /// `get_child` from the main menu.
+    fn current_value(&self) -> Option<String> {
+        let mut value = self.builder.as_ref();
+        for field in self.navigator.current_fields() {
+            value = value.get_child(field)?;
+        }
+        match value.to_node() {
+            Node::Leaf(Field::String(value)) | Node::Leaf(Field::Warning(value, _)) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// The options of the error recovery menu, shown after an invalid input.
+    fn error_recovery_options(&self, error: &ChooseError) -> Options {
+        let mut choices = vec![Choice {
+            choice_id: RETRY_ID.into(),
+            text: "Try again".to_string(),
+            needs_action: false,
+            ..Default::default()
+        }];
+        if !self.navigator.is_main_menu() {
+            choices.push(Choice {
+                choice_id: BACK_ID.into(),
+                text: "Go back".to_string(),
+                needs_action: false,
+                ..Default::default()
+            });
+        }
+        Options {
+            query: error.to_string(),
+            text_input: false,
+            choices,
+            ..Default::default()
+        }
+    }
+
+    /// Apply the input to the error recovery menu. Only `RETRY_ID` and `BACK_ID` are accepted,
+    /// the latter only outside of the main menu.
+    fn choose_error_recovery(&mut self, input: &Input) -> Result<Option<T>, ChooseError> {
+        match input {
+            Input::Choice(data) if data == RETRY_ID => {}
+            Input::Choice(data) if data == BACK_ID && !self.navigator.is_main_menu() => {
+                self.go_back();
+                self.record(Input::choice(BACK_ID), false);
             }
+            Input::Choice(_) => return Err(ChooseError::UnexpectedChoice),
+            Input::Text(_) => return Err(ChooseError::UnexpectedText),
         }
-        self.builder.apply(input, &self.current_fields)?;
-        self.current_fields.pop();
+        self.pending_error = None;
         Ok(None)
     }
 
+    /// Add an input applied with success to the history used by `undo`, discarding the inputs
+    /// that could be redone.
+    fn record(&mut self, input: Input, sensitive: bool) {
+        self.log(input, InputOutcome::Applied, sensitive);
+        self.undone.clear();
+    }
+
+    /// Whether the input is the text of a secret, in the current menu (see `Options::sensitive`).
+    fn is_sensitive(&self, input: &Input) -> bool {
+        matches!(input, Input::Text(_))
+            && self
+                .builder
+                .get_options(self.navigator.current_fields())
+                .sensitive
+    }
+
+    /// The input to record in place of the one just applied, see `BuildableValue::recorded_input`.
+    fn recorded(&mut self, input: Input) -> Input {
+        self.recorded_input.take().unwrap_or(input)
+    }
+
+    /// Add an input to the history, with the menu the user is in after it.
+    fn log(&mut self, input: Input, outcome: InputOutcome, sensitive: bool) {
+        self.history.push(AppliedInput {
+            at: self.config.clock.now(),
+            path: self.navigator.current_fields().to_vec(),
+            input,
+            outcome,
+            sensitive,
+        });
+    }
+
+    /// All the inputs received by `choose` since the builder has been made (or since the last
+    /// `replace_field` or `invalidate`), in order, with their outcome. The inputs reverted with
+    /// `undo` and the ones rejected with `ChooseError::RateLimited` are not included. This is
+    /// useful for debugging a session, together with `replay_to`. The texts of the inputs are
+    /// redacted if the redaction is active (see `BuilderConfig::redaction`), the texts of the
+    /// secrets are always replaced with `redaction::PLACEHOLDER`.
+    pub fn history(&self) -> Cow<'_, [AppliedInput]> {
+        let redaction = self.redaction();
+        if !redaction.is_active() && !self.history.iter().any(|entry| entry.sensitive) {
+            return Cow::Borrowed(&self.history);
+        }
+        let history = self.history.iter().map(|entry| AppliedInput {
+            input: if entry.sensitive {
+                Redaction::Placeholder.redact_input(&entry.input)
+            } else {
+                redaction.redact_input(&entry.input)
+            },
+            ..entry.clone()
+        });
+        Cow::Owned(history.collect())
+    }
+
+    /// Rebuild the state the builder had after the first `n` entries of the `history`, discarding
+    /// the following ones. The discarded inputs can be applied again with `redo`, one at a time.
+    ///
+    /// Returns `false` if `n` is greater than the length of the history, or if the builder cannot
+    /// rebuild its states (see `undo`).
+    pub fn replay_to(&mut self, n: usize) -> bool {
+        if n > self.history.len() {
+            return false;
+        }
+        let mut history = self.history.clone();
+        let discarded = history.split_off(n);
+        if self.replay(history).is_err() {
+            return false;
+        }
+        self.undone.extend(
+            discarded
+                .into_iter()
+                .rev()
+                .filter(|entry| entry.outcome == InputOutcome::Applied)
+                .map(|entry| entry.input),
+        );
+        true
+    }
+
+    /// Revert the last input applied with `choose`, both the changes to the value and the
+    /// movements between the menus. Returns `false` if there is nothing to undo.
+    ///
+    /// The state is rebuilt applying again all the previous inputs to a new value, so the handlers
+    /// of the actions are run again. Only the builders made with `Buildable::builder` or
+    /// `Builder::from_default` can undo (not the ones made with `Builder::from_value`). After
+    /// `replace_field` and `invalidate` the inputs are applied to a copy of the state they left,
+    /// so the changes made before them cannot be undone, and nothing can be undone if the value
+    /// cannot be copied (see `BuildableValue::clone_boxed`).
+    pub fn undo(&mut self) -> bool {
+        let last = self
+            .history
+            .iter()
+            .rposition(|entry| entry.outcome == InputOutcome::Applied);
+        let last = match last {
+            Some(last) => last,
+            None => return false,
+        };
+        let mut history = self.history.clone();
+        let entry = history.remove(last);
+        // the rejected inputs after the undone one are discarded with it
+        history.truncate(last);
+        if self.replay(history).is_err() {
+            return false;
+        }
+        self.undone.push(entry.input);
+        true
+    }
+
+    /// Reset the state to the base of the replays and apply to it the inputs of `history`, which
+    /// becomes the new history. The entries that were not applied are kept as they are. If one of
+    /// the inputs is not valid anymore the error is returned and the state is left unchanged.
+    fn replay(&mut self, history: Vec<AppliedInput>) -> Result<(), RestoreError> {
+        let (builder, navigator) = match &self.initial {
+            Some(ReplayBase::New(new)) => (new(), None),
+            Some(ReplayBase::Snapshot(value, navigator)) => (
+                value.clone_boxed().ok_or(RestoreError::Unsupported)?,
+                Some(navigator.clone()),
+            ),
+            None => return Err(RestoreError::Unsupported),
+        };
+        let fresh = navigator.is_none();
+        let previous = (
+            std::mem::replace(&mut self.builder, builder),
+            std::mem::replace(&mut self.navigator, navigator.unwrap_or_default()),
+            self.pending_error.take(),
+            self.notice.take(),
+            self.open_group.take(),
+            self.search.take(),
+        );
+        if fresh {
+            self.auto_enter(MenuEntry::Entered);
+        }
+        match self.apply_history(history) {
+            Ok(history) => {
+                self.history = history;
+                self.revision += 1;
+                Ok(())
+            }
+            Err(e) => {
+                (
+                    self.builder,
+                    self.navigator,
+                    self.pending_error,
+                    self.notice,
+                    self.open_group,
+                    self.search,
+                ) = previous;
+                Err(e)
+            }
+        }
+    }
+
+    /// Apply the inputs of the history to the current state, returning the history with the
+    /// inputs as they have been recorded again.
+    fn apply_history(
+        &mut self,
+        history: Vec<AppliedInput>,
+    ) -> Result<Vec<AppliedInput>, RestoreError> {
+        let mut applied = Vec::with_capacity(history.len());
+        for (index, mut entry) in history.into_iter().enumerate() {
+            if entry.outcome != InputOutcome::Applied {
+                applied.push(entry);
+                continue;
+            }
+            entry.sensitive = self.is_sensitive(&entry.input);
+            match self.choose_inner(&entry.input) {
+                Ok(None) => {
+                    entry.input = self.recorded(entry.input);
+                    entry.path = self.navigator.current_fields().to_vec();
+                    applied.push(entry);
+                }
+                Ok(Some(_)) => return Err(RestoreError::Finalized { index }),
+                Err(error) => return Err(RestoreError::InvalidInput { index, error }),
+            }
+        }
+        Ok(applied)
+    }
+
+    /// Make the current state the base of the replays, after a change not made with an input: the
+    /// history is cleared, and `undo` cannot go past this state. If the value cannot be copied the
+    /// builder cannot rebuild its states anymore.
+    fn rebase(&mut self) {
+        self.history.clear();
+        self.undone.clear();
+        if self.initial.is_some() {
+            self.initial = self
+                .builder
+                .clone_boxed()
+                .map(|value| ReplayBase::Snapshot(value, self.navigator.clone()));
+        }
+    }
+
+    /// Whether the snapshots made by `save_state` can be restored by a new builder, i.e. the state
+    /// can be rebuilt from a new value and the history.
+    fn is_restorable(&self) -> bool {
+        matches!(self.initial, Some(ReplayBase::New(_)))
+    }
+
+    /// Take a snapshot of the state of the builder: the values inserted so far and the current
+    /// menu. It can be stored (with the `serde` feature it implements `Serialize` and
+    /// `Deserialize`) and loaded later with `restore_state` or `Builder::from_saved_state`, for
+    /// example for resuming a session after the restart of a bot.
+    ///
+    /// After `replace_field` and `invalidate` the snapshot contains only the inputs applied since
+    /// then, so it can be restored only by this builder, and the storage of `with_persistence` is
+    /// not updated anymore.
+    ///
+    /// The snapshot is never redacted: it contains the texts of the secrets (see
+    /// `builders::SecretBuilder`) as the user inserted them, so it must be stored as carefully as
+    /// the value itself.
+    pub fn save_state(&self) -> SavedState {
+        let applied = self
+            .history
+            .iter()
+            .filter(|entry| entry.outcome == InputOutcome::Applied);
+        SavedState {
+            inputs: applied.clone().map(|entry| entry.input.clone()).collect(),
+            paths: applied
+                .map(|entry| entry.path.iter().map(|f| f.to_string()).collect())
+                .collect(),
+            version: self.config.state_version,
+        }
+    }
+
+    /// Replace the state of the builder with the one of a snapshot made with `save_state`. The
+    /// configuration should be the same of the builder that made the snapshot, since it affects
+    /// the movements between the menus.
+    ///
+    /// The snapshot is restored applying again its inputs to a new value, so the handlers of the
+    /// actions are run again. If one of the inputs is not valid anymore (e.g. the type has changed)
+    /// an error is returned and the state of the builder is left unchanged, `restore_state_lenient`
+    /// skips the invalid inputs instead. Only the builders made with `Buildable::builder` or
+    /// `Builder::from_default` can restore a snapshot.
+    ///
+    /// The snapshots made with an older `BuilderConfig::state_version` are converted first with
+    /// `BuilderConfig::migrations`.
+    pub fn restore_state(&mut self, state: &SavedState) -> Result<(), RestoreError> {
+        let state = self.migrate(state)?;
+        let now = self.config.clock.now();
+        let inputs = state.inputs.iter().map(|input| AppliedInput {
+            at: now,
+            path: vec![],
+            input: input.clone(),
+            outcome: InputOutcome::Applied,
+            sensitive: false,
+        });
+        self.replay(inputs.collect())?;
+        self.undone.clear();
+        Ok(())
+    }
+
+    /// Replace the state of the builder with the one of a snapshot made with `save_state`, even if
+    /// the type has changed since the snapshot has been made (e.g. after a new deployment of a
+    /// bot). Unlike `restore_state` the invalid inputs are skipped: the values of the fields that
+    /// still exist are restored, the ones of the removed fields are dropped, and the new fields are
+    /// left to be inserted. The returned `RestoreReport` lists the dropped inputs.
+    ///
+    /// The inputs meant for the menu of a removed field are recognized using `SavedState::paths`,
+    /// without them only the inputs rejected by the builder are dropped. Like in `restore_state`,
+    /// the migrations are run first.
+    pub fn restore_state_lenient(
+        &mut self,
+        state: &SavedState,
+    ) -> Result<RestoreReport, RestoreError> {
+        let state = self.migrate(state)?;
+        self.replay(vec![])?;
+        self.undone.clear();
+        let now = self.config.clock.now();
+        let paths = Some(&state.paths).filter(|paths| paths.len() == state.inputs.len());
+        let mut report = RestoreReport::default();
+        for (index, input) in state.inputs.iter().enumerate() {
+            let path = self.navigator.current_fields().to_vec();
+            // the menu the user was in before this input, when the snapshot has been made
+            let expected = match index {
+                0 => None,
+                _ => paths.map(|paths| &paths[index - 1]),
+            };
+            let path = match expected {
+                // the previous input has been rejected, so the user didn't leave its menu
+                Some(expected)
+                    if path.len() > expected.len() && path[..expected.len()] == expected[..] =>
+                {
+                    self.navigator.truncate(expected.len());
+                    self.navigator.current_fields().to_vec()
+                }
+                _ => path,
+            };
+            let sensitive = self.is_sensitive(input);
+            let outcome = if expected.is_some_and(|expected| *expected != path) {
+                Err(DropReason::UnknownMenu)
+            } else {
+                match self.choose_inner(input) {
+                    Ok(None) => Ok(()),
+                    Ok(Some(_)) => Err(DropReason::Finalized),
+                    Err(error) => Err(DropReason::Rejected(error)),
+                }
+            };
+            match outcome {
+                Ok(()) => {
+                    report.applied += 1;
+                    let input = self.recorded(input.clone());
+                    self.history.push(AppliedInput {
+                        at: now,
+                        path: self.navigator.current_fields().to_vec(),
+                        input,
+                        outcome: InputOutcome::Applied,
+                        sensitive,
+                    });
+                }
+                Err(reason) => report.dropped.push(DroppedInput {
+                    index,
+                    path,
+                    input: input.clone(),
+                    reason,
+                }),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Convert a snapshot to the current `BuilderConfig::state_version`.
+    fn migrate(&self, state: &SavedState) -> Result<SavedState, RestoreError> {
+        self.config
+            .migrations
+            .migrate(state.clone(), self.config.state_version)
+    }
+
+    /// Apply again the last input reverted with `undo`. Returns `false` if there is nothing to
+    /// redo, i.e. if no input has been undone or another input has been applied after the undo.
+    pub fn redo(&mut self) -> bool {
+        let input = match self.undone.pop() {
+            Some(input) => input,
+            None => return false,
+        };
+        self.pending_error = None;
+        self.notice = None;
+        let sensitive = self.is_sensitive(&input);
+        match self.choose_inner(&input) {
+            Ok(None) => {
+                let input = self.recorded(input);
+                self.log(input, InputOutcome::Applied, sensitive);
+                self.revision += 1;
+                true
+            }
+            _ => {
+                self.undone.clear();
+                false
+            }
+        }
+    }
+
     /// If the process is done try to finalize the process, even if the user hasn't completed the
     /// the selection yet.
+    ///
+    /// On failure all the problems are reported in `FinalizeError::Incomplete`, each one with the
+    /// path of its field (usable with `Builder::goto`).
     pub fn finalize(&self) -> Result<T, FinalizeError> {
+        let mut issues = vec![];
+        collect_issues(self.builder.as_ref(), &mut vec![], &mut issues);
+        if !issues.is_empty() {
+            return Err(FinalizeError::Incomplete { issues });
+        }
         self.builder
             .get_value_any()
-            .ok_or(FinalizeError::MissingField)
             .map(|r| *r.downcast::<T>().unwrap())
+            .ok_or_else(|| FinalizeError::Incomplete {
+                issues: vec![FinalizeIssue {
+                    path: vec![],
+                    reason: FinalizeIssueReason::Unbuildable,
+                }],
+            })
+    }
+
+    /// Finalize the builder like `finalize`, bundling the value with how it has been collected: the
+    /// transcript of the inputs, some timing statistics and the final tree of the state. With the
+    /// `serde` feature the bundle can be serialized, for archiving it.
+    ///
+    /// The transcript and the tree are redacted like `history` and `to_node` if the redaction is
+    /// active (see `BuilderConfig::redaction`), the value is not.
+    pub fn export(&self) -> Result<Export<T>, FinalizeError> {
+        let value = self.finalize()?;
+        let transcript: Vec<_> = self
+            .history()
+            .iter()
+            .map(|entry| ExportedInput {
+                elapsed: entry.at.duration_since(self.started),
+                path: entry.path.iter().map(|f| f.to_string()).collect(),
+                input: entry.input.clone(),
+                error: match &entry.outcome {
+                    InputOutcome::Rejected(error) => Some(error.to_string()),
+                    InputOutcome::Applied | InputOutcome::Finalized => None,
+                },
+            })
+            .collect();
+        let stats = ExportStats {
+            duration: self.config.clock.now().duration_since(self.started),
+            inputs: transcript.len(),
+            rejected: transcript.iter().filter(|i| i.error.is_some()).count(),
+        };
+        Ok(Export {
+            value,
+            transcript,
+            stats,
+            node: self.to_node(),
+        })
     }
 
     /// Check if all the fields have been set and the call to `finalize()` will be successful.
     pub fn is_done(&self) -> bool {
-        self.builder.get_value_any().is_some()
+        self.builder.is_complete()
     }
 
-    /// Return the tree structure of the `Builder` internal state.
+    /// Return the tree structure of the `Builder` internal state. The values are redacted if the
+    /// redaction is active (see `BuilderConfig::redaction`).
     pub fn to_node(&self) -> Node {
-        self.builder.to_node()
+        self.redaction().redact_node(self.builder.to_node())
+    }
+
+    /// Return a compact single-line rendering of the current state, at most `max_len` characters
+    /// long, for example for the titles of the sessions in a list. See `Node::summary`.
+    pub fn summary(&self, max_len: usize) -> String {
+        self.to_node().summary(max_len)
+    }
+
+    /// Return the JSON Schema of the type being built, see the `schema` module.
+    pub fn to_schema(&self) -> Schema {
+        self.builder.schema()
+    }
+
+    /// Return the tree structure of the `Builder` internal state, omitting the composite nodes
+    /// deeper than `depth` (they are replaced with `Field::Elided`). This is useful for rendering
+    /// only the top of a huge state.
+    pub fn to_node_pruned(&self, depth: usize) -> Node {
+        self.redaction()
+            .redact_node(self.builder.to_node_pruned(depth))
+    }
+
+    /// Walk the tree structure of the `Builder` internal state calling the `visitor`, without
+    /// building the whole `Node` tree. The subtrees skipped by the visitor are not computed at all,
+    /// which makes rendering only the visible part of huge collections cheap.
+    pub fn visit_nodes(&self, visitor: &mut dyn NodeVisitor) {
+        let redaction = self.redaction();
+        if redaction.is_active() {
+            let mut visitor = RedactingVisitor {
+                redaction,
+                inner: visitor,
+            };
+            self.builder.visit_nodes(&mut visitor)
+        } else {
+            self.builder.visit_nodes(visitor)
+        }
+    }
+
+    /// The redaction of the values in effect for this builder: the one of
+    /// `BuilderConfig::redaction`, or the global one.
+    pub fn redaction(&self) -> Redaction {
+        self.config.redaction()
+    }
+
+    /// Return a read-only view over the current state of the `Builder`, for inspecting the fields
+    /// that have been set so far.
+    pub fn partial_state(&self) -> PartialState<'_> {
+        PartialState::new(self.builder.as_ref())
+    }
+
+    /// Replace the builder of the field at `path` (e.g. `["server", "port"]`) with the provided
+    /// one, returning the previous builder. An empty path replaces the whole builder. If the user
+    /// is inside the replaced field they are moved to the menu that contains it. The history of
+    /// `undo` is cleared, and `undo` cannot revert the replacement (see `undo`).
+    ///
+    /// The new builder must build a value of the same type of the field, otherwise `finalize` will
+    /// panic.
+    pub fn replace_field<S: AsRef<str>>(
+        &mut self,
+        path: &[S],
+        value: Box<dyn BuildableValue>,
+    ) -> Result<Box<dyn BuildableValue>, ReplaceFieldError> {
+        let previous = std::mem::replace(self.field_slot(path)?, value);
+        self.revision += 1;
+        if let Some((_, parents)) = path.split_last() {
+            refresh_path(self.builder.as_mut(), parents);
+        }
+        let inside = path.len() <= self.navigator.current_fields().len()
+            && path
+                .iter()
+                .zip(self.navigator.current_fields().iter())
+                .all(|(a, b)| a.as_ref() == b);
+        if inside {
+            self.navigator.truncate(path.len().saturating_sub(1));
+            self.open_group = None;
+        }
+        self.rebase();
+        Ok(previous)
+    }
+
+    /// Mark the field at `path` (e.g. `["plan", "quota"]`) as to be confirmed again by the user,
+    /// for example after an external change that may have made the answer wrong. The field keeps
+    /// its value, suggested as the default, but it's incomplete until the user selects "Keep"
+    /// (`builders::KEEP_ID`) in its menu or inserts a new value. An empty path invalidates the
+    /// whole value.
+    ///
+    /// The field is wrapped in a `builders::StaleBuilder`, so like with `replace_field` the history
+    /// of `undo` is cleared: the following inputs can be undone, but the invalidation and the
+    /// inputs before it cannot.
+    pub fn invalidate<S: AsRef<str>>(&mut self, path: &[S]) -> Result<(), ReplaceFieldError> {
+        let slot = self.field_slot(path)?;
+        let already_stale = slot
+            .get_options(&[])
+            .choices
+            .iter()
+            .any(|c| c.choice_id == KEEP_ID);
+        if !already_stale {
+            // any builder works as a placeholder, it's replaced right away
+            let placeholder = ConfirmBuilder::new(Default::default());
+            let value = std::mem::replace(slot, Box::new(placeholder));
+            *slot = Box::new(StaleBuilder::new(value));
+        }
+        self.revision += 1;
+        if let Some((_, parents)) = path.split_last() {
+            refresh_path(self.builder.as_mut(), parents);
+        }
+        self.rebase();
+        Ok(())
+    }
+
+    /// The builder of the field at `path`, following `get_child_mut` from the whole value.
+    fn field_slot<S: AsRef<str>>(
+        &mut self,
+        path: &[S],
+    ) -> Result<&mut Box<dyn BuildableValue>, ReplaceFieldError> {
+        let mut slot = &mut self.builder;
+        for field in path {
+            slot = slot.get_child_mut(field.as_ref()).ok_or_else(|| {
+                ReplaceFieldError::UnknownField {
+                    field: field.as_ref().to_string(),
+                }
+            })?;
+        }
+        Ok(slot)
+    }
+
+    /// Move the user directly to the menu of the field at `path` (e.g. `["server", "port"]`), as
+    /// if they selected all the fields along the way from the main menu. An empty path moves the
+    /// user to the main menu. This is useful for the frontends that show the whole tree of
+    /// `to_node` and let the user click on a field to edit it.
+    ///
+    /// Only the fields of the nested structs can be reached this way, the ones inside lists,
+    /// options and enums have to be navigated interactively. If the path is not valid an error is
+    /// returned and the user stays in the current menu. The fields that ask to be entered first
+    /// and the skipped menus (see `BuilderConfig::skip_single_field_menus`) are handled like with
+    /// `choose`.
+    ///
+    /// The movement is recorded in the history as a choice with `GOTO_ID_PREFIX`, so it's applied
+    /// again by `undo`, `restore_state` and the other replays.
+    pub fn goto<S: AsRef<str>>(&mut self, path: &[S]) -> Result<(), GotoError> {
+        let path: Vec<_> = path.iter().map(|field| field.as_ref()).collect();
+        self.goto_path(&path)?;
+        // recorded like an input, so that the history can be applied again (e.g. by `undo`)
+        self.record(
+            Input::choice(format!("{}{}", GOTO_ID_PREFIX, path.join("."))),
+            false,
+        );
+        self.revision += 1;
+        self.autosave(false);
+        Ok(())
+    }
+
+    /// Move the user from the current menu to the menu of the field at `path`, see `goto`.
+    fn goto_path(&mut self, path: &[&str]) -> Result<(), GotoError> {
+        let unknown = |field: &str| GotoError::UnknownField {
+            field: field.to_string(),
+        };
+        let mut value = self.builder.as_ref();
+        for field in path {
+            value = value.get_child(field).ok_or_else(|| unknown(field))?;
+        }
+        let previous = std::mem::take(&mut self.navigator);
+        for field in path {
+            if self.navigator.enter(self.builder.as_mut(), field).is_err() {
+                self.navigator = previous;
+                return Err(unknown(field));
+            }
+        }
+        self.pending_error = None;
+        self.open_group = None;
+        self.search = None;
+        self.auto_enter(MenuEntry::Entered);
+        Ok(())
+    }
+
+    /// List all the fields reachable from the main menu, descending into the nested structs. The
+    /// values with their own menus that cannot be enumerated (e.g. lists and enums with fields) are
+    /// reported as a single path with `InputKind::Menu`.
+    pub fn paths(&self) -> Vec<PathInfo> {
+        let mut paths = vec![];
+        collect_paths(self.builder.as_ref(), &mut vec![], &mut paths);
+        paths
+    }
+}
+
+/// Refresh the values along the path, from the deepest one to `value`.
+fn refresh_path<S: AsRef<str>>(value: &mut dyn BuildableValue, path: &[S]) {
+    if let Some((field, rest)) = path.split_first() {
+        if let Some(child) = value.get_child_mut(field.as_ref()) {
+            refresh_path(child.as_mut(), rest);
+        }
+    }
+    value.refresh();
+}
+
+/// Insert the JSON value in the subfield of `value` at `path`, selecting the choices and entering
+/// the subfields like the user would. The invalid parts of the document are skipped.
+#[cfg(feature = "serde")]
+fn load_json_value(value: &mut dyn BuildableValue, path: &mut Vec<Id>, json: &serde_json::Value) {
+    use serde_json::Value;
+
+    let options = value.get_options(path);
+    let subfields = value.get_subfields(path);
+    let has_choice = |id: &str| options.choices.iter().any(|c| c.choice_id == id);
+    let text = match json {
+        Value::String(text) => text.clone(),
+        json => json.to_string(),
+    };
+    match json {
+        // the `None` of an `Option`
+        Value::Null if has_choice("__remove") && !subfields.iter().any(|f| f == "__remove") => {
+            let _ = value.apply(&Input::choice("__remove"), path);
+        }
+        Value::Null => {}
+        _ if options.text_input => {
+            let _ = value.apply(&Input::text(text), path);
+        }
+        Value::Array(items) if has_choice("__new") => {
+            for item in items {
+                load_json_field(value, path, "__new", item);
+            }
+        }
+        // a map: every entry is a new item with a key and a value
+        Value::Object(entries) if has_choice("__new") => {
+            for (key, item) in entries {
+                if value.apply(&Input::choice("__new"), path).is_err() {
+                    continue;
+                }
+                path.push("__new".into());
+                path.push("key".into());
+                let _ = value.apply(&Input::text(key.clone()), path);
+                path.pop();
+                load_json_field(value, path, "value", item);
+                path.pop();
+            }
+        }
+        Value::Object(entries) if entries.keys().any(|k| subfields.iter().any(|f| f == k)) => {
+            for (key, item) in entries {
+                load_json_field(value, path, key, item);
+            }
+        }
+        // a variant of an enum with `inline_variant_fields`: its fields are in the same menu
+        Value::Object(entries) if entries.len() == 1 && entries.keys().all(|k| has_choice(k)) => {
+            let (key, item) = entries.iter().next().unwrap();
+            if value.apply(&Input::choice(key.clone()), path).is_ok() {
+                load_json_value(value, path, item);
+            }
+        }
+        Value::Bool(_) | Value::Number(_) | Value::String(_) if has_choice(&text) => {
+            let _ = value.apply(&Input::choice(text), path);
+        }
+        // a value wrapped in another one, like the `Some` of an `Option`
+        _ if subfields.len() == 1 => {
+            load_json_field(value, path, &subfields[0], json);
+        }
+        _ => {}
+    }
+}
+
+/// Enter the subfield `field` of the menu at `path`, like `Navigator::enter`, and insert the JSON
+/// value in it.
+#[cfg(feature = "serde")]
+fn load_json_field(
+    value: &mut dyn BuildableValue,
+    path: &mut Vec<Id>,
+    field: &str,
+    json: &serde_json::Value,
+) {
+    let field = match value.subfield(path, field) {
+        Some(field) => field,
+        None => return,
+    };
+    if value.apply(&Input::choice(field.as_str()), path).is_err() {
+        return;
+    }
+    path.push(field);
+    load_json_value(value, path, json);
+    path.pop();
+}
+
+/// The maximum number of fields shown as the result of a search.
+const MAX_SEARCH_RESULTS: usize = 10;
+
+/// Filter the paths whose name or identifiers fuzzy-match the text, i.e. contain all its chars in
+/// the same order ignoring the case. The paths containing the text as is come first.
+fn search_paths(paths: Vec<PathInfo>, text: &str) -> Vec<PathInfo> {
+    let text = text.trim().to_lowercase();
+    let fuzzy = |haystack: &str| {
+        let mut haystack = haystack.chars();
+        text.chars().all(|c| haystack.any(|h| h == c))
+    };
+    let mut found: Vec<_> = paths
+        .into_iter()
+        .filter_map(|info| {
+            let haystacks = [info.name.to_lowercase(), info.path.join(".").to_lowercase()];
+            if haystacks.iter().any(|h| h.contains(&text)) {
+                Some((0, info))
+            } else if haystacks.iter().any(|h| fuzzy(h)) {
+                Some((1, info))
+            } else {
+                None
+            }
+        })
+        .collect();
+    found.sort_by_key(|(rank, _)| *rank);
+    found.into_iter().map(|(_, info)| info).collect()
+}
+
+/// The text of a group of choices: the range of the initials of the choices (e.g. "A–F"), or the
+/// range of their texts if they all start with the same letter.
+fn group_label(group: &[Choice]) -> String {
+    let first = group.first().map(|c| c.text.as_str()).unwrap_or_default();
+    let last = group.last().map(|c| c.text.as_str()).unwrap_or_default();
+    let initial = |text: &str| {
+        text.chars()
+            .next()
+            .unwrap_or(' ')
+            .to_uppercase()
+            .to_string()
+    };
+    if group.len() == 1 {
+        first.to_string()
+    } else if initial(first) != initial(last) {
+        format!("{}–{}", initial(first), initial(last))
+    } else {
+        format!("{}–{}", first, last)
+    }
+}
+
+/// Whether some of the subfields of the value can be inspected with `get_child`.
+fn has_children(value: &dyn BuildableValue) -> bool {
+    value
+        .get_subfields(&[])
+        .iter()
+        .any(|name| value.get_child(name).is_some())
+}
+
+/// Add to `paths` all the paths reachable from `value`, which is located at `prefix`.
+fn collect_paths(value: &dyn BuildableValue, prefix: &mut Vec<String>, paths: &mut Vec<PathInfo>) {
+    // the display names of the fields, in the same order of the subfields
+    let names: Vec<_> = match value.to_node() {
+        Node::Composite(_, fields) => fields
+            .into_iter()
+            .map(|field| match field {
+                FieldKind::Named(name, _) => Some(name),
+                FieldKind::Unnamed(_) => None,
+            })
+            .collect(),
+        Node::Leaf(_) => vec![],
+    };
+    for (index, field) in value.get_subfields(&[]).into_iter().enumerate() {
+        let child = match value.get_child(&field) {
+            Some(child) => child,
+            None => continue,
+        };
+        prefix.push(field.to_string());
+        if has_children(child) {
+            collect_paths(child, prefix, paths);
+        } else {
+            let name = names
+                .get(index)
+                .cloned()
+                .flatten()
+                .unwrap_or_else(|| field.to_string());
+            paths.push(PathInfo {
+                path: prefix.clone(),
+                name,
+                kind: InputKind::of(child),
+                required: !child.is_complete(),
+            });
+        }
+        prefix.pop();
+    }
+}
+
+/// Collect the problems that prevent `value` from being built, see `Builder::finalize`. The
+/// problems of a value are reported on its deepest subfields, the value itself is reported only if
+/// none of them has problems.
+fn collect_issues(
+    value: &dyn BuildableValue,
+    prefix: &mut Vec<String>,
+    issues: &mut Vec<FinalizeIssue>,
+) {
+    if let Some(error) = value.validation_error() {
+        issues.push(FinalizeIssue {
+            path: prefix.clone(),
+            reason: FinalizeIssueReason::Invalid(error),
+        });
+        return;
+    }
+    let before = issues.len();
+    for field in value.get_subfields(&[]) {
+        if let Some(child) = value.get_child(&field) {
+            prefix.push(field.to_string());
+            collect_issues(child, prefix, issues);
+            prefix.pop();
+        }
+    }
+    if issues.len() == before && value.get_value_any().is_none() {
+        let reason = if value.is_complete() {
+            FinalizeIssueReason::Unbuildable
+        } else {
+            FinalizeIssueReason::Missing
+        };
+        issues.push(FinalizeIssue {
+            path: prefix.clone(),
+            reason,
+        });
+    }
+}
+
+/// The description of a field reachable from the main menu of a `Builder`, see `Builder::paths`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct PathInfo {
+    /// The identifiers of the fields to select, from the main menu, for reaching this field.
+    pub path: Vec<String>,
+    /// The name of the field to show to the user.
+    pub name: String,
+    /// The kind of input the field accepts.
+    pub kind: InputKind,
+    /// Whether the field is still missing, i.e. the `Builder` cannot be finalized until it's set.
+    /// In a new `Builder` only the fields without a default value are required.
+    pub required: bool,
+}
+
+/// The kind of input accepted by a field, see `PathInfo`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum InputKind {
+    /// The field accepts only textual inputs.
+    Text,
+    /// The field accepts only one of these choices.
+    Choice(Vec<String>),
+    /// The field accepts a textual input or one of these choices.
+    TextOrChoice(Vec<String>),
+    /// The field has its own menus (e.g. a list), it has to be navigated interactively.
+    Menu,
+}
+
+impl InputKind {
+    /// The kind of input accepted by the main menu of the value.
+    fn of(value: &dyn BuildableValue) -> InputKind {
+        if !value.get_subfields(&[]).is_empty() {
+            return InputKind::Menu;
+        }
+        let options = value.get_options(&[]);
+        let choices: Vec<_> = options
+            .choices
+            .into_iter()
+            .map(|c| c.choice_id.into())
+            .collect();
+        match (options.text_input, choices.is_empty()) {
+            (true, true) => InputKind::Text,
+            (true, false) => InputKind::TextOrChoice(choices),
+            (false, _) => InputKind::Choice(choices),
+        }
     }
 }
 
 /// The options that the user has for the next choice in the `Builder`.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Default, Eq, PartialEq)]
 pub struct Options {
     /// A textual message with the query to show to the user.
     pub query: String,
@@ -339,23 +2873,63 @@ pub struct Options {
     pub text_input: bool,
     /// The list of all the choices the user can use.
     pub choices: Vec<Choice>,
+    /// A transient feedback message to show to the user, for example the result of the last
+    /// action (e.g. "Value saved" or "Connection test OK").
+    pub notice: Option<String>,
+    /// A non-blocking validation warning about the current value (see `Validation::Warning`).
+    pub warning: Option<String>,
+    /// Some likely values for the text input, for the frontends that support autocompletion. The
+    /// user can still insert any other text.
+    pub suggestions: Vec<String>,
+    /// A short description of the values accepted by the text input (e.g. "Between 0 and 100"),
+    /// for the frontends that show it next to the input.
+    pub hint: Option<String>,
+    /// The key of the query in the translations, set with `#[ibuilder(prompt_key = "...")]`. The
+    /// `Builder` replaces the query with its translation when `BuilderConfig::localizer` is set.
+    pub prompt_key: Option<String>,
+    /// A secondary explanation of the current menu, set with `#[ibuilder(help = "...")]` on the
+    /// field or the variant being edited, for the frontends that show it under the query.
+    pub help: Option<String>,
+    /// The hints for aligning the menu, set when `BuilderConfig::layout_hints` is enabled.
+    pub layout: Option<Layout>,
+    /// The `choice_id` of the choice to preselect, for the frontends that highlight it or select
+    /// it with the Enter key: "Done" when the value is complete, otherwise the current value of
+    /// the field (e.g. the default variant of an enum), if any.
+    pub default_choice: Option<Id>,
+    /// Whether the value of the menu is secret (e.g. a password), set with `#[ibuilder(secret)]`:
+    /// the frontends should not echo the text inserted by the user, and the value is masked in the
+    /// `Node`s.
+    pub sensitive: bool,
 }
 
 /// A single choice that the user can select.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Default, Eq, PartialEq)]
 pub struct Choice {
     /// Identifier of the choice, may not be shown to the user. Its value has to be used as the
     /// value in `Input::Choice`.
-    pub choice_id: String,
+    pub choice_id: Id,
     /// Textual message to show to the user about this choice.
     pub text: String,
     /// This choice probably needs to be selected sooner or later because there is a field inside
     /// that is missing.
     pub needs_action: bool,
+    /// A machine value associated with this choice, for example the numeric code of a variant of
+    /// a protocol-style enum. When present the choice can also be selected sending this value as
+    /// `Input::Text`.
+    pub value: Option<String>,
+    /// The key identifying this choice in the translations of the frontend, made of the name of
+    /// the type and the identifier of the field or of the variant in snake case (e.g.
+    /// `person.full_name` or `shape.circle`). It's `None` for the builtin choices, like the ones
+    /// for editing a list.
+    pub message_key: Option<String>,
+    /// A secondary explanation of this choice, set with `#[ibuilder(help = "...")]` on the field or
+    /// the variant, for the frontends that show it under the text of the choice.
+    pub help: Option<String>,
 }
 
 /// An input of the user to the `Builder`.
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Input {
     /// The user inserted some raw textual content. Can be used only if the `text_input` field of
     /// the last `Options` was set to `true`.
@@ -379,7 +2953,7 @@ impl Input {
 }
 
 /// The `Input` provided to `Builder::choose` was is invalid.
-#[derive(Debug, Fail, Eq, PartialEq)]
+#[derive(Debug, Clone, Fail, Eq, PartialEq)]
 pub enum ChooseError {
     /// The textual input is not valid.
     #[fail(display = "Invalid input: {}", error)]
@@ -390,12 +2964,105 @@ pub enum ChooseError {
     /// Provided an `Input::Choice` with an invalid id.
     #[fail(display = "Unexpected choice")]
     UnexpectedChoice,
+    /// The input has been rejected because it exceeds the limits of `BuilderConfig`, the state of
+    /// the builder is unchanged.
+    #[fail(display = "Too many inputs, slow down")]
+    RateLimited,
+    /// The input of `Builder::choose_if` has been rejected because the state of the builder has
+    /// been changed in the meantime, and now it's at this revision.
+    #[fail(display = "The value has been changed in the meantime")]
+    Conflict { revision: u64 },
+    /// The input would make an item of a set equal to another one, the item is unchanged.
+    #[fail(display = "The value is already in the set")]
+    DuplicateValue,
+    /// The textual input is longer than `BuilderConfig::max_text_len` characters.
+    #[fail(display = "The text is too long, the limit is {} characters", max)]
+    TextTooLong { max: usize },
 }
 
 /// The finalization of the result failed.
 #[derive(Debug, Fail, Eq, PartialEq)]
 pub enum FinalizeError {
-    /// One or more fields were still missing.
+    /// One or more fields were still missing, returned by the conversion of the drafts.
     #[fail(display = "There is at least a missing field")]
     MissingField,
+    /// Some fields are missing or invalid, returned by `Builder::finalize` with all the problems.
+    #[fail(display = "Some fields are missing or invalid")]
+    Incomplete { issues: Vec<FinalizeIssue> },
+}
+
+/// A problem of a field that prevents the `Builder` from being finalized, see
+/// `FinalizeError::Incomplete`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FinalizeIssue {
+    /// The identifiers of the fields to select, from the main menu, for reaching the field. It's
+    /// empty for the whole value.
+    pub path: Vec<String>,
+    /// What's wrong with the field.
+    pub reason: FinalizeIssueReason,
+}
+
+impl std::fmt::Display for FinalizeIssue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "The value")?;
+        } else {
+            write!(f, "{}", self.path.join("."))?;
+        }
+        match &self.reason {
+            FinalizeIssueReason::Missing => write!(f, " is missing"),
+            FinalizeIssueReason::Invalid(error) => write!(f, " is not valid: {}", error),
+            FinalizeIssueReason::Unbuildable => write!(f, " cannot be built"),
+        }
+    }
+}
+
+/// What's wrong with a field, see `FinalizeIssue`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum FinalizeIssueReason {
+    /// The field has not been set yet.
+    Missing,
+    /// The value of the field doesn't pass the validators, with their error.
+    Invalid(String),
+    /// All the subfields are set but the value cannot be built from them (e.g. a custom builder
+    /// failed).
+    Unbuildable,
+}
+
+/// The restore of a `SavedState` failed.
+#[derive(Debug, Fail, Eq, PartialEq)]
+pub enum RestoreError {
+    /// The builder cannot restore a snapshot, since it has not been made with
+    /// `Buildable::builder`.
+    #[fail(display = "The builder cannot restore a snapshot")]
+    Unsupported,
+    /// The input at this index of the snapshot is not valid anymore.
+    #[fail(display = "Invalid input {}: {}", index, error)]
+    InvalidInput { index: usize, error: ChooseError },
+    /// The input at this index of the snapshot finalized the builder.
+    #[fail(display = "Input {} finalized the builder", index)]
+    Finalized { index: usize },
+    /// The snapshot has been made with a `BuilderConfig::state_version` newer than the current
+    /// one.
+    #[fail(display = "The snapshot has the unknown version {}", version)]
+    NewerVersion { version: u32 },
+    /// There is no migration from this version of the snapshot to the next one.
+    #[fail(display = "Missing the migration from version {}", from)]
+    MissingMigration { from: u32 },
+}
+
+/// The move of the user to a field with `Builder::goto` failed.
+#[derive(Debug, Fail, Eq, PartialEq)]
+pub enum GotoError {
+    /// The path doesn't point to a field of the nested structs.
+    #[fail(display = "Unknown field: {}", field)]
+    UnknownField { field: String },
+}
+
+/// The replacement of the builder of a field failed.
+#[derive(Debug, Fail, Eq, PartialEq)]
+pub enum ReplaceFieldError {
+    /// The path doesn't point to a field that can be replaced.
+    #[fail(display = "Unknown field: {}", field)]
+    UnknownField { field: String },
 }
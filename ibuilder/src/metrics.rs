@@ -0,0 +1,91 @@
+//! Hooks for collecting analytics about the interaction of the user with a `Builder`.
+//!
+//! A `MetricsSink` attached to a `Builder` with `Builder::with_metrics` is notified about the
+//! inputs the user makes, the errors, and how long the user spends in each menu. This allows, for
+//! example, to export the data to a monitoring system and find out where the users abandon a form.
+//!
+//! The `Counters` sink just accumulates the values in memory.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crate::{ChooseError, Id, Input};
+
+/// A receiver of the events of a `Builder`. All the methods have an empty default implementation.
+///
+/// `fields` is always the path of the menu the event refers to, empty for the main menu.
+pub trait MetricsSink {
+    /// An input has been applied successfully in the menu at `fields`.
+    fn input(&mut self, _fields: &[Id], _input: &Input) {}
+
+    /// An input in the menu at `fields` has been rejected with `error`.
+    fn error(&mut self, _fields: &[Id], _error: &ChooseError) {}
+
+    /// The user left the menu at `fields` after spending `duration` in it.
+    fn dwell(&mut self, _fields: &[Id], _duration: Duration) {}
+
+    /// The user finalized the value, `duration` after the creation of the `Builder`.
+    fn finalized(&mut self, _duration: Duration) {}
+}
+
+/// Share a sink between the `Builder` and the rest of the application, for reading the collected
+/// values while the `Builder` is still alive.
+impl<M: MetricsSink> MetricsSink for Rc<RefCell<M>> {
+    fn input(&mut self, fields: &[Id], input: &Input) {
+        self.borrow_mut().input(fields, input)
+    }
+
+    fn error(&mut self, fields: &[Id], error: &ChooseError) {
+        self.borrow_mut().error(fields, error)
+    }
+
+    fn dwell(&mut self, fields: &[Id], duration: Duration) {
+        self.borrow_mut().dwell(fields, duration)
+    }
+
+    fn finalized(&mut self, duration: Duration) {
+        self.borrow_mut().finalized(duration)
+    }
+}
+
+/// A `MetricsSink` that keeps simple counters in memory.
+///
+/// The menus are identified by their path joined with `.` (the main menu is the empty string).
+#[derive(Debug, Clone, Default)]
+pub struct Counters {
+    /// The number of inputs applied successfully.
+    pub inputs: usize,
+    /// The number of rejected inputs, by menu.
+    pub errors: HashMap<String, usize>,
+    /// The total time spent in each menu.
+    pub dwell: HashMap<String, Duration>,
+    /// The total duration of the process, if the value has been finalized.
+    pub finalized: Option<Duration>,
+}
+
+impl Counters {
+    /// The total number of rejected inputs.
+    pub fn total_errors(&self) -> usize {
+        self.errors.values().sum()
+    }
+}
+
+impl MetricsSink for Counters {
+    fn input(&mut self, _fields: &[Id], _input: &Input) {
+        self.inputs += 1;
+    }
+
+    fn error(&mut self, fields: &[Id], _error: &ChooseError) {
+        *self.errors.entry(fields.join(".")).or_default() += 1;
+    }
+
+    fn dwell(&mut self, fields: &[Id], duration: Duration) {
+        *self.dwell.entry(fields.join(".")).or_default() += duration;
+    }
+
+    fn finalized(&mut self, duration: Duration) {
+        self.finalized = Some(duration);
+    }
+}
@@ -0,0 +1,141 @@
+//! The navigation of the user between the menus of a `BuildableValue`.
+//!
+//! The position of the user is the list of the fields selected from the main menu, the same list
+//! that is passed as `current_fields` to the methods of `BuildableValue`. The `Navigator` keeps
+//! it and moves the user after each input:
+//!
+//! - a choice that is a subfield of the current menu (see `BuildableValue::get_subfields`) enters
+//!   its menu;
+//! - any other valid input is applied to the current field, then the user returns to the parent
+//!   menu, unless the value asks to stay (see `BuildableValue::stay_after_apply`);
+//! - going back moves the user to the parent menu without changing the value.
+//!
+//! The inputs are checked against the `Options` of the current menu before being applied: in a
+//! menu without text input a choice that is not listed is rejected with
+//! `ChooseError::UnexpectedChoice`, and so is a text with `ChooseError::UnexpectedText`. This way
+//! the implementors of `BuildableValue` don't have to handle the inputs they didn't offer. The
+//! choices of the menus with text input are considered suggestions (e.g. the matches of a search),
+//! so the value is responsible for checking them.
+
+use crate::{BuildableValue, ChooseError, Id, Input, MenuEntry};
+
+/// The position of the user in the menus of a `BuildableValue`.
+#[derive(Debug, Clone, Default)]
+pub struct Navigator {
+    current_fields: Vec<Id>,
+}
+
+/// How the position of the user changed after an input applied with `Navigator::apply`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Transition {
+    /// The user entered the menu of the selected subfield.
+    Entered,
+    /// The value has been changed and the user is still in the same menu.
+    Stayed,
+    /// The value has been changed and the user returned to the parent menu, leaving the field
+    /// with this name.
+    Returned(Id),
+}
+
+impl Navigator {
+    /// Make a new `Navigator` in the main menu.
+    pub fn new() -> Navigator {
+        Default::default()
+    }
+
+    /// The fields selected from the main menu to reach the current one.
+    pub fn current_fields(&self) -> &[Id] {
+        &self.current_fields
+    }
+
+    /// Whether the user is in the main menu.
+    pub fn is_main_menu(&self) -> bool {
+        self.current_fields.is_empty()
+    }
+
+    /// Check that the input is accepted by the current menu, apply it to the value and move the
+    /// user accordingly.
+    pub fn apply(
+        &mut self,
+        value: &mut dyn BuildableValue,
+        input: &Input,
+    ) -> Result<Transition, ChooseError> {
+        self.apply_recorded(value, input)
+            .map(|(transition, _)| transition)
+    }
+
+    /// Like `apply`, also returning the input to record in place of the applied one, if the value
+    /// asks for it (see `BuildableValue::recorded_input`).
+    pub fn apply_recorded(
+        &mut self,
+        value: &mut dyn BuildableValue,
+        input: &Input,
+    ) -> Result<(Transition, Option<Input>), ChooseError> {
+        let options = value.get_options(&self.current_fields);
+        if !options.text_input {
+            match input {
+                Input::Choice(data) => {
+                    if !options.choices.iter().any(|c| &c.choice_id == data) {
+                        return Err(ChooseError::UnexpectedChoice);
+                    }
+                }
+                Input::Text(_) => return Err(ChooseError::UnexpectedText),
+            }
+        }
+        // only a choice can select a subfield
+        let subfield = match input {
+            Input::Choice(data) => value.subfield(&self.current_fields, data),
+            _ => None,
+        };
+        value.apply(input, &self.current_fields)?;
+        if let Some(field) = subfield {
+            self.current_fields.push(field);
+            return Ok((Transition::Entered, None));
+        }
+        let recorded = value.recorded_input(input, &self.current_fields);
+        if value.stay_after_apply(&self.current_fields) {
+            return Ok((Transition::Stayed, recorded));
+        }
+        match self.current_fields.pop() {
+            Some(field) => Ok((Transition::Returned(field), recorded)),
+            None => Ok((Transition::Stayed, recorded)),
+        }
+    }
+
+    /// Select the subfield of the current menu with this name, entering its menu.
+    pub fn enter(
+        &mut self,
+        value: &mut dyn BuildableValue,
+        field: &str,
+    ) -> Result<(), ChooseError> {
+        let field = value
+            .subfield(&self.current_fields, field)
+            .ok_or(ChooseError::UnexpectedChoice)?;
+        value.apply(&Input::Choice(field.to_string()), &self.current_fields)?;
+        self.current_fields.push(field);
+        Ok(())
+    }
+
+    /// Move the user to the parent menu, returning the name of the field that has been left.
+    pub fn back(&mut self) -> Option<Id> {
+        self.current_fields.pop()
+    }
+
+    /// Move the user up to the menu at the provided depth, where 0 is the main menu.
+    pub fn truncate(&mut self, depth: usize) {
+        self.current_fields.truncate(depth);
+    }
+
+    /// Move the user inside the subfields the current menu asks to enter first, if any (see
+    /// `BuildableValue::auto_enter`).
+    pub fn auto_enter(&mut self, value: &dyn BuildableValue, entry: MenuEntry) {
+        let mut entry = entry;
+        while let Some(field) = value.auto_enter(&self.current_fields, entry) {
+            entry = MenuEntry::Entered;
+            match value.subfield(&self.current_fields, &field) {
+                Some(field) => self.current_fields.push(field),
+                None => break,
+            }
+        }
+    }
+}
@@ -3,9 +3,13 @@
 //! The `Builder` exposes the `to_node()` method that returns a tree-like structures with all the
 //! visible fields of the builder. This structure can be used for pretty-printing the internal
 //! builder state is a customized manner.
+//!
+//! For huge states `Builder::visit_nodes()` walks the same tree calling a `NodeVisitor`, without
+//! building it, and allows skipping the parts that are not going to be rendered.
 
 /// A `Node` of the tree, it represents an item that can be interacted with.
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Node {
     /// The `Node` is a leaf node of the tree, i.e. it doesn't contains subfields, just a value.
     Leaf(Field),
@@ -16,7 +20,8 @@ pub enum Node {
 
 /// A field of a composite structure. The field may be named (like in `struct`s), or be unnamed
 /// (like in `Vec`).
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum FieldKind {
     /// The field is named, the first item is the name of the field, the second is the inner node of
     /// it.
@@ -26,10 +31,223 @@ pub enum FieldKind {
 }
 
 /// A leaf field of the tree structure.
-#[derive(Debug)]
+#[derive(Debug, Eq, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Field {
     /// The field is valid and the textual representation of it is provided.
     String(String),
+    /// The field is valid but it has a validation warning. The first item is the textual
+    /// representation of the value, the second is the warning message.
+    Warning(String, String),
     /// The field is not present yet.
     Missing,
+    /// The content of the field has been omitted because it's too deep in the tree, see
+    /// `Builder::to_node_pruned`.
+    Elided,
+}
+
+/// The number of items of a list (or of a map) and how many of them are still incomplete, see
+/// `Node::list_stats`. It's shown like `3 items, 1 incomplete`.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub struct ListStats {
+    /// The number of items.
+    pub items: usize,
+    /// The number of items with at least a missing field.
+    pub incomplete: usize,
+}
+
+impl std::fmt::Display for ListStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} item", self.items)?;
+        if self.items != 1 {
+            write!(f, "s")?;
+        }
+        if self.incomplete > 0 {
+            write!(f, ", {} incomplete", self.incomplete)?;
+        }
+        Ok(())
+    }
+}
+
+/// A visitor of the tree structure of a value, see `Builder::visit_nodes`. The methods are called
+/// in the same order the nodes would appear in the `Node` tree.
+pub trait NodeVisitor {
+    /// A composite node with the provided name is being visited. Return `false` for skipping all
+    /// its fields, in that case `leave_composite` is not called.
+    fn enter_composite(&mut self, name: &str) -> bool;
+
+    /// All the fields of the last entered composite node have been visited.
+    fn leave_composite(&mut self) {}
+
+    /// A field of the current composite node is being visited, `name` is `None` for the unnamed
+    /// fields. Return `false` for skipping it.
+    fn enter_field(&mut self, _name: Option<&str>) -> bool {
+        true
+    }
+
+    /// A leaf node is being visited.
+    fn leaf(&mut self, field: &Field);
+}
+
+impl Node {
+    /// Walk this tree calling the `visitor` on each node.
+    pub fn visit(&self, visitor: &mut dyn NodeVisitor) {
+        match self {
+            Node::Leaf(field) => visitor.leaf(field),
+            Node::Composite(name, fields) => {
+                if !visitor.enter_composite(name) {
+                    return;
+                }
+                for field in fields {
+                    let (name, node) = match field {
+                        FieldKind::Named(name, node) => (Some(name.as_str()), node),
+                        FieldKind::Unnamed(node) => (None, node),
+                    };
+                    if visitor.enter_field(name) {
+                        node.visit(visitor);
+                    }
+                }
+                visitor.leave_composite();
+            }
+        }
+    }
+
+    /// A compact single-line rendering of the tree, at most `max_len` characters long (the longer
+    /// ones are truncated with "…"), e.g. `Person{name: edomora97, age: Adult, +1 missing}`. The
+    /// structs are rendered with their fields in braces, the lists in brackets, and the missing
+    /// fields are only counted.
+    pub fn summary(&self, max_len: usize) -> String {
+        let mut summary = String::new();
+        self.write_summary(&mut summary);
+        if summary.chars().count() <= max_len {
+            return summary;
+        }
+        let mut truncated: String = summary.chars().take(max_len.saturating_sub(1)).collect();
+        if max_len > 0 {
+            truncated.push('…');
+        }
+        truncated
+    }
+
+    /// Append the summary of this node to `out`, see `summary`.
+    fn write_summary(&self, out: &mut String) {
+        let (name, fields) = match self {
+            Node::Leaf(Field::String(value)) | Node::Leaf(Field::Warning(value, _)) => {
+                // the multi-line values are rendered on a single line
+                out.extend(value.chars().map(|c| if c.is_control() { ' ' } else { c }));
+                return;
+            }
+            Node::Leaf(Field::Missing) => return out.push('?'),
+            Node::Leaf(Field::Elided) => return out.push('…'),
+            Node::Composite(name, fields) => (name, fields),
+        };
+        let named = fields.iter().any(|f| matches!(f, FieldKind::Named(..)));
+        let (open, close) = match (named, name.is_empty()) {
+            (true, _) => ('{', '}'),
+            (false, true) => ('[', ']'),
+            (false, false) => ('(', ')'),
+        };
+        out.push_str(name);
+        out.push(open);
+        let mut missing = 0;
+        let mut first = true;
+        for field in fields {
+            let (name, node) = match field {
+                FieldKind::Named(name, node) => (Some(name), node),
+                FieldKind::Unnamed(node) => (None, node),
+            };
+            if let Node::Leaf(Field::Missing) = node {
+                missing += 1;
+                continue;
+            }
+            if !first {
+                out.push_str(", ");
+            }
+            first = false;
+            if let Some(name) = name {
+                out.push_str(name);
+                out.push_str(": ");
+            }
+            node.write_summary(out);
+        }
+        if missing > 0 {
+            if !first {
+                out.push_str(", ");
+            }
+            out.push_str(&format!("+{} missing", missing));
+        }
+        out.push(close);
+    }
+
+    /// The statistics of the items of this node, if it's a list or a map (i.e. a composite node
+    /// without a name). The elided items are counted as complete.
+    pub fn list_stats(&self) -> Option<ListStats> {
+        match self {
+            Node::Composite(name, fields) if name.is_empty() => Some(ListStats {
+                items: fields.len(),
+                incomplete: fields
+                    .iter()
+                    .filter(|field| match field {
+                        FieldKind::Named(_, node) | FieldKind::Unnamed(node) => node.has_missing(),
+                    })
+                    .count(),
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether this node, or any node inside it, is `Field::Missing`.
+    pub fn has_missing(&self) -> bool {
+        match self {
+            Node::Leaf(field) => *field == Field::Missing,
+            Node::Composite(_, fields) => fields.iter().any(|field| match field {
+                FieldKind::Named(_, node) | FieldKind::Unnamed(node) => node.has_missing(),
+            }),
+        }
+    }
+
+    /// Name the lists and the maps of the tree with their `ListStats` (e.g. `3 items, 1
+    /// incomplete`), so that a preview can summarize them without expanding their items.
+    ///
+    /// The renamed nodes are not recognized anymore by `list_stats` and `summary`, so this is
+    /// meant to be the last step before rendering the tree.
+    pub fn with_list_stats(self) -> Node {
+        let stats = self.list_stats();
+        match self {
+            Node::Leaf(field) => Node::Leaf(field),
+            Node::Composite(name, fields) => Node::Composite(
+                stats.map(|stats| stats.to_string()).unwrap_or(name),
+                fields
+                    .into_iter()
+                    .map(|field| match field {
+                        FieldKind::Named(name, node) => {
+                            FieldKind::Named(name, node.with_list_stats())
+                        }
+                        FieldKind::Unnamed(node) => FieldKind::Unnamed(node.with_list_stats()),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Replace the composite nodes deeper than `depth` with `Field::Elided`. With a `depth` of 0
+    /// only a leaf node is kept as is.
+    pub fn pruned(self, depth: usize) -> Node {
+        match self {
+            Node::Leaf(field) => Node::Leaf(field),
+            Node::Composite(_, _) if depth == 0 => Node::Leaf(Field::Elided),
+            Node::Composite(name, fields) => Node::Composite(
+                name,
+                fields
+                    .into_iter()
+                    .map(|field| match field {
+                        FieldKind::Named(name, node) => {
+                            FieldKind::Named(name, node.pruned(depth - 1))
+                        }
+                        FieldKind::Unnamed(node) => FieldKind::Unnamed(node.pruned(depth - 1)),
+                    })
+                    .collect(),
+            ),
+        }
+    }
 }
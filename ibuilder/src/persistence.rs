@@ -0,0 +1,163 @@
+//! Automatic persistence of the state of a `Builder`, so that a session survives the restart of
+//! the process (e.g. of a bot).
+//!
+//! A `StatePersistence` attached to a `Builder` with `Builder::with_persistence` receives the
+//! snapshot of the state (see `Builder::save_state`) after every successful `choose`, under the key
+//! of the session (e.g. the identifier of the chat). When the builder is made again with the same
+//! key the snapshot is restored, and when the value is finalized the snapshot is removed.
+//!
+//! `MemoryPersistence` keeps the snapshots in memory, `FilePersistence` (with the `serde` feature)
+//! writes them to a directory as JSON files. The other storages (e.g. the databases) can implement
+//! the trait.
+//!
+//! The snapshots are not redacted, they contain the texts of the secrets (the fields with
+//! `#[ibuilder(secret)]`) in plain text: the storage must be protected like the values themselves.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use failure::Fail;
+
+use crate::SavedState;
+
+/// A storage of the snapshots of the state of the builders, indexed by the key of the session.
+pub trait StatePersistence {
+    /// Load the snapshot stored with this key, if any.
+    fn load(&mut self, key: &str) -> Result<Option<SavedState>, PersistenceError>;
+
+    /// Store the snapshot with this key, replacing the previous one.
+    fn save(&mut self, key: &str, state: &SavedState) -> Result<(), PersistenceError>;
+
+    /// Remove the snapshot stored with this key, if any.
+    fn remove(&mut self, key: &str) -> Result<(), PersistenceError>;
+}
+
+/// Share a storage between the `Builder` and the rest of the application, for example between the
+/// builders of many sessions.
+impl<P: StatePersistence> StatePersistence for Rc<RefCell<P>> {
+    fn load(&mut self, key: &str) -> Result<Option<SavedState>, PersistenceError> {
+        self.borrow_mut().load(key)
+    }
+
+    fn save(&mut self, key: &str, state: &SavedState) -> Result<(), PersistenceError> {
+        self.borrow_mut().save(key, state)
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), PersistenceError> {
+        self.borrow_mut().remove(key)
+    }
+}
+
+/// The loading or the storing of a snapshot failed.
+#[derive(Debug, Fail)]
+pub enum PersistenceError {
+    /// The storage cannot be read or written.
+    #[fail(display = "I/O error: {}", _0)]
+    Io(#[cause] std::io::Error),
+    /// The stored snapshot is not valid, or it cannot be restored.
+    #[fail(display = "Invalid snapshot: {}", _0)]
+    Invalid(String),
+    /// Another error of the storage.
+    #[fail(display = "{}", _0)]
+    Other(String),
+}
+
+impl From<std::io::Error> for PersistenceError {
+    fn from(error: std::io::Error) -> Self {
+        PersistenceError::Io(error)
+    }
+}
+
+/// A `StatePersistence` that keeps the snapshots in memory, useful for the tests and for the
+/// processes that live longer than the builders.
+#[derive(Debug, Clone, Default)]
+pub struct MemoryPersistence {
+    /// The stored snapshots, by key.
+    pub states: HashMap<String, SavedState>,
+}
+
+impl StatePersistence for MemoryPersistence {
+    fn load(&mut self, key: &str) -> Result<Option<SavedState>, PersistenceError> {
+        Ok(self.states.get(key).cloned())
+    }
+
+    fn save(&mut self, key: &str, state: &SavedState) -> Result<(), PersistenceError> {
+        self.states.insert(key.to_string(), state.clone());
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), PersistenceError> {
+        self.states.remove(key);
+        Ok(())
+    }
+}
+
+/// A `StatePersistence` that writes each snapshot to a JSON file in a directory, named after the
+/// key. The characters of the key other than the ASCII letters, the digits, `-` and `_` are
+/// escaped in the name of the file (e.g. `chat/42` is stored in `chat%2F42.json`).
+///
+/// The files are not encrypted, the secrets are written in plain text (see the module
+/// documentation).
+///
+/// Available with the `serde` feature.
+#[cfg(feature = "serde")]
+#[derive(Debug, Clone)]
+pub struct FilePersistence {
+    dir: std::path::PathBuf,
+}
+
+#[cfg(feature = "serde")]
+impl FilePersistence {
+    /// Store the snapshots in this directory, which is created if missing.
+    pub fn new<P: Into<std::path::PathBuf>>(dir: P) -> Result<FilePersistence, PersistenceError> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(FilePersistence { dir })
+    }
+
+    /// The path of the file of the snapshot with this key.
+    fn path(&self, key: &str) -> std::path::PathBuf {
+        let mut name = String::new();
+        for byte in key.bytes() {
+            if byte.is_ascii_alphanumeric() || byte == b'-' || byte == b'_' {
+                name.push(byte as char);
+            } else {
+                name.push_str(&format!("%{:02X}", byte));
+            }
+        }
+        self.dir.join(name + ".json")
+    }
+}
+
+#[cfg(feature = "serde")]
+impl StatePersistence for FilePersistence {
+    fn load(&mut self, key: &str) -> Result<Option<SavedState>, PersistenceError> {
+        let text = match std::fs::read_to_string(self.path(key)) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+        serde_json::from_str(&text)
+            .map(Some)
+            .map_err(|e| PersistenceError::Invalid(e.to_string()))
+    }
+
+    fn save(&mut self, key: &str, state: &SavedState) -> Result<(), PersistenceError> {
+        let text =
+            serde_json::to_string(state).map_err(|e| PersistenceError::Other(e.to_string()))?;
+        // write the whole file before replacing the previous one, a crash never leaves half of it
+        let path = self.path(key);
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, text)?;
+        std::fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, key: &str) -> Result<(), PersistenceError> {
+        match std::fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+//! Let the user pick one of some existing values, or build a new one.
+//!
+//! A `PickOrBuild` shows a menu with the existing values, identified by their labels, and a
+//! "Create new" (`CREATE_ID`) choice. Selecting a value returns it right away, while "Create new"
+//! moves the user to the main menu of the `Builder`, which has a "Go back" (`BACK_ID`) choice for
+//! returning to the list. This is the usual "select an existing item or create a new one" step of
+//! the CRUD bots.
+//!
+//! ```
+//! use ibuilder::pick::{PickOrBuild, CREATE_ID};
+//! use ibuilder::*;
+//!
+//! #[derive(Clone, Debug, IBuilder, PartialEq)]
+//! struct Project {
+//!     name: String,
+//! }
+//!
+//! let existing = vec![("ibuilder".to_string(), Project { name: "ibuilder".into() })];
+//! let mut pick = PickOrBuild::new(existing, Project::builder());
+//! pick.choose(Input::choice(CREATE_ID)).unwrap();
+//! pick.choose(Input::choice("name")).unwrap();
+//! pick.choose(Input::text("new project")).unwrap();
+//! let project = pick.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+//! assert_eq!(project.name, "new project");
+//! ```
+
+use crate::nodes::{Field, Node};
+use crate::{Builder, Choice, ChooseError, Input, Options, BACK_ID};
+
+/// The identifier of the "Create new" choice.
+pub const CREATE_ID: &str = "__create";
+
+/// A menu for picking one of some existing values or building a new one, see the module
+/// documentation.
+pub struct PickOrBuild<T> {
+    existing: Vec<(String, T)>,
+    builder: Builder<T>,
+    prompt: String,
+    /// Whether the user is building a new value.
+    building: bool,
+}
+
+impl<T> std::fmt::Debug for PickOrBuild<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let labels: Vec<_> = self.existing.iter().map(|(label, _)| label).collect();
+        f.debug_struct("PickOrBuild")
+            .field("existing", &labels)
+            .field("builder", &self.builder)
+            .field("building", &self.building)
+            .finish()
+    }
+}
+
+impl<T: Clone + 'static> PickOrBuild<T> {
+    /// Make a new menu with the existing values, each with the label to show to the user, and the
+    /// builder for making a new one.
+    pub fn new(existing: Vec<(String, T)>, builder: Builder<T>) -> PickOrBuild<T> {
+        PickOrBuild {
+            existing,
+            builder,
+            prompt: "Choose an item".to_string(),
+            building: false,
+        }
+    }
+
+    /// Change the query of the menu with the existing values.
+    pub fn with_prompt<S: Into<String>>(mut self, prompt: S) -> PickOrBuild<T> {
+        self.prompt = prompt.into();
+        self
+    }
+
+    /// Whether the user is building a new value, instead of looking at the existing ones.
+    pub fn is_building(&self) -> bool {
+        self.building
+    }
+
+    /// Return all the valid options in the current state: the existing values or the options of the
+    /// builder, with the "Go back" choice in its main menu.
+    pub fn get_options(&self) -> Options {
+        if self.building {
+            let mut options = self.builder.get_options();
+            if self.builder.is_at_main_menu() {
+                options.choices.push(Choice {
+                    choice_id: BACK_ID.into(),
+                    text: "Go back".to_string(),
+                    needs_action: false,
+                    ..Default::default()
+                });
+            }
+            return options;
+        }
+        let mut choices: Vec<_> = self
+            .existing
+            .iter()
+            .enumerate()
+            .map(|(index, (label, _))| Choice {
+                choice_id: index.to_string().into(),
+                text: label.clone(),
+                needs_action: false,
+                ..Default::default()
+            })
+            .collect();
+        choices.push(Choice {
+            choice_id: CREATE_ID.into(),
+            text: "Create new".to_string(),
+            needs_action: false,
+            ..Default::default()
+        });
+        Options {
+            query: self.prompt.clone(),
+            text_input: false,
+            choices,
+            ..Default::default()
+        }
+    }
+
+    /// Apply an input, returning `Ok(Some(T))` when the user picked an existing value or finalized
+    /// the new one.
+    pub fn choose(&mut self, input: Input) -> Result<Option<T>, ChooseError> {
+        if self.building {
+            if self.builder.is_at_main_menu() && input == Input::choice(BACK_ID) {
+                self.building = false;
+                return Ok(None);
+            }
+            return self.builder.choose(input);
+        }
+        let data = match input {
+            Input::Choice(data) => data,
+            Input::Text(_) => return Err(ChooseError::UnexpectedText),
+        };
+        if data == CREATE_ID {
+            self.building = true;
+            return Ok(None);
+        }
+        data.parse::<usize>()
+            .ok()
+            .and_then(|index| self.existing.get(index))
+            .map(|(_, value)| Some(value.clone()))
+            .ok_or(ChooseError::UnexpectedChoice)
+    }
+
+    /// Return the tree structure of the state of the builder, or a missing leaf while the user is
+    /// looking at the existing values.
+    pub fn to_node(&self) -> Node {
+        if self.building {
+            self.builder.to_node()
+        } else {
+            Node::Leaf(Field::Missing)
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Module with ready-made preprocessors for the text inserted by the user, they are used by the
+//! convenience attributes of the derive macro (e.g. `#[ibuilder(trim)]`) but can also be used with
+//! `#[ibuilder(preprocess = "...")]`.
+
+/// Remove the leading and trailing whitespace, often added by the chat clients.
+pub fn trim(text: &str) -> String {
+    text.trim().to_string()
+}
+
+/// Convert the text to lowercase.
+pub fn lowercase(text: &str) -> String {
+    text.to_lowercase()
+}
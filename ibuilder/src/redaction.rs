@@ -0,0 +1,143 @@
+//! Redaction of the values inserted by the user from the state of a `Builder`, so that the logs of
+//! a production service never contain personal data.
+//!
+//! When the redaction is active (see `BuilderConfig::redaction` and `set_global_redaction`) the
+//! `Debug` of the `Builder`, its `to_node` (and the other views of the tree), the texts in its
+//! `history` and the inputs sent to the `MetricsSink` have the values replaced with a placeholder
+//! or with their hash. The structure (the names of the fields, the missing ones, the choices of
+//! the user) is kept. The snapshots made with `Builder::save_state` are not redacted, since they
+//! are needed for restoring the state.
+//!
+//! The secrets (see `builders::SecretBuilder`) are replaced with the placeholder even when the
+//! redaction is off, except in the snapshots.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::sync::atomic::{AtomicU8, Ordering};
+
+use crate::nodes::{Field, FieldKind, Node, NodeVisitor};
+use crate::Input;
+
+/// The text that replaces the values with `Redaction::Placeholder`.
+pub const PLACEHOLDER: &str = "[redacted]";
+
+/// How the values inserted by the user are redacted.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum Redaction {
+    /// The values are shown as they are.
+    #[default]
+    Off,
+    /// The values are replaced with `PLACEHOLDER`.
+    Placeholder,
+    /// The values are replaced with a hash of their text (e.g. `[hash:5bd81c2a9e3f7d01]`), so the
+    /// equal values can still be recognized in the logs. The hash is not cryptographic, it doesn't
+    /// protect the values that can be guessed (e.g. the small numbers).
+    Hash,
+}
+
+/// The redaction of the builders without `BuilderConfig::redaction`.
+static GLOBAL: AtomicU8 = AtomicU8::new(0);
+
+/// Set the redaction of all the builders without `BuilderConfig::redaction`, for example at the
+/// start of a production service.
+pub fn set_global_redaction(redaction: Redaction) {
+    let value = match redaction {
+        Redaction::Off => 0,
+        Redaction::Placeholder => 1,
+        Redaction::Hash => 2,
+    };
+    GLOBAL.store(value, Ordering::Relaxed);
+}
+
+/// The redaction of the builders without `BuilderConfig::redaction`, see `set_global_redaction`.
+pub fn global_redaction() -> Redaction {
+    match GLOBAL.load(Ordering::Relaxed) {
+        0 => Redaction::Off,
+        1 => Redaction::Placeholder,
+        _ => Redaction::Hash,
+    }
+}
+
+impl Redaction {
+    /// Whether the values are redacted.
+    pub fn is_active(self) -> bool {
+        self != Redaction::Off
+    }
+
+    /// Redact a single value.
+    pub fn redact(self, value: &str) -> String {
+        match self {
+            Redaction::Off => value.to_string(),
+            Redaction::Placeholder => PLACEHOLDER.to_string(),
+            Redaction::Hash => {
+                let mut hasher = DefaultHasher::new();
+                value.hash(&mut hasher);
+                format!("[hash:{:016x}]", hasher.finish())
+            }
+        }
+    }
+
+    /// Redact the value of a leaf of the tree, the warnings are kept.
+    pub fn redact_field(self, field: &Field) -> Field {
+        match field {
+            Field::String(value) => Field::String(self.redact(value)),
+            Field::Warning(value, warning) => Field::Warning(self.redact(value), warning.clone()),
+            Field::Missing => Field::Missing,
+            Field::Elided => Field::Elided,
+        }
+    }
+
+    /// Redact all the leaves of the tree.
+    pub fn redact_node(self, node: Node) -> Node {
+        if !self.is_active() {
+            return node;
+        }
+        match node {
+            Node::Leaf(field) => Node::Leaf(self.redact_field(&field)),
+            Node::Composite(name, fields) => Node::Composite(
+                name,
+                fields
+                    .into_iter()
+                    .map(|field| match field {
+                        FieldKind::Named(name, node) => {
+                            FieldKind::Named(name, self.redact_node(node))
+                        }
+                        FieldKind::Unnamed(node) => FieldKind::Unnamed(self.redact_node(node)),
+                    })
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Redact the text of an input, the choices are kept.
+    pub fn redact_input(self, input: &Input) -> Input {
+        match input {
+            Input::Text(text) if self.is_active() => Input::Text(self.redact(text)),
+            _ => input.clone(),
+        }
+    }
+}
+
+/// A `NodeVisitor` that redacts the leaves before forwarding them to another visitor.
+pub(crate) struct RedactingVisitor<'a> {
+    pub(crate) redaction: Redaction,
+    pub(crate) inner: &'a mut dyn NodeVisitor,
+}
+
+impl NodeVisitor for RedactingVisitor<'_> {
+    fn enter_composite(&mut self, name: &str) -> bool {
+        self.inner.enter_composite(name)
+    }
+
+    fn leave_composite(&mut self) {
+        self.inner.leave_composite()
+    }
+
+    fn enter_field(&mut self, name: Option<&str>) -> bool {
+        self.inner.enter_field(name)
+    }
+
+    fn leaf(&mut self, field: &Field) {
+        self.inner.leaf(&self.redaction.redact_field(field))
+    }
+}
@@ -0,0 +1,261 @@
+//! Describe the type built by a `Builder` with a [JSON Schema](https://json-schema.org).
+//!
+//! `Builder::to_schema` returns the `Schema` of the whole value, made of the schemas of its fields
+//! returned by `BuildableValue::schema`. A web frontend can render a form from it, instead of
+//! driving the choice-by-choice protocol of the `Options`, and send back the inserted values.
+//!
+//! The schema reflects the current state of the builder: the `default` of a field is its current
+//! value, and only the fields without a value are `required`. For a new `Builder` these are the
+//! defaults declared with `#[ibuilder(default = ...)]` and the fields without them.
+//!
+//! The structs are objects, the enums are `oneOf` their variants (the variants with fields are
+//! objects with a single property named after the variant), the `Vec`s are arrays, and the maps
+//! are objects with `additionalProperties`.
+
+use crate::nodes::{Field, Node};
+use crate::BuildableValue;
+
+/// The description of a value, see the module documentation.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Schema {
+    /// The type of the value.
+    pub kind: SchemaKind,
+    /// The name of the value to show to the user, if different from the name of the property.
+    pub title: Option<String>,
+    /// A longer explanation of the value, from `#[ibuilder(help = "...")]`.
+    pub description: Option<String>,
+    /// The textual representation of the default value, if any.
+    pub default: Option<String>,
+}
+
+/// The type of a value described by a `Schema`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SchemaKind {
+    /// Any textual value, parsed by the builder.
+    String,
+    /// An integer number.
+    Integer,
+    /// A floating point number.
+    Number,
+    /// `true` or `false`.
+    Boolean,
+    /// One of these strings.
+    Enum(Vec<String>),
+    /// A list of items with this schema.
+    Array(Box<Schema>),
+    /// An object with arbitrary keys, whose values have this schema.
+    Map(Box<Schema>),
+    /// An object with these properties.
+    Object(Vec<Property>),
+    /// A value matching exactly one of these schemas.
+    OneOf(Vec<Schema>),
+    /// A value with this schema, or `null`.
+    Nullable(Box<Schema>),
+}
+
+/// A property of a `SchemaKind::Object`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Property {
+    /// The name of the property.
+    pub name: String,
+    /// The schema of the value of the property.
+    pub schema: Schema,
+    /// Whether the value of the property has to be inserted.
+    pub required: bool,
+}
+
+impl Schema {
+    /// Make a new schema of the provided kind, without title, description and default.
+    pub fn new(kind: SchemaKind) -> Schema {
+        Schema {
+            kind,
+            title: None,
+            description: None,
+            default: None,
+        }
+    }
+
+    /// The schema of a value guessed from its menu and its state: an object if it has subfields, an
+    /// enum if it accepts only choices, otherwise a string. This is the default implementation of
+    /// `BuildableValue::schema`.
+    pub fn guess<V: BuildableValue + ?Sized>(value: &V) -> Schema {
+        let subfields = value.get_subfields(&[]);
+        if !subfields.is_empty() {
+            let properties = subfields
+                .into_iter()
+                .filter_map(|name| {
+                    let child = value.get_child(&name)?;
+                    Some(Property {
+                        required: !child.is_complete(),
+                        schema: child.schema(),
+                        name: name.into(),
+                    })
+                })
+                .collect();
+            return Schema::new(SchemaKind::Object(properties));
+        }
+        let options = value.get_options(&[]);
+        let kind = if options.text_input || options.choices.is_empty() {
+            SchemaKind::String
+        } else {
+            SchemaKind::Enum(
+                options
+                    .choices
+                    .into_iter()
+                    .map(|c| c.choice_id.into())
+                    .collect(),
+            )
+        };
+        Schema::new(kind).with_default_from(value)
+    }
+
+    /// Set the default of the schema to the current value of the leaf, if any.
+    pub fn with_default_from<V: BuildableValue + ?Sized>(mut self, value: &V) -> Schema {
+        self.default = match value.to_node() {
+            Node::Leaf(Field::String(value)) | Node::Leaf(Field::Warning(value, _)) => Some(value),
+            _ => None,
+        };
+        self
+    }
+
+    /// Serialize the schema to a JSON string.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        self.write_json(&mut json);
+        json
+    }
+
+    /// Append the JSON object of this schema to `json`.
+    fn write_json(&self, json: &mut String) {
+        json.push('{');
+        let mut first = true;
+        let mut key = |json: &mut String, key: &str| {
+            if !first {
+                json.push(',');
+            }
+            first = false;
+            write_string(json, key);
+            json.push(':');
+        };
+        match &self.kind {
+            SchemaKind::String => {
+                key(json, "type");
+                json.push_str("\"string\"");
+            }
+            SchemaKind::Integer => {
+                key(json, "type");
+                json.push_str("\"integer\"");
+            }
+            SchemaKind::Number => {
+                key(json, "type");
+                json.push_str("\"number\"");
+            }
+            SchemaKind::Boolean => {
+                key(json, "type");
+                json.push_str("\"boolean\"");
+            }
+            SchemaKind::Enum(values) => {
+                key(json, "enum");
+                write_list(json, values, |json, value| write_string(json, value));
+            }
+            SchemaKind::Array(items) => {
+                key(json, "type");
+                json.push_str("\"array\"");
+                key(json, "items");
+                items.write_json(json);
+            }
+            SchemaKind::Map(values) => {
+                key(json, "type");
+                json.push_str("\"object\"");
+                key(json, "additionalProperties");
+                values.write_json(json);
+            }
+            SchemaKind::Object(properties) => {
+                key(json, "type");
+                json.push_str("\"object\"");
+                key(json, "properties");
+                json.push('{');
+                for (i, property) in properties.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    write_string(json, &property.name);
+                    json.push(':');
+                    property.schema.write_json(json);
+                }
+                json.push('}');
+                let required: Vec<_> = properties.iter().filter(|p| p.required).collect();
+                if !required.is_empty() {
+                    key(json, "required");
+                    write_list(json, &required, |json, p| write_string(json, &p.name));
+                }
+            }
+            SchemaKind::OneOf(schemas) => {
+                key(json, "oneOf");
+                write_list(json, schemas, |json, schema| schema.write_json(json));
+            }
+            SchemaKind::Nullable(inner) => {
+                key(json, "oneOf");
+                json.push('[');
+                inner.write_json(json);
+                json.push_str(",{\"type\":\"null\"}]");
+            }
+        }
+        if let Some(title) = &self.title {
+            key(json, "title");
+            write_string(json, title);
+        }
+        if let Some(description) = &self.description {
+            key(json, "description");
+            write_string(json, description);
+        }
+        if let Some(default) = &self.default {
+            key(json, "default");
+            if self.is_literal(default) {
+                json.push_str(default);
+            } else {
+                write_string(json, default);
+            }
+        }
+        json.push('}');
+    }
+
+    /// Whether the default can be written as is in the JSON, without quoting it.
+    fn is_literal(&self, default: &str) -> bool {
+        match &self.kind {
+            SchemaKind::Integer => default.parse::<i128>().is_ok(),
+            SchemaKind::Number => default.parse::<f64>().is_ok_and(|n| n.is_finite()),
+            SchemaKind::Boolean => default == "true" || default == "false",
+            _ => false,
+        }
+    }
+}
+
+/// Append the JSON array with the items to `json`.
+fn write_list<T, F: Fn(&mut String, &T)>(json: &mut String, items: &[T], write: F) {
+    json.push('[');
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            json.push(',');
+        }
+        write(json, item);
+    }
+    json.push(']');
+}
+
+/// Append the JSON string with the text to `json`, escaping it.
+fn write_string(json: &mut String, text: &str) {
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
@@ -0,0 +1,271 @@
+//! Module with the value types that have no equivalent in the standard library, but that are
+//! commonly found in the configurations built interactively.
+
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+use failure::Fail;
+
+/// A time of the day, with the precision of the minute. It's parsed from and displayed as `HH:MM`,
+/// using the 24-hour clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeOfDay {
+    /// The hour, from 0 to 23.
+    pub hour: u8,
+    /// The minute, from 0 to 59.
+    pub minute: u8,
+}
+
+/// The error returned when parsing an invalid `TimeOfDay`.
+#[derive(Debug, Fail)]
+#[fail(display = "Invalid time, expecting HH:MM")]
+pub struct ParseTimeOfDayError;
+
+impl TimeOfDay {
+    /// Make a new `TimeOfDay`, returning `None` if the hour or the minute are out of range.
+    pub fn new(hour: u8, minute: u8) -> Option<TimeOfDay> {
+        if hour < 24 && minute < 60 {
+            Some(TimeOfDay { hour, minute })
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for TimeOfDay {
+    type Err = ParseTimeOfDayError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().splitn(2, ':');
+        let hour = parts.next().ok_or(ParseTimeOfDayError)?;
+        let minute = parts.next().ok_or(ParseTimeOfDayError)?;
+        let digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+        if hour.is_empty()
+            || hour.len() > 2
+            || minute.len() != 2
+            || !digits(hour)
+            || !digits(minute)
+        {
+            return Err(ParseTimeOfDayError);
+        }
+        let hour = hour.parse().map_err(|_| ParseTimeOfDayError)?;
+        let minute = minute.parse().map_err(|_| ParseTimeOfDayError)?;
+        TimeOfDay::new(hour, minute).ok_or(ParseTimeOfDayError)
+    }
+}
+
+impl Display for TimeOfDay {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:02}:{:02}", self.hour, self.minute)
+    }
+}
+
+/// A method of an HTTP request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HttpMethod {
+    /// The `GET` method.
+    Get,
+    /// The `HEAD` method.
+    Head,
+    /// The `POST` method.
+    Post,
+    /// The `PUT` method.
+    Put,
+    /// The `DELETE` method.
+    Delete,
+    /// The `CONNECT` method.
+    Connect,
+    /// The `OPTIONS` method.
+    Options,
+    /// The `TRACE` method.
+    Trace,
+    /// The `PATCH` method.
+    Patch,
+}
+
+/// The error returned when parsing an unknown `HttpMethod`.
+#[derive(Debug, Fail)]
+#[fail(display = "Unknown HTTP method")]
+pub struct ParseHttpMethodError;
+
+impl HttpMethod {
+    /// All the HTTP methods.
+    pub const ALL: [HttpMethod; 9] = [
+        HttpMethod::Get,
+        HttpMethod::Head,
+        HttpMethod::Post,
+        HttpMethod::Put,
+        HttpMethod::Delete,
+        HttpMethod::Connect,
+        HttpMethod::Options,
+        HttpMethod::Trace,
+        HttpMethod::Patch,
+    ];
+
+    /// The name of the method, as used in the HTTP requests.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            HttpMethod::Get => "GET",
+            HttpMethod::Head => "HEAD",
+            HttpMethod::Post => "POST",
+            HttpMethod::Put => "PUT",
+            HttpMethod::Delete => "DELETE",
+            HttpMethod::Connect => "CONNECT",
+            HttpMethod::Options => "OPTIONS",
+            HttpMethod::Trace => "TRACE",
+            HttpMethod::Patch => "PATCH",
+        }
+    }
+}
+
+impl FromStr for HttpMethod {
+    type Err = ParseHttpMethodError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        HttpMethod::ALL
+            .iter()
+            .find(|method| method.as_str().eq_ignore_ascii_case(s))
+            .copied()
+            .ok_or(ParseHttpMethodError)
+    }
+}
+
+impl Display for HttpMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// A MIME type (e.g. `text/html; charset=utf-8`), checked to be syntactically valid according to
+/// RFC 6838. The type and the subtype are stored lowercase.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct MimeType(String);
+
+/// The error returned when parsing an invalid `MimeType`.
+#[derive(Debug, Fail)]
+#[fail(display = "Invalid MIME type, expecting type/subtype")]
+pub struct ParseMimeTypeError;
+
+impl MimeType {
+    /// The MIME types suggested to the user.
+    pub const COMMON: [&'static str; 12] = [
+        "application/json",
+        "application/xml",
+        "application/octet-stream",
+        "application/x-www-form-urlencoded",
+        "application/pdf",
+        "multipart/form-data",
+        "text/plain",
+        "text/html",
+        "text/csv",
+        "image/png",
+        "image/jpeg",
+        "image/svg+xml",
+    ];
+
+    /// The MIME type as a string.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The MIME type without the parameters, i.e. `type/subtype`.
+    pub fn essence(&self) -> &str {
+        self.0.split(';').next().unwrap_or_default()
+    }
+}
+
+/// Check that the name is a valid `restricted-name` of RFC 6838.
+fn is_restricted_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphanumeric() => {}
+        _ => return false,
+    }
+    name.len() <= 127 && chars.all(|c| c.is_ascii_alphanumeric() || "!#$&-^_.+".contains(c))
+}
+
+impl FromStr for MimeType {
+    type Err = ParseMimeTypeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split(';');
+        let mut essence = parts.next().unwrap_or_default().splitn(2, '/');
+        let ty = essence.next().unwrap_or_default().trim();
+        let subtype = essence.next().ok_or(ParseMimeTypeError)?.trim();
+        if !is_restricted_name(ty) || !is_restricted_name(subtype) {
+            return Err(ParseMimeTypeError);
+        }
+        let mut res = format!("{}/{}", ty.to_lowercase(), subtype.to_lowercase());
+        for param in parts {
+            let mut param = param.splitn(2, '=');
+            let name = param.next().unwrap_or_default().trim();
+            let value = param.next().ok_or(ParseMimeTypeError)?.trim();
+            if !is_restricted_name(name) || value.is_empty() {
+                return Err(ParseMimeTypeError);
+            }
+            res += &format!("; {}={}", name.to_lowercase(), value);
+        }
+        Ok(MimeType(res))
+    }
+}
+
+impl Display for MimeType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The answer to a yes/no question. Its builder shows the two choices with customizable labels,
+/// see `Builder::confirm`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Confirm {
+    /// The user confirmed.
+    Yes,
+    /// The user declined.
+    No,
+}
+
+/// The error returned when parsing an invalid `Confirm`.
+#[derive(Debug, Fail)]
+#[fail(display = "Invalid answer, expecting yes or no")]
+pub struct ParseConfirmError;
+
+impl Confirm {
+    /// Whether the user confirmed.
+    pub fn is_yes(self) -> bool {
+        self == Confirm::Yes
+    }
+
+    /// The identifier of the answer, used as the identifier of its choice.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Confirm::Yes => "yes",
+            Confirm::No => "no",
+        }
+    }
+}
+
+impl From<Confirm> for bool {
+    fn from(confirm: Confirm) -> bool {
+        confirm.is_yes()
+    }
+}
+
+impl FromStr for Confirm {
+    type Err = ParseConfirmError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "yes" => Ok(Confirm::Yes),
+            "no" => Ok(Confirm::No),
+            _ => Err(ParseConfirmError),
+        }
+    }
+}
+
+impl Display for Confirm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
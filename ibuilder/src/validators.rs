@@ -0,0 +1,154 @@
+//! Module with ready-made validators for the common kinds of values, they are used by the
+//! convenience attributes of the derive macro (e.g. `#[ibuilder(hostname)]`) but can also be used
+//! with `#[ibuilder(validate = "...")]`.
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::net::ToSocketAddrs;
+
+use crate::Validation;
+
+/// Check that the value is a valid hostname according to RFC 1123: a list of labels separated by
+/// dots, each made of 1 to 63 letters, digits or hyphens, not starting or ending with a hyphen.
+/// A single trailing dot is allowed.
+pub fn hostname(value: &str) -> Validation {
+    let name = value.strip_suffix('.').unwrap_or(value);
+    if name.is_empty() {
+        return Validation::Invalid("The hostname cannot be empty".into());
+    }
+    if name.len() > 253 {
+        return Validation::Invalid("The hostname is too long".into());
+    }
+    for label in name.split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Validation::Invalid("Each part of the hostname must be 1 to 63 chars".into());
+        }
+        if !label.chars().all(|c| c.is_ascii_alphanumeric() || c == '-') {
+            return Validation::Invalid(format!("Invalid character in \"{}\"", label));
+        }
+        if label.starts_with('-') || label.ends_with('-') {
+            return Validation::Invalid(format!("\"{}\" cannot start or end with '-'", label));
+        }
+    }
+    Validation::Valid
+}
+
+/// Check that the hostname can be resolved, warning the user if it cannot. Note that the
+/// resolution is blocking.
+pub fn resolvable_hostname(value: &str) -> Validation {
+    let resolved = match (value, 0).to_socket_addrs() {
+        Ok(mut addrs) => addrs.next().is_some(),
+        Err(_) => false,
+    };
+    if resolved {
+        Validation::Valid
+    } else {
+        Validation::Warning(format!("Cannot resolve {}", value))
+    }
+}
+
+/// Check that the port number is not 0, which is usually not a valid port to connect or bind to.
+pub fn non_zero_port(value: &u16) -> Validation {
+    if *value == 0 {
+        Validation::Invalid("The port cannot be 0".into())
+    } else {
+        Validation::Valid
+    }
+}
+
+/// Warn the user if the port is privileged (i.e. below 1024), since binding to it usually
+/// requires root privileges.
+pub fn unprivileged_port(value: &u16) -> Validation {
+    if *value != 0 && *value < 1024 {
+        Validation::Warning(format!("Port {} is privileged", value))
+    } else {
+        Validation::Valid
+    }
+}
+
+/// The regular expressions of `matches_regex`, re-exported for the code generated by the derive
+/// macro. Available with the `regex` feature.
+#[cfg(feature = "regex")]
+pub use regex::Regex;
+
+/// Make a validator that rejects the texts that don't match the regular expression. The error is
+/// `message`, or a generic one mentioning the pattern. Available with the `regex` feature.
+///
+/// The pattern is compiled on each call, see `matches_regex` for reusing a compiled one.
+///
+/// Panics if the pattern is not a valid regular expression.
+#[cfg(feature = "regex")]
+pub fn pattern(pattern: &str, message: Option<&str>) -> impl Fn(&String) -> Validation {
+    let regex =
+        Regex::new(pattern).unwrap_or_else(|e| panic!("Invalid pattern {:?}: {}", pattern, e));
+    matches_regex(regex, message)
+}
+
+/// Like `pattern`, with a regular expression already compiled. Used by
+/// `#[ibuilder(pattern = "...")]`, which compiles the pattern of each field only once.
+#[cfg(feature = "regex")]
+pub fn matches_regex(regex: Regex, message: Option<&str>) -> impl Fn(&String) -> Validation {
+    let message = match message {
+        Some(message) => message.to_string(),
+        None => format!("The value must match the pattern {}", regex.as_str()),
+    };
+    move |value| {
+        if regex.is_match(value) {
+            Validation::Valid
+        } else {
+            Validation::Invalid(message.clone())
+        }
+    }
+}
+
+/// Make a validator that rejects the values outside of the range, used by
+/// `#[ibuilder(min = ..., max = ...)]`. Both the bounds are inclusive and `None` means unbounded.
+/// The values that cannot be compared with the bounds (i.e. `NaN`) are rejected as well.
+pub fn range<T: PartialOrd + Display>(min: Option<T>, max: Option<T>) -> impl Fn(&T) -> Validation {
+    move |value| {
+        let too_low = min.as_ref().is_some_and(|min| {
+            !matches!(
+                value.partial_cmp(min),
+                Some(Ordering::Greater) | Some(Ordering::Equal)
+            )
+        });
+        let too_high = max.as_ref().is_some_and(|max| {
+            !matches!(
+                value.partial_cmp(max),
+                Some(Ordering::Less) | Some(Ordering::Equal)
+            )
+        });
+        if too_low || too_high {
+            Validation::Invalid(format!(
+                "The value must be {}",
+                range_text(&min, &max, false)
+            ))
+        } else {
+            Validation::Valid
+        }
+    }
+}
+
+/// The hint that describes the values accepted by `range`, if there is at least a bound.
+pub fn range_hint<T: Display>(min: Option<T>, max: Option<T>) -> Option<String> {
+    if min.is_none() && max.is_none() {
+        None
+    } else {
+        Some(range_text(&min, &max, true))
+    }
+}
+
+/// Describe the range with the provided bounds, capitalized for the hints.
+fn range_text<T: Display>(min: &Option<T>, max: &Option<T>, capitalize: bool) -> String {
+    let text = match (min, max) {
+        (Some(min), Some(max)) => format!("between {} and {}", min, max),
+        (Some(min), None) => format!("at least {}", min),
+        (None, Some(max)) => format!("at most {}", max),
+        (None, None) => "any value".to_string(),
+    };
+    if capitalize {
+        text[0..1].to_uppercase() + &text[1..]
+    } else {
+        text
+    }
+}
@@ -0,0 +1,96 @@
+#![allow(dead_code)]
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(action(id = "ping", text = "Test connection", handler = "ping"))]
+#[ibuilder(action(id = "nop", handler = "nop"))]
+struct Server {
+    host: String,
+    #[ibuilder(default = 80)]
+    port: u16,
+}
+
+#[derive(Debug, IBuilder)]
+struct Config {
+    server: Server,
+}
+
+fn ping(state: &PartialState) -> Option<String> {
+    let host: String = state.get("host")?;
+    let port: u16 = state.get("port")?;
+    Some(format!("{}:{} is reachable", host, port))
+}
+
+fn nop(_: &PartialState) -> Option<String> {
+    None
+}
+
+#[test]
+fn test_action_choice() {
+    let builder = Server::builder();
+    let options = builder.get_options();
+    let action = options
+        .choices
+        .iter()
+        .find(|c| c.choice_id == "ping")
+        .unwrap();
+    assert_eq!(action.text, "Test connection");
+    let action = options
+        .choices
+        .iter()
+        .find(|c| c.choice_id == "nop")
+        .unwrap();
+    assert_eq!(action.text, "nop");
+}
+
+#[test]
+fn test_action_message() {
+    let mut builder = Server::builder();
+    builder.choose(Input::choice("host")).unwrap();
+    builder.choose(Input::text("example.com")).unwrap();
+
+    builder.choose(Input::choice("ping")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.query, "Test connection");
+    assert_eq!(options.notice.unwrap(), "example.com:80 is reachable");
+    assert!(!options.text_input);
+    assert_eq!(options.choices.len(), 1);
+    assert_eq!(options.choices[0].choice_id, BACK_ID);
+    assert_eq!(
+        builder.choose(Input::text("nope")).unwrap_err(),
+        ChooseError::UnexpectedText
+    );
+
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("nop")).unwrap();
+    assert_eq!(builder.get_options().notice, None);
+}
+
+#[test]
+fn test_action_partial_state() {
+    let mut builder = Server::builder();
+    builder.choose(Input::choice("ping")).unwrap();
+    // the host is missing, so the handler has nothing to say
+    assert_eq!(builder.get_options().notice, None);
+}
+
+#[test]
+fn test_nested_action() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("server")).unwrap();
+    builder.choose(Input::choice("host")).unwrap();
+    builder.choose(Input::text("localhost")).unwrap();
+    builder.choose(Input::choice("ping")).unwrap();
+    assert_eq!(
+        builder.get_options().notice.unwrap(),
+        "localhost:80 is reachable"
+    );
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    // back to the menu of the server
+    assert!(builder
+        .get_options()
+        .choices
+        .iter()
+        .any(|c| c.choice_id == "ping"));
+}
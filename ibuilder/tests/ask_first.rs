@@ -0,0 +1,98 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Deployment {
+    name: String,
+    #[ibuilder(ask_first)]
+    kind: Kind,
+    #[ibuilder(ask_first)]
+    replicas: u32,
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+enum Kind {
+    Local,
+    Cloud { region: String },
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Project {
+    owner: String,
+    deployment: Deployment,
+}
+
+fn choice_ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_ask_first_at_start() {
+    let mut builder = Deployment::builder();
+    assert_eq!(
+        choice_ids(&builder.get_options()),
+        vec!["Local", "Cloud", BACK_ID]
+    );
+    builder.choose(Input::choice("Local")).unwrap();
+    // then the second ask_first field
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("3")).unwrap();
+    // then the menu of the struct
+    assert_eq!(
+        choice_ids(&builder.get_options()),
+        vec!["name", "kind", "replicas"]
+    );
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("prod")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Deployment {
+            name: "prod".into(),
+            kind: Kind::Local,
+            replicas: 3
+        }
+    );
+}
+
+#[test]
+fn test_ask_first_back() {
+    let mut builder = Deployment::builder();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(
+        choice_ids(&builder.get_options()),
+        vec!["name", "kind", "replicas"]
+    );
+    // entering the struct menu again is not needed, but setting a field routes back to the
+    // unanswered questions
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("prod")).unwrap();
+    assert_eq!(
+        choice_ids(&builder.get_options()),
+        vec!["Local", "Cloud", BACK_ID]
+    );
+}
+
+#[test]
+fn test_ask_first_nested() {
+    let mut builder = Project::builder();
+    assert_eq!(
+        choice_ids(&builder.get_options()),
+        vec!["owner", "deployment"]
+    );
+    builder.choose(Input::choice("deployment")).unwrap();
+    assert_eq!(
+        choice_ids(&builder.get_options()),
+        vec!["Local", "Cloud", BACK_ID]
+    );
+    builder.choose(Input::choice("Local")).unwrap();
+    // back in the deployment menu, the replicas are asked
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("1")).unwrap();
+    assert_eq!(
+        choice_ids(&builder.get_options()),
+        vec!["name", "kind", "replicas", BACK_ID]
+    );
+}
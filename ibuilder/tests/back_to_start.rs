@@ -0,0 +1,59 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Config {
+    server: Server,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Server {
+    tls: Tls,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Tls {
+    cert: String,
+}
+
+fn ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_back_to_start() {
+    let mut builder = Config::builder().with_config(BuilderConfig {
+        back_to_start: true,
+        ..Default::default()
+    });
+    builder.choose(Input::choice("server")).unwrap();
+    // one level deep "Go back" is enough
+    assert_eq!(ids(&builder.get_options()), vec!["tls", "port", BACK_ID]);
+    assert_eq!(
+        builder.choose(Input::choice(ROOT_ID)),
+        Err(ChooseError::UnexpectedChoice)
+    );
+
+    builder.choose(Input::choice("tls")).unwrap();
+    assert_eq!(ids(&builder.get_options()), vec!["cert", BACK_ID, ROOT_ID]);
+    builder.choose(Input::choice("cert")).unwrap();
+    assert_eq!(ids(&builder.get_options()), vec![BACK_ID, ROOT_ID]);
+    builder.choose(Input::choice(ROOT_ID)).unwrap();
+    assert_eq!(ids(&builder.get_options()), vec!["server"]);
+}
+
+#[test]
+fn test_back_to_start_disabled() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("server")).unwrap();
+    builder.choose(Input::choice("tls")).unwrap();
+    assert_eq!(ids(&builder.get_options()), vec!["cert", BACK_ID]);
+    assert_eq!(
+        builder.choose(Input::choice(ROOT_ID)),
+        Err(ChooseError::UnexpectedChoice)
+    );
+}
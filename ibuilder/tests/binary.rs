@@ -0,0 +1,69 @@
+use ibuilder::builders::{Base64, BinaryEncoding, Hex};
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Keys {
+    #[ibuilder(encoding = "base64")]
+    public: Vec<u8>,
+    #[ibuilder(encoding = "hex", validate = "check_len")]
+    secret: Vec<u8>,
+}
+
+fn check_len(value: &[u8]) -> Validation {
+    if value.len() == 4 {
+        Validation::Valid
+    } else {
+        Validation::Invalid("Expecting 4 bytes".into())
+    }
+}
+
+#[test]
+fn test_base64() {
+    assert_eq!(Base64::decode("aGVsbG8=").unwrap(), b"hello");
+    assert_eq!(Base64::decode("aGVsbG8").unwrap(), b"hello");
+    assert_eq!(Base64::decode("aGVs\nbG8h").unwrap(), b"hello!");
+    assert_eq!(Base64::decode("-_8=").unwrap(), vec![0xfb, 0xff]);
+    assert_eq!(Base64::decode("+/8=").unwrap(), vec![0xfb, 0xff]);
+    assert_eq!(Base64::decode("").unwrap(), b"");
+    assert!(Base64::decode("a").is_err());
+    assert!(Base64::decode("aGVsbG8==").is_err());
+    assert!(Base64::decode("aG=sbG8=").is_err());
+    assert!(Base64::decode("aGVsb*8=").is_err());
+}
+
+#[test]
+fn test_hex() {
+    assert_eq!(
+        Hex::decode("deadBEEF").unwrap(),
+        vec![0xde, 0xad, 0xbe, 0xef]
+    );
+    assert_eq!(Hex::decode("0xde ad").unwrap(), vec![0xde, 0xad]);
+    assert!(Hex::decode("abc").is_err());
+    assert!(Hex::decode("zz").is_err());
+    assert!(Hex::decode("é1").is_err());
+}
+
+#[test]
+fn test_builder() {
+    let mut builder = Keys::builder();
+    builder.choose(Input::choice("public")).unwrap();
+    assert_eq!(builder.get_options().query, "Paste the base64 data");
+    let res = builder.choose(Input::text("not base64!"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("aGVsbG8=")).unwrap();
+    builder.choose(Input::choice("secret")).unwrap();
+    let res = builder.choose(Input::text("dead"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("deadbeef")).unwrap();
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[0] {
+            FieldKind::Named(_, Node::Leaf(Field::String(s))) => assert_eq!(s, "5 bytes"),
+            _ => panic!("expecting a string"),
+        },
+        _ => panic!("expecting a composite"),
+    }
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.public, b"hello");
+    assert_eq!(value.secret, vec![0xde, 0xad, 0xbe, 0xef]);
+}
@@ -0,0 +1,54 @@
+#![allow(dead_code)]
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Profile {
+    #[ibuilder(default = "anonymous")]
+    nickname: String,
+    name: String,
+    #[ibuilder(default = 18)]
+    age: u8,
+    email: String,
+}
+
+fn ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_missing_first() {
+    let mut builder = Profile::builder().with_config(BuilderConfig {
+        choice_order: ChoiceOrder::MissingFirst,
+        ..Default::default()
+    });
+    assert_eq!(
+        ids(&builder.get_options()),
+        vec!["name", "email", "nickname", "age"]
+    );
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("Alice")).unwrap();
+    assert_eq!(
+        ids(&builder.get_options()),
+        vec!["email", "nickname", "name", "age"]
+    );
+    builder.choose(Input::choice("email")).unwrap();
+    builder.choose(Input::text("alice@example.com")).unwrap();
+    assert_eq!(
+        ids(&builder.get_options()),
+        vec!["nickname", "name", "age", "email", FINALIZE_ID]
+    );
+}
+
+#[test]
+fn test_declaration_order() {
+    let builder = Profile::builder();
+    assert_eq!(
+        ids(&builder.get_options()),
+        vec!["nickname", "name", "age", "email"]
+    );
+}
@@ -0,0 +1,60 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Deploy {
+    #[ibuilder(choices("dev", "staging", "prod"))]
+    environment: String,
+    #[ibuilder(choices("eu-west-1", "us-east-1"), allow_other)]
+    region: String,
+}
+
+fn choice_ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_choices() {
+    let mut builder = Deploy::builder();
+    builder.choose(Input::choice("environment")).unwrap();
+    let options = builder.get_options();
+    assert!(!options.text_input);
+    assert_eq!(
+        choice_ids(&options),
+        vec!["dev", "staging", "prod", BACK_ID]
+    );
+    assert_eq!(
+        builder.choose(Input::text("qa")),
+        Err(ChooseError::UnexpectedText)
+    );
+    builder.choose(Input::choice("staging")).unwrap();
+    builder.choose(Input::choice("region")).unwrap();
+    builder.choose(Input::choice("us-east-1")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Deploy {
+            environment: "staging".into(),
+            region: "us-east-1".into(),
+        }
+    );
+}
+
+#[test]
+fn test_choices_allow_other() {
+    let mut builder = Deploy::builder();
+    builder.choose(Input::choice("region")).unwrap();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    assert_eq!(
+        choice_ids(&options),
+        vec!["eu-west-1", "us-east-1", BACK_ID]
+    );
+    builder.choose(Input::text("ap-south-1")).unwrap();
+    assert_eq!(
+        builder.partial_state().get::<String>("region"),
+        Some("ap-south-1".to_string())
+    );
+}
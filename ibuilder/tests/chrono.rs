@@ -0,0 +1,164 @@
+#![cfg(feature = "chrono")]
+
+use chrono::{Duration, Local, NaiveDate, TimeZone, Utc};
+
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Event {
+    day: NaiveDate,
+    start: chrono::DateTime<Utc>,
+    length: Duration,
+}
+
+fn date(text: &str) -> Result<NaiveDate, ChooseError> {
+    let mut builder = NaiveDate::new_buildable_value(Default::default());
+    builder.apply(&Input::text(text), &[])?;
+    Ok(*builder.get_value_any().unwrap().downcast().unwrap())
+}
+
+fn duration(text: &str) -> Result<Duration, ChooseError> {
+    let mut builder = Duration::new_buildable_value(Default::default());
+    builder.apply(&Input::text(text), &[])?;
+    Ok(*builder.get_value_any().unwrap().downcast().unwrap())
+}
+
+#[test]
+fn test_date_formats() {
+    let expected = NaiveDate::from_ymd_opt(2024, 3, 5).unwrap();
+    for text in &[
+        "2024-03-05",
+        "2024/03/05",
+        "05/03/2024",
+        "5.3.2024",
+        "5 March 2024",
+        "Mar 5, 2024",
+    ] {
+        assert_eq!(date(text).unwrap(), expected, "{}", text);
+    }
+    let today = Local::now().date_naive();
+    assert_eq!(date("Tomorrow").unwrap(), today.succ_opt().unwrap());
+    assert!(matches!(
+        date("2024-02-30"),
+        Err(ChooseError::InvalidText { .. })
+    ));
+}
+
+#[test]
+fn test_duration_formats() {
+    assert_eq!(duration("1h30m").unwrap(), Duration::minutes(90));
+    assert_eq!(duration("90s").unwrap(), Duration::seconds(90));
+    assert_eq!(duration("2 days 3h").unwrap(), Duration::hours(51));
+    assert_eq!(
+        duration("1w 500ms").unwrap(),
+        Duration::weeks(1) + Duration::milliseconds(500)
+    );
+    assert_eq!(
+        duration("90"),
+        Err(ChooseError::InvalidText {
+            error: "Missing the unit after 90".into()
+        })
+    );
+    assert!(matches!(
+        duration("3 fortnights"),
+        Err(ChooseError::InvalidText { .. })
+    ));
+}
+
+#[test]
+fn test_chrono_builder() {
+    let mut builder = Event::builder();
+    builder.choose(Input::choice("day")).unwrap();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    assert_eq!(options.choices[0].choice_id, "today");
+    builder.choose(Input::choice("today")).unwrap();
+    builder.choose(Input::choice("start")).unwrap();
+    builder
+        .choose(Input::text("2024-03-05T10:30:00+01:00"))
+        .unwrap();
+    builder.choose(Input::choice("length")).unwrap();
+    let options = builder.get_options();
+    assert!(options
+        .choices
+        .iter()
+        .all(|choice| choice.choice_id == BACK_ID));
+    builder.choose(Input::text("1h30m")).unwrap();
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[2] {
+            FieldKind::Named(_, Node::Leaf(Field::String(length))) => assert_eq!(length, "1h 30m"),
+            field => panic!("Unexpected field {:?}", field),
+        },
+        node => panic!("Unexpected node {:?}", node),
+    }
+    let event = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(event.day, Local::now().date_naive());
+    assert_eq!(
+        event.start,
+        Utc.with_ymd_and_hms(2024, 3, 5, 9, 30, 0).unwrap()
+    );
+    assert_eq!(event.length, Duration::minutes(90));
+}
+
+#[test]
+fn test_datetime_now() {
+    let mut builder = chrono::DateTime::<Utc>::new_buildable_value(Default::default());
+    let before = Utc::now();
+    builder.apply(&Input::choice("now"), &[]).unwrap();
+    let now = *builder
+        .get_value_any()
+        .unwrap()
+        .downcast::<chrono::DateTime<Utc>>()
+        .unwrap();
+    assert!(now >= before && now <= Utc::now());
+    assert_eq!(
+        builder.apply(&Input::choice("today"), &[]),
+        Err(ChooseError::UnexpectedChoice)
+    );
+}
+
+#[test]
+fn test_datetime_now_recorded() {
+    let mut builder = Event::builder();
+    builder.choose(Input::choice("start")).unwrap();
+    builder.choose(Input::text("Now")).unwrap();
+    builder.choose(Input::choice("day")).unwrap();
+    builder.choose(Input::choice("today")).unwrap();
+    let history = builder.history();
+    let start = match &history[1].input {
+        Input::Text(text) => chrono::DateTime::parse_from_rfc3339(text).unwrap(),
+        input => panic!("Unexpected input {:?}", input),
+    };
+    assert_eq!(
+        history[3].input,
+        Input::text(Local::now().date_naive().to_string())
+    );
+
+    // the replay builds the same value
+    let state = builder.save_state();
+    let mut builder = Event::builder();
+    builder.restore_state(&state).unwrap();
+    builder.choose(Input::choice("length")).unwrap();
+    builder.choose(Input::text("1h")).unwrap();
+    assert_eq!(builder.finalize().unwrap().start, start);
+}
+
+#[test]
+fn test_chrono_fixed_clock() {
+    let fixed = Utc.with_ymd_and_hms(2024, 3, 5, 12, 0, 0).unwrap();
+    let mut builder = Event::builder().with_config(BuilderConfig {
+        clock: Clock::default().with_system_time(move || fixed.into()),
+        ..Default::default()
+    });
+    builder.choose(Input::choice("day")).unwrap();
+    builder.choose(Input::text("tomorrow")).unwrap();
+    builder.choose(Input::choice("start")).unwrap();
+    builder.choose(Input::text("now")).unwrap();
+    builder.choose(Input::choice("length")).unwrap();
+    builder.choose(Input::text("1h")).unwrap();
+    let event = builder.finalize().unwrap();
+    let today = chrono::DateTime::<Local>::from(std::time::SystemTime::from(fixed)).date_naive();
+    assert_eq!(event.day, today.succ_opt().unwrap());
+    assert_eq!(event.start, fixed);
+}
@@ -0,0 +1,71 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use ibuilder::metrics::Counters;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Base {
+    integer: i32,
+}
+
+/// A clock that moves only when told to.
+fn manual_clock() -> (Clock, Rc<Cell<Instant>>) {
+    let now = Rc::new(Cell::new(Instant::now()));
+    let clock = {
+        let now = now.clone();
+        Clock::new(move || now.get())
+    };
+    (clock, now)
+}
+
+fn advance(now: &Cell<Instant>, millis: u64) {
+    now.set(now.get() + Duration::from_millis(millis));
+}
+
+#[test]
+fn test_clock_debounce() {
+    let (clock, now) = manual_clock();
+    let mut builder = Base::builder().with_config(BuilderConfig {
+        debounce: Some(Duration::from_millis(100)),
+        clock,
+        ..Default::default()
+    });
+    builder.choose(Input::choice("integer")).unwrap();
+    advance(&now, 99);
+    assert_eq!(
+        builder.choose(Input::choice("integer")).unwrap_err(),
+        ChooseError::RateLimited
+    );
+    // the same input is not a double click anymore, but it's not valid in this menu
+    advance(&now, 1);
+    assert_eq!(
+        builder.choose(Input::choice("integer")).unwrap_err(),
+        ChooseError::UnexpectedChoice
+    );
+}
+
+#[test]
+fn test_clock_metrics() {
+    let (clock, now) = manual_clock();
+    let counters = Rc::new(RefCell::new(Counters::default()));
+    let mut builder = Base::builder()
+        .with_config(BuilderConfig {
+            clock,
+            ..Default::default()
+        })
+        .with_metrics(counters.clone());
+    advance(&now, 1000);
+    builder.choose(Input::choice("integer")).unwrap();
+    advance(&now, 500);
+    builder.choose(Input::text("42")).unwrap();
+    advance(&now, 250);
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.integer, 42);
+
+    let counters = counters.borrow();
+    assert_eq!(counters.dwell[""], Duration::from_millis(1250));
+    assert_eq!(counters.dwell["integer"], Duration::from_millis(500));
+    assert_eq!(counters.finalized, Some(Duration::from_millis(1750)));
+}
@@ -0,0 +1,46 @@
+mod mod1 {
+    use ibuilder::*;
+
+    #[derive(IBuilder)]
+    #[ibuilder(vis = "pub(crate)")]
+    pub struct Foo {
+        bar: i32,
+        baz: Baz,
+    }
+
+    #[derive(IBuilder)]
+    #[ibuilder(vis = "pub(crate)")]
+    pub struct Unnamed(i32);
+
+    #[derive(IBuilder)]
+    #[ibuilder(vis = "pub(crate)")]
+    pub enum Baz {
+        Empty,
+        Named { field: i32 },
+        Unnamed(Unnamed),
+    }
+}
+
+mod mod2 {
+    use ibuilder::*;
+
+    // same names of the items of mod1, the generated types don't collide
+    #[derive(IBuilder)]
+    pub struct Foo {
+        bar: i32,
+    }
+
+    #[derive(IBuilder)]
+    pub enum Baz {
+        Named { field: i32 },
+    }
+}
+
+fn main() {
+    use ibuilder::BuildableValue;
+    let builder = mod1::__Foo_BuildableValueImpl::new(Default::default());
+    let _ = builder.get_options(&[]);
+    let builder = mod1::__Baz_BuildableValueImpl::new(Default::default());
+    let _ = builder.get_options(&[]);
+    let _ = mod1::__Unnamed_BuildableValueImpl::new(Default::default());
+}
@@ -0,0 +1,52 @@
+use ibuilder::types::Confirm;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Deletion {
+    path: String,
+    #[ibuilder(prompt = "Delete recursively?")]
+    recursive: Confirm,
+}
+
+#[test]
+fn test_confirm() {
+    let mut builder = Builder::confirm_with_labels("Delete the file?", "Delete", "Keep");
+    let options = builder.get_options();
+    assert_eq!(options.query, "Delete the file?");
+    let choices: Vec<_> = options
+        .choices
+        .iter()
+        .map(|c| (c.choice_id.as_str(), c.text.as_str()))
+        .collect();
+    assert_eq!(choices, vec![("yes", "Delete"), ("no", "Keep")]);
+    assert_eq!(
+        builder.answer(Input::text("yes")),
+        Err(ChooseError::UnexpectedText)
+    );
+    assert_eq!(
+        builder.answer(Input::choice("maybe")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    assert_eq!(builder.answer(Input::choice("no")), Ok(Some(Confirm::No)));
+}
+
+#[test]
+fn test_confirm_default_labels() {
+    let mut builder = Builder::confirm("Continue?");
+    assert_eq!(builder.get_options().choices[0].text, "Yes");
+    let answer = builder.answer(Input::choice("yes")).unwrap().unwrap();
+    assert!(answer.is_yes());
+    assert!(bool::from(answer));
+}
+
+#[test]
+fn test_confirm_field() {
+    let mut builder = Deletion::builder();
+    builder.choose(Input::choice("path")).unwrap();
+    builder.choose(Input::text("/tmp")).unwrap();
+    builder.choose(Input::choice("recursive")).unwrap();
+    assert_eq!(builder.get_options().query, "Delete recursively?");
+    builder.choose(Input::choice("yes")).unwrap();
+    let deletion = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(deletion.recursive, Confirm::Yes);
+}
@@ -0,0 +1,54 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+#[ibuilder(constructor = "Range::new")]
+struct Range {
+    start: i64,
+    end: i64,
+    #[ibuilder(hidden, default = 1)]
+    step: i64,
+}
+
+impl Range {
+    fn new(start: i64, end: i64, step: i64) -> Range {
+        Range {
+            start: start.min(end),
+            end: start.max(end),
+            step,
+        }
+    }
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+#[ibuilder(constructor = "Even::from_half")]
+struct Even(u32);
+
+impl Even {
+    fn from_half(half: u32) -> Even {
+        Even(half * 2)
+    }
+}
+
+#[test]
+fn test_named_constructor() {
+    let mut builder = Range::builder();
+    builder.choose(Input::choice("start")).unwrap();
+    builder.choose(Input::text("10")).unwrap();
+    builder.choose(Input::choice("end")).unwrap();
+    builder.choose(Input::text("3")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Range {
+            start: 3,
+            end: 10,
+            step: 1
+        }
+    );
+}
+
+#[test]
+fn test_unnamed_constructor() {
+    let mut builder = Even::builder();
+    builder.choose(Input::text("21")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Even(42));
+}
@@ -0,0 +1,24 @@
+#![cfg(feature = "cron")]
+
+use std::str::FromStr;
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Job {
+    schedule: cron::Schedule,
+}
+
+#[test]
+fn test_cron() {
+    let mut builder = Job::builder();
+    builder.choose(Input::choice("schedule")).unwrap();
+    let res = builder.choose(Input::text("every day at noon"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("0 30 9 * * Mon-Fri *")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value.schedule,
+        cron::Schedule::from_str("0 30 9 * * Mon-Fri *").unwrap()
+    );
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "data")]
+
+use ibuilder::data::{CountryCode, CurrencyCode, TimeZone};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Shop {
+    country: CountryCode,
+    currency: CurrencyCode,
+    timezone: TimeZone,
+}
+
+#[test]
+fn test_exact_match() {
+    let mut builder = Shop::builder();
+    builder.choose(Input::choice("country")).unwrap();
+    builder.choose(Input::text("it")).unwrap();
+    builder.choose(Input::choice("currency")).unwrap();
+    builder.choose(Input::text("euro")).unwrap();
+    builder.choose(Input::choice("timezone")).unwrap();
+    builder.choose(Input::text("Europe/Rome")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Shop {
+            country: CountryCode("IT".into()),
+            currency: CurrencyCode("EUR".into()),
+            timezone: TimeZone("Europe/Rome".into()),
+        }
+    );
+}
+
+#[test]
+fn test_search() {
+    let mut builder = Shop::builder();
+    builder.choose(Input::choice("currency")).unwrap();
+    builder.choose(Input::text("franc")).unwrap();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    assert!(options.choices.iter().any(|c| c.choice_id == "CHF"));
+    assert!(options.choices.iter().any(|c| c.choice_id == "XOF"));
+    assert!(!options.choices.iter().any(|c| c.choice_id == "EUR"));
+    // refine the search until a single entry matches
+    builder.choose(Input::text("new zealand")).unwrap();
+    let options = builder.get_options();
+    assert!(options.choices.iter().any(|c| c.choice_id == "timezone"));
+    builder.choose(Input::choice("timezone")).unwrap();
+    builder.choose(Input::text("a")).unwrap();
+    let options = builder.get_options();
+    let shown = options.choices.iter().filter(|c| c.choice_id != BACK_ID);
+    assert_eq!(shown.count(), 20);
+    assert!(options.notice.is_some());
+    builder.choose(Input::choice("Asia/Tokyo")).unwrap();
+    builder.choose(Input::choice("country")).unwrap();
+    let res = builder.choose(Input::text("Atlantis"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("Japan")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.currency.to_string(), "NZD");
+    assert_eq!(value.timezone.0, "Asia/Tokyo");
+    assert_eq!(value.country.0, "JP");
+}
@@ -0,0 +1,38 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+enum Theme {
+    Light,
+    #[ibuilder(default)]
+    Dark,
+    Custom(String),
+}
+
+#[derive(Debug, IBuilder)]
+struct Settings {
+    theme: Theme,
+    #[ibuilder(default = true)]
+    telemetry: bool,
+    name: String,
+}
+
+#[test]
+fn test_default_choice() {
+    let mut builder = Settings::builder();
+    assert_eq!(builder.get_options().default_choice, None);
+    builder.choose(Input::choice("theme")).unwrap();
+    assert_eq!(builder.get_options().default_choice, Some("Dark".into()));
+    builder.choose(Input::choice("Custom")).unwrap();
+    builder.choose(Input::text("solarized")).unwrap();
+    assert_eq!(builder.get_options().default_choice, Some("Custom".into()));
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("telemetry")).unwrap();
+    assert_eq!(builder.get_options().default_choice, Some("true".into()));
+    builder.choose(Input::choice("false")).unwrap();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("home")).unwrap();
+    assert_eq!(
+        builder.get_options().default_choice,
+        Some(FINALIZE_ID.into())
+    );
+}
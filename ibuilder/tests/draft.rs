@@ -0,0 +1,65 @@
+use std::convert::TryFrom;
+
+use ibuilder::*;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, IBuilder)]
+#[ibuilder(draft(derive(Debug, Clone, PartialEq, Serialize, Deserialize)))]
+pub struct Profile {
+    pub name: String,
+    pub age: u8,
+    #[ibuilder(hidden, default = 42)]
+    pub secret: u32,
+}
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(draft)]
+struct Minimal {
+    field: i32,
+}
+
+#[test]
+fn test_draft_from_builder() {
+    let mut builder = Profile::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("Edoardo")).unwrap();
+
+    let draft = ProfileDraft::from(&builder);
+    assert_eq!(
+        draft,
+        ProfileDraft {
+            name: Some("Edoardo".to_string()),
+            age: None,
+        }
+    );
+    assert_eq!(Profile::try_from(draft), Err(FinalizeError::MissingField));
+}
+
+#[test]
+fn test_draft_serde() {
+    let draft = ProfileDraft {
+        name: Some("Edoardo".to_string()),
+        age: None,
+    };
+    let json = serde_json::to_string(&draft).unwrap();
+    assert_eq!(json, r#"{"name":"Edoardo","age":null}"#);
+    let mut draft: ProfileDraft = serde_json::from_str(&json).unwrap();
+    draft.age = Some(24);
+    let profile = Profile::try_from(draft).unwrap();
+    assert_eq!(
+        profile,
+        Profile {
+            name: "Edoardo".to_string(),
+            age: 24,
+            secret: 42,
+        }
+    );
+}
+
+#[test]
+fn test_draft_default() {
+    let draft = MinimalDraft::default();
+    assert!(draft.field.is_none());
+    let value = Minimal::try_from(MinimalDraft { field: Some(1) }).unwrap();
+    assert_eq!(value.field, 1);
+}
@@ -0,0 +1,128 @@
+use ibuilder::types::HttpMethod;
+use ibuilder::*;
+
+#[derive(Debug, Clone, IBuilder, PartialEq)]
+struct Route {
+    path: String,
+    methods: Vec<HttpMethod>,
+    target: Target,
+    #[ibuilder(hidden, default = "42")]
+    weight: u32,
+}
+
+#[derive(Debug, Clone, IBuilder, PartialEq)]
+enum Target {
+    Static(String),
+    Proxy {
+        upstream: String,
+        timeout: Option<u32>,
+    },
+    Redirect,
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Routes {
+    routes: Vec<Route>,
+}
+
+#[test]
+fn test_duplicate_item() {
+    let mut builder = Routes::builder();
+    builder.choose(Input::choice("routes")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice("path")).unwrap();
+    builder.choose(Input::text("/api")).unwrap();
+    builder.choose(Input::choice("methods")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice("GET")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("target")).unwrap();
+    builder.choose(Input::choice("Proxy")).unwrap();
+    builder.choose(Input::choice("upstream")).unwrap();
+    builder.choose(Input::text("backend")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let options = builder.get_options();
+    assert!(options.choices.iter().any(|c| c.choice_id == "__duplicate"));
+    builder.choose(Input::choice("__duplicate")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.query, "Select the item to duplicate");
+    assert_eq!(options.choices[0].text, "Duplicate item 0");
+    builder.choose(Input::choice("0")).unwrap();
+
+    // the copy is independent from the original
+    builder.choose(Input::choice("1")).unwrap();
+    builder.choose(Input::choice("path")).unwrap();
+    builder.choose(Input::text("/www")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    let route = |path: &str| Route {
+        path: path.into(),
+        methods: vec![HttpMethod::Get],
+        target: Target::Proxy {
+            upstream: "backend".into(),
+            timeout: None,
+        },
+        weight: 42,
+    };
+    assert_eq!(value.routes, vec![route("/api"), route("/www")]);
+}
+
+#[test]
+fn test_duplicate_incomplete_item() {
+    let mut builder = Routes::builder();
+    builder.choose(Input::choice("routes")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("__duplicate")).unwrap();
+    builder.choose(Input::choice("0")).unwrap();
+    assert_eq!(builder.partial_state().get::<Vec<Route>>("routes"), None);
+    let options = builder.get_options();
+    let pending = options.choices.iter().filter(|c| c.needs_action).count();
+    assert_eq!(pending, 2);
+}
+
+#[test]
+fn test_duplicate_invalid_item() {
+    let mut builder = Routes::builder();
+    builder.choose(Input::choice("routes")).unwrap();
+    assert!(!builder
+        .get_options()
+        .choices
+        .iter()
+        .any(|c| c.choice_id == "__duplicate"));
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("__duplicate")).unwrap();
+    assert_eq!(
+        builder.choose(Input::choice("7")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+}
+
+#[test]
+fn test_clone_boxed() {
+    let mut builder = Target::new_buildable_value(Default::default());
+    builder.apply(&Input::choice("Static"), &[]).unwrap();
+    builder
+        .apply(&Input::text("/var/www"), &["Static".into()])
+        .unwrap();
+    let copy = builder.clone_boxed().unwrap();
+    builder.apply(&Input::choice("Redirect"), &[]).unwrap();
+    assert_eq!(
+        *copy.get_value_any().unwrap().downcast::<Target>().unwrap(),
+        Target::Static("/var/www".into())
+    );
+    assert_eq!(
+        *builder
+            .get_value_any()
+            .unwrap()
+            .downcast::<Target>()
+            .unwrap(),
+        Target::Redirect
+    );
+}
@@ -0,0 +1,84 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Base {
+    integer: i32,
+}
+
+fn recovery_builder() -> Builder<Base> {
+    Base::builder().with_config(BuilderConfig {
+        error_recovery: true,
+        ..Default::default()
+    })
+}
+
+fn choice_ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_no_recovery_by_default() {
+    let mut builder = Base::builder();
+    builder.choose(Input::choice("integer")).unwrap();
+    assert!(builder.choose(Input::text("nope")).is_err());
+    let options = builder.get_options();
+    assert!(options.text_input);
+}
+
+#[test]
+fn test_retry() {
+    let mut builder = recovery_builder();
+    builder.choose(Input::choice("integer")).unwrap();
+    let err = builder.choose(Input::text("nope")).unwrap_err();
+
+    let options = builder.get_options();
+    assert_eq!(options.query, err.to_string());
+    assert!(!options.text_input);
+    assert_eq!(choice_ids(&options), vec![RETRY_ID, BACK_ID]);
+
+    // only the recovery choices are accepted
+    assert_eq!(
+        builder.choose(Input::text("42")),
+        Err(ChooseError::UnexpectedText)
+    );
+    assert_eq!(
+        builder.choose(Input::choice("integer")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+
+    builder.choose(Input::choice(RETRY_ID)).unwrap();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    builder.choose(Input::text("42")).unwrap();
+    assert!(builder.is_done());
+}
+
+#[test]
+fn test_go_back() {
+    let mut builder = recovery_builder();
+    builder.choose(Input::choice("integer")).unwrap();
+    builder.choose(Input::text("nope")).unwrap_err();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let options = builder.get_options();
+    assert_eq!(choice_ids(&options), vec!["integer"]);
+}
+
+#[test]
+fn test_main_menu_error() {
+    let mut builder = recovery_builder();
+    builder.choose(Input::choice("nope")).unwrap_err();
+
+    let options = builder.get_options();
+    assert_eq!(choice_ids(&options), vec![RETRY_ID]);
+    assert_eq!(
+        builder.choose(Input::choice(BACK_ID)),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    builder.choose(Input::choice(RETRY_ID)).unwrap();
+    assert_eq!(choice_ids(&builder.get_options()), vec!["integer"]);
+}
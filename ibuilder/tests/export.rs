@@ -0,0 +1,94 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct Ticket {
+    title: String,
+    priority: u8,
+}
+
+fn session() -> Builder<Ticket> {
+    let now = Rc::new(Cell::new(Instant::now()));
+    let clock = {
+        let now = now.clone();
+        Clock::new(move || now.get())
+    };
+    let mut builder = Ticket::builder().with_config(BuilderConfig {
+        clock,
+        ..Default::default()
+    });
+    let mut step = |input: Input| {
+        now.set(now.get() + Duration::from_secs(1));
+        let _ = builder.choose(input);
+    };
+    step(Input::choice("title"));
+    step(Input::text("Broken login"));
+    step(Input::choice("priority"));
+    step(Input::text("high"));
+    step(Input::text("1"));
+    now.set(now.get() + Duration::from_secs(1));
+    builder
+}
+
+#[test]
+fn test_export() {
+    let builder = session();
+    let export = builder.export().unwrap();
+    assert_eq!(
+        export.value,
+        Ticket {
+            title: "Broken login".into(),
+            priority: 1
+        }
+    );
+    assert_eq!(export.transcript.len(), 5);
+    assert_eq!(
+        export.transcript[0],
+        ExportedInput {
+            elapsed: Duration::from_secs(1),
+            path: vec!["title".into()],
+            input: Input::choice("title"),
+            error: None,
+        }
+    );
+    assert!(export.transcript[3].error.is_some());
+    assert_eq!(
+        export.stats,
+        ExportStats {
+            duration: Duration::from_secs(6),
+            inputs: 5,
+            rejected: 1,
+        }
+    );
+    match &export.node {
+        Node::Composite(_, fields) => match &fields[1] {
+            FieldKind::Named(_, Node::Leaf(Field::String(priority))) => assert_eq!(priority, "1"),
+            field => panic!("Unexpected field {:?}", field),
+        },
+        node => panic!("Unexpected node {:?}", node),
+    }
+}
+
+#[test]
+fn test_export_incomplete() {
+    let builder = Ticket::builder();
+    assert!(matches!(
+        builder.export(),
+        Err(FinalizeError::Incomplete { .. })
+    ));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_export_serialize() {
+    let export = session().export().unwrap();
+    let json = serde_json::to_value(&export).unwrap();
+    assert_eq!(json["value"]["title"], "Broken login");
+    assert_eq!(json["stats"]["rejected"], 1);
+    assert_eq!(json["transcript"][1]["input"]["Text"], "Broken login");
+}
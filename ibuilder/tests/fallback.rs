@@ -0,0 +1,102 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Ticket {
+    #[ibuilder(default = 1, max_attempts = 3, fallback_default)]
+    quantity: u32,
+    #[ibuilder(max_attempts = 2, fallback_default)]
+    seat: Option<u32>,
+}
+
+#[test]
+fn test_fallback_default() {
+    let mut builder = Ticket::builder();
+    builder.choose(Input::choice("quantity")).unwrap();
+    builder.choose(Input::text("five")).unwrap_err();
+    builder.choose(Input::text("5")).unwrap();
+
+    // a valid text resets the attempts
+    builder.choose(Input::choice("quantity")).unwrap();
+    builder.choose(Input::text("six")).unwrap_err();
+    builder.choose(Input::text("seven")).unwrap_err();
+    assert_eq!(builder.get_options().query, "Type an integer");
+    builder.choose(Input::text("eight")).unwrap();
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+
+    builder.choose(Input::choice("seat")).unwrap();
+    builder.choose(Input::choice("__set")).unwrap();
+    assert!(matches!(
+        builder.choose(Input::text("A1")),
+        Err(ChooseError::InvalidText { .. })
+    ));
+    builder.choose(Input::text("B2")).unwrap();
+    // back in the menu of the option, without a value
+    assert_eq!(builder.get_options().query, "Choose an option");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Ticket {
+            quantity: 1,
+            seat: None,
+        }
+    );
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Order {
+    #[ibuilder(default = 1, max_attempts = 2, fallback_default)]
+    a: u32,
+    b: u32,
+}
+
+/// Trigger the fallback of `a` and then insert `b`.
+fn fallen_back() -> Builder<Order> {
+    let mut builder = Order::builder();
+    builder.choose(Input::choice("a")).unwrap();
+    builder.choose(Input::text("3")).unwrap();
+    builder.choose(Input::choice("a")).unwrap();
+    builder.choose(Input::text("x")).unwrap_err();
+    builder.choose(Input::text("y")).unwrap();
+    builder.choose(Input::choice("b")).unwrap();
+    builder.choose(Input::text("5")).unwrap();
+    builder
+}
+
+#[test]
+fn test_fallback_recorded() {
+    let builder = fallen_back();
+    let inputs = builder.save_state().inputs;
+    assert_eq!(inputs[3], Input::choice(builders::FALLBACK_ID));
+    assert!(!inputs.contains(&Input::text("y")));
+}
+
+#[test]
+fn test_fallback_restore_state() {
+    let builder = fallen_back();
+    let state = builder.save_state();
+    let mut restored = Order::builder();
+    restored.restore_state(&state).unwrap();
+    assert_eq!(restored.finalize().unwrap(), Order { a: 1, b: 5 });
+    assert_eq!(restored.save_state(), state);
+}
+
+#[test]
+fn test_fallback_undo() {
+    let mut builder = fallen_back();
+    // only the value of b is reverted
+    assert!(builder.undo());
+    assert_eq!(builder.get_options().query, "Type an integer");
+    builder.choose(Input::text("6")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Order { a: 1, b: 6 });
+    // then the entering of b, and the fallback
+    assert!(builder.undo());
+    assert!(builder.undo());
+    assert!(builder.undo());
+    assert_eq!(builder.get_options().query, "Type an integer");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("b")).unwrap();
+    builder.choose(Input::text("7")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Order { a: 3, b: 7 });
+}
@@ -0,0 +1,36 @@
+use std::marker::PhantomData;
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Account {
+    #[ibuilder(rename = "user name")]
+    username: String,
+    email: Option<String>,
+    #[ibuilder(hidden, default = 1)]
+    version: u32,
+}
+
+#[derive(Debug, IBuilder)]
+struct Tagged<T: NewBuildableValue + 'static> {
+    value: T,
+    marker: PhantomData<T>,
+}
+
+assert_fields!(Account, ["email", "username"]);
+assert_fields!(Tagged<u8>, ["value"]);
+
+#[test]
+fn test_field_list() {
+    assert_eq!(Account::FIELD_COUNT, 2);
+    assert_eq!(Account::FIELD_NAMES, &["username", "email"]);
+    assert_eq!(Tagged::<u8>::FIELD_NAMES, &["value"]);
+}
+
+#[test]
+fn test_fields_match() {
+    assert!(fields_match(&["a", "b"], &["b", "a", "a"]));
+    assert!(fields_match(&[], &[]));
+    assert!(!fields_match(&["a", "b"], &["a"]));
+    assert!(!fields_match(&["a"], &["a", "ab"]));
+}
@@ -0,0 +1,105 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Config {
+    name: String,
+    server: Server,
+    database: Database,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Database {
+    #[ibuilder(rename = "database host")]
+    host: String,
+    pool_size: u32,
+}
+
+fn ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+fn builder() -> Builder<Config> {
+    Config::builder().with_config(BuilderConfig {
+        field_search: true,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_field_search() {
+    let mut builder = builder();
+    assert_eq!(
+        ids(&builder.get_options()),
+        vec!["name", "server", "database", SEARCH_ID]
+    );
+    builder.choose(Input::choice(SEARCH_ID)).unwrap();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    assert_eq!(ids(&options), vec![BACK_ID]);
+
+    builder.choose(Input::text("host")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(ids(&options), vec!["server.host", "database.host", BACK_ID]);
+    assert_eq!(options.choices[1].text, "database host (database > host)");
+
+    builder.choose(Input::choice("database.host")).unwrap();
+    assert_eq!(builder.get_options().query, "Type a string");
+    builder.choose(Input::text("db.local")).unwrap();
+    // back in the menu of the database
+    assert_eq!(
+        ids(&builder.get_options()),
+        vec!["host", "pool_size", BACK_ID]
+    );
+}
+
+#[test]
+fn test_field_search_fuzzy() {
+    let mut builder = builder();
+    builder.choose(Input::choice(SEARCH_ID)).unwrap();
+    // "pool_size" contains "psz" in order
+    builder.choose(Input::text("PSZ")).unwrap();
+    assert_eq!(
+        ids(&builder.get_options()),
+        vec!["database.pool_size", BACK_ID]
+    );
+    builder.choose(Input::choice("database.pool_size")).unwrap();
+    builder.choose(Input::text("4")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(
+        ids(&builder.get_options()),
+        vec!["name", "server", "database", SEARCH_ID]
+    );
+}
+
+#[test]
+fn test_field_search_errors() {
+    let mut builder = builder();
+    builder.choose(Input::choice(SEARCH_ID)).unwrap();
+    assert!(matches!(
+        builder.choose(Input::text("nope")),
+        Err(ChooseError::InvalidText { .. })
+    ));
+    assert_eq!(
+        builder.choose(Input::choice("server.host")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(!builder.get_options().text_input);
+
+    // the search is disabled by default
+    let mut builder = Config::builder();
+    assert_eq!(
+        builder.choose(Input::choice(SEARCH_ID)),
+        Err(ChooseError::UnexpectedChoice)
+    );
+}
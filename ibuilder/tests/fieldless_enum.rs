@@ -0,0 +1,55 @@
+use std::cmp::Ordering;
+
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+const ORDERING: BuilderFn = ibuilder_for_fieldless_enum!(Ordering, [Less, Equal, Greater]);
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Config {
+    #[ibuilder(builder = "ORDERING", prompt = "Pick an ordering")]
+    ordering: Ordering,
+    #[ibuilder(builder = "ORDERING")]
+    other: Ordering,
+}
+
+#[test]
+fn test_fieldless_enum() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("ordering")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.query, "Pick an ordering");
+    assert!(!options.text_input);
+    let choices: Vec<_> = options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect();
+    assert_eq!(choices, vec!["Less", "Equal", "Greater", BACK_ID]);
+    assert_eq!(
+        builder.choose(Input::choice("Nope")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    assert_eq!(
+        builder.choose(Input::text("Less")),
+        Err(ChooseError::UnexpectedText)
+    );
+    builder.choose(Input::choice("Greater")).unwrap();
+    builder.choose(Input::choice("other")).unwrap();
+    builder.choose(Input::choice("Less")).unwrap();
+
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[0] {
+            FieldKind::Named(name, Node::Leaf(Field::String(value))) => {
+                assert_eq!(name, "ordering");
+                assert_eq!(value, "Greater");
+            }
+            _ => panic!("expecting a named leaf"),
+        },
+        Node::Leaf(_) => panic!("expecting a composite"),
+    }
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.ordering, Ordering::Greater);
+    assert_eq!(value.other, Ordering::Less);
+}
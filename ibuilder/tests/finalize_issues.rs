@@ -0,0 +1,99 @@
+use std::rc::Rc;
+
+use ibuilder::builders::U8Builder;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(Debug, IBuilder)]
+enum Contact {
+    Email(String),
+    Phone { number: String },
+}
+
+#[derive(Debug, IBuilder)]
+struct Person {
+    name: String,
+    address: Address,
+    contact: Contact,
+    #[ibuilder(default = 3)]
+    pets: u8,
+}
+
+fn issue(path: &[&str], reason: FinalizeIssueReason) -> FinalizeIssue {
+    FinalizeIssue {
+        path: path.iter().map(|f| f.to_string()).collect(),
+        reason,
+    }
+}
+
+#[test]
+fn test_finalize_all_missing() {
+    let mut builder = Person::builder();
+    builder.choose(Input::choice("address")).unwrap();
+    builder.choose(Input::choice("city")).unwrap();
+    builder.choose(Input::text("Pisa")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("contact")).unwrap();
+    builder.choose(Input::choice("Phone")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap_err(),
+        FinalizeError::Incomplete {
+            issues: vec![
+                issue(&["name"], FinalizeIssueReason::Missing),
+                issue(&["address", "street"], FinalizeIssueReason::Missing),
+                // like in `Builder::paths`, the enums are reported as a whole
+                issue(&["contact"], FinalizeIssueReason::Missing),
+            ]
+        }
+    );
+}
+
+#[test]
+fn test_finalize_issue_display() {
+    assert_eq!(
+        issue(&["address", "street"], FinalizeIssueReason::Missing).to_string(),
+        "address.street is missing"
+    );
+    assert_eq!(
+        issue(&[], FinalizeIssueReason::Unbuildable).to_string(),
+        "The value cannot be built"
+    );
+}
+
+#[test]
+fn test_finalize_invalid_default() {
+    let builder = U8Builder::new(BuildableValueConfig {
+        default: Some(0),
+        validators: vec![Rc::new(|value: &u8| {
+            if *value > 0 {
+                Validation::Valid
+            } else {
+                Validation::Invalid("Must be positive".into())
+            }
+        })],
+        ..Default::default()
+    });
+    let mut builder = Builder::<u8>::from_buildable_value(Box::new(builder));
+    assert!(builder.is_done());
+    let issues = vec![issue(
+        &[],
+        FinalizeIssueReason::Invalid("Must be positive".into()),
+    )];
+    assert_eq!(
+        builder.finalize(),
+        Err(FinalizeError::Incomplete {
+            issues: issues.clone()
+        })
+    );
+    assert_eq!(
+        builder.choose(Input::choice(FINALIZE_ID)),
+        Err(ChooseError::InvalidText {
+            error: "The value is not valid: Must be positive".into()
+        })
+    );
+}
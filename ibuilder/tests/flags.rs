@@ -0,0 +1,88 @@
+#![cfg(feature = "bitflags")]
+
+use ibuilder::builders::FlagsBuilder;
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+bitflags::bitflags! {
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    struct Permissions: u8 {
+        const READ = 0b001;
+        const WRITE = 0b010;
+        const EXECUTE = 0b100;
+    }
+}
+
+impl NewBuildableValue for Permissions {
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(FlagsBuilder::<Permissions>::new(config))
+    }
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct File {
+    name: String,
+    permissions: Permissions,
+}
+
+fn permissions_node(builder: &Builder<File>) -> Field {
+    match builder.to_node() {
+        Node::Composite(_, fields) => fields
+            .into_iter()
+            .find_map(|f| match f {
+                FieldKind::Named(n, Node::Leaf(field)) if n == "permissions" => Some(field),
+                _ => None,
+            })
+            .unwrap(),
+        _ => panic!("Expecting a composite"),
+    }
+}
+
+#[test]
+fn test_toggle_flags() {
+    let mut builder = File::builder();
+    builder.choose(Input::choice("permissions")).unwrap();
+    builder.choose(Input::choice("READ")).unwrap();
+    // still in the flags menu
+    let options = builder.get_options();
+    assert_eq!(options.choices[0].text, "[x] READ");
+    assert_eq!(options.choices[1].text, "[ ] WRITE");
+    builder.choose(Input::choice("WRITE")).unwrap();
+    builder.choose(Input::choice("EXECUTE")).unwrap();
+    builder.choose(Input::choice("EXECUTE")).unwrap();
+    assert_eq!(
+        permissions_node(&builder),
+        Field::String("READ | WRITE".into())
+    );
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("foo")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        File {
+            name: "foo".into(),
+            permissions: Permissions::READ | Permissions::WRITE
+        }
+    );
+}
+
+#[test]
+fn test_flags_text() {
+    let mut builder = File::builder();
+    builder.choose(Input::choice("permissions")).unwrap();
+    builder.choose(Input::choice("WRITE")).unwrap();
+    builder.choose(Input::text("READ|EXECUTE")).unwrap();
+    assert_eq!(
+        permissions_node(&builder),
+        Field::String("READ | EXECUTE".into())
+    );
+    assert!(matches!(
+        builder.choose(Input::text("READ | DELETE")),
+        Err(ChooseError::InvalidText { .. })
+    ));
+    assert_eq!(
+        builder.choose(Input::choice("DELETE")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+}
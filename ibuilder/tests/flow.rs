@@ -0,0 +1,80 @@
+use ibuilder::flow::Flow;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Account {
+    username: String,
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Preferences {
+    #[ibuilder(default = "en")]
+    language: String,
+    dark_mode: bool,
+}
+
+fn choice_ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_flow() {
+    let mut flow = Flow::new((Account::builder(), Preferences::builder()));
+    assert_eq!(flow.step_count(), 2);
+    assert_eq!(flow.current_step(), 0);
+    assert_eq!(choice_ids(&flow.get_options()), vec!["username"]);
+    flow.choose(Input::choice("username")).unwrap();
+    flow.choose(Input::text("edomora97")).unwrap();
+    assert_eq!(flow.choose(Input::choice(FINALIZE_ID)).unwrap(), None);
+    assert_eq!(flow.current_step(), 1);
+    assert_eq!(
+        choice_ids(&flow.get_options()),
+        vec!["language", "dark_mode", BACK_ID]
+    );
+    flow.choose(Input::choice("dark_mode")).unwrap();
+    // inside a field "Go back" is handled by the builder
+    assert_eq!(
+        choice_ids(&flow.get_options()),
+        vec!["true", "false", BACK_ID]
+    );
+    flow.choose(Input::choice("true")).unwrap();
+    let (account, preferences) = flow.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        account,
+        Account {
+            username: "edomora97".to_string()
+        }
+    );
+    assert_eq!(
+        preferences,
+        Preferences {
+            language: "en".to_string(),
+            dark_mode: true,
+        }
+    );
+}
+
+#[test]
+fn test_flow_back() {
+    let mut flow = Flow::new((Account::builder(), Preferences::builder()));
+    flow.choose(Input::choice("username")).unwrap();
+    flow.choose(Input::text("edomora97")).unwrap();
+    flow.choose(Input::choice(FINALIZE_ID)).unwrap();
+    flow.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(flow.current_step(), 0);
+    // the first builder has no "Go back" in its main menu, and keeps its value
+    assert_eq!(
+        choice_ids(&flow.get_options()),
+        vec!["username", FINALIZE_ID]
+    );
+    assert_eq!(
+        flow.choose(Input::choice(BACK_ID)),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    flow.choose(Input::choice(FINALIZE_ID)).unwrap();
+    assert_eq!(flow.current_step(), 1);
+}
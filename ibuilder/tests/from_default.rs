@@ -0,0 +1,134 @@
+use std::collections::BTreeMap;
+
+use ibuilder::*;
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+struct Server {
+    host: String,
+    port: u16,
+    tls: Option<Tls>,
+    aliases: Vec<String>,
+    headers: BTreeMap<String, String>,
+    mode: Mode,
+    #[ibuilder(hidden)]
+    secret: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+struct Tls {
+    cert: String,
+}
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+enum Mode {
+    Static(String),
+    Proxy { upstream: String, retries: u8 },
+}
+
+impl Default for Server {
+    fn default() -> Self {
+        Server {
+            host: "localhost".into(),
+            port: 8080,
+            tls: Some(Tls {
+                cert: "cert.pem".into(),
+            }),
+            aliases: vec!["www".into(), "api".into()],
+            headers: vec![("x-server".to_string(), "ibuilder".to_string())]
+                .into_iter()
+                .collect(),
+            mode: Mode::Proxy {
+                upstream: "backend".into(),
+                retries: 3,
+            },
+            secret: 42,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Pool {
+    name: String,
+    #[ibuilder(min = 1)]
+    workers: u32,
+}
+
+impl Default for Pool {
+    fn default() -> Self {
+        Pool {
+            name: "default".into(),
+            workers: 0,
+        }
+    }
+}
+
+#[test]
+fn test_from_default() {
+    let mut builder = Builder::<Server>::from_default();
+    assert!(builder.is_done());
+    assert_eq!(builder.finalize().unwrap(), Server::default());
+
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("443")).unwrap();
+    let server = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        server,
+        Server {
+            port: 443,
+            ..Server::default()
+        }
+    );
+}
+
+#[test]
+fn test_from_default_edit_nested() {
+    let mut builder = Builder::<Server>::from_default();
+    builder.choose(Input::choice("aliases")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("cdn")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("mode")).unwrap();
+    builder.choose(Input::choice("Proxy")).unwrap();
+    builder.choose(Input::choice("retries")).unwrap();
+    builder.choose(Input::text("5")).unwrap();
+    let server = builder.finalize().unwrap();
+    assert_eq!(server.aliases, vec!["www", "api", "cdn"]);
+    assert_eq!(
+        server.mode,
+        Mode::Proxy {
+            upstream: "backend".into(),
+            retries: 5,
+        }
+    );
+    assert_eq!(server.secret, 42);
+}
+
+#[test]
+fn test_from_default_undo() {
+    let mut builder = Builder::<Server>::from_default();
+    builder.choose(Input::choice("host")).unwrap();
+    builder.choose(Input::text("example.com")).unwrap();
+    assert!(builder.undo());
+    assert!(builder.undo());
+    assert_eq!(builder.finalize().unwrap(), Server::default());
+}
+
+#[test]
+fn test_from_default_invalid() {
+    let mut builder = Builder::<Pool>::from_default();
+    // the default number of workers is rejected by the range, so it's left to be inserted
+    assert!(!builder.is_done());
+    assert_eq!(
+        builder.partial_state().get::<String>("name").unwrap(),
+        "default"
+    );
+    builder.choose(Input::choice("workers")).unwrap();
+    builder.choose(Input::text("4")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Pool {
+            name: "default".into(),
+            workers: 4,
+        }
+    );
+}
@@ -0,0 +1,59 @@
+use ibuilder::*;
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+struct Server {
+    host: String,
+    port: u16,
+    aliases: Vec<String>,
+    mode: Mode,
+}
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+enum Mode {
+    Static(String),
+    Proxy { upstream: String },
+}
+
+fn server() -> Server {
+    Server {
+        host: "localhost".into(),
+        port: 8080,
+        aliases: vec!["www".into()],
+        mode: Mode::Proxy {
+            upstream: "backend".into(),
+        },
+    }
+}
+
+#[test]
+fn test_from_value() {
+    let mut builder = Builder::from_value(server());
+    assert!(builder.is_done());
+    assert_eq!(builder.finalize().unwrap(), server());
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("9090")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Server {
+            port: 9090,
+            ..server()
+        }
+    );
+}
+
+#[test]
+fn test_builder_from() {
+    let mut builder = server().builder_from();
+    builder.choose(Input::choice("mode")).unwrap();
+    builder.choose(Input::choice("Proxy")).unwrap();
+    builder.choose(Input::choice("upstream")).unwrap();
+    builder.choose(Input::text("other")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap().mode,
+        Mode::Proxy {
+            upstream: "other".into()
+        }
+    );
+    assert!(!builder.undo());
+}
@@ -0,0 +1,112 @@
+use std::collections::VecDeque;
+
+use ibuilder::frontend::*;
+use ibuilder::nodes::Node;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Base {
+    integer: i32,
+    #[ibuilder(default = "lol")]
+    string: String,
+}
+
+/// A frontend that replays a fixed list of inputs, recording what it has been shown.
+struct ScriptedFrontend {
+    inputs: VecDeque<Input>,
+    queries: Vec<String>,
+    errors: Vec<ChooseError>,
+}
+
+impl ScriptedFrontend {
+    fn new(inputs: Vec<Input>) -> ScriptedFrontend {
+        ScriptedFrontend {
+            inputs: inputs.into(),
+            queries: vec![],
+            errors: vec![],
+        }
+    }
+}
+
+impl AsyncFrontend for ScriptedFrontend {
+    async fn present(&mut self, options: Options, _node: Node) -> Input {
+        self.queries.push(options.query);
+        self.inputs.pop_front().expect("Out of inputs")
+    }
+
+    async fn present_error(&mut self, error: ChooseError) {
+        self.errors.push(error);
+    }
+}
+
+#[test]
+fn test_run_builder() {
+    let mut frontend = ScriptedFrontend::new(vec![
+        Input::choice("integer"),
+        Input::text("42"),
+        Input::choice(FINALIZE_ID),
+    ]);
+    let value = futures_executor::block_on(run_builder(&mut frontend, Base::builder()));
+    assert_eq!(
+        value,
+        Base {
+            integer: 42,
+            string: "lol".into()
+        }
+    );
+    assert_eq!(frontend.queries.len(), 3);
+    assert!(frontend.errors.is_empty());
+}
+
+#[test]
+fn test_run_builder_reprompt() {
+    let mut frontend = ScriptedFrontend::new(vec![
+        Input::choice("nope"),
+        Input::choice("integer"),
+        Input::text("not a number"),
+        Input::text("42"),
+        Input::choice(FINALIZE_ID),
+    ]);
+    let value = futures_executor::block_on(run_builder(&mut frontend, Base::builder()));
+    assert_eq!(value.integer, 42);
+    assert_eq!(frontend.errors.len(), 2);
+    assert_eq!(frontend.errors[0], ChooseError::UnexpectedChoice);
+    assert!(matches!(
+        frontend.errors[1],
+        ChooseError::InvalidText { .. }
+    ));
+    // the field prompt is shown again after the invalid text
+    assert_eq!(frontend.queries[2], frontend.queries[3]);
+}
+
+impl Frontend for ScriptedFrontend {
+    fn present(&mut self, options: Options, _node: Node) -> Input {
+        self.queries.push(options.query);
+        self.inputs.pop_front().expect("Out of inputs")
+    }
+
+    fn present_error(&mut self, error: ChooseError) {
+        self.errors.push(error);
+    }
+}
+
+#[test]
+fn test_run_builder_sync() {
+    let mut frontend = ScriptedFrontend::new(vec![
+        Input::choice("integer"),
+        Input::text("nope"),
+        Input::text("42"),
+        Input::choice("string"),
+        Input::text("foo"),
+        Input::choice(FINALIZE_ID),
+    ]);
+    let value = run_builder_sync(&mut frontend, Base::builder());
+    assert_eq!(
+        value,
+        Base {
+            integer: 42,
+            string: "foo".into()
+        }
+    );
+    assert_eq!(frontend.errors.len(), 1);
+}
@@ -0,0 +1,102 @@
+use std::marker::PhantomData;
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Wrapper<T> {
+    value: T,
+    label: String,
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Boxed<T>(Box<Wrapper<T>>)
+where
+    T: Clone;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Tagged<T, U: Default + Clone> {
+    id: u32,
+    #[ibuilder(hidden)]
+    tag: U,
+    _marker: PhantomData<T>,
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+enum Either<L, R> {
+    Left(L),
+    Right { value: R },
+    Neither,
+}
+
+#[test]
+fn test_generic_struct() {
+    let mut builder = Wrapper::<i32>::builder();
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    builder.choose(Input::choice("label")).unwrap();
+    builder.choose(Input::text("answer")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Wrapper {
+            value: 42,
+            label: "answer".into()
+        }
+    );
+
+    let mut builder = Wrapper::<Vec<i32>>::builder();
+    builder.choose(Input::choice("value")).unwrap();
+    assert_eq!(builder.get_options().query, "Select an action");
+}
+
+#[test]
+fn test_generic_unnamed() {
+    let mut builder = Boxed::<char>::builder();
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::text("x")).unwrap();
+    builder.choose(Input::choice("label")).unwrap();
+    builder.choose(Input::text("boxed")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Boxed(Box::new(Wrapper {
+            value: 'x',
+            label: "boxed".into()
+        }))
+    );
+}
+
+#[test]
+fn test_generic_hidden() {
+    let mut builder = Tagged::<String, i64>::builder();
+    builder.choose(Input::choice("id")).unwrap();
+    builder.choose(Input::text("7")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Tagged {
+            id: 7,
+            tag: 0,
+            _marker: PhantomData
+        }
+    );
+}
+
+#[test]
+fn test_generic_enum() {
+    let mut builder = Either::<u16, String>::builder();
+    builder.choose(Input::choice("Left")).unwrap();
+    builder.choose(Input::text("80")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Either::Left(80));
+
+    builder.choose(Input::choice("Right")).unwrap();
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::text("right")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Either::Right {
+            value: "right".into()
+        }
+    );
+
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("Neither")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Either::Neither);
+}
@@ -0,0 +1,144 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Config {
+    name: String,
+    server: Box<Server>,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Wrapper {
+    inner: Inner,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Inner {
+    value: i32,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Server {
+    #[ibuilder(prompt = "Type the host")]
+    host: String,
+    port: u16,
+}
+
+#[test]
+fn test_goto() {
+    let mut builder = Config::builder();
+    builder.goto(&["server", "host"]).unwrap();
+    assert_eq!(builder.get_options().query, "Type the host");
+    builder.choose(Input::text("localhost")).unwrap();
+    // after the input the user is in the menu of the struct, as usual
+    let options = builder.get_options();
+    assert!(options.choices.iter().any(|c| c.choice_id == "port"));
+
+    builder.goto(&["name"]).unwrap();
+    builder.choose(Input::text("app")).unwrap();
+    builder.goto(&["server", "port"]).unwrap();
+    builder.choose(Input::text("8080")).unwrap();
+    builder.goto::<&str>(&[]).unwrap();
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+    builder.choose(Input::choice("tags")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let config = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        config,
+        Config {
+            name: "app".into(),
+            server: Box::new(Server {
+                host: "localhost".into(),
+                port: 8080,
+            }),
+            tags: vec![],
+        }
+    );
+}
+
+#[test]
+fn test_goto_invalid() {
+    let mut builder = Config::builder();
+    builder.goto(&["server"]).unwrap();
+    assert_eq!(
+        builder.goto(&["server", "hostname"]),
+        Err(GotoError::UnknownField {
+            field: "hostname".into()
+        })
+    );
+    // the items of a list cannot be reached directly
+    assert_eq!(
+        builder.goto(&["tags", "__new"]),
+        Err(GotoError::UnknownField {
+            field: "__new".into()
+        })
+    );
+    // the user stays where they were
+    let options = builder.get_options();
+    assert!(options.choices.iter().any(|c| c.choice_id == "host"));
+    assert!(builder
+        .partial_state()
+        .get::<Vec<String>>("tags")
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_goto_saved_state() {
+    let mut builder = Config::builder();
+    builder.goto(&["server", "host"]).unwrap();
+    builder.choose(Input::text("localhost")).unwrap();
+    builder.goto(&["name"]).unwrap();
+    builder.choose(Input::text("x")).unwrap();
+    builder.goto(&["server", "port"]).unwrap();
+    let state = builder.save_state();
+
+    let mut builder = Builder::<Config>::from_saved_state(&state).unwrap();
+    assert_eq!(builder.get_options().query, "Type an integer");
+    builder.choose(Input::text("80")).unwrap();
+    builder.goto(&["tags"]).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let config = builder.finalize().unwrap();
+    assert_eq!(config.name, "x");
+    assert_eq!(config.server.host, "localhost");
+    assert_eq!(config.server.port, 80);
+}
+
+#[test]
+fn test_goto_undo() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("app")).unwrap();
+    builder.goto(&["server", "host"]).unwrap();
+    builder.choose(Input::text("localhost")).unwrap();
+
+    assert!(builder.undo());
+    assert_eq!(builder.get_options().query, "Type the host");
+    assert!(builder.undo());
+    // back in the main menu, where the user was before the goto
+    assert!(builder
+        .get_options()
+        .choices
+        .iter()
+        .any(|c| c.choice_id == "server"));
+    assert!(builder.redo());
+    assert_eq!(builder.get_options().query, "Type the host");
+    assert_eq!(
+        builder.partial_state().get::<String>("name"),
+        Some("app".to_string())
+    );
+}
+
+#[test]
+fn test_goto_skip_single_field_menus() {
+    let mut builder = Wrapper::builder().with_config(BuilderConfig {
+        skip_single_field_menus: true,
+        ..Default::default()
+    });
+    builder.goto(&["inner"]).unwrap();
+    // the menu of `Inner` has a single field, the user is moved inside it
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("42")).unwrap();
+    assert_eq!(builder.finalize().unwrap().inner.value, 42);
+}
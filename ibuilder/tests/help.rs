@@ -0,0 +1,78 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Server {
+    #[ibuilder(help = "The TCP port the server listens on")]
+    port: u16,
+    host: String,
+    backend: Backend,
+}
+
+#[derive(Debug, IBuilder)]
+enum Backend {
+    #[ibuilder(help = "Serve the files of a directory")]
+    Static(String),
+    Proxy {
+        #[ibuilder(help = "The address of the upstream server")]
+        upstream: String,
+    },
+}
+
+#[derive(Debug, IBuilder)]
+struct Port(#[ibuilder(help = "A number between 1 and 65535")] u16);
+
+fn helps(options: &Options) -> Vec<Option<&str>> {
+    options.choices.iter().map(|c| c.help.as_deref()).collect()
+}
+
+#[test]
+fn test_help() {
+    let mut builder = Server::builder();
+    let options = builder.get_options();
+    assert_eq!(options.help, None);
+    assert_eq!(
+        helps(&options),
+        vec![Some("The TCP port the server listens on"), None, None]
+    );
+    builder.choose(Input::choice("port")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(
+        options.help.as_deref(),
+        Some("The TCP port the server listens on")
+    );
+    assert_eq!(options.query, "Type an integer");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("host")).unwrap();
+    assert_eq!(builder.get_options().help, None);
+}
+
+#[test]
+fn test_help_variants() {
+    let mut builder = Backend::builder();
+    assert_eq!(
+        helps(&builder.get_options()),
+        vec![Some("Serve the files of a directory"), None]
+    );
+    builder.choose(Input::choice("Static")).unwrap();
+    assert_eq!(
+        builder.get_options().help.as_deref(),
+        Some("Serve the files of a directory")
+    );
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("Proxy")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.help, None);
+    assert_eq!(
+        helps(&options)[0],
+        Some("The address of the upstream server")
+    );
+}
+
+#[test]
+fn test_help_unnamed() {
+    let builder = Port::builder();
+    assert_eq!(
+        builder.get_options().help.as_deref(),
+        Some("A number between 1 and 65535")
+    );
+}
@@ -115,3 +115,29 @@ fn hidden_field_without_default() {
     let res = builder.finalize().unwrap();
     assert_eq!(res.field.field, "success");
 }
+
+#[derive(Clone, Default)]
+struct NotDebug;
+
+#[derive(IBuilder)]
+struct StructNotDebug {
+    #[ibuilder(hidden)]
+    field: NotDebug,
+    inner: InnerNotDebug,
+}
+
+#[derive(IBuilder)]
+enum InnerNotDebug {
+    Var {
+        #[ibuilder(hidden)]
+        field: NotDebug,
+        field2: i32,
+    },
+}
+
+#[test]
+fn test_hidden_not_debug() {
+    let builder = StructNotDebug::builder();
+    let debug = format!("{:?}", builder);
+    assert!(debug.contains("[hidden]"));
+}
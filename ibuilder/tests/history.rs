@@ -0,0 +1,119 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Person {
+    name: String,
+    age: u8,
+}
+
+fn history(builder: &Builder<Person>) -> Vec<(Vec<String>, Input, InputOutcome)> {
+    builder
+        .history()
+        .iter()
+        .map(|entry| {
+            (
+                entry.path.iter().map(|f| f.to_string()).collect(),
+                entry.input.clone(),
+                entry.outcome.clone(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_history() {
+    let start = Instant::now();
+    let now = Rc::new(Cell::new(start));
+    let clock = {
+        let now = now.clone();
+        Clock::new(move || now.get())
+    };
+    let mut builder = Person::builder().with_config(BuilderConfig {
+        clock,
+        ..Default::default()
+    });
+    builder.choose(Input::choice("age")).unwrap();
+    now.set(start + Duration::from_secs(1));
+    builder.choose(Input::text("many")).unwrap_err();
+    builder.choose(Input::text("42")).unwrap();
+    builder.choose(Input::choice(FINALIZE_ID)).unwrap_err();
+
+    let invalid = ChooseError::InvalidText {
+        error: "invalid digit found in string".into(),
+    };
+    assert_eq!(
+        history(&builder),
+        vec![
+            (
+                vec!["age".into()],
+                Input::choice("age"),
+                InputOutcome::Applied
+            ),
+            (
+                vec!["age".into()],
+                Input::text("many"),
+                InputOutcome::Rejected(invalid)
+            ),
+            (vec![], Input::text("42"), InputOutcome::Applied),
+            (
+                vec![],
+                Input::choice(FINALIZE_ID),
+                InputOutcome::Rejected(ChooseError::UnexpectedChoice)
+            ),
+        ]
+    );
+    assert_eq!(builder.history()[0].at, start);
+    assert_eq!(builder.history()[1].at, start + Duration::from_secs(1));
+
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("edomora97")).unwrap();
+    builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        builder.history().last().unwrap().outcome,
+        InputOutcome::Finalized
+    );
+    // only the applied inputs are saved
+    assert_eq!(builder.save_state().inputs.len(), 4);
+}
+
+#[test]
+fn test_replay_to() {
+    let mut builder = Person::builder();
+    builder.choose(Input::choice("age")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("edomora97")).unwrap();
+    assert!(builder.is_done());
+
+    assert!(!builder.replay_to(5));
+    assert!(builder.replay_to(2));
+    assert_eq!(builder.history().len(), 2);
+    assert!(!builder.is_done());
+    assert_eq!(builder.partial_state().get::<u8>("age"), Some(42));
+    assert_eq!(builder.partial_state().get::<String>("name"), None);
+
+    // the discarded inputs can be applied again
+    assert!(builder.redo());
+    assert!(builder.redo());
+    assert!(!builder.redo());
+    assert!(builder.is_done());
+
+    assert!(builder.replay_to(0));
+    assert!(builder.history().is_empty());
+    assert_eq!(builder.partial_state().get::<u8>("age"), None);
+}
+
+#[test]
+fn test_replay_to_unsupported() {
+    let mut builder = Builder::from_value(Person {
+        name: "edomora97".into(),
+        age: 42,
+    });
+    builder.choose(Input::choice("age")).unwrap();
+    assert!(!builder.replay_to(0));
+    assert_eq!(builder.history().len(), 1);
+}
@@ -0,0 +1,63 @@
+use ibuilder::validators::{hostname, resolvable_hostname};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Server {
+    #[ibuilder(hostname)]
+    host: String,
+    #[ibuilder(hostname = "resolve")]
+    fallback: String,
+}
+
+#[test]
+fn test_hostname_validator() {
+    for valid in &[
+        "localhost",
+        "example.com",
+        "example.com.",
+        "a-b.c0",
+        "192.168.0.1",
+    ] {
+        assert_eq!(hostname(valid), Validation::Valid, "{}", valid);
+    }
+    let long_label = "a".repeat(64);
+    let long_name = vec!["a"; 128].join(".");
+    for invalid in &[
+        "",
+        ".",
+        "exa mple.com",
+        "example..com",
+        "-example.com",
+        "example-.com",
+        "ex_ample.com",
+        long_label.as_str(),
+        long_name.as_str(),
+    ] {
+        assert!(
+            matches!(hostname(invalid), Validation::Invalid(_)),
+            "{} should be invalid",
+            invalid
+        );
+    }
+}
+
+#[test]
+fn test_resolvable_hostname() {
+    assert_eq!(resolvable_hostname("localhost"), Validation::Valid);
+}
+
+#[test]
+fn test_hostname_attribute() {
+    let mut builder = Server::builder();
+    builder.choose(Input::choice("host")).unwrap();
+    let res = builder.choose(Input::text("not a hostname"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("example.com")).unwrap();
+    builder.choose(Input::choice("fallback")).unwrap();
+    let res = builder.choose(Input::text("local_host"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("localhost")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.host, "example.com");
+    assert_eq!(value.fallback, "localhost");
+}
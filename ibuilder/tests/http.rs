@@ -0,0 +1,77 @@
+use std::rc::Rc;
+use std::str::FromStr;
+
+use ibuilder::builders::MimeTypeBuilder;
+use ibuilder::types::{HttpMethod, MimeType};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Route {
+    method: HttpMethod,
+    content_type: MimeType,
+}
+
+#[test]
+fn test_http_method() {
+    let mut builder = Route::builder();
+    builder.choose(Input::choice("method")).unwrap();
+    let options = builder.get_options();
+    assert!(!options.text_input);
+    assert!(options.choices.iter().any(|c| c.choice_id == "PATCH"));
+    let res = builder.choose(Input::text("GET"));
+    assert_eq!(res.unwrap_err(), ChooseError::UnexpectedText);
+    builder.choose(Input::choice("POST")).unwrap();
+    builder.choose(Input::choice("content_type")).unwrap();
+    builder.choose(Input::choice("application/json")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.method, HttpMethod::Post);
+    assert_eq!(value.content_type.as_str(), "application/json");
+}
+
+#[test]
+fn test_mime_type() {
+    let mut builder = Route::builder();
+    builder.choose(Input::choice("content_type")).unwrap();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    for mime in &["text", "text/", "/html", "text/ht ml", "text/html; charset"] {
+        let res = builder.choose(Input::text(*mime));
+        assert!(
+            matches!(res, Err(ChooseError::InvalidText { .. })),
+            "{} should be invalid",
+            mime
+        );
+    }
+    builder
+        .choose(Input::text("Text/HTML;charset=utf-8"))
+        .unwrap();
+    builder.choose(Input::choice("method")).unwrap();
+    builder.choose(Input::choice("GET")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.content_type.to_string(), "text/html; charset=utf-8");
+    assert_eq!(value.content_type.essence(), "text/html");
+    assert_eq!(
+        value.content_type,
+        MimeType::from_str("text/html; charset=utf-8").unwrap()
+    );
+}
+
+#[test]
+fn test_mime_type_preprocess() {
+    // what `#[ibuilder(trim, charset = "a-z/")]` would generate, the spaces pass the charset only
+    // if they are trimmed first
+    let mut builder = MimeTypeBuilder::new(BuildableValueConfig {
+        preprocessors: vec![Rc::new(preprocessors::trim) as Preprocessor],
+        charset: Some(charset::Charset::new("a-z/")),
+        ..Default::default()
+    });
+    builder.apply(&Input::text("  text/html\n"), &[]).unwrap();
+    let value = builder
+        .get_value_any()
+        .unwrap()
+        .downcast::<MimeType>()
+        .unwrap();
+    assert_eq!(value.as_str(), "text/html");
+    let res = builder.apply(&Input::text("text/html; charset=utf-8"), &[]);
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+}
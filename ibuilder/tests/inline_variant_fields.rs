@@ -0,0 +1,99 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+#[ibuilder(inline_variant_fields)]
+enum Storage {
+    Memory,
+    Disk { path: String, size: u64 },
+    Remote(String),
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Config {
+    name: String,
+    storage: Storage,
+}
+
+fn choice_ids(builder: &Builder<Config>) -> Vec<String> {
+    builder
+        .get_options()
+        .choices
+        .into_iter()
+        .map(|choice| choice.choice_id.to_string())
+        .collect()
+}
+
+#[test]
+fn test_inline_variant_fields() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("storage")).unwrap();
+    assert_eq!(
+        choice_ids(&builder),
+        vec!["Memory", "Disk", "Remote", BACK_ID]
+    );
+    // the user stays in the menu of the enum, which now has the fields of the variant
+    builder.choose(Input::choice("Disk")).unwrap();
+    assert_eq!(
+        choice_ids(&builder),
+        vec!["Memory", "Disk", "Remote", "path", "size", BACK_ID]
+    );
+    builder.choose(Input::choice("path")).unwrap();
+    assert_eq!(choice_ids(&builder), vec![BACK_ID]);
+    builder.choose(Input::text("/var/data")).unwrap();
+    assert_eq!(
+        choice_ids(&builder),
+        vec!["Memory", "Disk", "Remote", "path", "size", BACK_ID]
+    );
+    builder.choose(Input::choice("size")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("main")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Config {
+            name: "main".into(),
+            storage: Storage::Disk {
+                path: "/var/data".into(),
+                size: 42
+            }
+        }
+    );
+}
+
+#[test]
+fn test_inline_variant_text() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("storage")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("s3://bucket")),
+        Err(ChooseError::UnexpectedText)
+    );
+    builder.choose(Input::choice("Remote")).unwrap();
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("s3://bucket")).unwrap();
+    // the variant can still be changed from the same menu
+    builder.choose(Input::choice("Memory")).unwrap();
+    assert_eq!(choice_ids(&builder), vec!["name", "storage"]);
+    assert_eq!(
+        builder.partial_state().get::<Storage>("storage"),
+        Some(Storage::Memory)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_inline_variant_load_json() {
+    let json = r#"{"name": "main", "storage": {"Disk": {"path": "/tmp", "size": 1}}}"#;
+    let builder = Builder::<Config>::load_json(json).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Config {
+            name: "main".into(),
+            storage: Storage::Disk {
+                path: "/tmp".into(),
+                size: 1
+            }
+        }
+    );
+}
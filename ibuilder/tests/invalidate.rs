@@ -0,0 +1,131 @@
+use ibuilder::builders::KEEP_ID;
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Account {
+    name: String,
+    limits: Limits,
+}
+
+#[derive(Debug, IBuilder)]
+struct Limits {
+    plan: Plan,
+    quota: u32,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+enum Plan {
+    Free,
+    Pro,
+}
+
+fn complete() -> Builder<Account> {
+    let mut builder = Account::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("edomora97")).unwrap();
+    builder.choose(Input::choice("limits")).unwrap();
+    builder.choose(Input::choice("plan")).unwrap();
+    builder.choose(Input::choice("Pro")).unwrap();
+    builder.choose(Input::choice("quota")).unwrap();
+    builder.choose(Input::text("100")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(builder.is_done());
+    builder
+}
+
+#[test]
+fn test_invalidate_keep() {
+    let mut builder = complete();
+    builder.invalidate(&["limits", "quota"]).unwrap();
+    assert!(!builder.is_done());
+    let options = builder.get_options();
+    let limits = options
+        .choices
+        .iter()
+        .find(|c| c.choice_id == "limits")
+        .unwrap();
+    assert!(limits.needs_action);
+
+    builder.choose(Input::choice("limits")).unwrap();
+    builder.choose(Input::choice("quota")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.choices[0].choice_id, KEEP_ID);
+    assert_eq!(options.choices[0].text, "Keep 100");
+    assert_eq!(options.suggestions[0], "100");
+    builder.choose(Input::choice(KEEP_ID)).unwrap();
+    assert!(builder.is_done());
+    assert_eq!(builder.finalize().unwrap().limits.quota, 100);
+}
+
+#[test]
+fn test_invalidate_new_value() {
+    let mut builder = complete();
+    builder.invalidate(&["limits", "quota"]).unwrap();
+    builder.invalidate(&["limits", "quota"]).unwrap();
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[1] {
+            FieldKind::Named(_, Node::Composite(_, limits)) => assert_eq!(
+                limits[1],
+                FieldKind::Named(
+                    "quota".into(),
+                    Node::Leaf(Field::Warning("100".into(), "To be confirmed".into()))
+                )
+            ),
+            _ => panic!("expecting the limits"),
+        },
+        _ => panic!("expecting a composite"),
+    }
+    builder.choose(Input::choice("limits")).unwrap();
+    builder.choose(Input::choice("quota")).unwrap();
+    builder.choose(Input::text("10")).unwrap();
+    assert!(builder.is_done());
+    assert_eq!(builder.finalize().unwrap().limits.quota, 10);
+}
+
+#[test]
+fn test_invalidate_choice() {
+    let mut builder = complete();
+    builder.invalidate(&["limits", "plan"]).unwrap();
+    assert!(!builder.is_done());
+    builder.choose(Input::choice("limits")).unwrap();
+    builder.choose(Input::choice("plan")).unwrap();
+    builder.choose(Input::choice("Free")).unwrap();
+    assert!(builder.is_done());
+    assert_eq!(builder.finalize().unwrap().limits.plan, Plan::Free);
+}
+
+#[test]
+fn test_invalidate_unknown_field() {
+    let mut builder = complete();
+    assert_eq!(
+        builder.invalidate(&["limits", "price"]),
+        Err(ReplaceFieldError::UnknownField {
+            field: "price".into()
+        })
+    );
+    assert!(builder.is_done());
+}
+
+#[test]
+fn test_invalidate_undo() {
+    let mut builder = complete();
+    builder.invalidate(&["limits", "quota"]).unwrap();
+    builder.choose(Input::choice("limits")).unwrap();
+    builder.choose(Input::choice("quota")).unwrap();
+    builder.choose(Input::text("200")).unwrap();
+
+    assert!(builder.undo());
+    assert!(builder.undo());
+    assert!(builder.undo());
+    assert!(!builder.undo());
+    // the values inserted before the invalidation are kept, the quota is stale again
+    assert!(!builder.is_done());
+    builder.choose(Input::choice("limits")).unwrap();
+    builder.choose(Input::choice("quota")).unwrap();
+    builder.choose(Input::choice(KEEP_ID)).unwrap();
+    let value = builder.finalize().unwrap();
+    assert_eq!(value.name, "edomora97");
+    assert_eq!(value.limits.plan, Plan::Pro);
+    assert_eq!(value.limits.quota, 100);
+}
@@ -0,0 +1,62 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Address {
+    #[ibuilder(invalidates = "state", invalidates = "zip")]
+    country: String,
+    #[ibuilder(invalidates = "city")]
+    state: String,
+    city: String,
+    #[ibuilder(default = 0)]
+    zip: u32,
+    notes: Vec<String>,
+}
+
+fn set(builder: &mut Builder<Address>, field: &str, value: &str) {
+    builder.choose(Input::choice(field)).unwrap();
+    builder.choose(Input::text(value)).unwrap();
+}
+
+#[test]
+fn test_invalidates() {
+    let mut builder = Address::builder();
+    set(&mut builder, "country", "Italy");
+    set(&mut builder, "state", "Veneto");
+    set(&mut builder, "city", "Padova");
+    set(&mut builder, "zip", "35100");
+    assert!(builder.is_done());
+
+    // the same value doesn't invalidate anything
+    set(&mut builder, "country", "Italy");
+    assert!(builder.is_done());
+    // entering the field doesn't either
+    builder.choose(Input::choice("country")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(builder.is_done());
+
+    // the dependent fields are cleared, transitively
+    set(&mut builder, "country", "France");
+    assert!(!builder.is_done());
+    let options = builder.get_options();
+    let missing: Vec<_> = options
+        .choices
+        .iter()
+        .filter(|c| c.needs_action)
+        .map(|c| c.choice_id.as_str())
+        .collect();
+    assert_eq!(missing, vec!["state", "city"]);
+
+    set(&mut builder, "state", "Bretagne");
+    set(&mut builder, "city", "Rennes");
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Address {
+            country: "France".into(),
+            state: "Bretagne".into(),
+            city: "Rennes".into(),
+            zip: 0,
+            notes: vec![],
+        }
+    );
+}
@@ -0,0 +1,107 @@
+#![allow(dead_code)]
+
+use std::collections::HashMap;
+
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Root {
+    name: String,
+    shape: Shape,
+    maybe: Option<Box<Wrapped>>,
+    items: Vec<Item>,
+    map: HashMap<String, String>,
+    #[ibuilder(hidden)]
+    secret: i32,
+}
+
+#[derive(IBuilder)]
+enum Shape {
+    Empty,
+    Circle { radius: i32 },
+}
+
+#[derive(IBuilder)]
+struct Wrapped(i32);
+
+#[derive(IBuilder)]
+struct Item {
+    value: i32,
+}
+
+fn check(builder: &Builder<Root>, complete: bool) {
+    let state = builder.partial_state();
+    assert_eq!(state.is_done(), complete);
+    assert_eq!(builder.is_done(), complete);
+}
+
+#[test]
+fn test_is_complete() {
+    let mut builder = Root::builder();
+    check(&builder, false);
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("root")).unwrap();
+    builder.choose(Input::choice("shape")).unwrap();
+    builder.choose(Input::choice("Circle")).unwrap();
+    // the variant misses the radius
+    check(&builder, false);
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("Empty")).unwrap();
+    check(&builder, true);
+
+    builder.choose(Input::choice("maybe")).unwrap();
+    builder.choose(Input::choice("__set")).unwrap();
+    check(&builder, false);
+    builder.choose(Input::text("1")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    check(&builder, true);
+
+    builder.choose(Input::choice("items")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    check(&builder, false);
+    let options = builder.get_options();
+    assert!(options.choices.iter().any(|c| c.choice_id == "value"));
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    check(&builder, true);
+    let options = builder.get_options();
+    let item = options.choices.iter().find(|c| c.choice_id == "0").unwrap();
+    assert!(!item.needs_action);
+}
+
+#[derive(IBuilder)]
+struct Tracked {
+    #[ibuilder(default = 1)]
+    count: i32,
+    items: Vec<Item>,
+    inner: Box<Item>,
+}
+
+#[test]
+fn test_is_complete_tracking() {
+    let mut builder = Tracked::builder();
+    let value = Box::new(builders::I32Builder::new(BuildableValueConfig {
+        default: Some(2),
+        ..Default::default()
+    }));
+    builder.replace_field(&["inner", "value"], value).unwrap();
+    assert!(builder.is_done());
+
+    builder.choose(Input::choice("items")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(!builder.is_done());
+    builder.choose(Input::choice("__remove")).unwrap();
+    builder.choose(Input::choice("0")).unwrap();
+    assert!(!builder.is_done());
+    builder.choose(Input::choice("__remove")).unwrap();
+    builder.choose(Input::choice("1")).unwrap();
+    assert!(builder.is_done());
+
+    let value = Box::new(builders::I32Builder::new(Default::default()));
+    builder.replace_field(&["count"], value).unwrap();
+    assert!(!builder.is_done());
+}
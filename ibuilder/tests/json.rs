@@ -0,0 +1,52 @@
+#![cfg(feature = "serde_json")]
+
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+use serde_json::json;
+
+#[derive(Debug, IBuilder)]
+struct Plugin {
+    name: String,
+    settings: serde_json::Value,
+}
+
+fn settings_node(builder: &Builder<Plugin>) -> String {
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[1] {
+            FieldKind::Named(_, Node::Leaf(Field::String(s))) => s.clone(),
+            _ => panic!("expecting a string"),
+        },
+        _ => panic!("expecting a composite"),
+    }
+}
+
+#[test]
+fn test_json() {
+    let mut builder = Plugin::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("cache")).unwrap();
+    builder.choose(Input::choice("settings")).unwrap();
+    let res = builder.choose(Input::text("{\"size\": "));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder
+        .choose(Input::text("{\"size\": 42, \"eviction\": [\"lru\"]}"))
+        .unwrap();
+    assert_eq!(
+        settings_node(&builder),
+        "{\n  \"eviction\": [\n    \"lru\"\n  ],\n  \"size\": 42\n}"
+    );
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.name, "cache");
+    assert_eq!(value.settings, json!({"size": 42, "eviction": ["lru"]}));
+}
+
+#[test]
+fn test_json_truncated() {
+    let mut builder = Plugin::builder();
+    builder.choose(Input::choice("settings")).unwrap();
+    let long = format!("\"{}\"", "x".repeat(500));
+    builder.choose(Input::text(long)).unwrap();
+    let node = settings_node(&builder);
+    assert_eq!(node.len(), 203);
+    assert!(node.ends_with("..."));
+}
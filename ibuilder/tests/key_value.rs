@@ -0,0 +1,74 @@
+use std::collections::HashMap;
+
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Deployment {
+    labels: HashMap<String, String>,
+}
+
+#[test]
+fn test_key_value() {
+    let mut builder = Deployment::builder();
+    builder.choose(Input::choice("labels")).unwrap();
+    // an empty map is valid
+    assert!(builder.is_done());
+    builder.choose(Input::choice("__new")).unwrap();
+    // the key is asked first, then the value
+    assert_eq!(builder.get_options().query, "Type the key");
+    builder.choose(Input::text("app")).unwrap();
+    assert_eq!(builder.get_options().query, "Type the value");
+    assert!(!builder.is_done());
+    builder.choose(Input::text("web")).unwrap();
+    assert_eq!(builder.get_options().query, "Edit the entry");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("__new")).unwrap();
+    let res = builder.choose(Input::text("app"));
+    assert_eq!(
+        res.unwrap_err(),
+        ChooseError::InvalidText {
+            error: "Duplicated key: app".into()
+        }
+    );
+    builder.choose(Input::text("tier")).unwrap();
+    builder.choose(Input::text("frontend")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    // edit the value of the first entry
+    builder.choose(Input::choice("0")).unwrap();
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::text("api")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let options = builder.get_options();
+    let texts: Vec<_> = options.choices.iter().map(|c| c.text.as_str()).collect();
+    assert!(texts.contains(&"Edit app"));
+    assert!(texts.contains(&"Edit tier"));
+
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[0] {
+            FieldKind::Named(_, Node::Composite(_, entries)) => {
+                assert_eq!(entries.len(), 2);
+                match &entries[0] {
+                    FieldKind::Named(key, Node::Leaf(Field::String(value))) => {
+                        assert_eq!(key, "app");
+                        assert_eq!(value, "api");
+                    }
+                    _ => panic!("expecting a named entry"),
+                }
+            }
+            _ => panic!("expecting a composite"),
+        },
+        _ => panic!("expecting a composite"),
+    }
+
+    builder.choose(Input::choice("__remove")).unwrap();
+    builder.choose(Input::choice("1")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    let mut expected = HashMap::new();
+    expected.insert("app".to_string(), "api".to_string());
+    assert_eq!(value.labels, expected);
+}
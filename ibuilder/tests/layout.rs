@@ -0,0 +1,61 @@
+use ibuilder::layout::{display_width, text_direction, Layout, TextDirection};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(prompt = "Choose the size")]
+enum Size {
+    #[ibuilder(rename = "Small 🐭")]
+    Small,
+    #[ibuilder(rename = "Large 🐘🐘")]
+    Large,
+}
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(prompt = "اختر الحجم")]
+struct Shirt {
+    size: Size,
+}
+
+fn with_hints() -> BuilderConfig {
+    BuilderConfig {
+        layout_hints: true,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_no_layout_by_default() {
+    let builder = Size::builder();
+    assert_eq!(builder.get_options().layout, None);
+}
+
+#[test]
+fn test_layout() {
+    let builder = Size::builder().with_config(with_hints());
+    assert_eq!(
+        builder.get_options().layout,
+        Some(Layout {
+            direction: TextDirection::LeftToRight,
+            button_width: 10,
+        })
+    );
+}
+
+#[test]
+fn test_layout_rtl() {
+    let mut builder = Shirt::builder().with_config(with_hints());
+    let layout = builder.get_options().layout.unwrap();
+    assert_eq!(layout.direction, TextDirection::RightToLeft);
+    builder.choose(Input::choice("size")).unwrap();
+    let layout = builder.get_options().layout.unwrap();
+    assert_eq!(layout.direction, TextDirection::LeftToRight);
+}
+
+#[test]
+fn test_display_width() {
+    assert_eq!(display_width("abc"), 3);
+    assert_eq!(display_width("🐘"), 2);
+    assert_eq!(display_width("日本"), 4);
+    assert_eq!(text_direction("123 שלום"), Some(TextDirection::RightToLeft));
+    assert_eq!(text_direction("42!"), None);
+}
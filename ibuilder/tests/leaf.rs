@@ -0,0 +1,76 @@
+use ibuilder::builders::LeafBuilder;
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Version {
+    major: u32,
+    minor: u32,
+}
+
+impl LeafBuildableValue for Version {
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut parts = text.split('.').map(|p| p.parse::<u32>());
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some(Ok(major)), Some(Ok(minor)), None) => Ok(Version { major, minor }),
+            _ => Err(format!("Invalid version: {}", text)),
+        }
+    }
+
+    fn render(&self) -> String {
+        format!("{}.{}", self.major, self.minor)
+    }
+}
+
+impl NewBuildableValue for Version {
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(LeafBuilder::<Version>::new(config))
+    }
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Package {
+    version: Version,
+    #[ibuilder(prompt = "Minimum version")]
+    min_version: Option<Version>,
+}
+
+#[test]
+fn test_leaf() {
+    let mut builder = Package::builder();
+    builder.choose(Input::choice("version")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.query, "Type a value");
+    assert!(options.text_input);
+    assert_eq!(
+        builder.choose(Input::text("1.x")),
+        Err(ChooseError::InvalidText {
+            error: "Invalid version: 1.x".into()
+        })
+    );
+    builder.choose(Input::text("1.2")).unwrap();
+
+    builder.choose(Input::choice("min_version")).unwrap();
+    assert_eq!(builder.get_options().query, "Minimum version");
+    builder.choose(Input::choice("__set")).unwrap();
+    builder.choose(Input::text("0.9")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[0] {
+            FieldKind::Named(_, Node::Leaf(Field::String(version))) => {
+                assert_eq!(version, "1.2")
+            }
+            field => panic!("Unexpected field: {:?}", field),
+        },
+        node => panic!("Unexpected node: {:?}", node),
+    }
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Package {
+            version: Version { major: 1, minor: 2 },
+            min_version: Some(Version { major: 0, minor: 9 }),
+        }
+    );
+}
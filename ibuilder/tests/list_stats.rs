@@ -0,0 +1,73 @@
+use ibuilder::nodes::{FieldKind, ListStats, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Cluster {
+    name: String,
+    servers: Vec<Server>,
+}
+
+#[derive(Debug, IBuilder)]
+struct Server {
+    host: String,
+}
+
+fn servers(node: &Node) -> &Node {
+    match node {
+        Node::Composite(_, fields) => match &fields[1] {
+            FieldKind::Named(_, node) => node,
+            FieldKind::Unnamed(_) => panic!("Expecting a named field"),
+        },
+        Node::Leaf(_) => panic!("Expecting a composite node"),
+    }
+}
+
+#[test]
+fn test_list_stats() {
+    let mut builder = Cluster::builder();
+    let node = builder.to_node();
+    assert_eq!(node.list_stats(), None);
+    assert_eq!(
+        servers(&node).list_stats(),
+        Some(ListStats {
+            items: 0,
+            incomplete: 0
+        })
+    );
+
+    builder.choose(Input::choice("servers")).unwrap();
+    for host in &["a", "b"] {
+        builder.choose(Input::choice("__new")).unwrap();
+        builder.choose(Input::choice("host")).unwrap();
+        builder.choose(Input::text(*host)).unwrap();
+        builder.choose(Input::choice(BACK_ID)).unwrap();
+    }
+    builder.choose(Input::choice("__new")).unwrap();
+    let node = builder.to_node();
+    let stats = servers(&node).list_stats().unwrap();
+    assert_eq!(
+        stats,
+        ListStats {
+            items: 3,
+            incomplete: 1
+        }
+    );
+    assert_eq!(stats.to_string(), "3 items, 1 incomplete");
+
+    let node = node.with_list_stats();
+    match servers(&node) {
+        Node::Composite(name, items) => {
+            assert_eq!(name, "3 items, 1 incomplete");
+            assert_eq!(items.len(), 3);
+        }
+        Node::Leaf(_) => panic!("Expecting a composite node"),
+    }
+}
+
+#[test]
+fn test_list_stats_display() {
+    let stats = |items, incomplete| ListStats { items, incomplete }.to_string();
+    assert_eq!(stats(0, 0), "0 items");
+    assert_eq!(stats(1, 0), "1 item");
+    assert_eq!(stats(1, 1), "1 item, 1 incomplete");
+}
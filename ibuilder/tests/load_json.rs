@@ -0,0 +1,101 @@
+#![cfg(feature = "serde")]
+
+use std::collections::HashMap;
+
+use ibuilder::*;
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+struct Server {
+    host: String,
+    port: u16,
+    tls: bool,
+    aliases: Vec<String>,
+    mode: Mode,
+    level: Level,
+    timeout: Option<u32>,
+    headers: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+enum Mode {
+    Static(String),
+    Proxy { upstream: String },
+}
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+enum Level {
+    Debug,
+    Info,
+}
+
+#[test]
+fn test_load_json() {
+    let json = r#"{
+        "host": "localhost",
+        "port": 8080,
+        "tls": true,
+        "aliases": ["www", "api"],
+        "mode": {"Proxy": {"upstream": "backend"}},
+        "level": "Info",
+        "timeout": 30,
+        "headers": {"X-Served-By": "ibuilder"}
+    }"#;
+    let builder = Builder::<Server>::load_json(json).unwrap();
+    assert!(builder.is_done());
+    let mut headers = HashMap::new();
+    headers.insert("X-Served-By".to_string(), "ibuilder".to_string());
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Server {
+            host: "localhost".into(),
+            port: 8080,
+            tls: true,
+            aliases: vec!["www".into(), "api".into()],
+            mode: Mode::Proxy {
+                upstream: "backend".into()
+            },
+            level: Level::Info,
+            timeout: Some(30),
+            headers,
+        }
+    );
+}
+
+#[test]
+fn test_load_json_missing_and_invalid() {
+    let json = r#"{
+        "host": "localhost",
+        "port": "not a port",
+        "tls": false,
+        "aliases": [],
+        "mode": {"Static": "/var/www"},
+        "level": "Trace",
+        "timeout": null,
+        "headers": {}
+    }"#;
+    let mut builder = Builder::<Server>::load_json(json).unwrap();
+    assert!(!builder.is_done());
+    let partial = builder.partial_state();
+    assert_eq!(partial.get::<String>("host"), Some("localhost".into()));
+    assert_eq!(partial.get::<u16>("port"), None);
+    assert_eq!(
+        partial.get::<Mode>("mode"),
+        Some(Mode::Static("/var/www".into()))
+    );
+    assert_eq!(partial.get::<Level>("level"), None);
+    assert_eq!(partial.get::<Option<u32>>("timeout"), Some(None));
+
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("80")).unwrap();
+    builder.choose(Input::choice("level")).unwrap();
+    builder.choose(Input::choice("Debug")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.port, 80);
+    assert_eq!(value.level, Level::Debug);
+    assert!(value.aliases.is_empty());
+}
+
+#[test]
+fn test_load_json_not_json() {
+    assert!(Builder::<Server>::load_json("{\"host\": ").is_err());
+}
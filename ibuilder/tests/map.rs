@@ -0,0 +1,94 @@
+use std::collections::{BTreeMap, HashMap};
+
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Inventory {
+    stock: HashMap<u32, Item>,
+    aliases: BTreeMap<String, Vec<u32>>,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Item {
+    name: String,
+    #[ibuilder(default = 1)]
+    quantity: u32,
+}
+
+#[test]
+fn test_map() {
+    let mut builder = Inventory::builder();
+    builder.choose(Input::choice("stock")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    assert_eq!(builder.get_options().query, "Type the key");
+    assert!(builder.choose(Input::text("first")).is_err());
+    builder.choose(Input::text("42")).unwrap();
+    // the value is a struct, its menu is shown
+    let options = builder.get_options();
+    assert!(!options.text_input);
+    assert_eq!(options.choices[0].choice_id, "name");
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("screw")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(builder.get_options().query, "Edit the entry");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("__new")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("42")),
+        Err(ChooseError::InvalidText {
+            error: "Duplicated key: 42".into()
+        })
+    );
+    builder.choose(Input::text("7")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    // the value of the second entry is missing
+    assert!(!builder.is_done());
+    builder.choose(Input::choice("__remove")).unwrap();
+    builder.choose(Input::choice("1")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("aliases")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("screws")).unwrap();
+    // an empty list is a valid value, it's not entered automatically
+    assert_eq!(builder.get_options().query, "Edit the entry");
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[0] {
+            FieldKind::Named(_, Node::Composite(_, entries)) => match &entries[0] {
+                FieldKind::Named(key, Node::Composite(_, item)) => {
+                    assert_eq!(key, "42");
+                    assert_eq!(
+                        item[0],
+                        FieldKind::Named("name".into(), Node::Leaf(Field::String("screw".into())))
+                    );
+                }
+                entry => panic!("Unexpected entry: {:?}", entry),
+            },
+            field => panic!("Unexpected field: {:?}", field),
+        },
+        node => panic!("Unexpected node: {:?}", node),
+    }
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    let mut stock = HashMap::new();
+    stock.insert(
+        42,
+        Item {
+            name: "screw".into(),
+            quantity: 1,
+        },
+    );
+    let mut aliases = BTreeMap::new();
+    aliases.insert("screws".to_string(), vec![42]);
+    assert_eq!(value, Inventory { stock, aliases });
+}
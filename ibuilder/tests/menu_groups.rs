@@ -0,0 +1,95 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Base {
+    fruit: Fruit,
+    count: i32,
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+enum Fruit {
+    Banana,
+    Apple,
+    Cherry,
+    Fig,
+    Grape,
+    Kiwi,
+    Lemon,
+}
+
+fn ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+fn texts(options: &Options) -> Vec<&str> {
+    options.choices.iter().map(|c| c.text.as_str()).collect()
+}
+
+#[test]
+fn test_menu_groups() {
+    let mut builder = Base::builder().with_config(BuilderConfig {
+        max_choices_per_menu: Some(4),
+        ..Default::default()
+    });
+    // the main menu is small enough
+    assert_eq!(ids(&builder.get_options()), vec!["fruit", "count"]);
+
+    builder.choose(Input::choice("fruit")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(
+        ids(&options),
+        vec!["__group_0", "__group_1", "__group_2", BACK_ID]
+    );
+    assert_eq!(texts(&options), vec!["A–C", "F–K", "Lemon", "Go back"]);
+
+    builder.choose(Input::choice("__group_1")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(ids(&options), vec!["Fig", "Grape", "Kiwi", BACK_ID]);
+    // going back from a group shows the groups again
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(builder.get_options().choices.len(), 4);
+
+    builder.choose(Input::choice("__group_0")).unwrap();
+    builder.choose(Input::choice("Cherry")).unwrap();
+    // back to the main menu, the group is closed
+    assert_eq!(ids(&builder.get_options()), vec!["fruit", "count"]);
+
+    builder.choose(Input::choice("count")).unwrap();
+    builder.choose(Input::text("3")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Base {
+            fruit: Fruit::Cherry,
+            count: 3
+        }
+    );
+}
+
+#[test]
+fn test_menu_groups_invalid() {
+    let mut builder = Base::builder().with_config(BuilderConfig {
+        max_choices_per_menu: Some(4),
+        ..Default::default()
+    });
+    assert_eq!(
+        builder.choose(Input::choice("__group_0")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    builder.choose(Input::choice("fruit")).unwrap();
+    assert_eq!(
+        builder.choose(Input::choice("__group_3")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    builder.choose(Input::choice("__group_2")).unwrap();
+    assert_eq!(
+        builder.choose(Input::choice("__group_0")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    // the group is still open after an error
+    assert_eq!(ids(&builder.get_options()), vec!["Lemon", BACK_ID]);
+}
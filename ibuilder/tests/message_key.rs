@@ -0,0 +1,73 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(action(id = "check", handler = "check"))]
+struct HttpServer {
+    #[ibuilder(rename = "Full name")]
+    full_name: String,
+    shape: Shape,
+    tags: Vec<String>,
+}
+
+#[derive(Debug, IBuilder)]
+enum Shape {
+    Empty,
+    BigCircle { radius: i32 },
+}
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(message_key = "account.person")]
+struct Person {
+    name: String,
+}
+
+fn check(_: &PartialState) -> Option<String> {
+    None
+}
+
+fn keys(options: &Options) -> Vec<Option<&str>> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.message_key.as_deref())
+        .collect()
+}
+
+#[test]
+fn test_message_key() {
+    let mut builder = HttpServer::builder();
+    assert_eq!(
+        keys(&builder.get_options()),
+        vec![
+            Some("http_server.full_name"),
+            Some("http_server.shape"),
+            Some("http_server.tags"),
+            Some("http_server.check"),
+        ]
+    );
+    builder.choose(Input::choice("shape")).unwrap();
+    assert_eq!(
+        keys(&builder.get_options()),
+        vec![Some("shape.empty"), Some("shape.big_circle"), None]
+    );
+    builder.choose(Input::choice("BigCircle")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(
+        options.choices[0].message_key.as_deref(),
+        Some("shape.big_circle.radius")
+    );
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("tags")).unwrap();
+    // the builtin choices don't have a key
+    assert!(keys(&builder.get_options()).iter().all(|k| k.is_none()));
+}
+
+#[test]
+fn test_custom_message_key() {
+    let builder = Person::builder();
+    assert_eq!(
+        keys(&builder.get_options()),
+        vec![Some("account.person.name")]
+    );
+}
@@ -0,0 +1,47 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ibuilder::metrics::Counters;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Base {
+    integer: i32,
+    #[ibuilder(default = "lol")]
+    string: String,
+}
+
+#[test]
+fn test_counters() {
+    let counters = Rc::new(RefCell::new(Counters::default()));
+    let mut builder = Base::builder().with_metrics(counters.clone());
+    builder.choose(Input::choice("integer")).unwrap();
+    builder.choose(Input::text("nope")).unwrap_err();
+    builder.choose(Input::text("42")).unwrap();
+    builder.choose(Input::choice("nope")).unwrap_err();
+    {
+        let counters = counters.borrow();
+        assert_eq!(counters.inputs, 2);
+        assert_eq!(counters.total_errors(), 2);
+        assert_eq!(counters.errors["integer"], 1);
+        assert_eq!(counters.errors[""], 1);
+        assert!(counters.dwell.contains_key(""));
+        assert!(counters.dwell.contains_key("integer"));
+        assert!(!counters.dwell.contains_key("string"));
+        assert_eq!(counters.finalized, None);
+    }
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.string, "lol");
+    let counters = counters.borrow();
+    assert_eq!(counters.inputs, 3);
+    assert!(counters.finalized.is_some());
+}
+
+#[test]
+fn test_no_metrics() {
+    let mut builder = Base::builder();
+    builder.choose(Input::choice("integer")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.integer, 42);
+}
@@ -0,0 +1,98 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Volume {
+    #[ibuilder(min = 0, max = 100)]
+    level: u8,
+    #[ibuilder(min = "-10.5")]
+    gain: f32,
+    #[ibuilder(max = 3)]
+    channels: i32,
+    name: String,
+}
+
+#[test]
+fn test_hint() {
+    let mut builder = Volume::builder();
+    builder.choose(Input::choice("level")).unwrap();
+    assert_eq!(builder.get_options().hint, Some("Between 0 and 100".into()));
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("gain")).unwrap();
+    assert_eq!(builder.get_options().hint, Some("At least -10.5".into()));
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("channels")).unwrap();
+    assert_eq!(builder.get_options().hint, Some("At most 3".into()));
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("name")).unwrap();
+    assert_eq!(builder.get_options().hint, None);
+}
+
+#[test]
+fn test_out_of_range() {
+    let mut builder = Volume::builder();
+    builder.choose(Input::choice("level")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("101")),
+        Err(ChooseError::InvalidText {
+            error: "The value must be between 0 and 100".into()
+        })
+    );
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("100")).unwrap();
+
+    builder.choose(Input::choice("gain")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("-11")),
+        Err(ChooseError::InvalidText {
+            error: "The value must be at least -10.5".into()
+        })
+    );
+    builder.choose(Input::text("-10.5")).unwrap();
+
+    builder.choose(Input::choice("channels")).unwrap();
+    assert!(builder.choose(Input::text("4")).is_err());
+    builder.choose(Input::text("-4")).unwrap();
+
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("main")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Volume {
+            level: 100,
+            gain: -10.5,
+            channels: -4,
+            name: "main".into()
+        }
+    );
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Mix {
+    #[ibuilder(min = 0, max = 100)]
+    ratio: f64,
+}
+
+#[test]
+fn test_float_integer_bounds() {
+    let mut builder = Mix::builder();
+    builder.choose(Input::choice("ratio")).unwrap();
+    assert_eq!(builder.get_options().hint, Some("Between 0 and 100".into()));
+    assert!(builder.choose(Input::text("100.5")).is_err());
+    builder.choose(Input::text("99.5")).unwrap();
+    assert_eq!(builder.finalize().unwrap().ratio, 99.5);
+}
+
+#[test]
+fn test_nan() {
+    let mut builder = Mix::builder();
+    builder.choose(Input::choice("ratio")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("NaN")),
+        Err(ChooseError::InvalidText {
+            error: "The value must be between 0 and 100".into()
+        })
+    );
+    let validate = validators::range(Some(0.0), None);
+    assert!(matches!(validate(&f64::NAN), Validation::Invalid(_)));
+    assert!(matches!(validate(&0.0), Validation::Valid));
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+
+use ibuilder::navigation::{Navigator, Transition};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Root {
+    items: Vec<i32>,
+    labels: HashMap<String, String>,
+    inner: Inner,
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Inner {
+    value: i32,
+}
+
+#[test]
+fn test_unlisted_choices() {
+    let mut builder = Root::builder();
+    builder.choose(Input::choice("items")).unwrap();
+    // an empty Vec doesn't offer to remove items
+    assert_eq!(
+        builder.choose(Input::choice("__remove")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    assert_eq!(builder.get_options().query, "Select an action");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("labels")).unwrap();
+    assert_eq!(
+        builder.choose(Input::choice("__remove")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    assert_eq!(
+        builder.choose(Input::text("text")),
+        Err(ChooseError::UnexpectedText)
+    );
+}
+
+#[test]
+fn test_navigator_transitions() {
+    let mut value = Root::new_buildable_value(Default::default());
+    let mut navigator = Navigator::new();
+    assert!(navigator.is_main_menu());
+
+    let transition = navigator.apply(value.as_mut(), &Input::choice("inner"));
+    assert_eq!(transition, Ok(Transition::Entered));
+    let transition = navigator.apply(value.as_mut(), &Input::choice("value"));
+    assert_eq!(transition, Ok(Transition::Entered));
+    assert_eq!(navigator.current_fields(), ["inner", "value"]);
+
+    let transition = navigator.apply(value.as_mut(), &Input::text("nope"));
+    assert!(transition.is_err());
+    assert_eq!(navigator.current_fields(), ["inner", "value"]);
+    let transition = navigator.apply(value.as_mut(), &Input::text("42"));
+    assert_eq!(transition, Ok(Transition::Returned("value".into())));
+
+    assert_eq!(navigator.back(), Some("inner".into()));
+    assert_eq!(navigator.back(), None);
+    assert!(navigator.is_main_menu());
+}
+
+#[test]
+fn test_subfield() {
+    let mut value = Root::new_buildable_value(Default::default());
+    let mut navigator = Navigator::new();
+    for field in value.get_subfields(&[]) {
+        assert_eq!(value.subfield(&[], &field), Some(field));
+    }
+    assert_eq!(value.subfield(&[], "value"), None);
+    navigator
+        .apply(value.as_mut(), &Input::choice("inner"))
+        .unwrap();
+    assert_eq!(
+        value.subfield(navigator.current_fields(), "value"),
+        Some(Id::from_static("value"))
+    );
+    assert_eq!(value.subfield(navigator.current_fields(), "inner"), None);
+}
@@ -0,0 +1,38 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Server {
+    bind: SocketAddr,
+    gateway: Ipv4Addr,
+    dns: Vec<IpAddr>,
+    loopback: Ipv6Addr,
+}
+
+#[test]
+fn test_net() {
+    let mut builder = Server::builder();
+    builder.choose(Input::choice("bind")).unwrap();
+    let res = builder.choose(Input::text("0.0.0.0"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("0.0.0.0:8080")).unwrap();
+    builder.choose(Input::choice("gateway")).unwrap();
+    let res = builder.choose(Input::text("::1"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("192.168.1.1")).unwrap();
+    builder.choose(Input::choice("dns")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("2001:4860:4860::8888")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("loopback")).unwrap();
+    builder.choose(Input::text("::1")).unwrap();
+    let server = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(server.bind, "0.0.0.0:8080".parse().unwrap());
+    assert_eq!(server.gateway, Ipv4Addr::new(192, 168, 1, 1));
+    assert_eq!(
+        server.dns,
+        vec!["2001:4860:4860::8888".parse::<IpAddr>().unwrap()]
+    );
+    assert_eq!(server.loopback, Ipv6Addr::LOCALHOST);
+}
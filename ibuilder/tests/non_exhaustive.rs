@@ -0,0 +1,50 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+#[non_exhaustive]
+struct Config {
+    port: u16,
+    mode: Mode,
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+#[non_exhaustive]
+enum Mode {
+    Fast,
+    #[non_exhaustive]
+    Slow {
+        delay: u32,
+    },
+    #[non_exhaustive]
+    Tuple(i32),
+}
+
+#[test]
+fn test_non_exhaustive_struct() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("8080")).unwrap();
+    builder.choose(Input::choice("mode")).unwrap();
+    builder.choose(Input::choice("Fast")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Config {
+            port: 8080,
+            mode: Mode::Fast
+        }
+    );
+}
+
+#[test]
+fn test_non_exhaustive_variants() {
+    let mut builder = Mode::builder();
+    builder.choose(Input::choice("Slow")).unwrap();
+    builder.choose(Input::choice("delay")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Mode::Slow { delay: 42 });
+
+    let mut builder = Mode::builder();
+    builder.choose(Input::choice("Tuple")).unwrap();
+    builder.choose(Input::text("-1")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Mode::Tuple(-1));
+}
@@ -0,0 +1,12 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Foo {
+    #[ibuilder(ask_first, hidden)]
+    field: i32,
+}
+
+#[derive(IBuilder)]
+struct Bar(#[ibuilder(ask_first)] i32);
+
+fn main() {}
@@ -0,0 +1,11 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Account {
+    username: String,
+    email: String,
+}
+
+assert_fields!(Account, ["username"]);
+
+fn main() {}
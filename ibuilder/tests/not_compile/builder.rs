@@ -0,0 +1,31 @@
+use ibuilder::*;
+
+fn custom(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+    <i32 as NewBuildableValue>::new_buildable_value(config)
+}
+
+#[derive(IBuilder)]
+struct HiddenBuilder {
+    #[ibuilder(hidden, builder = "custom")]
+    field: i32,
+}
+
+#[derive(IBuilder)]
+struct DuplicatedBuilder {
+    #[ibuilder(builder = "custom", builder = "custom")]
+    field: i32,
+}
+
+#[derive(IBuilder)]
+struct DefaultBuilder {
+    #[ibuilder(builder = "custom", default = 42)]
+    field: i32,
+}
+
+#[derive(IBuilder)]
+struct BuilderAndWith {
+    #[ibuilder(builder = "custom", with = "ibuilder::builders::I32Builder")]
+    field: i32,
+}
+
+fn main() {}
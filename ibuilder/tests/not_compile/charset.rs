@@ -0,0 +1,21 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct NotPlain {
+    #[ibuilder(charset = "a-z")]
+    field: Vec<String>,
+}
+
+#[derive(IBuilder)]
+struct InvalidRange {
+    #[ibuilder(charset = "z-a")]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct Empty {
+    #[ibuilder(charset = "")]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,33 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct NotString {
+    #[ibuilder(choices("1", "2"))]
+    field: u8,
+}
+
+#[derive(IBuilder)]
+struct Empty {
+    #[ibuilder(choices())]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct Duplicated {
+    #[ibuilder(choices("a", "a"))]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct WithQuick {
+    #[ibuilder(choices("a"), quick = "b")]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct OtherWithoutChoices {
+    #[ibuilder(allow_other)]
+    field: String,
+}
+
+fn main() {}
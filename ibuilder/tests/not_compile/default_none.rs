@@ -0,0 +1,9 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Foo {
+    #[ibuilder(default_none)]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+#[ibuilder(draft)]
+struct Unnamed(i32);
+
+#[derive(IBuilder)]
+#[ibuilder(draft(serialize))]
+struct Unknown {
+    field: i32,
+}
+
+fn main() {}
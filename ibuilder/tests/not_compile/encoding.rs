@@ -0,0 +1,15 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Foo {
+    #[ibuilder(encoding = "base64")]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct Bar {
+    #[ibuilder(encoding = "base32")]
+    field: Vec<u8>,
+}
+
+fn main() {}
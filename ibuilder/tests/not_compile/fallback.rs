@@ -0,0 +1,22 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Alone {
+    #[ibuilder(max_attempts = 3)]
+    field: u32,
+}
+
+#[derive(IBuilder)]
+struct Zero {
+    #[ibuilder(max_attempts = 0, fallback_default)]
+    field: u32,
+}
+
+#[derive(IBuilder)]
+struct Hidden {
+    #[ibuilder(hidden, max_attempts = 3, fallback_default)]
+    field: u32,
+    other: u32,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Borrowed<'a> {
+    field: &'a str,
+}
+
+#[derive(IBuilder)]
+#[ibuilder(draft)]
+struct Drafted<T> {
+    field: T,
+}
+
+fn main() {}
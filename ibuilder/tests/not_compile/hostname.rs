@@ -0,0 +1,9 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Foo {
+    #[ibuilder(hostname)]
+    field: u16,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+#[ibuilder(inline_variant_fields)]
+enum Clash {
+    Empty,
+    Named { Empty: u8 },
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+#[ibuilder(action(id = "ping", text = "Ping"))]
+struct MissingHandler {
+    field: i64,
+}
+
+#[derive(IBuilder)]
+#[ibuilder(action(id = "ping", handler = "ping"))]
+struct Unnamed(i64);
+
+#[derive(IBuilder)]
+#[ibuilder(action(id = "field", handler = "ping"))]
+struct Clash {
+    field: i64,
+}
+
+fn ping(_: &PartialState) -> Option<String> {
+    None
+}
+
+fn main() {}
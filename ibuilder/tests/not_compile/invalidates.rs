@@ -0,0 +1,22 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Unknown {
+    #[ibuilder(invalidates = "nope")]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct Itself {
+    #[ibuilder(invalidates = "field")]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct Hidden {
+    #[ibuilder(hidden, invalidates = "other")]
+    field: String,
+    other: String,
+}
+
+fn main() {}
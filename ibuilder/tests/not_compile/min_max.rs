@@ -0,0 +1,28 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct NotNumeric {
+    #[ibuilder(min = 1)]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct NotNumber {
+    #[ibuilder(max = true)]
+    field: u32,
+}
+
+#[derive(IBuilder)]
+struct Duplicated {
+    #[ibuilder(min = 1, min = 2)]
+    field: u32,
+}
+
+#[derive(IBuilder)]
+struct Hidden {
+    #[ibuilder(hidden, min = 1)]
+    field: u32,
+    other: u32,
+}
+
+fn main() {}
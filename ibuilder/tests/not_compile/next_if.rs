@@ -0,0 +1,30 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct NotWizard {
+    #[ibuilder(next_if(cond = "cond", goto = "b"))]
+    a: i32,
+    b: i32,
+}
+
+#[derive(IBuilder)]
+#[ibuilder(mode = "wizard")]
+struct Backward {
+    a: i32,
+    #[ibuilder(next_if(cond = "cond", goto = "a"))]
+    b: i32,
+}
+
+#[derive(IBuilder)]
+#[ibuilder(mode = "wizard")]
+struct Unknown {
+    #[ibuilder(next_if(cond = "cond", goto = "c"))]
+    a: i32,
+    b: i32,
+}
+
+fn cond(_: &PartialState) -> bool {
+    true
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct NotString {
+    #[ibuilder(options_from = "list")]
+    field: u32,
+}
+
+#[derive(IBuilder)]
+struct Unnamed(#[ibuilder(options_from = "list")] String);
+
+#[derive(IBuilder)]
+struct Hidden {
+    #[ibuilder(hidden, options_from = "list")]
+    field: String,
+    other: String,
+}
+
+fn list(_: &PartialState) -> Vec<String> {
+    vec![]
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct NotString {
+    #[ibuilder(pattern = "^[0-9]+$")]
+    field: u8,
+}
+
+#[derive(IBuilder)]
+struct MessageWithoutPattern {
+    #[ibuilder(pattern_message = "only digits")]
+    field: String,
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Foo {
+    #[ibuilder(port)]
+    field: i32,
+}
+
+fn main() {}
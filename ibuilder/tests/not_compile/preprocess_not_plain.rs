@@ -0,0 +1,9 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Foo {
+    #[ibuilder(trim)]
+    field: Vec<String>,
+}
+
+fn main() {}
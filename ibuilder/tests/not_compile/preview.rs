@@ -0,0 +1,10 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Hidden {
+    #[ibuilder(hidden, preview)]
+    field: String,
+    other: String,
+}
+
+fn main() {}
@@ -0,0 +1,25 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct NotString {
+    #[ibuilder(quick = "1,2")]
+    field: u8,
+}
+
+#[derive(IBuilder)]
+struct Empty {
+    #[ibuilder(quick = " , ")]
+    field: String,
+}
+
+#[derive(IBuilder)]
+struct WithOptionsFrom {
+    #[ibuilder(quick = "a", options_from = "list")]
+    field: String,
+}
+
+fn list(_: &PartialState) -> Vec<String> {
+    vec![]
+}
+
+fn main() {}
@@ -0,0 +1,15 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct NotString {
+    #[ibuilder(secret)]
+    pin: u32,
+}
+
+#[derive(IBuilder)]
+struct Hidden {
+    #[ibuilder(secret, hidden, default = "x")]
+    password: String,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Hidden {
+    #[ibuilder(hidden, suggest_fn = "suggest")]
+    field: String,
+    other: String,
+}
+
+#[derive(IBuilder)]
+struct Unnamed(#[ibuilder(suggest_fn = "suggest")] String);
+
+#[derive(IBuilder)]
+struct Duplicated {
+    #[ibuilder(suggest_fn = "suggest", suggest_fn = "suggest")]
+    field: String,
+}
+
+fn suggest(_: &PartialState) -> Vec<String> {
+    vec![]
+}
+
+fn main() {}
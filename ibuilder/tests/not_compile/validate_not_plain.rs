@@ -0,0 +1,13 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Foo {
+    #[ibuilder(validate = "check")]
+    field: Vec<i32>,
+}
+
+fn check(_: &Vec<i32>) -> Validation {
+    Validation::Valid
+}
+
+fn main() {}
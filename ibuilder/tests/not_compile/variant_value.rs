@@ -0,0 +1,18 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+enum Foo {
+    #[ibuilder(value = 1)]
+    Var1(i32),
+    #[ibuilder(value = 2)]
+    Var2,
+}
+
+#[derive(IBuilder)]
+enum Bar {
+    Var1 = 1,
+    #[ibuilder(value = 1)]
+    Var2,
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+#[ibuilder(vis = "public")]
+struct Invalid {
+    field: i64,
+}
+
+#[derive(IBuilder)]
+#[ibuilder(vis = "pub", vis = "pub(crate)")]
+enum Duplicated {
+    Var,
+}
+
+mod private {
+    use ibuilder::*;
+
+    #[derive(IBuilder)]
+    pub struct Foo {
+        field: i64,
+    }
+}
+
+fn main() {
+    let _ = private::__Foo_BuildableValueImpl::new(Default::default());
+}
@@ -0,0 +1,13 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+#[ibuilder(mode = "questionnaire")]
+struct Foo {
+    field: i32,
+}
+
+#[derive(IBuilder)]
+#[ibuilder(mode = "wizard")]
+struct Bar(i32);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct InvalidPattern {
+    #[ibuilder(pattern = "^[0-9+$")]
+    field: String,
+}
+
+fn main() {}
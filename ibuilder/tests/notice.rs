@@ -0,0 +1,32 @@
+#![allow(dead_code)]
+
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Base {
+    integer: i32,
+}
+
+#[test]
+fn test_no_notice() {
+    let builder = Base::builder();
+    assert_eq!(builder.get_options().notice, None);
+}
+
+#[test]
+fn test_notify() {
+    let mut builder = Base::builder();
+    builder.notify("Welcome!");
+    assert_eq!(builder.get_options().notice.unwrap(), "Welcome!");
+    // the notice survives multiple renders
+    assert_eq!(builder.get_options().notice.unwrap(), "Welcome!");
+
+    builder.choose(Input::choice("integer")).unwrap();
+    assert_eq!(builder.get_options().notice, None);
+
+    builder.choose(Input::text("42")).unwrap();
+    builder.notify("Value saved");
+    let options = builder.get_options();
+    assert_eq!(options.notice.unwrap(), "Value saved");
+    assert!(options.choices.iter().any(|c| c.choice_id == FINALIZE_ID));
+}
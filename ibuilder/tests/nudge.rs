@@ -0,0 +1,55 @@
+#![allow(dead_code)]
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Server {
+    #[ibuilder(default = 8080)]
+    port: u16,
+    #[ibuilder(suggest_fn = "suggest_host")]
+    host: String,
+    name: Option<String>,
+}
+
+fn suggest_host(_: &PartialState) -> Vec<String> {
+    vec!["localhost".into(), "example.com".into()]
+}
+
+#[test]
+fn test_nudge() {
+    let mut builder = Server::builder();
+    // the first field that needs action comes first
+    let options = builder.nudge();
+    assert_eq!(options.choices[0].choice_id, "host");
+    assert_eq!(
+        options.notice.as_deref(),
+        Some("Still there? The suggested answer is \"Edit host\"")
+    );
+    assert_eq!(builder.get_options().notice, None);
+
+    builder.choose(Input::choice("port")).unwrap();
+    let options = builder.nudge();
+    assert_eq!(options.suggestions, vec!["8080"]);
+    assert_eq!(
+        options.notice.as_deref(),
+        Some("Still there? The suggested answer is \"8080\"")
+    );
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("host")).unwrap();
+    let options = builder.nudge();
+    assert_eq!(options.suggestions, vec!["localhost", "example.com"]);
+    assert_eq!(
+        options.notice.as_deref(),
+        Some("Still there? The suggested answer is \"localhost\"")
+    );
+    builder.choose(Input::text("example.com")).unwrap();
+
+    builder.choose(Input::choice("host")).unwrap();
+    let options = builder.nudge();
+    assert_eq!(options.suggestions, vec!["example.com", "localhost"]);
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("name")).unwrap();
+    assert_eq!(builder.nudge().notice.as_deref(), Some("Still there?"));
+}
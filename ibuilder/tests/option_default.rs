@@ -0,0 +1,68 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Profile {
+    #[ibuilder(default = "edomora97")]
+    nickname: Option<String>,
+    #[ibuilder(default = 42)]
+    age: Option<u8>,
+    #[ibuilder(default_none)]
+    website: Option<Website>,
+    bio: Option<String>,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Website {
+    url: String,
+}
+
+#[test]
+fn test_option_default() {
+    let builder = Profile::builder();
+    assert!(builder.is_done());
+    let profile = builder.finalize().unwrap();
+    assert_eq!(profile.nickname, Some("edomora97".to_string()));
+    assert_eq!(profile.age, Some(42));
+    assert_eq!(profile.website, None);
+    assert_eq!(profile.bio, None);
+}
+
+#[test]
+fn test_option_default_removed() {
+    let mut builder = Profile::builder();
+    builder.choose(Input::choice("age")).unwrap();
+    builder.choose(Input::choice("__remove")).unwrap();
+    assert_eq!(builder.finalize().unwrap().age, None);
+    // setting the value again starts from the default
+    builder.choose(Input::choice("age")).unwrap();
+    builder.choose(Input::choice("__set")).unwrap();
+    assert_eq!(builder.finalize().unwrap().age, Some(42));
+}
+
+#[test]
+fn test_default_none() {
+    let mut builder = Profile::builder();
+    builder.choose(Input::choice("website")).unwrap();
+    builder.choose(Input::choice("__set")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(builder.is_done());
+    assert_eq!(builder.finalize().unwrap().website, None);
+
+    builder.choose(Input::choice("__edit")).unwrap();
+    builder.choose(Input::choice("url")).unwrap();
+    builder.choose(Input::text("https://example.com")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap().website,
+        Some(Website {
+            url: "https://example.com".into()
+        })
+    );
+}
+
+#[test]
+fn test_without_default_none() {
+    let mut builder = Profile::builder();
+    builder.choose(Input::choice("bio")).unwrap();
+    builder.choose(Input::choice("__set")).unwrap();
+    assert!(!builder.is_done());
+}
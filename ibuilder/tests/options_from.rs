@@ -0,0 +1,78 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Storage {
+    region: String,
+    #[ibuilder(options_from = "list_buckets")]
+    bucket: String,
+}
+
+fn list_buckets(state: &PartialState) -> Vec<String> {
+    match state.get::<String>("region").as_deref() {
+        Some("eu") => vec!["backups".into(), "logs".into()],
+        Some("us") => vec!["media".into()],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn test_options_from_previous_answers() {
+    let mut builder = Storage::builder();
+    builder.choose(Input::choice("region")).unwrap();
+    builder.choose(Input::text("eu")).unwrap();
+    builder.choose(Input::choice("bucket")).unwrap();
+    let options = builder.get_options();
+    assert!(!options.text_input);
+    let choices: Vec<_> = options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect();
+    assert!(choices.contains(&"backups"));
+    assert!(choices.contains(&"logs"));
+    assert_eq!(
+        builder.choose(Input::text("media")),
+        Err(ChooseError::UnexpectedText)
+    );
+    assert_eq!(
+        builder.choose(Input::choice("media")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    builder.choose(Input::choice("logs")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Storage {
+            region: "eu".into(),
+            bucket: "logs".into()
+        }
+    );
+
+    builder.choose(Input::choice("region")).unwrap();
+    builder.choose(Input::text("us")).unwrap();
+    builder.choose(Input::choice("bucket")).unwrap();
+    let choices: Vec<_> = builder
+        .get_options()
+        .choices
+        .into_iter()
+        .map(|c| c.choice_id)
+        .collect();
+    assert!(choices.contains(&Id::from("media")));
+    assert!(!choices.contains(&Id::from("logs")));
+}
+
+#[test]
+fn test_no_options() {
+    let mut builder = Storage::builder();
+    builder.choose(Input::choice("bucket")).unwrap();
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("custom")).unwrap();
+    builder.choose(Input::choice("region")).unwrap();
+    builder.choose(Input::text("mars")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Storage {
+            region: "mars".into(),
+            bucket: "custom".into()
+        }
+    );
+}
@@ -0,0 +1,80 @@
+#![allow(dead_code)]
+
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Config {
+    #[ibuilder(rename = "application name")]
+    name: String,
+    #[ibuilder(default = 3)]
+    retries: u8,
+    verbose: bool,
+    level: Level,
+    server: Box<Server>,
+    tags: Vec<String>,
+    #[ibuilder(hidden)]
+    secret: String,
+}
+
+#[derive(IBuilder)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(IBuilder)]
+enum Level {
+    Low,
+    High,
+}
+
+fn path(path: &[&str]) -> Vec<String> {
+    path.iter().map(|s| s.to_string()).collect()
+}
+
+#[test]
+fn test_paths() {
+    let builder = Config::builder();
+    let paths = builder.paths();
+    let ids: Vec<_> = paths.iter().map(|p| p.path.clone()).collect();
+    assert_eq!(
+        ids,
+        vec![
+            path(&["name"]),
+            path(&["retries"]),
+            path(&["verbose"]),
+            path(&["level"]),
+            path(&["server", "host"]),
+            path(&["server", "port"]),
+            path(&["tags"]),
+        ]
+    );
+
+    assert_eq!(paths[0].name, "application name");
+    assert_eq!(paths[0].kind, InputKind::Text);
+    assert!(paths[0].required);
+
+    assert_eq!(paths[1].name, "retries");
+    assert!(!paths[1].required);
+
+    assert_eq!(
+        paths[2].kind,
+        InputKind::Choice(vec!["true".to_string(), "false".to_string()])
+    );
+    assert_eq!(
+        paths[3].kind,
+        InputKind::Choice(vec!["Low".to_string(), "High".to_string()])
+    );
+    assert_eq!(paths[5].name, "port");
+    assert_eq!(paths[6].kind, InputKind::Menu);
+}
+
+#[test]
+fn test_paths_required() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("app")).unwrap();
+    let paths = builder.paths();
+    assert!(!paths[0].required);
+    assert!(paths[4].required);
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "regex")]
+
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Account {
+    #[ibuilder(
+        pattern = "^[a-z0-9_-]{3,16}$",
+        pattern_message = "3 to 16 lowercase letters, digits, _ or -"
+    )]
+    username: String,
+    #[ibuilder(pattern = "^#[0-9a-f]{6}$")]
+    color: String,
+}
+
+#[test]
+fn test_pattern() {
+    let mut builder = Account::builder();
+    builder.choose(Input::choice("username")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("Jo")),
+        Err(ChooseError::InvalidText {
+            error: "3 to 16 lowercase letters, digits, _ or -".into()
+        })
+    );
+    builder.choose(Input::text("jo_doe")).unwrap();
+    builder.choose(Input::choice("color")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("red")),
+        Err(ChooseError::InvalidText {
+            error: "The value must match the pattern ^#[0-9a-f]{6}$".into()
+        })
+    );
+    builder.choose(Input::text("#ff8800")).unwrap();
+    let account = builder.finalize().unwrap();
+    assert_eq!(account.username, "jo_doe");
+    assert_eq!(account.color, "#ff8800");
+}
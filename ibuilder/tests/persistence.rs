@@ -0,0 +1,147 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ibuilder::persistence::{MemoryPersistence, PersistenceError, StatePersistence};
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Order {
+    item: String,
+    quantity: u32,
+}
+
+#[test]
+fn test_persistence_resume() {
+    let store = Rc::new(RefCell::new(MemoryPersistence::default()));
+    let mut builder = Order::builder()
+        .with_persistence(store.clone(), "chat-1")
+        .unwrap();
+    builder.choose(Input::choice("item")).unwrap();
+    builder.choose(Input::text("pizza")).unwrap();
+    assert_eq!(store.borrow().states["chat-1"], builder.save_state());
+    // the rejected inputs don't change the snapshot
+    builder.choose(Input::choice("quantity")).unwrap();
+    builder.choose(Input::text("many")).unwrap_err();
+    assert_eq!(store.borrow().states["chat-1"].inputs.len(), 3);
+    drop(builder);
+
+    // the process restarted: the session continues where it was left
+    let mut builder = Order::builder()
+        .with_persistence(store.clone(), "chat-1")
+        .unwrap();
+    assert_eq!(
+        builder.partial_state().get::<String>("item"),
+        Some("pizza".to_string())
+    );
+    builder.choose(Input::text("2")).unwrap();
+    let order = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(order.quantity, 2);
+    // the completed sessions are removed
+    assert!(store.borrow().states.is_empty());
+    assert!(builder.take_persistence_error().is_none());
+}
+
+#[test]
+fn test_persistence_keys() {
+    let store = Rc::new(RefCell::new(MemoryPersistence::default()));
+    let mut first = Order::builder()
+        .with_persistence(store.clone(), "chat-1")
+        .unwrap();
+    first.choose(Input::choice("item")).unwrap();
+    let second = Order::builder()
+        .with_persistence(store.clone(), "chat-2")
+        .unwrap();
+    assert!(!second.is_done());
+    assert_eq!(store.borrow().states.len(), 1);
+    // the changes not made with `choose` are saved explicitly
+    first.undo();
+    first.persist().unwrap();
+    assert!(store.borrow().states["chat-1"].inputs.is_empty());
+}
+
+/// A storage that is always unavailable.
+struct Broken;
+
+impl StatePersistence for Broken {
+    fn load(&mut self, _key: &str) -> Result<Option<SavedState>, PersistenceError> {
+        Ok(None)
+    }
+
+    fn save(&mut self, _key: &str, _state: &SavedState) -> Result<(), PersistenceError> {
+        Err(PersistenceError::Other("database down".into()))
+    }
+
+    fn remove(&mut self, _key: &str) -> Result<(), PersistenceError> {
+        Ok(())
+    }
+}
+
+#[test]
+fn test_persistence_error() {
+    let mut builder = Order::builder().with_persistence(Broken, "chat-1").unwrap();
+    // the input is applied anyway
+    builder.choose(Input::choice("item")).unwrap();
+    let error = builder.take_persistence_error().unwrap();
+    assert_eq!(error.to_string(), "database down");
+    assert!(builder.take_persistence_error().is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_file_persistence() {
+    use ibuilder::persistence::FilePersistence;
+
+    let dir = std::env::temp_dir().join(format!("ibuilder-persistence-{}", std::process::id()));
+    let store = FilePersistence::new(&dir).unwrap();
+    let mut builder = Order::builder()
+        .with_persistence(store.clone(), "user/42")
+        .unwrap();
+    builder.choose(Input::choice("item")).unwrap();
+    builder.choose(Input::text("pasta")).unwrap();
+    assert!(dir.join("user%2F42.json").exists());
+
+    let builder = Order::builder()
+        .with_persistence(store.clone(), "user/42")
+        .unwrap();
+    assert_eq!(
+        builder.partial_state().get::<String>("item"),
+        Some("pasta".to_string())
+    );
+    let mut store = store;
+    store.remove("user/42").unwrap();
+    assert_eq!(store.load("user/42").unwrap(), None);
+    std::fs::write(dir.join("bad.json"), "{").unwrap();
+    assert!(matches!(
+        store.load("bad"),
+        Err(PersistenceError::Invalid(_))
+    ));
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_persistence_not_restorable() {
+    let order = Order {
+        item: "pizza".into(),
+        quantity: 2,
+    };
+    let res = Builder::from_value(order).with_persistence(MemoryPersistence::default(), "chat-1");
+    assert!(matches!(res, Err(PersistenceError::Invalid(_))));
+}
+
+#[test]
+fn test_persistence_after_invalidate() {
+    let storage = Rc::new(RefCell::new(MemoryPersistence::default()));
+    let mut builder = Order::builder()
+        .with_persistence(storage.clone(), "chat-1")
+        .unwrap();
+    builder.choose(Input::choice("item")).unwrap();
+    builder.choose(Input::text("pizza")).unwrap();
+    builder.invalidate(&["item"]).unwrap();
+    builder.choose(Input::choice("quantity")).unwrap();
+    // the snapshot without the invalidation is not overwritten
+    assert!(matches!(
+        builder.take_persistence_error(),
+        Some(PersistenceError::Invalid(_))
+    ));
+    assert_eq!(storage.borrow().states["chat-1"].inputs.len(), 2);
+}
@@ -0,0 +1,69 @@
+use std::marker::PhantomData;
+
+use ibuilder::nodes::{FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, PartialEq)]
+struct Metric;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Distance {
+    value: f64,
+    _unit: PhantomData<Metric>,
+    _marker: (),
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Meters(PhantomData<Metric>, f64);
+
+#[derive(Debug, IBuilder, PartialEq)]
+enum Length {
+    Meters(f64, PhantomData<Metric>),
+    Infinite,
+}
+
+#[test]
+fn test_named_markers() {
+    let mut builder = Distance::builder();
+    let choices: Vec<_> = builder
+        .get_options()
+        .choices
+        .into_iter()
+        .map(|c| c.choice_id)
+        .collect();
+    assert!(choices.contains(&Id::from("value")));
+    assert!(!choices.contains(&Id::from("_unit")));
+    match builder.to_node() {
+        Node::Composite(_, fields) => {
+            assert_eq!(fields.len(), 1);
+            assert!(matches!(&fields[0], FieldKind::Named(name, _) if name == "value"));
+        }
+        node => panic!("Unexpected node: {:?}", node),
+    }
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::text("1.5")).unwrap();
+    assert!(builder.is_done());
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Distance {
+            value: 1.5,
+            _unit: PhantomData,
+            _marker: (),
+        }
+    );
+}
+
+#[test]
+fn test_unnamed_markers() {
+    let mut builder = Meters::builder();
+    builder.choose(Input::text("3")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Meters(PhantomData, 3.0));
+
+    let mut builder = Length::builder();
+    builder.choose(Input::choice("Meters")).unwrap();
+    builder.choose(Input::text("2")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Length::Meters(2.0, PhantomData)
+    );
+}
@@ -0,0 +1,84 @@
+use ibuilder::pick::{PickOrBuild, CREATE_ID};
+use ibuilder::*;
+
+#[derive(Clone, Debug, IBuilder, Eq, PartialEq)]
+struct Project {
+    name: String,
+}
+
+fn projects() -> PickOrBuild<Project> {
+    let existing = vec![
+        (
+            "First".to_string(),
+            Project {
+                name: "first".into(),
+            },
+        ),
+        (
+            "Second".to_string(),
+            Project {
+                name: "second".into(),
+            },
+        ),
+    ];
+    PickOrBuild::new(existing, Project::builder()).with_prompt("Which project?")
+}
+
+fn choice_ids(options: &Options) -> Vec<&str> {
+    options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect()
+}
+
+#[test]
+fn test_pick_existing() {
+    let mut pick = projects();
+    let options = pick.get_options();
+    assert_eq!(options.query, "Which project?");
+    assert_eq!(choice_ids(&options), vec!["0", "1", CREATE_ID]);
+    assert_eq!(options.choices[1].text, "Second");
+    assert_eq!(
+        pick.choose(Input::choice("1")).unwrap(),
+        Some(Project {
+            name: "second".into()
+        })
+    );
+}
+
+#[test]
+fn test_pick_invalid() {
+    let mut pick = projects();
+    assert_eq!(
+        pick.choose(Input::choice("2")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    assert_eq!(
+        pick.choose(Input::text("0")),
+        Err(ChooseError::UnexpectedText)
+    );
+}
+
+#[test]
+fn test_pick_build() {
+    let mut pick = projects();
+    pick.choose(Input::choice(CREATE_ID)).unwrap();
+    assert!(pick.is_building());
+    assert_eq!(choice_ids(&pick.get_options()), vec!["name", BACK_ID]);
+    pick.choose(Input::choice("name")).unwrap();
+    // inside a field "Go back" is handled by the builder
+    pick.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(pick.is_building());
+    pick.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(!pick.is_building());
+    pick.choose(Input::choice(CREATE_ID)).unwrap();
+    pick.choose(Input::choice("name")).unwrap();
+    pick.choose(Input::text("third")).unwrap();
+    assert_eq!(
+        pick.choose(Input::choice(FINALIZE_ID)).unwrap(),
+        Some(Project {
+            name: "third".into()
+        })
+    );
+}
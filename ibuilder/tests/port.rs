@@ -0,0 +1,42 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Server {
+    #[ibuilder(port)]
+    port: u16,
+    #[ibuilder(port(allow_zero, warn_privileged))]
+    admin_port: u16,
+    #[ibuilder(port, prompt = "Metrics port")]
+    metrics_port: u16,
+}
+
+#[test]
+fn test_port() {
+    let mut builder = Server::builder();
+    builder.choose(Input::choice("port")).unwrap();
+    assert_eq!(builder.get_options().query, "Type the port number");
+    let res = builder.choose(Input::text("0"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("80")).unwrap();
+
+    builder.choose(Input::choice("admin_port")).unwrap();
+    builder.choose(Input::text("0")).unwrap();
+    builder.choose(Input::choice("admin_port")).unwrap();
+    assert_eq!(builder.get_options().warning, None);
+    builder.choose(Input::text("443")).unwrap();
+    builder.choose(Input::choice("admin_port")).unwrap();
+    assert_eq!(
+        builder.get_options().warning,
+        Some("Port 443 is privileged".to_string())
+    );
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("metrics_port")).unwrap();
+    assert_eq!(builder.get_options().query, "Metrics port");
+    builder.choose(Input::text("9090")).unwrap();
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.port, 80);
+    assert_eq!(value.admin_port, 443);
+    assert_eq!(value.metrics_port, 9090);
+}
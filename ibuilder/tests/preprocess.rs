@@ -0,0 +1,56 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Account {
+    #[ibuilder(trim, lowercase)]
+    email: String,
+    #[ibuilder(trim)]
+    age: u8,
+    #[ibuilder(preprocess = "strip_separators", validate = "check_phone")]
+    phone: u64,
+    name: String,
+}
+
+fn strip_separators(text: &str) -> String {
+    text.chars().filter(|c| !matches!(c, ' ' | '-')).collect()
+}
+
+fn check_phone(phone: &u64) -> Validation {
+    if *phone < 1_000_000 {
+        Validation::Invalid("Too short".into())
+    } else {
+        Validation::Valid
+    }
+}
+
+#[test]
+fn test_preprocess() {
+    let mut builder = Account::builder();
+    builder.choose(Input::choice("email")).unwrap();
+    builder.choose(Input::text("  Foo@Example.com\n")).unwrap();
+    builder.choose(Input::choice("age")).unwrap();
+    builder.choose(Input::text(" 42 ")).unwrap();
+    builder.choose(Input::choice("phone")).unwrap();
+    // the validators receive the preprocessed value
+    assert_eq!(
+        builder.choose(Input::text("123-4")),
+        Err(ChooseError::InvalidText {
+            error: "Too short".into()
+        })
+    );
+    builder.choose(Input::text("333 123-4567")).unwrap();
+    // the fields without preprocessors keep the text as is
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text(" Foo ")).unwrap();
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Account {
+            email: "foo@example.com".into(),
+            age: 42,
+            phone: 3331234567,
+            name: " Foo ".into(),
+        }
+    );
+}
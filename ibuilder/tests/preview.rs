@@ -0,0 +1,87 @@
+use ibuilder::builders::LeafBuilder;
+use ibuilder::*;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Date {
+    year: u32,
+    month: u32,
+    day: u32,
+}
+
+impl LeafBuildableValue for Date {
+    fn parse(text: &str) -> Result<Self, String> {
+        let parts: Vec<_> = text.split(['-', '/']).collect();
+        match parts.iter().map(|p| p.parse::<u32>()).collect::<Vec<_>>()[..] {
+            [Ok(year), Ok(month), Ok(day)] => Ok(Date { year, month, day }),
+            _ => Err(format!("Invalid date: {}", text)),
+        }
+    }
+
+    fn render(&self) -> String {
+        format!("{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl NewBuildableValue for Date {
+    fn new_buildable_value(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+        Box::new(LeafBuilder::<Date>::new(config))
+    }
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Event {
+    #[ibuilder(preview)]
+    date: Date,
+    #[ibuilder(preview, trim)]
+    guests: u32,
+    #[ibuilder(preview)]
+    tags: Vec<String>,
+}
+
+#[test]
+fn test_preview() {
+    let mut builder = Event::builder();
+    builder.choose(Input::choice("date")).unwrap();
+    assert!(builder.get_options().text_input);
+    assert!(builder.choose(Input::text("tomorrow")).is_err());
+    builder.choose(Input::text("2023/5/1")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.query, "Parsed as 2023-05-01");
+    assert!(!options.text_input);
+    assert_eq!(
+        builder.choose(Input::text("2023/5/2")),
+        Err(ChooseError::UnexpectedText)
+    );
+    // the value is not saved until it is confirmed
+    assert!(!builder.is_done());
+    builder.choose(Input::choice("__reenter")).unwrap();
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("2023/5/2")).unwrap();
+    builder.choose(Input::choice("__confirm")).unwrap();
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+
+    builder.choose(Input::choice("guests")).unwrap();
+    builder.choose(Input::text(" 12 ")).unwrap();
+    assert_eq!(builder.get_options().query, "Parsed as 12");
+    builder.choose(Input::choice("__confirm")).unwrap();
+
+    // the inner menus are not previewed
+    builder.choose(Input::choice("tags")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("party")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Event {
+            date: Date {
+                year: 2023,
+                month: 5,
+                day: 2
+            },
+            guests: 12,
+            tags: vec!["party".into()],
+        }
+    );
+}
@@ -0,0 +1,87 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(prompt = "Edit the person", prompt_key = "person-prompt")]
+struct Person {
+    #[ibuilder(prompt = "How old are you?", prompt_key = "person-age-prompt")]
+    age: u8,
+    #[ibuilder(prompt = "Where do you live?")]
+    address: Address,
+    pet: Pet,
+}
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(prompt_key = "address-prompt")]
+struct Address {
+    #[ibuilder(prompt_key = "address-city-prompt")]
+    city: String,
+}
+
+#[derive(Debug, IBuilder)]
+#[ibuilder(prompt = "Pick a pet", prompt_key = "pet-prompt")]
+enum Pet {
+    None,
+    #[ibuilder(prompt = "Name of the dog?", prompt_key = "pet-dog-prompt")]
+    Dog(String),
+}
+
+fn italian() -> BuilderConfig {
+    BuilderConfig {
+        localizer: Some(Localizer::new(|key| match key {
+            "person-prompt" => Some("Modifica la persona".into()),
+            "person-age-prompt" => Some("Quanti anni hai?".into()),
+            "pet-prompt" => Some("Scegli un animale".into()),
+            "pet-dog-prompt" => Some("Nome del cane?".into()),
+            _ => None,
+        })),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_prompt_key() {
+    let mut builder = Person::builder();
+    let options = builder.get_options();
+    assert_eq!(options.prompt_key.as_deref(), Some("person-prompt"));
+    assert_eq!(options.query, "Edit the person");
+    builder.choose(Input::choice("age")).unwrap();
+    assert_eq!(
+        builder.get_options().prompt_key.as_deref(),
+        Some("person-age-prompt")
+    );
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    // the prompt of the field replaces the one of the type, and its key
+    builder.choose(Input::choice("address")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.prompt_key, None);
+    assert_eq!(options.query, "Where do you live?");
+    builder.choose(Input::choice("city")).unwrap();
+    assert_eq!(
+        builder.get_options().prompt_key.as_deref(),
+        Some("address-city-prompt")
+    );
+}
+
+#[test]
+fn test_localizer() {
+    let mut builder = Person::builder().with_config(italian());
+    assert_eq!(builder.get_options().query, "Modifica la persona");
+    builder.choose(Input::choice("age")).unwrap();
+    assert_eq!(builder.get_options().query, "Quanti anni hai?");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("pet")).unwrap();
+    assert_eq!(builder.get_options().query, "Scegli un animale");
+    builder.choose(Input::choice("Dog")).unwrap();
+    assert_eq!(builder.get_options().query, "Nome del cane?");
+}
+
+#[test]
+fn test_localizer_missing_key() {
+    let mut builder = Person::builder().with_config(italian());
+    builder.choose(Input::choice("address")).unwrap();
+    builder.choose(Input::choice("city")).unwrap();
+    // the key is not translated, so the prompt is kept
+    let options = builder.get_options();
+    assert_eq!(options.prompt_key.as_deref(), Some("address-city-prompt"));
+    assert_eq!(options.query, "Type a string");
+}
@@ -0,0 +1,94 @@
+#![allow(dead_code)]
+
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Root {
+    name: String,
+    child: Child,
+    list: Vec<Leaf>,
+}
+
+#[derive(Debug, IBuilder)]
+struct Child {
+    inner: Leaf,
+}
+
+#[derive(Debug, IBuilder)]
+struct Leaf {
+    value: i32,
+}
+
+#[derive(Debug, IBuilder)]
+struct Tree {
+    children: Vec<Tree>,
+}
+
+fn named<'a>(node: &'a Node, name: &str) -> &'a Node {
+    match node {
+        Node::Composite(_, fields) => fields
+            .iter()
+            .find_map(|field| match field {
+                FieldKind::Named(n, node) if n == name => Some(node),
+                _ => None,
+            })
+            .unwrap(),
+        Node::Leaf(_) => panic!("Expecting a composite node"),
+    }
+}
+
+fn is_elided(node: &Node) -> bool {
+    matches!(node, Node::Leaf(Field::Elided))
+}
+
+fn depth(node: &Node) -> usize {
+    match node {
+        Node::Leaf(_) => 0,
+        Node::Composite(_, fields) => {
+            1 + fields
+                .iter()
+                .map(|field| match field {
+                    FieldKind::Named(_, node) | FieldKind::Unnamed(node) => depth(node),
+                })
+                .max()
+                .unwrap_or(0)
+        }
+    }
+}
+
+#[test]
+fn test_pruned_struct() {
+    let mut builder = Root::builder();
+    builder.choose(Input::choice("list")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+
+    assert!(is_elided(&builder.to_node_pruned(0)));
+
+    let node = builder.to_node_pruned(1);
+    assert_eq!(named(&node, "name"), &Node::Leaf(Field::Missing));
+    assert!(is_elided(named(&node, "child")));
+    assert!(is_elided(named(&node, "list")));
+
+    let node = builder.to_node_pruned(2);
+    assert!(is_elided(named(named(&node, "child"), "inner")));
+    assert!(!is_elided(named(&node, "list")));
+
+    assert_eq!(builder.to_node_pruned(usize::MAX), builder.to_node());
+    assert_eq!(builder.to_node().pruned(2), builder.to_node_pruned(2));
+}
+
+#[test]
+fn test_pruned_deep() {
+    let mut builder = Tree::builder();
+    builder.choose(Input::choice("children")).unwrap();
+    for _ in 0..200 {
+        builder.choose(Input::choice("__new")).unwrap();
+        builder.choose(Input::choice("children")).unwrap();
+    }
+    assert_eq!(depth(&builder.to_node()), 402);
+    let node = builder.to_node_pruned(5);
+    assert_eq!(depth(&node), 5);
+}
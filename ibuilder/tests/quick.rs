@@ -0,0 +1,43 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Rsvp {
+    #[ibuilder(quick = "yes, no,maybe")]
+    attending: String,
+    notes: String,
+}
+
+#[test]
+fn test_quick_replies() {
+    let mut builder = Rsvp::builder();
+    builder.choose(Input::choice("attending")).unwrap();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    let choices: Vec<_> = options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect();
+    assert_eq!(choices, vec!["yes", "no", "maybe", BACK_ID]);
+    builder.choose(Input::choice("maybe")).unwrap();
+    builder.choose(Input::choice("notes")).unwrap();
+    assert_eq!(
+        builder.choose(Input::choice("yes")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    builder.choose(Input::text("plus one")).unwrap();
+    let rsvp = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(rsvp.attending, "maybe");
+    assert_eq!(rsvp.notes, "plus one");
+}
+
+#[test]
+fn test_quick_free_text() {
+    let mut builder = Rsvp::builder();
+    builder.choose(Input::choice("attending")).unwrap();
+    builder.choose(Input::text("only on Friday")).unwrap();
+    assert_eq!(
+        builder.partial_state().get::<String>("attending"),
+        Some("only on Friday".to_string())
+    );
+}
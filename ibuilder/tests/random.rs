@@ -1,4 +1,4 @@
-#![allow(clippy::box_vec, clippy::type_complexity)]
+#![allow(dead_code, clippy::box_collection, clippy::type_complexity)]
 
 use ibuilder::*;
 use rand::prelude::*;
@@ -0,0 +1,82 @@
+use std::ops::{Range, RangeInclusive};
+
+use ibuilder::builders::RangeBuilder;
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Base {
+    ports: Range<u16>,
+    days: RangeInclusive<u8>,
+}
+
+#[test]
+fn test_range() {
+    let mut builder = Base::builder();
+    builder.choose(Input::choice("ports")).unwrap();
+    builder.choose(Input::choice("start")).unwrap();
+    builder.choose(Input::text("8000")).unwrap();
+    builder.choose(Input::choice("end")).unwrap();
+    builder.choose(Input::text("8080")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("days")).unwrap();
+    builder.choose(Input::choice("end")).unwrap();
+    builder.choose(Input::text("5")).unwrap();
+    builder.choose(Input::choice("start")).unwrap();
+    builder.choose(Input::text(" 1 ")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    match builder.to_node() {
+        Node::Composite(_, fields) => {
+            let values: Vec<_> = fields
+                .iter()
+                .map(|f| match f {
+                    FieldKind::Named(_, Node::Leaf(Field::String(s))) => s.as_str(),
+                    _ => panic!("expecting a string"),
+                })
+                .collect();
+            assert_eq!(values, vec!["8000..8080", "1..=5"]);
+        }
+        _ => panic!("expecting a composite"),
+    }
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Base {
+            ports: 8000..8080,
+            days: 1..=5
+        }
+    );
+}
+
+#[test]
+fn test_start_after_end() {
+    let mut builder = Base::builder();
+    builder.choose(Input::choice("ports")).unwrap();
+    builder.choose(Input::choice("start")).unwrap();
+    builder.choose(Input::text("8080")).unwrap();
+    builder.choose(Input::choice("end")).unwrap();
+    let res = builder.choose(Input::text("8000"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder.choose(Input::text("8080")).unwrap();
+    builder.choose(Input::choice("start")).unwrap();
+    let res = builder.choose(Input::text("9000"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    let res = builder.choose(Input::text("nope"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+}
+
+#[test]
+fn test_range_typed() {
+    let mut builder = RangeBuilder::<u8>::new(Default::default(), true);
+    builder.apply(&Input::choice("start"), &[]).unwrap();
+    builder.apply(&Input::text("1"), &["start".into()]).unwrap();
+    builder.apply(&Input::choice("end"), &[]).unwrap();
+    builder.apply(&Input::text("5"), &["end".into()]).unwrap();
+    assert_eq!(
+        TypedBuildableValue::<RangeInclusive<u8>>::get_value(&builder),
+        Some(1..=5)
+    );
+    assert_eq!(TypedBuildableValue::<Range<u8>>::get_value(&builder), None);
+    let value = builder.get_value_any().unwrap();
+    assert_eq!(*value.downcast::<RangeInclusive<u8>>().unwrap(), 1..=5);
+}
@@ -0,0 +1,109 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Base {
+    integer: i32,
+    items: Vec<i32>,
+}
+
+/// A clock that moves only when told to, so the tests don't depend on the speed of the machine.
+fn manual_clock() -> (Clock, Rc<Cell<Instant>>) {
+    let now = Rc::new(Cell::new(Instant::now()));
+    let clock = {
+        let now = now.clone();
+        Clock::new(move || now.get())
+    };
+    (clock, now)
+}
+
+fn advance(now: &Cell<Instant>, millis: u64) {
+    now.set(now.get() + Duration::from_millis(millis));
+}
+
+#[test]
+fn test_max_inputs_per_second() {
+    let (clock, now) = manual_clock();
+    let mut builder = Base::builder().with_config(BuilderConfig {
+        max_inputs_per_second: Some(2),
+        clock,
+        ..Default::default()
+    });
+    builder.choose(Input::choice("items")).unwrap();
+    advance(&now, 500);
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    advance(&now, 499);
+    assert_eq!(
+        builder.choose(Input::choice("integer")),
+        Err(ChooseError::RateLimited)
+    );
+    // the state didn't change
+    assert!(!builder.get_options().text_input);
+    // the first input left the window of the last second
+    advance(&now, 1);
+    builder.choose(Input::choice("integer")).unwrap();
+    assert!(builder.get_options().text_input);
+}
+
+#[test]
+fn test_debounce() {
+    let (clock, now) = manual_clock();
+    let mut builder = Base::builder().with_config(BuilderConfig {
+        debounce: Some(Duration::from_millis(200)),
+        clock,
+        ..Default::default()
+    });
+    builder.choose(Input::choice("items")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("1")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    // a double click doesn't select the button twice
+    advance(&now, 150);
+    assert_eq!(
+        builder.choose(Input::choice("__new")),
+        Err(ChooseError::RateLimited)
+    );
+    // after the interval the same input is not debounced anymore
+    advance(&now, 100);
+    assert_eq!(
+        builder.choose(Input::choice("__new")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+    builder.choose(Input::text("2")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("integer")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    let value = builder.finalize().unwrap();
+    assert_eq!(value.items, vec![1, 2]);
+    assert_eq!(value.integer, 42);
+}
+
+#[test]
+fn test_no_limits_by_default() {
+    let mut builder = Base::builder();
+    for _ in 0..100 {
+        builder.choose(Input::choice("items")).unwrap();
+        builder.choose(Input::choice(BACK_ID)).unwrap();
+    }
+}
+
+#[test]
+fn test_rate_limited_not_in_history() {
+    let (clock, _now) = manual_clock();
+    let mut builder = Base::builder().with_config(BuilderConfig {
+        max_inputs_per_second: Some(1),
+        clock,
+        ..Default::default()
+    });
+    builder.choose(Input::choice("items")).unwrap();
+    for _ in 0..100 {
+        assert_eq!(
+            builder.choose(Input::choice(BACK_ID)),
+            Err(ChooseError::RateLimited)
+        );
+    }
+    assert_eq!(builder.history().len(), 1);
+}
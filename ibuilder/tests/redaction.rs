@@ -0,0 +1,121 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use ibuilder::metrics::MetricsSink;
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::redaction::{global_redaction, set_global_redaction, Redaction, PLACEHOLDER};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Patient {
+    name: String,
+    age: u8,
+    notes: Vec<String>,
+}
+
+fn session(redaction: Redaction) -> Builder<Patient> {
+    let mut builder = Patient::builder().with_config(BuilderConfig {
+        redaction: Some(redaction),
+        ..Default::default()
+    });
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("Jane Roe")).unwrap();
+    builder.choose(Input::choice("notes")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("allergic")).unwrap();
+    builder
+}
+
+fn leaves(node: &Node) -> Vec<String> {
+    match node {
+        Node::Leaf(Field::String(value)) => vec![value.clone()],
+        Node::Leaf(_) => vec![],
+        Node::Composite(_, fields) => fields
+            .iter()
+            .flat_map(|field| match field {
+                FieldKind::Named(_, node) | FieldKind::Unnamed(node) => leaves(node),
+            })
+            .collect(),
+    }
+}
+
+#[test]
+fn test_redaction_off() {
+    let builder = session(Redaction::Off);
+    assert_eq!(leaves(&builder.to_node()), vec!["Jane Roe", "allergic"]);
+    assert!(format!("{:?}", builder).contains("Jane Roe"));
+    assert_eq!(builder.history()[1].input, Input::text("Jane Roe"));
+}
+
+#[test]
+fn test_redaction_placeholder() {
+    let builder = session(Redaction::Placeholder);
+    assert_eq!(leaves(&builder.to_node()), vec![PLACEHOLDER, PLACEHOLDER]);
+    assert_eq!(leaves(&builder.to_node_pruned(1)), vec![PLACEHOLDER]);
+    let debug = format!("{:?}", builder);
+    assert!(!debug.contains("Jane Roe"));
+    assert!(!debug.contains("allergic"));
+    let history = builder.history();
+    assert_eq!(history[0].input, Input::choice("name"));
+    assert_eq!(history[1].input, Input::text(PLACEHOLDER));
+    // the snapshots keep the values, they are needed for restoring the state
+    assert_eq!(builder.save_state().inputs[1], Input::text("Jane Roe"));
+    // the value is not affected
+    assert_eq!(
+        builder.partial_state().get::<String>("name"),
+        Some("Jane Roe".to_string())
+    );
+}
+
+#[test]
+fn test_redaction_hash() {
+    let mut builder = session(Redaction::Hash);
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("allergic")).unwrap();
+    let values = leaves(&builder.to_node());
+    assert!(values.iter().all(|value| value.starts_with("[hash:")));
+    assert_ne!(values[0], values[1]);
+    // the equal values have the same hash
+    assert_eq!(values[1], values[2]);
+}
+
+#[derive(Default)]
+struct Transcript(Vec<Input>);
+
+impl MetricsSink for Transcript {
+    fn input(&mut self, _fields: &[Id], input: &Input) {
+        self.0.push(input.clone());
+    }
+}
+
+#[test]
+fn test_redaction_metrics() {
+    let transcript = Rc::new(RefCell::new(Transcript::default()));
+    let mut builder = Patient::builder()
+        .with_config(BuilderConfig {
+            redaction: Some(Redaction::Placeholder),
+            ..Default::default()
+        })
+        .with_metrics(transcript.clone());
+    builder.choose(Input::choice("age")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    assert_eq!(
+        transcript.borrow().0,
+        vec![Input::choice("age"), Input::text(PLACEHOLDER)]
+    );
+}
+
+#[test]
+fn test_redaction_global() {
+    assert_eq!(global_redaction(), Redaction::Off);
+    set_global_redaction(Redaction::Placeholder);
+    let mut builder = Patient::builder();
+    builder.choose(Input::choice("age")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    assert_eq!(builder.redaction(), Redaction::Placeholder);
+    assert_eq!(leaves(&builder.to_node()), vec![PLACEHOLDER]);
+    // the configuration of the builder has the precedence
+    let builder = session(Redaction::Off);
+    assert_eq!(leaves(&builder.to_node()), vec!["Jane Roe", "allergic"]);
+    set_global_redaction(Redaction::Off);
+}
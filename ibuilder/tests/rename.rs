@@ -75,7 +75,7 @@ fn test_enum_empty() {
     match nodes {
         Node::Leaf(field) => match field {
             Field::String(name) => assert_eq!(name, "renamed variant"),
-            Field::Missing => panic!("Expecting a string"),
+            Field::Warning(_, _) | Field::Missing | Field::Elided => panic!("Expecting a string"),
         },
         Node::Composite(_, _) => panic!("Expecting a leaf"),
     }
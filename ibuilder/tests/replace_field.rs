@@ -0,0 +1,139 @@
+use ibuilder::builders::StringBuilder;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Config {
+    name: String,
+    server: Server,
+}
+
+#[derive(Debug, IBuilder)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+fn prefilled(value: &str) -> Box<dyn BuildableValue> {
+    Box::new(StringBuilder::new(BuildableValueConfig {
+        default: Some(value.to_string()),
+        prompt: Some("Pick the host".to_string()),
+        validators: vec![],
+        preprocessors: vec![],
+        hint: None,
+        charset: None,
+    }))
+}
+
+#[test]
+fn test_replace_field() {
+    let mut builder = Config::builder();
+    builder.replace_field(&["name"], prefilled("app")).unwrap();
+    builder
+        .replace_field(&["server", "host"], prefilled("localhost"))
+        .unwrap();
+
+    builder.choose(Input::choice("server")).unwrap();
+    builder.choose(Input::choice("host")).unwrap();
+    assert_eq!(builder.get_options().query, "Pick the host");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("8080")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.name, "app");
+    assert_eq!(value.server.host, "localhost");
+    assert_eq!(value.server.port, 8080);
+}
+
+#[test]
+fn test_replace_field_returns_previous() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("old")).unwrap();
+    let previous = builder.replace_field(&["name"], prefilled("new")).unwrap();
+    assert_eq!(
+        *previous
+            .get_value_any()
+            .unwrap()
+            .downcast::<String>()
+            .unwrap(),
+        "old"
+    );
+}
+
+#[test]
+fn test_replace_field_unknown() {
+    let mut builder = Config::builder();
+    let res = builder.replace_field(&["server", "nope"], prefilled("x"));
+    assert_eq!(
+        res.err(),
+        Some(ReplaceFieldError::UnknownField {
+            field: "nope".to_string()
+        })
+    );
+    let res = builder.replace_field(&["name", "inner"], prefilled("x"));
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_replace_field_moves_user_out() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("server")).unwrap();
+    builder.choose(Input::choice("host")).unwrap();
+    builder
+        .replace_field(&["server", "host"], prefilled("localhost"))
+        .unwrap();
+    let options = builder.get_options();
+    assert!(options.choices.iter().any(|c| c.choice_id == "host"));
+    assert!(options.choices.iter().any(|c| c.choice_id == "port"));
+}
+
+#[test]
+fn test_replace_field_undo() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("app")).unwrap();
+    builder
+        .replace_field(&["server", "host"], prefilled("localhost"))
+        .unwrap();
+    builder.choose(Input::choice("server")).unwrap();
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("8080")).unwrap();
+
+    assert!(builder.undo());
+    assert!(builder.undo());
+    assert!(builder.undo());
+    // the replacement and the inputs before it are kept
+    assert!(!builder.undo());
+    builder.choose(Input::choice("server")).unwrap();
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("80")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let value = builder.finalize().unwrap();
+    assert_eq!(value.name, "app");
+    assert_eq!(value.server.host, "localhost");
+    assert_eq!(value.server.port, 80);
+}
+
+#[test]
+fn test_replace_field_restore_state() {
+    let mut builder = Config::builder();
+    builder
+        .replace_field(&["server", "host"], prefilled("localhost"))
+        .unwrap();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("app")).unwrap();
+    let state = builder.save_state();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("other")).unwrap();
+
+    builder.restore_state(&state).unwrap();
+    builder.choose(Input::choice("server")).unwrap();
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("80")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let value = builder.finalize().unwrap();
+    assert_eq!(value.name, "app");
+    assert_eq!(value.server.host, "localhost");
+}
@@ -0,0 +1,140 @@
+use ibuilder::*;
+
+/// The type of the snapshot, before the deployment.
+#[derive(Debug, PartialEq, IBuilder)]
+struct OrderV1 {
+    item: String,
+    quantity: String,
+    gift: Gift,
+    shipping: Shipping,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Gift {
+    message: String,
+    wrapped: bool,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+enum Shipping {
+    Pickup,
+    Courier { address: String },
+}
+
+/// The type after the deployment: `gift` has been removed, `quantity` is now a number and `notes`
+/// is new.
+#[derive(Debug, PartialEq, IBuilder)]
+struct OrderV2 {
+    item: String,
+    quantity: u32,
+    shipping: Shipping,
+    notes: String,
+}
+
+fn session() -> Builder<OrderV1> {
+    let mut builder = OrderV1::builder();
+    builder.choose(Input::choice("item")).unwrap();
+    builder.choose(Input::text("book")).unwrap();
+    builder.choose(Input::choice("quantity")).unwrap();
+    builder.choose(Input::text("a few")).unwrap();
+    builder.choose(Input::choice("gift")).unwrap();
+    builder.choose(Input::choice("message")).unwrap();
+    builder.choose(Input::text("Happy birthday")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("shipping")).unwrap();
+    builder.choose(Input::choice("Courier")).unwrap();
+    builder.choose(Input::choice("address")).unwrap();
+    builder.choose(Input::text("Main Street")).unwrap();
+    builder
+}
+
+#[test]
+fn test_restore_changed_type() {
+    let state = session().save_state();
+    let mut builder = OrderV2::builder();
+    let report = builder.restore_state_lenient(&state).unwrap();
+    assert!(!report.is_complete());
+    assert_eq!(report.applied, 7);
+    let dropped: Vec<_> = report
+        .dropped
+        .iter()
+        .map(|d| (d.index, d.reason.clone()))
+        .collect();
+    assert_eq!(
+        dropped,
+        vec![
+            (
+                3,
+                DropReason::Rejected(ChooseError::InvalidText {
+                    error: "invalid digit found in string".into()
+                })
+            ),
+            (4, DropReason::Rejected(ChooseError::UnexpectedChoice)),
+            (5, DropReason::UnknownMenu),
+            (6, DropReason::UnknownMenu),
+            (7, DropReason::UnknownMenu),
+        ]
+    );
+    assert_eq!(report.dropped[1].input, Input::choice("gift"));
+    // the text was rejected, so the user is moved back to the main menu like in the snapshot
+    assert_eq!(report.dropped[1].path, Vec::<String>::new());
+    assert_eq!(report.dropped[2].input, Input::choice("message"));
+
+    let partial = builder.partial_state();
+    assert_eq!(partial.get::<String>("item"), Some("book".into()));
+    assert_eq!(partial.get::<u32>("quantity"), None);
+    assert_eq!(
+        partial.get::<Shipping>("shipping"),
+        Some(Shipping::Courier {
+            address: "Main Street".into()
+        })
+    );
+    builder.goto(&["quantity"]).unwrap();
+    builder.choose(Input::text("3")).unwrap();
+    builder.choose(Input::choice("notes")).unwrap();
+    builder.choose(Input::text("Ring twice")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.quantity, 3);
+    assert_eq!(value.notes, "Ring twice");
+    // the restored inputs are the history of the builder, with the goto
+    assert_eq!(builder.save_state().inputs.len(), 11);
+}
+
+#[test]
+fn test_restore_same_type() {
+    let state = session().save_state();
+    let mut builder = OrderV1::builder();
+    let report = builder.restore_state_lenient(&state).unwrap();
+    assert!(report.is_complete());
+    assert_eq!(report.applied, state.inputs.len());
+    assert_eq!(builder.save_state(), state);
+}
+
+#[test]
+fn test_restore_without_paths() {
+    let state = SavedState {
+        inputs: session().save_state().inputs,
+        ..Default::default()
+    };
+    let mut builder = OrderV2::builder();
+    let report = builder.restore_state_lenient(&state).unwrap();
+    // without the paths only the rejected inputs are dropped
+    assert!(report
+        .dropped
+        .iter()
+        .all(|d| matches!(d.reason, DropReason::Rejected(_))));
+    assert_eq!(
+        builder.partial_state().get::<String>("item"),
+        Some("book".into())
+    );
+}
+
+#[test]
+fn test_restore_lenient_unsupported() {
+    let mut builder =
+        Builder::<OrderV2>::from_buildable_value(OrderV2::new_buildable_value(Default::default()));
+    assert_eq!(
+        builder.restore_state_lenient(&session().save_state()),
+        Err(RestoreError::Unsupported)
+    );
+}
@@ -0,0 +1,78 @@
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Base {
+    outcome: Result<i32, String>,
+}
+
+#[test]
+fn test_ok() {
+    let mut builder = Base::builder();
+    builder.choose(Input::choice("outcome")).unwrap();
+    let options = builder.get_options();
+    let ids: Vec<_> = options
+        .choices
+        .iter()
+        .map(|c| c.choice_id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["Ok", "Err", BACK_ID]);
+    builder.choose(Input::choice("Ok")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value, Base { outcome: Ok(42) });
+}
+
+#[test]
+fn test_err() {
+    let mut builder = Base::builder();
+    builder.choose(Input::choice("outcome")).unwrap();
+    builder.choose(Input::choice("Err")).unwrap();
+    builder.choose(Input::text("nope")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    match builder.to_node() {
+        Node::Composite(_, fields) => match &fields[0] {
+            FieldKind::Named(name, Node::Composite(variant, inner)) => {
+                assert_eq!(name, "outcome");
+                assert_eq!(variant, "Err");
+                match &inner[0] {
+                    FieldKind::Unnamed(Node::Leaf(Field::String(s))) => assert_eq!(s, "nope"),
+                    _ => panic!("expecting a string"),
+                }
+            }
+            _ => panic!("expecting a composite"),
+        },
+        _ => panic!("expecting a composite"),
+    }
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Base {
+            outcome: Err("nope".into())
+        }
+    );
+}
+
+#[test]
+fn test_switch_variant() {
+    let mut builder = Base::builder();
+    builder.choose(Input::choice("outcome")).unwrap();
+    builder.choose(Input::choice("Ok")).unwrap();
+    builder.choose(Input::text("42")).unwrap();
+    assert!(builder.is_done());
+    // selecting the same variant keeps the value
+    builder.choose(Input::choice("Ok")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(builder.is_done());
+    builder.choose(Input::choice("Err")).unwrap();
+    assert!(!builder.is_done());
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let options = builder.get_options();
+    let err = options
+        .choices
+        .iter()
+        .find(|c| c.choice_id == "Err")
+        .unwrap();
+    assert!(err.needs_action);
+}
@@ -0,0 +1,44 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct BotConfig {
+    name: String,
+    admins: Vec<String>,
+}
+
+#[test]
+fn test_revision() {
+    let mut builder = BotConfig::builder();
+    assert_eq!(builder.revision(), 0);
+    builder.choose(Input::choice("name")).unwrap();
+    assert_eq!(builder.revision(), 1);
+    builder.choose(Input::choice("nope")).unwrap_err();
+    assert_eq!(builder.revision(), 1);
+    builder.choose(Input::text("bot")).unwrap();
+    assert_eq!(builder.revision(), 2);
+    assert!(builder.undo());
+    assert_eq!(builder.revision(), 3);
+    builder.goto(&["admins"]).unwrap();
+    assert_eq!(builder.revision(), 4);
+}
+
+#[test]
+fn test_choose_if_conflict() {
+    let mut builder = BotConfig::builder();
+    // both the admins see the main menu
+    let first = builder.revision();
+    let second = builder.revision();
+    builder.choose_if(first, Input::choice("name")).unwrap();
+    assert_eq!(
+        builder
+            .choose_if(second, Input::choice("admins"))
+            .unwrap_err(),
+        ChooseError::Conflict { revision: 1 }
+    );
+    // the rejected input didn't change the state
+    assert!(builder.get_options().text_input);
+    builder
+        .choose_if(builder.revision(), Input::text("bot"))
+        .unwrap();
+    assert_eq!(builder.revision(), 2);
+}
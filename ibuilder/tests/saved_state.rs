@@ -0,0 +1,87 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Order {
+    item: String,
+    quantity: u32,
+    shipping: Shipping,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+enum Shipping {
+    Pickup,
+    Courier { address: String },
+}
+
+fn session() -> Builder<Order> {
+    let mut builder = Order::builder();
+    builder.choose(Input::choice("item")).unwrap();
+    builder.choose(Input::text("book")).unwrap();
+    builder.choose(Input::choice("quantity")).unwrap();
+    builder.choose(Input::text("two")).unwrap_err();
+    builder.choose(Input::text("2")).unwrap();
+    builder.choose(Input::choice("shipping")).unwrap();
+    builder.choose(Input::choice("Courier")).unwrap();
+    builder
+}
+
+#[test]
+fn test_save_restore() {
+    let state = session().save_state();
+    assert_eq!(state.inputs.len(), 6);
+
+    let mut builder = Builder::<Order>::from_saved_state(&state).unwrap();
+    // the user is back in the menu of the variant
+    assert_eq!(builder.get_options().choices[0].choice_id, "address");
+    builder.choose(Input::choice("address")).unwrap();
+    builder.choose(Input::text("Main Street")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Order {
+            item: "book".into(),
+            quantity: 2,
+            shipping: Shipping::Courier {
+                address: "Main Street".into()
+            },
+        }
+    );
+}
+
+#[test]
+fn test_restore_invalid() {
+    let mut builder = session();
+    let state = SavedState {
+        inputs: vec![Input::choice("item"), Input::choice("color")],
+        ..Default::default()
+    };
+    assert_eq!(
+        builder.restore_state(&state),
+        Err(RestoreError::InvalidInput {
+            index: 1,
+            error: ChooseError::UnexpectedChoice
+        })
+    );
+    // the previous state is kept
+    assert_eq!(builder.save_state(), session().save_state());
+    assert_eq!(builder.get_options().choices[0].choice_id, "address");
+
+    let mut builder =
+        Builder::<Order>::from_buildable_value(Order::new_buildable_value(Default::default()));
+    assert_eq!(
+        builder.restore_state(&session().save_state()),
+        Err(RestoreError::Unsupported)
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serialize_state() {
+    let state = session().save_state();
+    let json = serde_json::to_string(&state).unwrap();
+    let state: SavedState = serde_json::from_str(&json).unwrap();
+    let builder = Builder::<Order>::from_saved_state(&state).unwrap();
+    assert_eq!(builder.save_state(), session().save_state());
+}
@@ -0,0 +1,101 @@
+use ibuilder::schema::{Property, Schema, SchemaKind};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Server {
+    #[ibuilder(help = "The TCP port")]
+    port: u16,
+    #[ibuilder(rename = "host name", default = "localhost")]
+    host: String,
+    workers: Option<u8>,
+    routes: Vec<Route>,
+}
+
+#[derive(Debug, IBuilder)]
+enum Route {
+    Static(String),
+    Proxy {
+        upstream: String,
+        #[ibuilder(default = 1.5)]
+        timeout: f32,
+    },
+    #[ibuilder(help = "Always reply 404")]
+    NotFound,
+}
+
+fn property(schema: &Schema, name: &str) -> Property {
+    match &schema.kind {
+        SchemaKind::Object(properties) => properties
+            .iter()
+            .find(|p| p.name == name)
+            .expect("Missing property")
+            .clone(),
+        kind => panic!("Not an object: {:?}", kind),
+    }
+}
+
+#[test]
+fn test_schema_struct() {
+    let schema = Server::builder().to_schema();
+    assert_eq!(schema.title.as_deref(), Some("Server"));
+    let port = property(&schema, "port");
+    assert!(port.required);
+    assert_eq!(port.schema.kind, SchemaKind::Integer);
+    assert_eq!(port.schema.description.as_deref(), Some("The TCP port"));
+    let host = property(&schema, "host");
+    assert!(!host.required);
+    assert_eq!(host.schema.title.as_deref(), Some("host name"));
+    assert_eq!(host.schema.default.as_deref(), Some("localhost"));
+    let workers = property(&schema, "workers");
+    assert_eq!(
+        workers.schema.kind,
+        SchemaKind::Nullable(Box::new(Schema::new(SchemaKind::Integer)))
+    );
+}
+
+#[test]
+fn test_schema_enum() {
+    let schema = Server::builder().to_schema();
+    let items = match property(&schema, "routes").schema.kind {
+        SchemaKind::Array(items) => items,
+        kind => panic!("Not an array: {:?}", kind),
+    };
+    let variants = match &items.kind {
+        SchemaKind::OneOf(variants) => variants,
+        kind => panic!("Not oneOf: {:?}", kind),
+    };
+    assert_eq!(variants.len(), 3);
+    assert_eq!(
+        property(&variants[0], "Static").schema.kind,
+        SchemaKind::String
+    );
+    let proxy = property(&variants[1], "Proxy").schema;
+    let timeout = property(&proxy, "timeout");
+    assert_eq!(timeout.schema.kind, SchemaKind::Number);
+    assert_eq!(timeout.schema.default.as_deref(), Some("1.5"));
+    assert_eq!(
+        variants[2].kind,
+        SchemaKind::Enum(vec!["NotFound".to_string()])
+    );
+    assert_eq!(variants[2].description.as_deref(), Some("Always reply 404"));
+}
+
+#[test]
+fn test_schema_json() {
+    let mut builder = Route::builder();
+    builder.choose(Input::choice("NotFound")).unwrap();
+    let json = builder.to_schema().to_json();
+    let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+    assert_eq!(value["title"], "Route");
+    assert_eq!(value["default"], "NotFound");
+    let proxy = &value["oneOf"][1];
+    assert_eq!(proxy["title"], "Proxy");
+    assert_eq!(proxy["required"][0], "Proxy");
+    let timeout = &proxy["properties"]["Proxy"]["properties"]["timeout"];
+    assert_eq!(timeout["type"], "number");
+    assert_eq!(timeout["default"], 1.5);
+    assert_eq!(
+        proxy["properties"]["Proxy"]["required"],
+        serde_json::json!(["upstream"])
+    );
+}
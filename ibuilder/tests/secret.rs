@@ -0,0 +1,91 @@
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::redaction::PLACEHOLDER;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Login {
+    username: String,
+    #[ibuilder(secret)]
+    password: String,
+    #[ibuilder(secret)]
+    token: Option<String>,
+}
+
+fn leaf(node: &Node, index: usize) -> &Node {
+    match node {
+        Node::Composite(_, fields) => match &fields[index] {
+            FieldKind::Named(_, node) | FieldKind::Unnamed(node) => node,
+        },
+        Node::Leaf(_) => panic!("Expecting a composite node"),
+    }
+}
+
+#[test]
+fn test_secret() {
+    let mut builder = Login::builder();
+    builder.choose(Input::choice("username")).unwrap();
+    assert!(!builder.get_options().sensitive);
+    builder.choose(Input::text("admin")).unwrap();
+    builder.choose(Input::choice("password")).unwrap();
+    assert!(builder.get_options().sensitive);
+    builder.choose(Input::text("hunter2")).unwrap();
+    assert!(!builder.get_options().sensitive);
+
+    let node = builder.to_node();
+    assert_eq!(*leaf(&node, 0), Node::Leaf(Field::String("admin".into())));
+    assert_eq!(
+        *leaf(&node, 1),
+        Node::Leaf(Field::String(PLACEHOLDER.into()))
+    );
+    assert!(!format!("{:?}", builder).contains("hunter2"));
+    assert!(!builder.summary(100).contains("hunter2"));
+    // the value is not affected
+    assert_eq!(
+        builder.partial_state().get::<String>("password"),
+        Some("hunter2".to_string())
+    );
+}
+
+#[test]
+fn test_secret_option() {
+    let mut builder = Login::builder();
+    builder.choose(Input::choice("token")).unwrap();
+    assert!(builder.get_options().sensitive);
+    builder.choose(Input::choice("__set")).unwrap();
+    assert!(builder.get_options().sensitive);
+    builder.choose(Input::text("s3cr3t")).unwrap();
+    assert!(!format!("{:?}", builder.to_node()).contains("s3cr3t"));
+    // the current value is not suggested again
+    builder.choose(Input::choice("__edit")).unwrap();
+    assert!(builder.nudge().suggestions.is_empty());
+}
+
+#[test]
+fn test_secret_history() {
+    let mut builder = Login::builder();
+    builder.choose(Input::choice("username")).unwrap();
+    builder.choose(Input::text("admin")).unwrap();
+    builder.choose(Input::choice("password")).unwrap();
+    builder.choose(Input::text("hunter2")).unwrap();
+
+    let history = builder.history();
+    assert_eq!(history[1].input, Input::text("admin"));
+    assert!(!history[1].sensitive);
+    assert_eq!(history[3].input, Input::text(PLACEHOLDER));
+    assert!(history[3].sensitive);
+    let export = builder.export().unwrap();
+    assert!(!format!("{:?}", export.transcript).contains("hunter2"));
+    assert_eq!(export.value.password, "hunter2");
+
+    // the snapshots keep the secret, and a replay applies it again
+    let state = builder.save_state();
+    assert!(builder.replay_to(4));
+    assert_eq!(
+        builder.partial_state().get::<String>("password"),
+        Some("hunter2".to_string())
+    );
+    let mut builder = Login::builder();
+    builder.restore_state(&state).unwrap();
+    assert!(builder.history()[3].sensitive);
+    assert_eq!(builder.finalize().unwrap().password, "hunter2");
+}
@@ -0,0 +1,98 @@
+use std::collections::{BTreeSet, HashSet};
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Tags {
+    names: BTreeSet<String>,
+    ports: HashSet<u16>,
+}
+
+fn push(builder: &mut Builder<Tags>, value: &str) -> Result<(), ChooseError> {
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text(value))?;
+    Ok(())
+}
+
+#[test]
+fn test_set() {
+    let mut builder = Tags::builder();
+    builder.choose(Input::choice("names")).unwrap();
+    push(&mut builder, "web").unwrap();
+    push(&mut builder, "api").unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("ports")).unwrap();
+    push(&mut builder, "80").unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value.names,
+        vec!["api".to_string(), "web".to_string()]
+            .into_iter()
+            .collect()
+    );
+    assert_eq!(value.ports, vec![80].into_iter().collect());
+}
+
+#[test]
+fn test_set_duplicate_value() {
+    let mut builder = Tags::builder();
+    builder.choose(Input::choice("names")).unwrap();
+    push(&mut builder, "web").unwrap();
+    assert_eq!(push(&mut builder, "web"), Err(ChooseError::DuplicateValue));
+    // the item is left as it was, still to be inserted
+    let options = builder.get_options();
+    assert!(options.text_input);
+    builder.choose(Input::text("api")).unwrap();
+    assert_eq!(
+        builder.partial_state().get::<BTreeSet<String>>("names"),
+        Some(
+            vec!["api".to_string(), "web".to_string()]
+                .into_iter()
+                .collect()
+        )
+    );
+
+    // editing an item into another one is rejected too
+    builder.choose(Input::choice("1")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("web")),
+        Err(ChooseError::DuplicateValue)
+    );
+    assert_eq!(
+        builder.partial_state().get::<BTreeSet<String>>("names"),
+        Some(
+            vec!["api".to_string(), "web".to_string()]
+                .into_iter()
+                .collect()
+        )
+    );
+}
+
+#[test]
+fn test_set_no_duplicate_action() {
+    let mut builder = Tags::builder();
+    builder.choose(Input::choice("ports")).unwrap();
+    push(&mut builder, "80").unwrap();
+    assert!(!builder
+        .get_options()
+        .choices
+        .iter()
+        .any(|c| c.choice_id == "__duplicate"));
+    assert_eq!(
+        builder.choose(Input::choice("__duplicate")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+}
+
+#[test]
+fn test_set_prefill() {
+    let tags = Tags {
+        names: vec!["db".to_string()].into_iter().collect(),
+        ports: vec![5432, 5433].into_iter().collect(),
+    };
+    let builder = Builder::from_value(tags);
+    let value = builder.finalize().unwrap();
+    assert_eq!(value.names.len(), 1);
+    assert_eq!(value.ports, vec![5432, 5433].into_iter().collect());
+}
@@ -0,0 +1,92 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Config {
+    port: Port,
+    limits: Limits,
+    backup: Option<Port>,
+    shape: Shape,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Port(u16);
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Limits {
+    connections: Connections,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Connections {
+    max: u32,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+enum Shape {
+    Circle { radius: u32 },
+    Square { side: u32 },
+}
+
+fn builder() -> Builder<Config> {
+    Config::builder().with_config(BuilderConfig {
+        skip_single_field_menus: true,
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_skip_single_field_menus() {
+    let mut builder = builder();
+    // nested single field structs are skipped together
+    builder.choose(Input::choice("limits")).unwrap();
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("10")).unwrap();
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+
+    builder.choose(Input::choice("limits")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+
+    // the struct with an unnamed field is skipped too
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("80")).unwrap();
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+
+    // the menu of the option is not skipped, setting the value is a choice of the user
+    builder.choose(Input::choice("backup")).unwrap();
+    builder.choose(Input::choice("__set")).unwrap();
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("8080")).unwrap();
+    assert_eq!(builder.get_options().query, "Choose an option");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    // so is the menu of the enum, but not the one of the selected variant
+    builder.choose(Input::choice("shape")).unwrap();
+    builder.choose(Input::choice("Circle")).unwrap();
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("3")).unwrap();
+    assert_eq!(builder.get_options().query, "Select a variant");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Config {
+            port: Port(80),
+            limits: Limits {
+                connections: Connections { max: 10 }
+            },
+            backup: Some(Port(8080)),
+            shape: Shape::Circle { radius: 3 },
+        }
+    );
+}
+
+#[test]
+fn test_single_field_menus_by_default() {
+    let mut builder = Config::builder();
+    builder.choose(Input::choice("limits")).unwrap();
+    let options = builder.get_options();
+    assert!(!options.text_input);
+    assert_eq!(options.choices[0].choice_id, "connections");
+}
@@ -0,0 +1,94 @@
+use ibuilder::*;
+
+/// The type of the old snapshots, with version 1.
+#[derive(Debug, PartialEq, IBuilder)]
+struct OrderV1 {
+    item: String,
+    qty: u32,
+}
+
+/// The type after the refactor, with version 2: `qty` has been renamed.
+#[derive(Debug, PartialEq, IBuilder)]
+struct OrderV2 {
+    item: String,
+    quantity: u32,
+}
+
+fn config(version: u32) -> BuilderConfig {
+    BuilderConfig {
+        state_version: version,
+        migrations: Migrations::new().add(1, |mut state| {
+            for input in &mut state.inputs {
+                if *input == Input::choice("qty") {
+                    *input = Input::choice("quantity");
+                }
+            }
+            for path in &mut state.paths {
+                for field in path.iter_mut().filter(|field| *field == "qty") {
+                    *field = "quantity".into();
+                }
+            }
+            state
+        }),
+        ..Default::default()
+    }
+}
+
+fn session() -> SavedState {
+    let mut builder = OrderV1::builder().with_config(config(1));
+    builder.choose(Input::choice("item")).unwrap();
+    builder.choose(Input::text("book")).unwrap();
+    builder.choose(Input::choice("qty")).unwrap();
+    builder.choose(Input::text("2")).unwrap();
+    builder.save_state()
+}
+
+#[test]
+fn test_state_version() {
+    let state = session();
+    assert_eq!(state.version, 1);
+    assert_eq!(OrderV1::builder().save_state().version, 0);
+}
+
+#[test]
+fn test_migrate() {
+    let state = session();
+    let mut builder = OrderV2::builder().with_config(config(2));
+    builder.restore_state(&state).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        OrderV2 {
+            item: "book".into(),
+            quantity: 2
+        }
+    );
+    let saved = builder.save_state();
+    assert_eq!(saved.version, 2);
+    assert_eq!(saved.inputs[2], Input::choice("quantity"));
+}
+
+#[test]
+fn test_migrate_lenient() {
+    let state = session();
+    let mut builder = OrderV2::builder().with_config(config(2));
+    let report = builder.restore_state_lenient(&state).unwrap();
+    assert!(report.is_complete());
+    assert_eq!(builder.partial_state().get::<u32>("quantity"), Some(2));
+}
+
+#[test]
+fn test_migrate_errors() {
+    let state = session();
+    let mut builder = OrderV2::builder().with_config(config(3));
+    assert_eq!(
+        builder.restore_state(&state),
+        Err(RestoreError::MissingMigration { from: 2 })
+    );
+    let mut builder = OrderV1::builder();
+    assert_eq!(
+        builder.restore_state(&state),
+        Err(RestoreError::NewerVersion { version: 1 })
+    );
+    // the state of the builder is left unchanged
+    assert_eq!(builder.save_state(), SavedState::default());
+}
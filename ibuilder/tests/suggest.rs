@@ -0,0 +1,48 @@
+#![allow(dead_code)]
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Address {
+    country: String,
+    #[ibuilder(suggest_fn = "suggest_city")]
+    city: String,
+    #[ibuilder(suggest_fn = "suggest_city")]
+    nearby: Vec<String>,
+    zip: Option<u32>,
+}
+
+fn suggest_city(state: &PartialState) -> Vec<String> {
+    match state.get::<String>("country").as_deref() {
+        Some("Italy") => vec!["Rome".into(), "Milan".into()],
+        _ => vec![],
+    }
+}
+
+#[test]
+fn test_suggestions() {
+    let mut builder = Address::builder();
+    builder.choose(Input::choice("city")).unwrap();
+    assert!(builder.get_options().suggestions.is_empty());
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("country")).unwrap();
+    builder.choose(Input::text("Italy")).unwrap();
+    assert!(builder.get_options().suggestions.is_empty());
+    builder.choose(Input::choice("city")).unwrap();
+    assert_eq!(builder.get_options().suggestions, vec!["Rome", "Milan"]);
+    // any text is still accepted
+    builder.choose(Input::text("Venice")).unwrap();
+
+    // the suggestions are shown in the text inputs inside the field
+    builder.choose(Input::choice("nearby")).unwrap();
+    assert!(builder.get_options().suggestions.is_empty());
+    builder.choose(Input::choice("__new")).unwrap();
+    assert_eq!(builder.get_options().suggestions, vec!["Rome", "Milan"]);
+    builder.choose(Input::text("Padua")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    builder.choose(Input::choice("zip")).unwrap();
+    builder.choose(Input::choice("__set")).unwrap();
+    assert!(builder.get_options().suggestions.is_empty());
+}
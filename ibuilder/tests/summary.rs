@@ -0,0 +1,64 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Person {
+    name: String,
+    age: AgeRange,
+    nicknames: Vec<String>,
+    pet: Pet,
+}
+
+#[derive(Debug, IBuilder)]
+enum AgeRange {
+    Child,
+    Adult,
+}
+
+#[derive(Debug, IBuilder)]
+enum Pet {
+    Dog { name: String },
+    Fish(u8),
+}
+
+#[test]
+fn test_summary() {
+    let mut builder = Person::builder();
+    assert_eq!(builder.summary(100), "Person{nicknames: [], +3 missing}");
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("edomora97")).unwrap();
+    builder.choose(Input::choice("age")).unwrap();
+    builder.choose(Input::choice("Adult")).unwrap();
+    assert_eq!(
+        builder.summary(100),
+        "Person{name: edomora97, age: Adult, nicknames: [], +1 missing}"
+    );
+    builder.choose(Input::choice("nicknames")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("edo")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("pet")).unwrap();
+    builder.choose(Input::choice("Dog")).unwrap();
+    assert_eq!(
+        builder.summary(100),
+        "Person{name: edomora97, age: Adult, nicknames: [edo], pet: Dog{+1 missing}}"
+    );
+}
+
+#[test]
+fn test_summary_truncated() {
+    let mut builder = Person::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("a very\nlong name")).unwrap();
+    assert_eq!(builder.summary(22), "Person{name: a very l…");
+    assert_eq!(builder.summary(1), "…");
+    assert_eq!(builder.summary(0), "");
+}
+
+#[test]
+fn test_summary_unnamed_variant() {
+    let mut builder = Pet::builder();
+    assert_eq!(builder.summary(100), "?");
+    builder.choose(Input::choice("Fish")).unwrap();
+    builder.choose(Input::text("3")).unwrap();
+    assert_eq!(builder.summary(100), "Fish(3)");
+}
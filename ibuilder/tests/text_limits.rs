@@ -0,0 +1,76 @@
+use ibuilder::charset::Charset;
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Account {
+    #[ibuilder(trim, lowercase, charset = "a-z0-9_-")]
+    username: String,
+    #[ibuilder(charset = "0-9")]
+    pin: u32,
+    bio: String,
+}
+
+fn limited(max: usize) -> Builder<Account> {
+    Account::builder().with_config(BuilderConfig {
+        max_text_len: Some(max),
+        ..Default::default()
+    })
+}
+
+#[test]
+fn test_max_text_len() {
+    let mut builder = limited(5);
+    builder.choose(Input::choice("bio")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("x".repeat(1 << 20))),
+        Err(ChooseError::TextTooLong { max: 5 })
+    );
+    // the characters are counted, not the bytes
+    builder.choose(Input::text("ééééé")).unwrap();
+    assert_eq!(
+        builder.partial_state().get::<String>("bio"),
+        Some("ééééé".to_string())
+    );
+    // the rejected text is not recorded
+    assert_eq!(builder.history().len(), 2);
+}
+
+#[test]
+fn test_max_text_len_choices() {
+    let mut builder = limited(1);
+    builder.choose(Input::choice("username")).unwrap();
+    builder.choose(Input::text("ab")).unwrap_err();
+    builder.choose(Input::text("a")).unwrap();
+}
+
+#[test]
+fn test_charset() {
+    let mut builder = Account::builder();
+    builder.choose(Input::choice("username")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("john doe")),
+        Err(ChooseError::InvalidText {
+            error: "The character ' ' is not allowed, only [a-z0-9_-] are".into()
+        })
+    );
+    // the preprocessors are applied before the check
+    builder.choose(Input::text("  John_Doe-1 ")).unwrap();
+    assert_eq!(
+        builder.partial_state().get::<String>("username"),
+        Some("john_doe-1".to_string())
+    );
+    builder.choose(Input::choice("pin")).unwrap();
+    builder.choose(Input::text("+12")).unwrap_err();
+    builder.choose(Input::text("12")).unwrap();
+}
+
+#[test]
+fn test_charset_parse() {
+    let charset = Charset::new("-a-cx");
+    assert!(charset.contains('-'));
+    assert!(charset.contains('b'));
+    assert!(charset.contains('x'));
+    assert!(!charset.contains('d'));
+    assert!(Charset::parse("z-a").is_err());
+    assert!(Charset::parse("").is_err());
+}
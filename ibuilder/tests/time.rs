@@ -0,0 +1,33 @@
+use ibuilder::types::TimeOfDay;
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Alarm {
+    at: TimeOfDay,
+}
+
+#[test]
+fn test_time_of_day() {
+    let mut builder = Alarm::builder();
+    builder.choose(Input::choice("at")).unwrap();
+    builder.choose(Input::text("7:30")).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.at, TimeOfDay::new(7, 30).unwrap());
+    assert_eq!(value.at.to_string(), "07:30");
+}
+
+#[test]
+fn test_invalid_time_of_day() {
+    let mut builder = Alarm::builder();
+    builder.choose(Input::choice("at")).unwrap();
+    for time in &["24:00", "12:60", "12", "12:5", "+1:30", "ab:cd", ""] {
+        let res = builder.choose(Input::text(*time));
+        assert!(
+            matches!(res, Err(ChooseError::InvalidText { .. })),
+            "{} should be invalid",
+            time
+        );
+    }
+    builder.choose(Input::text(" 23:59 ")).unwrap();
+    assert!(builder.is_done());
+}
@@ -1,6 +1,26 @@
+// The help of some errors lists the types that implement `NewBuildableValue`, which change with the
+// enabled features: the expected outputs are the ones with the default features only.
+#[cfg(not(any(
+    feature = "data",
+    feature = "serde",
+    feature = "bitflags",
+    feature = "cron",
+    feature = "chrono",
+    feature = "url",
+    feature = "uuid",
+    feature = "regex",
+)))]
 #[test]
 fn test_trybuild() {
     let t = trybuild::TestCases::new();
     t.pass("tests/compile/*.rs");
     t.compile_fail("tests/not_compile/*.rs");
 }
+
+/// The errors that are checked only with the `regex` feature, which don't list the implementors.
+#[cfg(feature = "regex")]
+#[test]
+fn test_trybuild_regex() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/not_compile_regex/*.rs");
+}
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use ibuilder::builders::{OptionBuilder, VecBuilder};
+use ibuilder::*;
+
+#[derive(Debug, Clone, PartialEq, IBuilder)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+enum Shape {
+    Empty,
+    Dot(Point),
+    Circle { center: Point, radius: u32 },
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Drawing {
+    shapes: Vec<Shape>,
+    #[ibuilder(hidden, default = "1")]
+    version: u32,
+}
+
+#[test]
+fn test_typed_struct() {
+    let mut builder = __Point_BuildableValueImpl::new(Default::default());
+    assert_eq!(builder.get_value(), None);
+    builder.apply(&Input::choice("x"), &[]).unwrap();
+    builder.apply(&Input::text("1"), &["x".into()]).unwrap();
+    builder.apply(&Input::choice("y"), &[]).unwrap();
+    builder.apply(&Input::text("2"), &["y".into()]).unwrap();
+    assert_eq!(builder.get_value(), Some(Point { x: 1, y: 2 }));
+}
+
+#[test]
+fn test_typed_enum() {
+    let mut builder = __Shape_BuildableValueImpl::new(Default::default());
+    builder.apply(&Input::choice("Circle"), &[]).unwrap();
+    assert_eq!(builder.get_value(), None);
+    builder.set_value_any(Box::new(Shape::Dot(Point { x: 3, y: 4 })));
+    assert_eq!(builder.get_value(), Some(Shape::Dot(Point { x: 3, y: 4 })));
+    builder.apply(&Input::choice("Empty"), &[]).unwrap();
+    assert_eq!(builder.get_value(), Some(Shape::Empty));
+}
+
+#[test]
+fn test_typed_builtin() {
+    let mut builder = VecBuilder::<Point>::new(Default::default());
+    assert_eq!(builder.get_value(), Some(vec![]));
+    builder.set_value_any(Box::new(vec![Point { x: 0, y: 0 }]));
+    assert_eq!(builder.get_value(), Some(vec![Point { x: 0, y: 0 }]));
+
+    let builder = OptionBuilder::<u8>::new(Default::default());
+    assert_eq!(builder.get_value(), Some(None));
+}
+
+#[test]
+fn test_typed_adapter() {
+    let mut builder = Drawing::builder();
+    builder.choose(Input::choice("shapes")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice("Empty")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let drawing = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        drawing,
+        Drawing {
+            shapes: vec![Shape::Empty],
+            version: 1
+        }
+    );
+    let mut builder = BTreeMap::<String, u8>::new_buildable_value(Default::default());
+    assert!(builder.set_value_any(Box::new(BTreeMap::from([("a".to_string(), 1u8)]))));
+    let map = builder
+        .get_value_any()
+        .unwrap()
+        .downcast::<BTreeMap<String, u8>>();
+    assert_eq!(*map.unwrap(), BTreeMap::from([("a".to_string(), 1)]));
+}
@@ -0,0 +1,89 @@
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Person {
+    name: String,
+    #[ibuilder(default = 2)]
+    hands: u8,
+    pets: Vec<String>,
+}
+
+#[test]
+fn test_undo_redo() {
+    let mut builder = Person::builder();
+    assert!(!builder.undo());
+    assert!(!builder.redo());
+
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("Alice")).unwrap();
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("Bob")).unwrap();
+    // the invalid inputs are not recorded
+    builder.choose(Input::choice("hands")).unwrap();
+    builder.choose(Input::text("many")).unwrap_err();
+
+    // leave the hands menu
+    assert!(builder.undo());
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+    // restore the previous name, staying in its menu
+    assert!(builder.undo());
+    assert!(builder.get_options().text_input);
+    assert_eq!(
+        builder.partial_state().get::<String>("name").unwrap(),
+        "Alice"
+    );
+
+    assert!(builder.redo());
+    assert_eq!(
+        builder.partial_state().get::<String>("name").unwrap(),
+        "Bob"
+    );
+    assert!(builder.undo());
+
+    // a new input discards the inputs that could be redone
+    builder.choose(Input::text("Carol")).unwrap();
+    assert!(!builder.redo());
+
+    builder.choose(Input::choice("pets")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text("cat")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert!(builder.undo());
+    assert!(builder.undo());
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("dog")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        Person {
+            name: "Carol".into(),
+            hands: 2,
+            pets: vec!["dog".into()],
+        }
+    );
+}
+
+#[test]
+fn test_undo_error_recovery() {
+    let mut builder = Person::builder().with_config(BuilderConfig {
+        error_recovery: true,
+        ..Default::default()
+    });
+    builder.choose(Input::choice("hands")).unwrap();
+    builder.choose(Input::text("many")).unwrap_err();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+    // the "Go back" of the error recovery is undone
+    assert!(builder.undo());
+    assert!(builder.get_options().text_input);
+}
+
+#[test]
+fn test_undo_custom_builder() {
+    let mut builder =
+        Builder::<Person>::from_buildable_value(Person::new_buildable_value(Default::default()));
+    builder.choose(Input::choice("name")).unwrap();
+    assert!(!builder.undo());
+}
@@ -0,0 +1,32 @@
+#![cfg(feature = "url")]
+
+use ibuilder::*;
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Webhook {
+    endpoint: url::Url,
+}
+
+#[test]
+fn test_url() {
+    let mut builder = Webhook::builder();
+    builder.choose(Input::choice("endpoint")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("example.com/hook")),
+        Err(ChooseError::InvalidText {
+            error: "Missing the scheme, e.g. https://example.com".into()
+        })
+    );
+    assert_eq!(
+        builder.choose(Input::text("https://example.com:99999")),
+        Err(ChooseError::InvalidText {
+            error: "Invalid port, it must be a number up to 65535".into()
+        })
+    );
+    builder
+        .choose(Input::text("https://example.com/hook?id=1"))
+        .unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.endpoint.host_str(), Some("example.com"));
+    assert_eq!(value.endpoint.query(), Some("id=1"));
+}
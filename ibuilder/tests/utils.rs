@@ -4,19 +4,24 @@ use std::io::{BufRead, Write};
 
 use failure::Error;
 
+use ibuilder::frontend::*;
 use ibuilder::nodes::*;
 use ibuilder::*;
 
 /// Pass a `Builder` to this function to use an interactive console inspecting the behaviour of the
 /// builder. You may want to add the `--nocapture` option to see the output of this function.
-pub fn interactive_console<T: 'static>(mut builder: Builder<T>) -> Result<T, Error> {
-    let stdin = std::io::stdin();
-    let mut iterator = stdin.lock().lines();
+pub fn interactive_console<T: 'static>(builder: Builder<T>) -> Result<T, Error> {
+    Ok(run_builder_sync(&mut ConsoleFrontend, builder))
+}
+
+/// A `Frontend` that prints the options on the standard output and reads the input from the
+/// standard input.
+pub struct ConsoleFrontend;
 
-    loop {
+impl Frontend for ConsoleFrontend {
+    fn present(&mut self, options: Options, node: Node) -> Input {
         println!("\n\n\n");
-        write_node(builder.to_node(), &mut std::io::stdout(), 0)?;
-        let options = builder.get_options();
+        write_node(node, &mut std::io::stdout(), 0).unwrap();
         println!("\n?: {}", options.query);
         for opt in &options.choices {
             println!(
@@ -29,18 +34,17 @@ pub fn interactive_console<T: 'static>(mut builder: Builder<T>) -> Result<T, Err
         if options.text_input {
             println!("- textual input (> followed by the content)");
         }
-        let line = iterator.next().unwrap()?;
-        let input = if let Some(stripped) = line.strip_prefix('>') {
+        let line = std::io::stdin().lock().lines().next().unwrap().unwrap();
+        if let Some(stripped) = line.strip_prefix('>') {
             Input::Text(stripped.to_string())
         } else {
             Input::Choice(line)
-        };
-        match builder.choose(input) {
-            Ok(Some(res)) => return Ok(res),
-            Ok(None) => {}
-            Err(e) => println!("\n{}\n", e),
         }
     }
+
+    fn present_error(&mut self, error: ChooseError) {
+        println!("\n{}\n", error);
+    }
 }
 
 pub fn write_node<W: Write>(node: Node, w: &mut W, indent: usize) -> Result<(), Error> {
@@ -63,7 +67,11 @@ pub fn write_node<W: Write>(node: Node, w: &mut W, indent: usize) -> Result<(),
         }
         Node::Leaf(field) => match field {
             Field::String(content) => w.write_all(format!("{}\n", content).as_bytes())?,
+            Field::Warning(content, warning) => {
+                w.write_all(format!("{} (warning: {})\n", content, warning).as_bytes())?
+            }
             Field::Missing => w.write_all(b"missing\n")?,
+            Field::Elided => w.write_all(b"...\n")?,
         },
     }
     Ok(())
@@ -0,0 +1,63 @@
+#![cfg(feature = "uuid")]
+
+use ibuilder::*;
+
+#[derive(Debug, IBuilder)]
+struct Device {
+    id: uuid::Uuid,
+}
+
+#[test]
+fn test_uuid() {
+    let mut builder = Device::builder();
+    builder.choose(Input::choice("id")).unwrap();
+    let res = builder.choose(Input::text("67e55044-10b1-426f-9247"));
+    assert!(matches!(res, Err(ChooseError::InvalidText { .. })));
+    builder
+        .choose(Input::text("67E5504410B1426F9247BB680E5FE0C8"))
+        .unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(value.id.to_string(), "67e55044-10b1-426f-9247-bb680e5fe0c8");
+}
+
+#[test]
+fn test_uuid_random() {
+    let mut builder = Device::builder();
+    builder.choose(Input::choice("id")).unwrap();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    assert_eq!(options.choices[0].choice_id, "random");
+    builder.choose(Input::choice("random")).unwrap();
+    let first = builder.finalize().unwrap().id;
+    builder.choose(Input::choice("id")).unwrap();
+    builder.choose(Input::choice("random")).unwrap();
+    let second = builder.finalize().unwrap().id;
+    assert_eq!(first.get_version_num(), 4);
+    assert_ne!(first, second);
+}
+
+#[test]
+fn test_uuid_random_recorded() {
+    let mut builder = Device::builder();
+    builder.choose(Input::choice("id")).unwrap();
+    builder.choose(Input::choice("random")).unwrap();
+    let state = builder.save_state();
+    let id = builder.finalize().unwrap().id;
+    assert_eq!(builder.history()[1].input, Input::text(id.to_string()));
+
+    let mut builder = Device::builder();
+    builder.restore_state(&state).unwrap();
+    assert_eq!(builder.finalize().unwrap().id, id);
+}
+
+#[test]
+fn test_uuid_random_source() {
+    let mut builder = Device::builder().with_config(BuilderConfig {
+        random: Some(Random::new(|bytes| bytes.fill(0xab))),
+        ..Default::default()
+    });
+    builder.choose(Input::choice("id")).unwrap();
+    builder.choose(Input::choice("random")).unwrap();
+    let id = builder.finalize().unwrap().id;
+    assert_eq!(id.to_string(), "abababab-abab-4bab-abab-abababababab");
+}
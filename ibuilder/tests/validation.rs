@@ -0,0 +1,135 @@
+use ibuilder::nodes::{Field, FieldKind, Node};
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Server {
+    #[ibuilder(validate = "check_port")]
+    port: u16,
+    #[ibuilder(validate = "not_empty", validate = "short")]
+    name: String,
+}
+
+fn check_port(port: &u16) -> Validation {
+    match *port {
+        0 => Validation::Invalid("port 0 is reserved".into()),
+        1..=1023 => Validation::Warning("port usually requires root".into()),
+        _ => Validation::Valid,
+    }
+}
+
+fn not_empty(name: &str) -> Validation {
+    if name.is_empty() {
+        Validation::Invalid("name cannot be empty".into())
+    } else {
+        Validation::Valid
+    }
+}
+
+fn short(name: &str) -> Validation {
+    if name.len() > 5 {
+        Validation::Warning("name is long".into())
+    } else {
+        Validation::Valid
+    }
+}
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct Account {
+    #[ibuilder(validate = "check_username")]
+    username: String,
+}
+
+fn check_username(username: &str) -> Result<(), String> {
+    if username.chars().all(|c| c.is_ascii_alphanumeric()) {
+        Ok(())
+    } else {
+        Err("only letters and digits are allowed".into())
+    }
+}
+
+fn field_node(builder: &Builder<Server>, name: &str) -> Field {
+    match builder.to_node() {
+        Node::Composite(_, fields) => fields
+            .into_iter()
+            .find_map(|f| match f {
+                FieldKind::Named(n, Node::Leaf(field)) if n == name => Some(field),
+                _ => None,
+            })
+            .unwrap(),
+        _ => panic!("Expecting a composite"),
+    }
+}
+
+#[test]
+fn test_invalid_rejected() {
+    let mut builder = Server::builder();
+    builder.choose(Input::choice("port")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("0")),
+        Err(ChooseError::InvalidText {
+            error: "port 0 is reserved".into()
+        })
+    );
+    // still in the field
+    assert!(builder.get_options().text_input);
+    assert_eq!(field_node(&builder, "port"), Field::Missing);
+}
+
+#[test]
+fn test_warning_accepted() {
+    let mut builder = Server::builder();
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("80")).unwrap();
+    assert_eq!(
+        field_node(&builder, "port"),
+        Field::Warning("80".into(), "port usually requires root".into())
+    );
+
+    builder.choose(Input::choice("port")).unwrap();
+    assert_eq!(
+        builder.get_options().warning,
+        Some("port usually requires root".into())
+    );
+    builder.choose(Input::text("8080")).unwrap();
+    assert_eq!(field_node(&builder, "port"), Field::String("8080".into()));
+
+    builder.choose(Input::choice("port")).unwrap();
+    assert_eq!(builder.get_options().warning, None);
+}
+
+#[test]
+fn test_multiple_validators() {
+    let mut builder = Server::builder();
+    builder.choose(Input::choice("name")).unwrap();
+    assert!(builder.choose(Input::text("")).is_err());
+    builder.choose(Input::text("long name")).unwrap();
+    builder.choose(Input::choice("port")).unwrap();
+    builder.choose(Input::text("8080")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Server {
+            port: 8080,
+            name: "long name".into()
+        }
+    );
+}
+
+#[test]
+fn test_result_validator() {
+    let mut builder = Account::builder();
+    builder.choose(Input::choice("username")).unwrap();
+    assert_eq!(
+        builder.choose(Input::text("john doe")),
+        Err(ChooseError::InvalidText {
+            error: "only letters and digits are allowed".into()
+        })
+    );
+    assert!(builder.get_options().text_input);
+    builder.choose(Input::text("john")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Account {
+            username: "john".into()
+        }
+    );
+}
@@ -0,0 +1,65 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+enum Status {
+    Ok = 200,
+    NotFound = 404,
+    #[ibuilder(value = "5xx")]
+    ServerError,
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+enum Plain {
+    Var1,
+    Var2,
+}
+
+#[test]
+fn test_choice_values() {
+    let builder = Status::builder();
+    let options = builder.get_options();
+    assert!(options.text_input);
+    let values: Vec<_> = options.choices.iter().map(|c| c.value.clone()).collect();
+    assert_eq!(
+        values,
+        vec![
+            Some("200".to_string()),
+            Some("404".to_string()),
+            Some("5xx".to_string())
+        ]
+    );
+}
+
+#[test]
+fn test_select_by_value() {
+    let mut builder = Status::builder();
+    builder.choose(Input::text("404")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Status::NotFound);
+
+    let mut builder = Status::builder();
+    builder.choose(Input::text(" 5xx ")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Status::ServerError);
+
+    let mut builder = Status::builder();
+    builder.choose(Input::choice("Ok")).unwrap();
+    assert_eq!(builder.finalize().unwrap(), Status::Ok);
+}
+
+#[test]
+fn test_unknown_value() {
+    let mut builder = Status::builder();
+    assert!(matches!(
+        builder.choose(Input::text("500")),
+        Err(ChooseError::InvalidText { .. })
+    ));
+}
+
+#[test]
+fn test_no_values() {
+    let mut builder = Plain::builder();
+    assert!(!builder.get_options().text_input);
+    assert_eq!(
+        builder.choose(Input::text("Var1")),
+        Err(ChooseError::UnexpectedText)
+    );
+}
@@ -0,0 +1,53 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, PartialEq)]
+struct List {
+    items: Vec<i32>,
+}
+
+fn choices(options: &Options) -> Vec<(&str, &str)> {
+    options
+        .choices
+        .iter()
+        .map(|c| (c.choice_id.as_str(), c.text.as_str()))
+        .collect()
+}
+
+fn push(builder: &mut Builder<List>, value: i32) {
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::text(value.to_string())).unwrap();
+}
+
+#[test]
+fn test_vec_stable_keys() {
+    let mut builder = List::builder();
+    builder.choose(Input::choice("items")).unwrap();
+    push(&mut builder, 10);
+    push(&mut builder, 20);
+    push(&mut builder, 30);
+
+    builder.choose(Input::choice("__remove")).unwrap();
+    builder.choose(Input::choice("0")).unwrap();
+    // the remaining items keep their identifiers
+    assert_eq!(
+        choices(&builder.get_options())[3..5],
+        [("1", "Edit item 0"), ("2", "Edit item 1")]
+    );
+    assert_eq!(
+        builder.choose(Input::choice("0")),
+        Err(ChooseError::UnexpectedChoice)
+    );
+
+    builder.choose(Input::choice("2")).unwrap();
+    builder.choose(Input::text("33")).unwrap();
+    // the new item is at the back, even if it reuses a key
+    push(&mut builder, 40);
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    let value = builder.choose(Input::choice(FINALIZE_ID)).unwrap().unwrap();
+    assert_eq!(
+        value,
+        List {
+            items: vec![20, 33, 40]
+        }
+    );
+}
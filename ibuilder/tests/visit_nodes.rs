@@ -0,0 +1,161 @@
+#![allow(dead_code)]
+
+use ibuilder::nodes::{Field, FieldKind, Node, NodeVisitor};
+use ibuilder::*;
+
+#[derive(IBuilder)]
+struct Root {
+    name: String,
+    shape: Shape,
+    wrapped: Box<Wrapped>,
+    maybe: Option<i32>,
+    items: Vec<Item>,
+    #[ibuilder(hidden)]
+    secret: i32,
+}
+
+#[derive(IBuilder)]
+enum Shape {
+    Empty,
+    Circle { radius: i32 },
+    Square(Item),
+}
+
+#[derive(IBuilder)]
+struct Wrapped(i32);
+
+#[derive(IBuilder)]
+struct Item {
+    value: i32,
+}
+
+/// Rebuild the `Node` tree from the visit.
+#[derive(Default)]
+struct Rebuild {
+    stack: Vec<(String, Vec<FieldKind>)>,
+    field: Vec<Option<String>>,
+    result: Option<Node>,
+}
+
+impl Rebuild {
+    fn push(&mut self, node: Node) {
+        if self.stack.is_empty() {
+            self.result = Some(node);
+            return;
+        }
+        let field = match self.field.pop().unwrap() {
+            Some(name) => FieldKind::Named(name, node),
+            None => FieldKind::Unnamed(node),
+        };
+        self.stack.last_mut().unwrap().1.push(field);
+    }
+}
+
+impl NodeVisitor for Rebuild {
+    fn enter_composite(&mut self, name: &str) -> bool {
+        self.stack.push((name.to_string(), vec![]));
+        true
+    }
+
+    fn leave_composite(&mut self) {
+        let (name, fields) = self.stack.pop().unwrap();
+        self.push(Node::Composite(name, fields));
+    }
+
+    fn enter_field(&mut self, name: Option<&str>) -> bool {
+        self.field.push(name.map(String::from));
+        true
+    }
+
+    fn leaf(&mut self, field: &Field) {
+        let field = match field {
+            Field::String(s) => Field::String(s.clone()),
+            Field::Warning(s, w) => Field::Warning(s.clone(), w.clone()),
+            Field::Missing => Field::Missing,
+            Field::Elided => Field::Elided,
+        };
+        self.push(Node::Leaf(field));
+    }
+}
+
+fn rebuild(builder: &Builder<Root>) -> Node {
+    let mut visitor = Rebuild::default();
+    builder.visit_nodes(&mut visitor);
+    visitor.result.unwrap()
+}
+
+#[test]
+fn test_visit_nodes_same_as_to_node() {
+    let mut builder = Root::builder();
+    assert_eq!(rebuild(&builder), builder.to_node());
+
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("root")).unwrap();
+    builder.choose(Input::choice("shape")).unwrap();
+    builder.choose(Input::choice("Circle")).unwrap();
+    builder.choose(Input::choice("radius")).unwrap();
+    builder.choose(Input::text("4")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("maybe")).unwrap();
+    builder.choose(Input::choice("__set")).unwrap();
+    builder.choose(Input::text("2")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice("items")).unwrap();
+    builder.choose(Input::choice("__new")).unwrap();
+    builder.choose(Input::choice("value")).unwrap();
+    builder.choose(Input::text("1")).unwrap();
+    assert_eq!(rebuild(&builder), builder.to_node());
+
+    let mut builder = Root::builder();
+    builder.choose(Input::choice("shape")).unwrap();
+    builder.choose(Input::choice("Square")).unwrap();
+    assert_eq!(rebuild(&builder), builder.to_node());
+}
+
+/// Count the leaves of only the first `window` items of the `Vec`s.
+struct Window {
+    window: usize,
+    index: Vec<usize>,
+    leaves: usize,
+}
+
+impl NodeVisitor for Window {
+    fn enter_composite(&mut self, _name: &str) -> bool {
+        self.index.push(0);
+        true
+    }
+
+    fn leave_composite(&mut self) {
+        self.index.pop();
+    }
+
+    fn enter_field(&mut self, name: Option<&str>) -> bool {
+        let index = self.index.last_mut().unwrap();
+        *index += 1;
+        name.is_some() || *index <= self.window
+    }
+
+    fn leaf(&mut self, _field: &Field) {
+        self.leaves += 1;
+    }
+}
+
+#[test]
+fn test_visit_nodes_skip() {
+    let mut builder = Root::builder();
+    builder.choose(Input::choice("items")).unwrap();
+    for _ in 0..1000 {
+        builder.choose(Input::choice("__new")).unwrap();
+        builder.choose(Input::choice(BACK_ID)).unwrap();
+    }
+    let mut visitor = Window {
+        window: 3,
+        index: vec![],
+        leaves: 0,
+    };
+    builder.visit_nodes(&mut visitor);
+    // name, shape, wrapped, maybe and the 3 visible items
+    assert_eq!(visitor.leaves, 7);
+    assert!(visitor.index.is_empty());
+}
@@ -0,0 +1,117 @@
+use std::any::Any;
+
+use ibuilder::builders::{LeafBuilder, StringBuilder};
+use ibuilder::nodes::{Field, Node};
+use ibuilder::*;
+
+/// A builder that lets the user pick one of the files, instead of typing the name.
+#[derive(Debug)]
+struct FilePicker {
+    prompt: String,
+    file: Option<String>,
+}
+
+impl FilePicker {
+    const FILES: &'static [&'static str] = &["a.txt", "b.txt"];
+
+    fn new(config: BuildableValueConfig<()>) -> FilePicker {
+        FilePicker {
+            prompt: config.prompt.unwrap_or_else(|| "Pick a file".into()),
+            file: None,
+        }
+    }
+}
+
+impl BuildableValue for FilePicker {
+    fn apply(&mut self, data: &Input, _current_fields: &[Id]) -> Result<(), ChooseError> {
+        match data {
+            Input::Choice(file) if FilePicker::FILES.contains(&file.as_str()) => {
+                self.file = Some(file.clone());
+                Ok(())
+            }
+            Input::Choice(_) => Err(ChooseError::UnexpectedChoice),
+            Input::Text(_) => Err(ChooseError::UnexpectedText),
+        }
+    }
+
+    fn get_options(&self, _current_fields: &[Id]) -> Options {
+        Options {
+            query: self.prompt.clone(),
+            choices: FilePicker::FILES
+                .iter()
+                .map(|file| Choice {
+                    choice_id: (*file).into(),
+                    text: file.to_string(),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    fn get_subfields(&self, _current_fields: &[Id]) -> Vec<Id> {
+        vec![]
+    }
+
+    fn to_node(&self) -> Node {
+        match &self.file {
+            Some(file) => Node::Leaf(Field::String(file.clone())),
+            None => Node::Leaf(Field::Missing),
+        }
+    }
+
+    fn get_value_any(&self) -> Option<Box<dyn Any>> {
+        self.file.clone().map(|file| Box::new(file) as Box<dyn Any>)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Version(u32);
+
+impl LeafBuildableValue for Version {
+    fn parse(text: &str) -> Result<Self, String> {
+        text.parse().map(Version).map_err(|_| "Not a number".into())
+    }
+
+    fn render(&self) -> String {
+        self.0.to_string()
+    }
+}
+
+#[derive(Debug, PartialEq, IBuilder)]
+struct Upload {
+    #[ibuilder(with = "FilePicker", prompt = "Which file?")]
+    file: String,
+    // `Version` doesn't implement `NewBuildableValue`
+    #[ibuilder(with = "LeafBuilder<Version>")]
+    version: Version,
+    // the config of `StringBuilder` is `BuildableValueConfig<String>`
+    #[ibuilder(with = "StringBuilder")]
+    notes: String,
+}
+
+#[test]
+fn test_with() {
+    let mut builder = Upload::builder();
+    builder.choose(Input::choice("file")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.query, "Which file?");
+    assert!(!options.text_input);
+    assert_eq!(
+        builder.choose(Input::text("c.txt")),
+        Err(ChooseError::UnexpectedText)
+    );
+    builder.choose(Input::choice("b.txt")).unwrap();
+    builder.choose(Input::choice("version")).unwrap();
+    builder.choose(Input::text("3")).unwrap();
+    builder.choose(Input::choice("notes")).unwrap();
+    builder.choose(Input::text("hello")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Upload {
+            file: "b.txt".into(),
+            version: Version(3),
+            notes: "hello".into(),
+        }
+    );
+}
@@ -0,0 +1,177 @@
+use ibuilder::*;
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+#[ibuilder(mode = "wizard")]
+struct Survey {
+    name: String,
+    kind: Kind,
+    age: u8,
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+enum Kind {
+    Student,
+    Worker { company: String },
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+struct Form {
+    survey: Survey,
+    #[ibuilder(default = true)]
+    accepted: bool,
+}
+
+fn query(builder: &Builder<Survey>) -> String {
+    builder.get_options().query
+}
+
+#[test]
+fn test_linear_flow() {
+    let mut builder = Survey::builder();
+    assert_eq!(query(&builder), "Type a string");
+    builder.choose(Input::text("Alice")).unwrap();
+    assert_eq!(query(&builder), "Select a variant");
+    builder.choose(Input::choice("Student")).unwrap();
+    assert_eq!(query(&builder), "Type an integer");
+    builder.choose(Input::text("20")).unwrap();
+    // the menu is shown at the end
+    assert_eq!(query(&builder), "Select the field to edit");
+    assert_eq!(
+        builder.choose(Input::choice(FINALIZE_ID)).unwrap(),
+        Some(Survey {
+            name: "Alice".into(),
+            kind: Kind::Student,
+            age: 20
+        })
+    );
+}
+
+#[test]
+fn test_back_moves_to_previous() {
+    let mut builder = Survey::builder();
+    builder.choose(Input::text("Alice")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(query(&builder), "Type a string");
+    builder.choose(Input::text("Bob")).unwrap();
+    builder.choose(Input::choice("Student")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(query(&builder), "Select a variant");
+    // back from the first question shows the menu
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(query(&builder), "Select the field to edit");
+}
+
+#[test]
+fn test_back_from_complete_section() {
+    let mut builder = Survey::builder();
+    builder.choose(Input::text("Alice")).unwrap();
+    builder.choose(Input::choice("Worker")).unwrap();
+    builder.choose(Input::choice("company")).unwrap();
+    builder.choose(Input::text("ACME")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(query(&builder), "Select a variant");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    // the variant is complete, move forward
+    assert_eq!(query(&builder), "Type an integer");
+}
+
+#[test]
+fn test_nested_wizard() {
+    let mut builder = Form::builder();
+    builder.choose(Input::choice("survey")).unwrap();
+    builder.choose(Input::text("Alice")).unwrap();
+    builder.choose(Input::choice("Student")).unwrap();
+    builder.choose(Input::text("20")).unwrap();
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(builder.get_options().query, "Select the field to edit");
+    assert!(builder.is_done());
+    // a complete wizard shows the menu
+    builder.choose(Input::choice("survey")).unwrap();
+    let options = builder.get_options();
+    assert_eq!(options.query, "Select the field to edit");
+    assert_eq!(options.choices[0].choice_id, "name");
+}
+
+#[test]
+fn test_correction_after_complete() {
+    let mut builder = Survey::builder();
+    builder.choose(Input::text("Alice")).unwrap();
+    builder.choose(Input::choice("Student")).unwrap();
+    builder.choose(Input::text("20")).unwrap();
+    assert_eq!(query(&builder), "Select the field to edit");
+    // correcting a field of the complete struct returns to the menu, not to the next question
+    builder.choose(Input::choice("name")).unwrap();
+    builder.choose(Input::text("Bob")).unwrap();
+    assert_eq!(query(&builder), "Select the field to edit");
+    builder.choose(Input::choice("age")).unwrap();
+    builder.choose(Input::text("21")).unwrap();
+    assert_eq!(query(&builder), "Select the field to edit");
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Survey {
+            name: "Bob".into(),
+            kind: Kind::Student,
+            age: 21
+        }
+    );
+}
+
+#[derive(Debug, IBuilder, Eq, PartialEq)]
+#[ibuilder(mode = "wizard")]
+struct Branching {
+    #[ibuilder(prompt = "student?")]
+    #[ibuilder(next_if(cond = "is_student", goto = "school"))]
+    student: bool,
+    #[ibuilder(default = "none", prompt = "company?")]
+    company: String,
+    #[ibuilder(default = "none", prompt = "school?")]
+    #[ibuilder(next_if(cond = "always", goto = "age"))]
+    school: String,
+    #[ibuilder(default = "none", prompt = "skipped?")]
+    skipped: String,
+    #[ibuilder(prompt = "age?")]
+    age: u8,
+}
+
+fn is_student(state: &PartialState) -> bool {
+    state.get("student").unwrap_or(false)
+}
+
+fn always(_: &PartialState) -> bool {
+    true
+}
+
+fn current(builder: &Builder<Branching>) -> String {
+    builder.get_options().query
+}
+
+#[test]
+fn test_branching() {
+    let mut builder = Branching::builder();
+    builder.choose(Input::choice("true")).unwrap();
+    assert_eq!(current(&builder), "school?");
+    builder.choose(Input::text("MIT")).unwrap();
+    assert_eq!(current(&builder), "age?");
+    // going back follows the same path
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(current(&builder), "school?");
+    builder.choose(Input::choice(BACK_ID)).unwrap();
+    assert_eq!(current(&builder), "student?");
+    builder.choose(Input::choice("false")).unwrap();
+    assert_eq!(current(&builder), "company?");
+    builder.choose(Input::text("ACME")).unwrap();
+    builder.choose(Input::text("Stanford")).unwrap();
+    assert_eq!(current(&builder), "age?");
+    builder.choose(Input::text("42")).unwrap();
+    assert_eq!(
+        builder.finalize().unwrap(),
+        Branching {
+            student: false,
+            company: "ACME".into(),
+            school: "Stanford".into(),
+            skipped: "none".into(),
+            age: 42
+        }
+    );
+}
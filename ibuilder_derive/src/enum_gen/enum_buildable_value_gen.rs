@@ -1,27 +1,50 @@
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
+use syn::Ident;
 
 use crate::enum_gen::{
     gen_variants_builder_ident, gen_variants_builder_variant_ident, EnumGenerator, VariantKind,
 };
+use crate::generics::phantom_type;
+use crate::struct_gen::StructGenerator;
 
-/// Generate the implementation of the `BuildableValue` trait.
+/// Generate the implementation of the `BuildableValue` and `TypedBuildableValue` traits.
 pub fn gen_impl_buildable_value(gen: &EnumGenerator) -> TokenStream {
     let builder_ident = &gen.builder_ident;
+    let ident = &gen.ident;
+    let (impl_generics, ty_generics, where_clause) = gen.generics.split_for_impl();
     let fn_apply = gen_fn_apply(gen);
     let fn_get_options = gen_fn_get_options(gen);
     let fn_get_subfields = gen_fn_get_subfields(gen);
+    let fn_subfield = gen_fn_subfield(gen);
     let fn_to_node = gen_fn_to_node(gen);
+    let fn_get_value = gen_fn_get_value(gen);
     let fn_get_value_any = gen_fn_get_value_any(gen);
+    let fn_set_value_any = gen_fn_set_value_any(gen);
+    let fn_stay_after_apply = gen_fn_stay_after_apply(gen);
+    let fn_auto_enter = gen_fn_auto_enter(gen);
+    let fn_schema = gen_fn_schema(gen);
+    let fn_clone_boxed = gen_fn_clone_boxed(gen);
     quote! {
         #[automatically_derived]
         #[allow(unreachable_code)]
-        impl ibuilder::BuildableValue for #builder_ident {
+        impl #impl_generics ibuilder::BuildableValue for #builder_ident #ty_generics #where_clause {
             #fn_apply
             #fn_get_options
             #fn_get_subfields
+            #fn_subfield
             #fn_to_node
             #fn_get_value_any
+            #fn_set_value_any
+            #fn_stay_after_apply
+            #fn_auto_enter
+            #fn_schema
+            #fn_clone_boxed
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ibuilder::TypedBuildableValue<#ident #ty_generics> for #builder_ident #ty_generics #where_clause {
+            #fn_get_value
         }
     }
 }
@@ -33,8 +56,45 @@ pub fn gen_impl_buildable_value(gen: &EnumGenerator) -> TokenStream {
 fn gen_fn_apply(gen: &EnumGenerator) -> TokenStream {
     let select_menu = fn_apply_select_menu(gen);
     let inner_menu = fn_apply_inner_menu(gen);
+    if gen.metadata.inline_variant_fields {
+        let visible = gen
+            .variants
+            .iter()
+            .filter(|v| !v.metadata.hidden)
+            .map(|var| &var.ident);
+        let forward = inline_forward(
+            gen,
+            quote! { return inner.apply(data, current_fields) },
+            quote! { {} },
+            true,
+        );
+        let inner_menu = inline_forward(
+            gen,
+            quote! { inner.apply(data, current_fields)? },
+            quote! { unreachable!("Invalid variant in value") },
+            true,
+        );
+        return quote! {
+            fn apply(&mut self, data: &ibuilder::Input, current_fields: &[ibuilder::Id]) -> Result<(), ibuilder::ChooseError> {
+                if current_fields.is_empty() {
+                    // the variants take the precedence over the fields of the selected one
+                    let is_variant = match data {
+                        ibuilder::Input::Choice(data) => [#(stringify!(#visible),)*].contains(&data.as_str()),
+                        ibuilder::Input::Text(_) => false,
+                    };
+                    if !is_variant {
+                        #forward
+                    }
+                    #select_menu
+                } else {
+                    #inner_menu
+                }
+                Ok(())
+            }
+        };
+    }
     quote! {
-        fn apply(&mut self, data: ibuilder::Input, current_fields: &[String]) -> Result<(), ibuilder::ChooseError> {
+        fn apply(&mut self, data: &ibuilder::Input, current_fields: &[ibuilder::Id]) -> Result<(), ibuilder::ChooseError> {
             // select variant menu
             if current_fields.is_empty() {
                 #select_menu
@@ -72,19 +132,42 @@ fn fn_apply_select_menu(gen: &EnumGenerator) -> TokenStream {
             }
         })
         .collect();
-    quote! {
-        match data {
-            ibuilder::Input::Choice(data) => {
-                match data.as_str() {
-                    #(#select_menu,)*
-                    _ => return Err(ibuilder::ChooseError::UnexpectedChoice),
+    let (values, value_variants) = variant_values(gen);
+    let from_text = if values.is_empty() {
+        quote! { return Err(ibuilder::ChooseError::UnexpectedText) }
+    } else {
+        quote! {
+            match text.trim() {
+                #(#values => stringify!(#value_variants),)*
+                _ => {
+                    return Err(ibuilder::ChooseError::InvalidText {
+                        error: format!("Unknown value: {}", text.trim()),
+                    })
                 }
             }
-            _ => return Err(ibuilder::ChooseError::UnexpectedText)
+        }
+    };
+    quote! {
+        let data: &str = match data {
+            ibuilder::Input::Choice(data) => data,
+            ibuilder::Input::Text(text) => #from_text,
+        };
+        match data {
+            #(#select_menu,)*
+            _ => return Err(ibuilder::ChooseError::UnexpectedChoice),
         }
     }
 }
 
+/// The machine values of the visible variants, with the identifiers of the respective variants.
+fn variant_values(gen: &EnumGenerator) -> (Vec<&String>, Vec<&Ident>) {
+    gen.variants
+        .iter()
+        .filter(|v| !v.metadata.hidden)
+        .filter_map(|v| Some((v.metadata.value.as_ref()?, &v.ident)))
+        .unzip()
+}
+
 /// Generate the forwarding of the apply to the variant.
 fn fn_apply_inner_menu(gen: &EnumGenerator) -> TokenStream {
     let builder = gen_variants_builder_ident(&gen.ident);
@@ -122,8 +205,38 @@ fn fn_apply_inner_menu(gen: &EnumGenerator) -> TokenStream {
 fn gen_fn_get_options(gen: &EnumGenerator) -> TokenStream {
     let select_menu = fn_get_options_select_menu(gen);
     let inner_menu = fn_get_options_inner_menu(gen);
+    if gen.metadata.inline_variant_fields {
+        let merge = inline_forward(
+            gen,
+            quote! {{
+                let inner = inner.get_options(current_fields);
+                options.text_input |= inner.text_input;
+                options.choices.extend(inner.choices);
+            }},
+            quote! { {} },
+            false,
+        );
+        let inner_menu = inline_forward(
+            gen,
+            quote! { inner.get_options(current_fields) },
+            quote! { unreachable!("Invalid variant in value") },
+            false,
+        );
+        return quote! {
+            fn get_options(&self, current_fields: &[ibuilder::Id]) -> ibuilder::Options {
+                if current_fields.is_empty() {
+                    // the fields of the selected variant follow the variants
+                    let mut options = #select_menu;
+                    #merge
+                    options
+                } else {
+                    #inner_menu
+                }
+            }
+        };
+    }
     quote! {
-        fn get_options(&self, current_fields: &[String]) -> ibuilder::Options {
+        fn get_options(&self, current_fields: &[ibuilder::Id]) -> ibuilder::Options {
             if current_fields.is_empty() {
                 #select_menu
             } else {
@@ -149,26 +262,67 @@ fn fn_get_options_select_menu(gen: &EnumGenerator) -> TokenStream {
                 VariantKind::Unnamed(_) | VariantKind::Named(_) => {
                     quote! {
                         match self.value.as_ref() {
-                            Some(#builder::#ident(inner)) => inner.get_value_any().is_none(),
+                            Some(#builder::#ident(inner)) => !inner.is_complete(),
                             _ => false,
                         }
                     }
                 }
             };
+            let value = match &var.metadata.value {
+                Some(value) => quote! { Some(#value.to_string()) },
+                None => quote! { None },
+            };
+            let message_key = gen.message_key(ident);
+            let help = match &var.metadata.help {
+                Some(help) => quote! { Some(#help.to_string()) },
+                None => quote! { None },
+            };
             quote! {
                 ibuilder::Choice {
-                    choice_id: stringify!(#ident).to_string(),
+                    choice_id: ibuilder::Id::from_static(stringify!(#ident)),
                     text: #name.to_string(),
                     needs_action: #needs_action,
+                    value: #value,
+                    message_key: Some(#message_key.to_string()),
+                    help: #help,
                 }
             }
         })
         .collect();
+    // the selected variant, the hidden ones are not in the choices
+    let selected: Vec<_> = gen
+        .variants
+        .iter()
+        .filter(|v| !v.metadata.hidden)
+        .map(|var| {
+            let ident = &var.ident;
+            match &var.kind {
+                VariantKind::Empty => quote! { Some(#builder::#ident) },
+                _ => quote! { Some(#builder::#ident(_)) },
+            }
+        })
+        .collect();
+    let selected_ident = gen
+        .variants
+        .iter()
+        .filter(|v| !v.metadata.hidden)
+        .map(|var| &var.ident);
+    let text_input = !variant_values(gen).0.is_empty();
+    let prompt_key = match &gen.metadata.prompt_key {
+        Some(key) => quote! { Some(#key.to_string()) },
+        None => quote! { None },
+    };
     quote! {
         ibuilder::Options {
             query: self.prompt.clone(),
-            text_input: false,
+            text_input: #text_input,
             choices: vec![ #(#choices,)* ],
+            prompt_key: #prompt_key,
+            default_choice: match self.value.as_ref() {
+                #(#selected => Some(ibuilder::Id::from_static(stringify!(#selected_ident))),)*
+                _ => None,
+            },
+            ..::std::default::Default::default()
         }
     }
 }
@@ -182,11 +336,22 @@ fn fn_get_options_inner_menu(gen: &EnumGenerator) -> TokenStream {
         .filter(|v| !v.metadata.hidden)
         .filter_map(|var| {
             let ident = &var.ident;
+            // the menu of the variant also shows its help
+            let options = match &var.metadata.help {
+                Some(help) => quote! {{
+                    let mut options = inner.get_options(rest);
+                    if rest.is_empty() {
+                        options.help = Some(#help.to_string());
+                    }
+                    options
+                }},
+                None => quote! { inner.get_options(rest) },
+            };
             match &var.kind {
                 VariantKind::Empty => None,
                 VariantKind::Unnamed(_) | VariantKind::Named(_) => Some(quote! {
                     stringify!(#ident) => match self.value.as_ref().unwrap() {
-                        #builder::#ident(inner) => inner.get_options(rest),
+                        #builder::#ident(inner) => #options,
                         _ => unreachable!("Invalid variant in value"),
                     }
                 }),
@@ -212,10 +377,23 @@ fn gen_fn_get_subfields(gen: &EnumGenerator) -> TokenStream {
         .filter(|var| !var.kind.is_empty() && !var.metadata.hidden)
         .map(|var| &var.ident)
         .collect();
+    if gen.metadata.inline_variant_fields {
+        let forward = inline_forward(
+            gen,
+            quote! { inner.get_subfields(current_fields) },
+            quote! { vec![] },
+            false,
+        );
+        return quote! {
+            fn get_subfields(&self, current_fields: &[ibuilder::Id]) -> Vec<ibuilder::Id> {
+                #forward
+            }
+        };
+    }
     quote! {
-        fn get_subfields(&self, current_fields: &[String]) -> Vec<String> {
+        fn get_subfields(&self, current_fields: &[ibuilder::Id]) -> Vec<ibuilder::Id> {
             if current_fields.is_empty() {
-                vec![ #(stringify!(#variants).to_string(),)* ]
+                vec![ #(ibuilder::Id::from_static(stringify!(#variants)),)* ]
             } else {
                 let field = &current_fields[0];
                 let rest = &current_fields[1..];
@@ -233,7 +411,266 @@ fn gen_fn_get_subfields(gen: &EnumGenerator) -> TokenStream {
     }
 }
 
-/// Generate the implementation of the `to_node` method.
+/// Generate the implementation of the `subfield` method, which compares the name with the ones of
+/// the variants without listing them.
+fn gen_fn_subfield(gen: &EnumGenerator) -> TokenStream {
+    let builder = gen_variants_builder_ident(&gen.ident);
+    let variants: Vec<_> = gen
+        .variants
+        .iter()
+        .filter(|var| !var.kind.is_empty() && !var.metadata.hidden)
+        .map(|var| &var.ident)
+        .collect();
+    if gen.metadata.inline_variant_fields {
+        let forward = inline_forward(
+            gen,
+            quote! { inner.subfield(current_fields, name) },
+            quote! { None },
+            false,
+        );
+        return quote! {
+            fn subfield(&self, current_fields: &[ibuilder::Id], name: &str) -> Option<ibuilder::Id> {
+                #forward
+            }
+        };
+    }
+    quote! {
+        fn subfield(&self, current_fields: &[ibuilder::Id], name: &str) -> Option<ibuilder::Id> {
+            if current_fields.is_empty() {
+                [#(stringify!(#variants),)*]
+                    .iter()
+                    .find(|variant| **variant == name)
+                    .map(|variant| ibuilder::Id::from_static(*variant))
+            } else {
+                let field = &current_fields[0];
+                let rest = &current_fields[1..];
+                match field.as_str() {
+                    #(
+                        stringify!(#variants) => match self.value.as_ref().unwrap() {
+                            #builder::#variants(inner) => inner.subfield(rest, name),
+                            _ => unreachable!("Invalid variant in value"),
+                        },
+                    )*
+                    _ => unreachable!("Invalid variant: {}", field),
+                }
+            }
+        }
+    }
+}
+
+/// Generate the implementation of the `stay_after_apply` and `recorded_input` methods.
+fn gen_fn_stay_after_apply(gen: &EnumGenerator) -> TokenStream {
+    let builder = gen_variants_builder_ident(&gen.ident);
+    let variants: Vec<_> = gen
+        .variants
+        .iter()
+        .filter(|var| !var.kind.is_empty() && !var.metadata.hidden)
+        .map(|var| &var.ident)
+        .collect();
+    if gen.metadata.inline_variant_fields {
+        // after selecting a variant with fields the user stays for filling them
+        let forward = inline_forward(
+            gen,
+            quote! { current_fields.is_empty() || inner.stay_after_apply(current_fields) },
+            quote! { false },
+            false,
+        );
+        let forward_recorded = inline_forward(
+            gen,
+            quote! { inner.recorded_input(data, current_fields) },
+            quote! { None },
+            false,
+        );
+        return quote! {
+            fn stay_after_apply(&self, current_fields: &[ibuilder::Id]) -> bool {
+                #forward
+            }
+
+            fn recorded_input(&self, data: &ibuilder::Input, current_fields: &[ibuilder::Id]) -> Option<ibuilder::Input> {
+                #forward_recorded
+            }
+        };
+    }
+    quote! {
+        fn stay_after_apply(&self, current_fields: &[ibuilder::Id]) -> bool {
+            if current_fields.is_empty() {
+                false
+            } else {
+                let field = &current_fields[0];
+                let rest = &current_fields[1..];
+                match field.as_str() {
+                    #(
+                        stringify!(#variants) => match self.value.as_ref().unwrap() {
+                            #builder::#variants(inner) => inner.stay_after_apply(rest),
+                            _ => unreachable!("Invalid variant in value"),
+                        },
+                    )*
+                    _ => unreachable!("Invalid variant: {}", field),
+                }
+            }
+        }
+
+        fn recorded_input(&self, data: &ibuilder::Input, current_fields: &[ibuilder::Id]) -> Option<ibuilder::Input> {
+            let field = current_fields.first()?;
+            let rest = &current_fields[1..];
+            match field.as_str() {
+                #(
+                    stringify!(#variants) => match self.value.as_ref().unwrap() {
+                        #builder::#variants(inner) => inner.recorded_input(data, rest),
+                        _ => unreachable!("Invalid variant in value"),
+                    },
+                )*
+                _ => unreachable!("Invalid variant: {}", field),
+            }
+        }
+    }
+}
+
+/// Generate the implementation of the `auto_enter` method.
+fn gen_fn_auto_enter(gen: &EnumGenerator) -> TokenStream {
+    let builder = gen_variants_builder_ident(&gen.ident);
+    let variants: Vec<_> = gen
+        .variants
+        .iter()
+        .filter(|var| !var.kind.is_empty() && !var.metadata.hidden)
+        .map(|var| &var.ident)
+        .collect();
+    if gen.metadata.inline_variant_fields {
+        let forward = inline_forward(
+            gen,
+            quote! { inner.auto_enter(current_fields, entry) },
+            quote! { None },
+            false,
+        );
+        return quote! {
+            fn auto_enter(&self, current_fields: &[ibuilder::Id], entry: ibuilder::MenuEntry) -> Option<ibuilder::Id> {
+                #forward
+            }
+        };
+    }
+    quote! {
+        fn auto_enter(&self, current_fields: &[ibuilder::Id], entry: ibuilder::MenuEntry) -> Option<ibuilder::Id> {
+            if current_fields.is_empty() {
+                None
+            } else {
+                let field = &current_fields[0];
+                let rest = &current_fields[1..];
+                match field.as_str() {
+                    #(
+                        stringify!(#variants) => match self.value.as_ref().unwrap() {
+                            #builder::#variants(inner) => inner.auto_enter(rest, entry),
+                            _ => unreachable!("Invalid variant in value"),
+                        },
+                    )*
+                    _ => unreachable!("Invalid variant: {}", field),
+                }
+            }
+        }
+    }
+}
+
+/// Generate the forwarding of a call to the builder of the selected variant, for the enums with
+/// `#[ibuilder(inline_variant_fields)]`: the paths inside the variant don't start with its name,
+/// the fields are reached directly from the menu of the enum. `fallback` is used when the selected
+/// variant has no fields.
+fn inline_forward(
+    gen: &EnumGenerator,
+    call: TokenStream,
+    fallback: TokenStream,
+    mutable: bool,
+) -> TokenStream {
+    let builder = gen_variants_builder_ident(&gen.ident);
+    let variants = gen
+        .variants
+        .iter()
+        .filter(|var| !var.kind.is_empty() && !var.metadata.hidden)
+        .map(|var| &var.ident);
+    let value = if mutable {
+        quote! { self.value.as_mut() }
+    } else {
+        quote! { self.value.as_ref() }
+    };
+    quote! {
+        match #value {
+            #(Some(#builder::#variants(inner)) => #call,)*
+            _ => #fallback,
+        }
+    }
+}
+
+/// Generate the implementation of the `schema` method: `oneOf` the visible variants, the empty ones
+/// are a single string and the others are an object with a single property named after them. The
+/// selected variant keeps its state, the others are described by a new builder.
+fn gen_fn_schema(gen: &EnumGenerator) -> TokenStream {
+    let builder = gen_variants_builder_ident(&gen.ident);
+    let (_, ty_generics, _) = gen.generics.split_for_impl();
+    let ident = &gen.ident;
+    let variants: Vec<_> = gen
+        .variants
+        .iter()
+        .filter(|v| !v.metadata.hidden)
+        .map(|var| {
+            let ident = &var.ident;
+            let name = var.actual_name();
+            let help = match &var.metadata.help {
+                Some(help) => quote! { Some(#help.to_string()) },
+                None => quote! { None },
+            };
+            let kind = match &var.kind {
+                VariantKind::Empty => quote! {
+                    ibuilder::schema::SchemaKind::Enum(vec![stringify!(#ident).to_string()])
+                },
+                VariantKind::Unnamed(_) | VariantKind::Named(_) => {
+                    let field_builder = gen_variants_builder_variant_ident(&gen.ident, ident);
+                    let inner_builder = StructGenerator::gen_builder_ident(&field_builder);
+                    let inner_new = var.inner_builder_new(&gen.ident);
+                    quote! {
+                        ibuilder::schema::SchemaKind::Object(vec![ibuilder::schema::Property {
+                            name: stringify!(#ident).to_string(),
+                            schema: match &self.value {
+                                Some(#builder::#ident(inner)) => ibuilder::BuildableValue::schema(inner),
+                                _ => {
+                                    let inner: #inner_builder #ty_generics = #inner_new;
+                                    ibuilder::BuildableValue::schema(&inner)
+                                }
+                            },
+                            required: true,
+                        }])
+                    }
+                }
+            };
+            quote! {
+                ibuilder::schema::Schema {
+                    title: Some(#name.to_string()),
+                    description: #help,
+                    ..ibuilder::schema::Schema::new(#kind)
+                }
+            }
+        })
+        .collect();
+    let empty: Vec<_> = gen
+        .variants
+        .iter()
+        .filter(|v| v.kind.is_empty() && !v.metadata.hidden)
+        .map(|var| &var.ident)
+        .collect();
+    quote! {
+        fn schema(&self) -> ibuilder::schema::Schema {
+            ibuilder::schema::Schema {
+                title: Some(stringify!(#ident).to_string()),
+                default: match &self.value {
+                    #(Some(#builder::#empty) => Some(stringify!(#empty).to_string()),)*
+                    _ => None,
+                },
+                ..ibuilder::schema::Schema::new(ibuilder::schema::SchemaKind::OneOf(vec![
+                    #(#variants,)*
+                ]))
+            }
+        }
+    }
+}
+
+/// Generate the implementation of the `to_node`, `to_node_pruned` and `visit_nodes` methods.
 fn gen_fn_to_node(gen: &EnumGenerator) -> TokenStream {
     let builder = gen_variants_builder_ident(&gen.ident);
     let variants: Vec<_> = gen
@@ -251,54 +688,89 @@ fn gen_fn_to_node(gen: &EnumGenerator) -> TokenStream {
                 },
                 VariantKind::Named(_) => quote! {
                     Some(#builder::#ident(inner)) => {
-                        let inner_node = inner.to_node();
+                        let inner_node = inner.to_node_pruned(depth);
                         let fields = match inner_node {
                             ibuilder::nodes::Node::Composite(_, fields) => fields,
-                            _ => unreachable!("Invalid node of enum content"),
+                            // the content has been elided
+                            leaf => return leaf,
                         };
                         ibuilder::nodes::Node::Composite(#name.to_string(), fields)
                     }
                 },
                 VariantKind::Unnamed(_) => quote! {
-                    Some(#builder::#ident(inner)) => inner.to_node()
+                    Some(#builder::#ident(inner)) => inner.to_node_pruned(depth)
+                },
+            }
+        })
+        .collect();
+    let visits: Vec<_> = gen
+        .variants
+        .iter()
+        .filter(|v| !v.metadata.hidden)
+        .map(|var| {
+            let ident = &var.ident;
+            let name = var.actual_name();
+            match &var.kind {
+                VariantKind::Empty => quote! {
+                    Some(#builder::#ident) => {
+                        visitor.leaf(&ibuilder::nodes::Field::String(#name.to_string()))
+                    }
+                },
+                // the builders of the variants with fields are already named after the variant
+                _ => quote! {
+                    Some(#builder::#ident(inner)) => inner.visit_nodes(visitor)
                 },
             }
         })
         .collect();
     quote! {
         fn to_node(&self) -> ibuilder::nodes::Node {
+            self.to_node_pruned(usize::MAX)
+        }
+
+        fn to_node_pruned(&self, depth: usize) -> ibuilder::nodes::Node {
             match &self.value {
                 None => ibuilder::nodes::Node::Leaf(ibuilder::nodes::Field::Missing),
                 #(#variants,)*
                 _ => unreachable!("Selected an hidden value")
             }
         }
+
+        fn visit_nodes(&self, visitor: &mut dyn ibuilder::nodes::NodeVisitor) {
+            match &self.value {
+                None => visitor.leaf(&ibuilder::nodes::Field::Missing),
+                #(#visits,)*
+                _ => unreachable!("Selected an hidden value")
+            }
+        }
     }
 }
 
-/// Generate the implementation of the `get_value_any` method.
-fn gen_fn_get_value_any(gen: &EnumGenerator) -> TokenStream {
+/// Generate the implementation of `TypedBuildableValue::get_value`. The builders of the variants
+/// with fields are the ones generated for their structs, so their values are typed.
+fn gen_fn_get_value(gen: &EnumGenerator) -> TokenStream {
     let builder = gen_variants_builder_ident(&gen.ident);
-    let base = &gen.ident;
+    let (_, ty_generics, _) = gen.generics.split_for_impl();
+    // the parameters cannot always be inferred from the fields of the variant
+    let turbofish = ty_generics.as_turbofish();
+    let ident = &gen.ident;
+    let base = quote! { #ident #turbofish };
     let variants: Vec<_> = gen
         .variants
         .iter()
         .map(|var| {
             let ident = &var.ident;
             match &var.kind {
-                VariantKind::Empty => quote! { #builder::#ident => Box::new(#base::#ident) },
+                VariantKind::Empty => quote! { #builder::#ident => #base::#ident },
                 VariantKind::Named(_) => {
                     let fields = var.field_names();
                     let field_builder = gen_variants_builder_variant_ident(&gen.ident, ident);
                     quote! {
                         #builder::#ident(inner) => {
-                            let inner = inner
-                                .get_value_any()?
-                                .downcast::<#field_builder>()
-                                .unwrap();
-                            Box::new(#base::#ident {
+                            let inner = ibuilder::TypedBuildableValue::<#field_builder #ty_generics>::get_value(inner)?;
+                            #base::#ident {
                                 #(#fields: inner.#fields,)*
-                            })
+                            }
                         }
                     }
                 }
@@ -307,19 +779,17 @@ fn gen_fn_get_value_any(gen: &EnumGenerator) -> TokenStream {
                     let field_builder = gen_variants_builder_variant_ident(&gen.ident, ident);
                     quote! {
                         #builder::#ident(inner) => {
-                            let inner = inner
-                                .get_value_any()?
-                                .downcast::<#field_builder>()
-                                .unwrap();
-                            Box::new(#base::#ident(#(inner.#fields,)*))
+                            let inner = ibuilder::TypedBuildableValue::<#field_builder #ty_generics>::get_value(inner)?;
+                            #base::#ident(#(inner.#fields,)*)
                         }
                     }
                 }
             }
         })
         .collect();
+    let self_type = quote! { #ident #ty_generics };
     quote! {
-        fn get_value_any(&self) -> Option<Box<dyn std::any::Any>> {
+        fn get_value(&self) -> Option<#self_type> {
             let variant = self.value.as_ref()?;
             Some(match variant {
                 #(#variants,)*
@@ -327,3 +797,123 @@ fn gen_fn_get_value_any(gen: &EnumGenerator) -> TokenStream {
         }
     }
 }
+
+/// Generate the implementation of the `get_value_any` and `is_complete` methods.
+fn gen_fn_get_value_any(gen: &EnumGenerator) -> TokenStream {
+    let builder = gen_variants_builder_ident(&gen.ident);
+    let (_, ty_generics, _) = gen.generics.split_for_impl();
+    let ident = &gen.ident;
+    let complete = gen.variants.iter().map(|var| {
+        let ident = &var.ident;
+        match &var.kind {
+            VariantKind::Empty => quote! { Some(#builder::#ident) => true },
+            _ => quote! { Some(#builder::#ident(inner)) => inner.is_complete() },
+        }
+    });
+    quote! {
+        ibuilder::typed_get_value_any!(#ident #ty_generics);
+
+        fn is_complete(&self) -> bool {
+            match &self.value {
+                None => false,
+                #(#complete,)*
+            }
+        }
+    }
+}
+
+/// Generate the implementation of the `set_value_any` method, which selects the variant of the
+/// value and sets its fields. The hidden variants cannot be selected, so they are not set.
+fn gen_fn_set_value_any(gen: &EnumGenerator) -> TokenStream {
+    let builder = gen_variants_builder_ident(&gen.ident);
+    let (_, ty_generics, _) = gen.generics.split_for_impl();
+    let ident = &gen.ident;
+    let phantom = phantom_type(&gen.generics).map(|_| quote! { ::std::marker::PhantomData });
+    let variants = gen.variants.iter().map(|var| {
+        let variant = &var.ident;
+        if var.metadata.hidden {
+            return quote! { #ident::#variant { .. } => false };
+        }
+        let field_builder = gen_variants_builder_variant_ident(&gen.ident, variant);
+        let (pattern, fields) = match &var.kind {
+            VariantKind::Empty => {
+                return quote! {
+                    #ident::#variant => {
+                        self.value = Some(#builder::#variant);
+                        true
+                    }
+                }
+            }
+            VariantKind::Named(_) => {
+                let fields = var.field_names();
+                let marker = phantom
+                    .as_ref()
+                    .map(|phantom| quote! { __marker: #phantom });
+                (
+                    quote! { #ident::#variant { #(#fields),* } },
+                    quote! { #field_builder { #(#fields,)* #marker } },
+                )
+            }
+            VariantKind::Unnamed(fields) => {
+                let fields: Vec<_> = (0..fields.len())
+                    .map(|i| format_ident!("__field{}", i))
+                    .collect();
+                (
+                    quote! { #ident::#variant(#(#fields),*) },
+                    quote! { #field_builder(#(#fields,)* #phantom) },
+                )
+            }
+        };
+        let inner_builder = StructGenerator::gen_builder_ident(&field_builder);
+        let inner_new = var.inner_builder_new(&gen.ident);
+        quote! {
+            #pattern => {
+                let fields: #field_builder #ty_generics = #fields;
+                let mut inner: #inner_builder #ty_generics = #inner_new;
+                let res = ibuilder::BuildableValue::set_value_any(&mut inner, Box::new(fields));
+                self.value = Some(#builder::#variant(inner));
+                res
+            }
+        }
+    });
+    quote! {
+        fn set_value_any(&mut self, value: Box<dyn std::any::Any>) -> bool {
+            let value: #ident #ty_generics = match value.downcast() {
+                Ok(value) => *value,
+                Err(_) => return false,
+            };
+            match value {
+                #(#variants,)*
+            }
+        }
+    }
+}
+
+/// Generate the implementation of the `clone_boxed` method, which copies the selected variant with
+/// the builder of its fields.
+fn gen_fn_clone_boxed(gen: &EnumGenerator) -> TokenStream {
+    let builder = gen_variants_builder_ident(&gen.ident);
+    let variants = gen.variants.iter().map(|var| {
+        let variant = &var.ident;
+        match &var.kind {
+            VariantKind::Empty => quote! {
+                Some(#builder::#variant) => Some(#builder::#variant)
+            },
+            _ => quote! {
+                Some(#builder::#variant(inner)) => Some(#builder::#variant(inner.try_clone()?))
+            },
+        }
+    });
+    quote! {
+        fn clone_boxed(&self) -> Option<Box<dyn ibuilder::BuildableValue>> {
+            let value = match &self.value {
+                #(#variants,)*
+                None => None,
+            };
+            Some(Box::new(Self {
+                value,
+                prompt: self.prompt.clone(),
+            }))
+        }
+    }
+}
@@ -1,12 +1,16 @@
+use std::collections::HashSet;
+
+use ibuilder_derive_internals::__private::{
+    builder_ident, snake_case, EnumMetadata, VariantMetadata,
+};
 use proc_macro2::TokenStream;
-use proc_macro_error::{abort, emit_warning, ResultExt};
-use syn::punctuated::Punctuated;
-use syn::{Fields, Ident, Meta, MetaNameValue, Token, Variant};
+use proc_macro_error::abort;
+use syn::{Fields, Ident, Variant};
 
 use quote::{format_ident, quote, ToTokens, TokenStreamExt};
 
 use crate::enum_gen::enum_buildable_value_gen::gen_impl_buildable_value;
-use crate::parse_string_meta;
+use crate::generics::{bounded_generics, phantom_type};
 use crate::struct_gen::{StructField, StructGenerator};
 
 mod enum_buildable_value_gen;
@@ -26,13 +30,9 @@ pub struct EnumGenerator {
     variants: Vec<EnumVariant>,
     /// The metadata associated with the enum.
     metadata: EnumMetadata,
-}
-
-/// The metadata of the enum, it's taken from the attributes of the `enum`.
-#[derive(Debug)]
-pub struct EnumMetadata {
-    /// The prompt to use for this enum's main menu.
-    prompt: Option<String>,
+    /// The generic parameters of the enum, with the bounds required for building it. The builder
+    /// and the state of the variants have the same parameters.
+    generics: syn::Generics,
 }
 
 /// The information about a variant of an enum.
@@ -46,19 +46,6 @@ pub struct EnumVariant {
     metadata: VariantMetadata,
 }
 
-/// The metadata of the variant, it's taken from the attributes of the `Variant`.
-#[derive(Debug)]
-pub struct VariantMetadata {
-    /// The prompt to use for this variant.
-    prompt: Option<String>,
-    /// Different name to use in the tree structure.
-    rename: Option<String>,
-    /// Whether this variant is hidden.
-    hidden: bool,
-    /// Whether this is the default variant.
-    default: bool,
-}
-
 /// The information about the type of variant.
 #[derive(Debug)]
 pub enum VariantKind {
@@ -88,6 +75,7 @@ impl EnumGenerator {
                     variants_builder_ident: gen_variants_builder_ident(&ast.ident),
                     variants: data.variants.iter().map(EnumVariant::from).collect(),
                     metadata: EnumMetadata::from(ast),
+                    generics: bounded_generics(&ast.generics),
                 };
                 if generator.variants.iter().all(|v| v.metadata.hidden) {
                     abort!(ast, "all the variants are hidden");
@@ -101,14 +89,60 @@ impl EnumGenerator {
                 {
                     abort!(ast, "at most one variant can be the default");
                 }
+                let mut values = HashSet::new();
+                for var in &generator.variants {
+                    if let Some(value) = &var.metadata.value {
+                        if !values.insert(value) {
+                            abort!(var.ident, "duplicated variant value");
+                        }
+                    }
+                }
+                if generator.metadata.inline_variant_fields {
+                    generator.check_inline_variant_fields();
+                }
                 generator
             }
             _ => panic!("expecting an enum"),
         }
     }
 
+    /// The message key of the choice of this variant (e.g. `shape.circle`), which is also the
+    /// prefix of the message keys of its fields. The prefix is the name of the enum in snake case,
+    /// unless it's overridden with `#[ibuilder(message_key = "...")]`.
+    fn message_key(&self, variant: &Ident) -> String {
+        let prefix = match &self.metadata.message_key {
+            Some(prefix) => prefix.clone(),
+            None => snake_case(&self.ident.to_string()),
+        };
+        format!("{}.{}", prefix, snake_case(&variant.to_string()))
+    }
+
+    /// With `#[ibuilder(inline_variant_fields)]` the choices of the variants and the fields of the
+    /// selected one are in the same menu, so their identifiers must be different.
+    fn check_inline_variant_fields(&self) {
+        let variants: HashSet<_> = self
+            .variants
+            .iter()
+            .filter(|v| !v.metadata.hidden)
+            .map(|v| v.ident.to_string())
+            .collect();
+        for var in &self.variants {
+            if let VariantKind::Named(fields) = &var.kind {
+                for field in fields {
+                    let ident = field.ident.as_ref().unwrap();
+                    if variants.contains(&ident.to_string()) {
+                        abort!(
+                            ident,
+                            "with inline_variant_fields the fields cannot have the name of a variant"
+                        );
+                    }
+                }
+            }
+        }
+    }
+
     /// Make a new `VariantsDefList` for this enum.
-    fn variants_def_list(&self) -> VariantsDefList {
+    fn variants_def_list(&self) -> VariantsDefList<'_> {
         VariantsDefList { gen: self }
     }
 }
@@ -116,36 +150,56 @@ impl EnumGenerator {
 impl EnumVariant {
     /// Generate (or not in case of empty variants) a structure that contains the internal state
     /// of a variant. This struct will have the same fields as the variant, and derives from
-    /// `IBuilder`.
-    fn gen_builder(&self, ident: Ident) -> TokenStream {
+    /// `IBuilder`. Its builder has the visibility and the generic parameters of the builder of the
+    /// enum.
+    fn gen_builder(
+        &self,
+        ident: Ident,
+        vis: &Option<syn::Visibility>,
+        generics: &syn::Generics,
+        message_key: String,
+    ) -> TokenStream {
         let name = self.actual_name();
         let mut attrs = Vec::new();
         if let Some(prompt) = &self.metadata.prompt {
             attrs.push(quote! { prompt = #prompt });
         }
+        if let Some(prompt_key) = &self.metadata.prompt_key {
+            attrs.push(quote! { prompt_key = #prompt_key });
+        }
         attrs.push(quote! { rename = #name });
+        attrs.push(quote! { message_key = #message_key });
+        if let Some(vis) = vis {
+            let vis = vis.to_token_stream().to_string();
+            attrs.push(quote! { vis = #vis });
+        }
+        let where_clause = &generics.where_clause;
         let fields_def = match &self.kind {
             VariantKind::Empty => return TokenStream::new(),
             VariantKind::Unnamed(fields) => {
-                let fields: Vec<_> = fields.iter().map(|f| &f.field).collect();
-                if fields.len() != 1 {
+                if fields.iter().filter(|f| !f.metadata.hidden).count() != 1 {
                     abort!(
                         self.ident,
                         "variants with unnamed fields are supported only with one field"
                     );
                 }
-                quote! { (#(#fields,)*); }
+                let fields: Vec<_> = fields.iter().map(|f| &f.field).collect();
+                // the marker is hidden by the derive, it makes the struct use all the parameters
+                let phantom = phantom_type(generics);
+                quote! { (#(#fields,)* #phantom) #where_clause; }
             }
             VariantKind::Named(fields) => {
                 let fields: Vec<_> = fields.iter().map(|f| &f.field).collect();
-                quote! { { #(#fields,)* } }
+                let phantom = phantom_type(generics).map(|ty| quote! { __marker: #ty });
+                quote! { #where_clause { #(#fields,)* #phantom } }
             }
         };
         quote! {
             #[allow(non_camel_case_types)]
+            #[doc(hidden)]
             #[derive(IBuilder)]
             #[ibuilder(#(#attrs,)*)]
-            struct #ident #fields_def
+            struct #ident #generics #fields_def
         }
     }
 
@@ -153,25 +207,33 @@ impl EnumVariant {
     fn builder_new(&self, base: &Ident) -> TokenStream {
         let variant = &self.ident;
         let builder = gen_variants_builder_ident(base);
-        let variant_builder = gen_variants_builder_variant_ident(base, variant);
-        let variant_builder = StructGenerator::gen_builder_ident(&variant_builder);
         match &self.kind {
             VariantKind::Empty => quote! { #builder::#variant },
             VariantKind::Unnamed(_) | VariantKind::Named(_) => {
-                let prompt = match &self.metadata.prompt {
-                    Some(prompt) => quote! {Some(#prompt.into())},
-                    None => quote! {None},
-                };
-                quote! {
-                    #builder::#variant(#variant_builder::new(ibuilder::BuildableValueConfig {
-                        default: None,
-                        prompt: #prompt,
-                    }))
-                }
+                let inner = self.inner_builder_new(base);
+                quote! { #builder::#variant(#inner) }
             }
         }
     }
 
+    /// Return the tokens for initializing the builder of the fields of this variant, which must
+    /// not be empty.
+    fn inner_builder_new(&self, base: &Ident) -> TokenStream {
+        let variant_builder = gen_variants_builder_variant_ident(base, &self.ident);
+        let variant_builder = StructGenerator::gen_builder_ident(&variant_builder);
+        let prompt = match &self.metadata.prompt {
+            Some(prompt) => quote! {Some(#prompt.into())},
+            None => quote! {None},
+        };
+        quote! {
+            #variant_builder::new(ibuilder::BuildableValueConfig {
+                default: None,
+                prompt: #prompt,
+                ..::std::default::Default::default()
+            })
+        }
+    }
+
     /// Return the list with the names of all the named fields in this variant.
     fn field_names(&self) -> Vec<Ident> {
         match &self.kind {
@@ -192,43 +254,6 @@ impl EnumVariant {
     }
 }
 
-impl From<&syn::DeriveInput> for EnumMetadata {
-    fn from(data: &syn::DeriveInput) -> EnumMetadata {
-        let mut metadata = EnumMetadata { prompt: None };
-        for attr in &data.attrs {
-            if attr.path.is_ident("ibuilder") {
-                let meta = attr
-                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-                    .unwrap_or_abort();
-                for meta in meta {
-                    parse_enum_meta(meta, &mut metadata);
-                }
-            }
-        }
-        metadata
-    }
-}
-
-/// Extract the `EnumMetadata` from a `Meta` entry in an attribute. `meta` comes from
-/// `#[ibuilder(HERE)]`.
-fn parse_enum_meta(meta: Meta, metadata: &mut EnumMetadata) {
-    match meta {
-        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
-            if path.is_ident("prompt") {
-                parse_string_meta(&mut metadata.prompt, lit);
-            } else if path.is_ident("rename") {
-                abort!(
-                    path,
-                    "renaming an enum is not supported since the name is not exposed"
-                );
-            } else {
-                abort!(path, "unknown attribute");
-            }
-        }
-        _ => abort!(meta, "unknown attribute"),
-    }
-}
-
 impl VariantKind {
     /// Check if this is `VariantKind::Empty`.
     fn is_empty(&self) -> bool {
@@ -238,7 +263,15 @@ impl VariantKind {
 
 impl From<&Variant> for EnumVariant {
     fn from(variant: &Variant) -> EnumVariant {
-        let metadata = VariantMetadata::from(variant);
+        let mut metadata = VariantMetadata::from(variant);
+        if metadata.value.is_some() && !matches!(variant.fields, Fields::Unit) {
+            abort!(variant, "value is supported only for empty variants");
+        }
+        if metadata.value.is_none() {
+            if let Some((_, discriminant)) = &variant.discriminant {
+                metadata.value = discriminant_value(discriminant);
+            }
+        }
         EnumVariant {
             ident: variant.ident.clone(),
             kind: match &variant.fields {
@@ -256,12 +289,22 @@ impl From<&Variant> for EnumVariant {
                             }
                         }
                     }
+                    if let Some(prompt_key) = &metadata.prompt_key {
+                        for field in fields.iter_mut() {
+                            if field.metadata.prompt_key.is_none() {
+                                field.metadata.prompt_key = Some(prompt_key.clone());
+                            }
+                        }
+                    }
                     VariantKind::Unnamed(fields)
                 }
                 Fields::Unit => {
                     if metadata.prompt.is_some() {
                         abort!(variant, "prompt not supported for empty variants");
                     }
+                    if metadata.prompt_key.is_some() {
+                        abort!(variant, "prompt_key not supported for empty variants");
+                    }
                     VariantKind::Empty
                 }
             },
@@ -270,64 +313,27 @@ impl From<&Variant> for EnumVariant {
     }
 }
 
-impl From<&Variant> for VariantMetadata {
-    fn from(var: &Variant) -> Self {
-        let mut metadata = VariantMetadata {
-            prompt: None,
-            rename: None,
-            hidden: false,
-            default: false,
-        };
-        for attr in &var.attrs {
-            if attr.path.is_ident("ibuilder") {
-                let meta = attr
-                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-                    .unwrap_or_abort();
-                for meta in meta {
-                    parse_variant_meta(meta, &mut metadata);
-                }
-            }
-        }
-        metadata
-    }
-}
-
-/// Extract the `VariantMetadata` from a `Meta` entry in a variant attribute. `meta` comes from
-/// `#[ibuilder(HERE)]`.
-fn parse_variant_meta(meta: Meta, metadata: &mut VariantMetadata) {
-    match meta {
-        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
-            if path.is_ident("prompt") {
-                parse_string_meta(&mut metadata.prompt, lit);
-            } else if path.is_ident("rename") {
-                parse_string_meta(&mut metadata.rename, lit);
-            } else {
-                abort!(path, "unknown attribute");
-            }
-        }
-        Meta::Path(path) => {
-            if path.is_ident("hidden") {
-                if metadata.hidden {
-                    emit_warning!(path, "duplicated attribute");
-                }
-                metadata.hidden = true;
-            } else if path.is_ident("default") {
-                if metadata.default {
-                    emit_warning!(path, "duplicated attribute");
-                }
-                metadata.default = true;
-            } else {
-                abort!(path, "unknown attribute");
-            }
-        }
-        _ => abort!(meta, "unknown attribute"),
+/// Extract the value of an explicit discriminant of a variant (e.g. `Var = 42`), only integer
+/// literals are considered.
+fn discriminant_value(expr: &syn::Expr) -> Option<String> {
+    match expr {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(value),
+            ..
+        }) => Some(value.base10_digits().to_string()),
+        syn::Expr::Unary(syn::ExprUnary {
+            op: syn::UnOp::Neg(_),
+            expr,
+            ..
+        }) => discriminant_value(expr).map(|value| format!("-{}", value)),
+        _ => None,
     }
 }
 
 /// Generate the `Ident` to use as the implementation of `BuildableValue` for an enum. The type of
 /// this element is `struct`.
 fn gen_builder_ident(ident: &Ident) -> Ident {
-    format_ident!("__{}_BuildableValueImpl", ident)
+    builder_ident(ident)
 }
 
 /// Generate the `Ident` to use as the inner state of the `BuildableValue` of the enum. The type
@@ -348,10 +354,12 @@ impl ToTokens for EnumGenerator {
         tokens.append_all(gen_variants_builder(self));
         // generate the structs for keeping the state of the fields of the variants
         for variant in &self.variants {
-            tokens.append_all(variant.gen_builder(gen_variants_builder_variant_ident(
-                &self.ident,
-                &variant.ident,
-            )));
+            tokens.append_all(variant.gen_builder(
+                gen_variants_builder_variant_ident(&self.ident, &variant.ident),
+                &self.metadata.vis,
+                &self.generics,
+                self.message_key(&variant.ident),
+            ));
         }
         tokens.append_all(gen_impl_new_buildable_value(self));
         tokens.append_all(gen_impl_buildable_value(self));
@@ -368,7 +376,8 @@ impl ToTokens for VariantsDefList<'_> {
                     let variant_builder =
                         gen_variants_builder_variant_ident(&self.gen.ident, ident);
                     let variant_builder = StructGenerator::gen_builder_ident(&variant_builder);
-                    tokens.append_all(quote! { (#variant_builder) });
+                    let (_, ty_generics, _) = self.gen.generics.split_for_impl();
+                    tokens.append_all(quote! { (#variant_builder #ty_generics) });
                 }
                 VariantKind::Empty => {}
             }
@@ -394,19 +403,32 @@ fn gen_struct_builder(gen: &EnumGenerator) -> TokenStream {
             default = quote! { Some(#init) };
         }
     }
+    let vis = &gen.metadata.vis;
+    let generics = &gen.generics;
+    let (impl_generics, ty_generics, where_clause) = gen.generics.split_for_impl();
     quote! {
         #[automatically_derived]
         #[allow(non_camel_case_types)]
         #[doc(hidden)]
-        #[derive(Debug)]
-        struct #builder_ident {
-            value: Option<#variants_builder_ident>,
+        #vis struct #builder_ident #generics #where_clause {
+            value: Option<#variants_builder_ident #ty_generics>,
             prompt: String,
         }
 
+        // not derived, since the parameters don't have to be `Debug`
+        #[automatically_derived]
+        impl #impl_generics std::fmt::Debug for #builder_ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_struct(stringify!(#builder_ident))
+                    .field("value", &self.value)
+                    .field("prompt", &self.prompt)
+                    .finish()
+            }
+        }
+
         #[automatically_derived]
-        impl #builder_ident {
-            fn new(config: ibuilder::BuildableValueConfig<()>) -> #builder_ident {
+        impl #impl_generics #builder_ident #ty_generics #where_clause {
+            #vis fn new(config: ibuilder::BuildableValueConfig<()>) -> Self {
                 #builder_ident {
                     value: #default,
                     prompt: config.prompt.unwrap_or_else(|| #prompt.to_string())
@@ -420,14 +442,39 @@ fn gen_struct_builder(gen: &EnumGenerator) -> TokenStream {
 fn gen_variants_builder(gen: &EnumGenerator) -> TokenStream {
     let variants_builder_ident = &gen.variants_builder_ident;
     let variants = gen.variants_def_list();
+    let vis = &gen.metadata.vis;
+    let generics = &gen.generics;
+    let (impl_generics, ty_generics, where_clause) = gen.generics.split_for_impl();
+    let debug_arms = gen.variants.iter().map(|var| {
+        let ident = &var.ident;
+        match &var.kind {
+            VariantKind::Empty => quote! {
+                #variants_builder_ident::#ident => f.write_str(stringify!(#ident))
+            },
+            _ => quote! {
+                #variants_builder_ident::#ident(inner) => {
+                    f.debug_tuple(stringify!(#ident)).field(inner).finish()
+                }
+            },
+        }
+    });
     quote! {
         #[automatically_derived]
         #[allow(non_camel_case_types)]
         #[doc(hidden)]
-        #[derive(Debug)]
-        enum #variants_builder_ident {
+        #vis enum #variants_builder_ident #generics #where_clause {
             #variants
         }
+
+        // not derived, since the parameters don't have to be `Debug`
+        #[automatically_derived]
+        impl #impl_generics std::fmt::Debug for #variants_builder_ident #ty_generics #where_clause {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match self {
+                    #(#debug_arms,)*
+                }
+            }
+        }
     }
 }
 
@@ -435,11 +482,13 @@ fn gen_variants_builder(gen: &EnumGenerator) -> TokenStream {
 fn gen_impl_new_buildable_value(gen: &EnumGenerator) -> TokenStream {
     let ident = &gen.ident;
     let builder_ident = &gen.builder_ident;
+    let (impl_generics, ty_generics, where_clause) = gen.generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
     quote! {
         #[automatically_derived]
-        impl ibuilder::NewBuildableValue for #ident {
+        impl #impl_generics ibuilder::NewBuildableValue for #ident #ty_generics #where_clause {
             fn new_buildable_value(config: ibuilder::BuildableValueConfig<()>) -> Box<dyn ibuilder::BuildableValue> {
-                Box::new(#builder_ident::new(config))
+                Box::new(#builder_ident #turbofish::new(config))
             }
         }
     }
@@ -0,0 +1,36 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+use syn::{parse_quote, GenericParam, Generics};
+
+use quote::quote;
+
+/// Add to the type parameters of the derived type the bounds required for building them, which
+/// are then forwarded to the generated builders. The lifetime parameters are not supported, since
+/// the built values must be `'static`.
+pub fn bounded_generics(generics: &Generics) -> Generics {
+    let mut generics = generics.clone();
+    for param in generics.params.iter_mut() {
+        match param {
+            GenericParam::Type(param) => {
+                param.bounds.push(parse_quote!(ibuilder::NewBuildableValue));
+                param.bounds.push(parse_quote!('static));
+            }
+            GenericParam::Lifetime(lifetime) => {
+                abort!(lifetime, "lifetime parameters are not supported")
+            }
+            GenericParam::Const(_) => {}
+        }
+    }
+    generics
+}
+
+/// The type of the marker field that makes the generated builders use all the type parameters,
+/// `None` if there are none.
+pub fn phantom_type(generics: &Generics) -> Option<TokenStream> {
+    let params: Vec<_> = generics.type_params().map(|p| &p.ident).collect();
+    if params.is_empty() {
+        None
+    } else {
+        Some(quote! { ::std::marker::PhantomData<fn() -> (#(#params,)*)> })
+    }
+}
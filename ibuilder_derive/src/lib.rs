@@ -34,10 +34,26 @@ use crate::enum_gen::EnumGenerator;
 use crate::struct_gen::StructGenerator;
 
 mod enum_gen;
+mod generics;
 mod struct_gen;
 
 /// Derive macro for `IBuilder`.
 ///
+/// The generated code always lives in the same crate of the type, so `#[non_exhaustive]` structs,
+/// enums and variants are supported as well.
+///
+/// Generic structs and enums are supported too: the builder has the same generic parameters, with
+/// the additional bound `NewBuildableValue + 'static` on the type parameters. The lifetime
+/// parameters are not supported.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Wrapper<T> {
+///     value: T,
+/// }
+/// ```
+///
 /// # Supported features
 /// This is a somewhat complete list of all the attributes it's possible to use deriving from
 /// `IBuilder`.
@@ -93,12 +109,43 @@ mod struct_gen;
 /// }
 /// ```
 ///
+/// ## `#[ibuilder(prompt_key = "...")]`
+/// Attach to the prompt of a struct, an enum, a field or a variant a key that the `Localizer` of
+/// `BuilderConfig` translates at `get_options()` time, enabling fully translated flows without
+/// recompiling. When the localizer is not set, or it doesn't know the key, the prompt is used as
+/// usual. Like the prompt, the key set on fields and variants overwrites the one of their type.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// #[ibuilder(prompt_key = "person-prompt")]
+/// struct Person {
+///     #[ibuilder(prompt = "How old are you?", prompt_key = "person-age-prompt")]
+///     age: u8,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(help = "...")]`
+/// Attach to a field or a variant a secondary explanation, separate from the prompt. It's in the
+/// `help` of the `Choice` for selecting it, and in the `help` of the `Options` of its menu, so that
+/// the frontends can show it under the menu entries.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Server {
+///     #[ibuilder(help = "The TCP port the server listens on, usually 80 or 443")]
+///     port: u16,
+/// }
+/// ```
+///
 /// ## `#[ibuilder(default = something)]`
 /// Set a default value for the field. After the equal sign a literal is expected, if it is a string
 /// literal the conversion is done using `FromStr` **at runtime**, otherwise the literal is
 /// converted using the `as` syntax.
 ///
-/// For now only the builtin types can be defaulted (numeric types, bool, char and String).
+/// For now only the builtin types can be defaulted (numeric types, bool, char and String), and the
+/// `Option`s of them: the default is the value inside the `Some`, and the user can still remove it.
 ///
 /// ```
 /// # use ibuilder_derive::IBuilder;
@@ -112,6 +159,8 @@ mod struct_gen;
 ///     field3: bool,
 ///     #[ibuilder(default = 'x')]
 ///     field4: char,
+///     #[ibuilder(default = "maybe")]
+///     field5: Option<String>,
 /// }
 /// #[derive(IBuilder)]
 /// enum Enum {
@@ -122,6 +171,23 @@ mod struct_gen;
 /// }
 /// ```
 ///
+/// ## `#[ibuilder(default_none)]`
+/// An `Option` field whose value has been set by the user but not completed is considered `None`,
+/// instead of preventing the builder from being finalized.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Struct {
+///     #[ibuilder(default_none)]
+///     field: Option<Inner>,
+/// }
+/// #[derive(IBuilder)]
+/// struct Inner {
+///     value: i32,
+/// }
+/// ```
+///
 /// ## `#[ibuilder(default)]`
 /// Set a variant of an enum as the default one for that enum. At most one variant can be set as
 /// default.
@@ -143,6 +209,12 @@ mod struct_gen;
 ///
 /// When hiding the fields of an enum, at least one of them must be visible.
 ///
+/// The markers without data (`PhantomData<T>`, `PhantomPinned` and `()`) are hidden automatically,
+/// also in the structs and variants with unnamed fields, which still need exactly one other field.
+///
+/// The types of the fields don't need to implement `Debug`: the `Debug` implementation of the
+/// builder prints the inner builders of the visible fields, and `"[hidden]"` for the hidden ones.
+///
 /// ```
 /// # use ibuilder_derive::IBuilder;
 /// #[derive(IBuilder)]
@@ -159,6 +231,570 @@ mod struct_gen;
 ///     Var2,
 /// }
 /// ```
+///
+/// ## `#[ibuilder(mode = "wizard")]`
+/// When applied to a struct with named fields, the fields are asked one after the other (like a
+/// questionnaire) instead of letting the user pick them from a menu. Setting a field moves to the
+/// next one, going back from a field moves to the previous one (going back from a complete nested
+/// struct or enum moves forward instead). After the last field the usual menu of the struct is
+/// shown. The default mode is `"menu"`.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// #[ibuilder(mode = "wizard")]
+/// struct Survey {
+///     name: String,
+///     age: u8,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(next_if(cond = "path::to::fn", goto = "field"))]`
+/// In wizard mode, after this field jump to the field `goto` if the function `cond` returns
+/// `true`, skipping the fields in between. The function has the signature
+/// `fn(&ibuilder::PartialState) -> bool` and receives the current state of the struct. The rules
+/// are checked in order and the flow can only jump forward. Going back follows the same path.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// #[ibuilder(mode = "wizard")]
+/// struct Survey {
+///     #[ibuilder(next_if(cond = "is_student", goto = "school"))]
+///     student: bool,
+///     company: Option<String>,
+///     school: Option<String>,
+/// }
+///
+/// fn is_student(state: &ibuilder::PartialState) -> bool {
+///     state.get("student").unwrap_or(false)
+/// }
+/// ```
+///
+/// ## `#[ibuilder(ask_first)]`
+/// When applied to a named field, the user is moved straight into that field when reaching the
+/// menu of the struct (or of the variant) and the field doesn't have a value yet. This is useful
+/// for the discriminating questions (e.g. an enum like "What kind of deployment?") that should be
+/// answered before the other fields. If more fields are marked, they are asked in order. Going
+/// back from the field shows the menu of the struct as usual.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Deployment {
+///     #[ibuilder(ask_first)]
+///     kind: Kind,
+///     replicas: u32,
+/// }
+/// #[derive(IBuilder)]
+/// enum Kind {
+///     Local,
+///     Cloud,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(value = 42)]`
+/// Associate a machine value (a string or an integer) to an empty variant of an enum. The value is
+/// provided in the `value` of the `Choice` and the variant can also be selected sending the value
+/// as `Input::Text`. If the attribute is missing, the explicit discriminant of the variant is used
+/// (only for integer literals). The values must be unique.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// enum Status {
+///     Ok = 200,
+///     NotFound = 404,
+///     #[ibuilder(value = "5xx")]
+///     ServerError,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(inline_variant_fields)]`
+/// When applied to an enum, the fields of the selected variant are shown in the menu of the enum,
+/// after the choices of the variants, instead of in a menu of their own. Selecting a variant with
+/// fields keeps the user in the menu, one navigation level less. The fields cannot have the name
+/// of a variant, the variants take the precedence.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// #[ibuilder(inline_variant_fields)]
+/// enum Storage {
+///     Memory,
+///     Disk { path: String, size: u64 },
+/// }
+/// ```
+///
+/// ## `#[ibuilder(validate = "path::to::fn")]`
+/// Check the value of a field after it has been parsed. The function is called with a reference
+/// to the new value and must return an `ibuilder::Validation`: if the value is `Invalid` it's
+/// rejected with an `InvalidText` error, if it's a `Warning` the value is accepted but the warning
+/// is shown in the `Options` and in the `Node` of the field. The function can also return a
+/// `Result<(), String>`, where the error rejects the value. The attribute can be repeated.
+///
+/// For now only the builtin types can be validated (numeric types, bool, char and String).
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Server {
+///     #[ibuilder(validate = "check_port")]
+///     port: u16,
+/// }
+///
+/// fn check_port(port: &u16) -> ibuilder::Validation {
+///     if *port < 1024 {
+///         ibuilder::Validation::Warning("port usually requires root".into())
+///     } else {
+///         ibuilder::Validation::Valid
+///     }
+/// }
+/// ```
+///
+/// ## `#[ibuilder(min = 0, max = 100)]`
+/// Restrict the value of a numeric field to a range, both the bounds are inclusive and can be used
+/// alone. A value outside of the range is rejected with an `InvalidText` error and the range is
+/// described in the `hint` of the `Options` of the field (e.g. "Between 0 and 100"). Since the
+/// negative numbers are not literals, they can be written as strings (e.g. `min = "-10"`).
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Volume {
+///     #[ibuilder(min = 0, max = 100)]
+///     level: u8,
+///     #[ibuilder(min = "-10.5")]
+///     gain: f32,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(pattern = "^[a-z0-9_-]{3,16}$")]`
+/// Reject the text of a `String` field that doesn't match the regular expression, with an
+/// `InvalidText` error. The message of the error can be set with
+/// `#[ibuilder(pattern_message = "...")]`, by default it mentions the pattern. Note that the
+/// pattern is not anchored unless it starts with `^` and ends with `$`.
+///
+/// Requires the `regex` feature of `ibuilder`. An invalid pattern is a compile error (see the
+/// `not_compile_regex/pattern.rs` test of `ibuilder`), and the pattern is compiled only once, when
+/// the first builder is made.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Account {
+///     #[ibuilder(
+///         pattern = "^[a-z0-9_-]{3,16}$",
+///         pattern_message = "3 to 16 lowercase letters, digits, _ or -"
+///     )]
+///     username: String,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(trim)]`, `#[ibuilder(lowercase)]` and `#[ibuilder(preprocess = "path::to::fn")]`
+/// Normalize the text inserted by the user before parsing it: `trim` removes the surrounding
+/// whitespace (often added by the chat clients), `lowercase` converts it to lowercase and
+/// `preprocess` calls a custom function with the signature `fn(&str) -> String`. They are applied
+/// in the order they are written, before the value is parsed and validated.
+///
+/// For now only the builtin types can be preprocessed (numeric types, char and String).
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Account {
+///     #[ibuilder(trim, lowercase)]
+///     email: String,
+///     #[ibuilder(preprocess = "strip_separators")]
+///     phone: u64,
+/// }
+///
+/// fn strip_separators(text: &str) -> String {
+///     text.chars().filter(|c| !matches!(c, ' ' | '-')).collect()
+/// }
+/// ```
+///
+/// ## `#[ibuilder(charset = "...")]`
+/// Reject with an `InvalidText` error the text containing characters outside of this set, after
+/// the preprocessors and before parsing it. The set is written like a character class of the
+/// regular expressions, without the brackets (see `ibuilder::charset::Charset`). Like the
+/// preprocessors, it's supported only on the builtin types.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Account {
+///     #[ibuilder(trim, lowercase, charset = "a-z0-9_-")]
+///     username: String,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(suggest_fn = "path::to::fn")]`
+/// Fill the `suggestions` of the `Options` of the text inputs of a named field, for the frontends
+/// that support autocompletion. The function has the signature
+/// `fn(&ibuilder::PartialState) -> Vec<String>` and receives the current state of the struct, so
+/// the suggestions can depend on the other fields. The user can still insert any text.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Address {
+///     country: String,
+///     #[ibuilder(suggest_fn = "suggest_city")]
+///     city: String,
+/// }
+///
+/// fn suggest_city(state: &ibuilder::PartialState) -> Vec<String> {
+///     match state.get::<String>("country").as_deref() {
+///         Some("Italy") => vec!["Rome".into(), "Milan".into()],
+///         _ => vec![],
+///     }
+/// }
+/// ```
+///
+/// ## `#[ibuilder(options_from = "path::to::fn")]`
+/// Let the user choose the value of a named `String` field from a list computed from the other
+/// fields (e.g. the buckets of the chosen region). The function has the signature
+/// `fn(&ibuilder::PartialState) -> Vec<String>` and each value becomes a choice of the menu of the
+/// field, replacing the text input. If the function returns no values the user can type the value
+/// instead.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Storage {
+///     region: String,
+///     #[ibuilder(options_from = "list_buckets")]
+///     bucket: String,
+/// }
+///
+/// fn list_buckets(state: &ibuilder::PartialState) -> Vec<String> {
+///     match state.get::<String>("region").as_deref() {
+///         Some("eu") => vec!["backups".into(), "logs".into()],
+///         _ => vec![],
+///     }
+/// }
+/// ```
+///
+/// ## `#[ibuilder(quick = "yes,no,maybe")]`
+/// Offer some common answers of a named `String` field as choices next to its text input, so the
+/// user of a chat can answer with a tap instead of typing. The answers are separated by commas,
+/// and the user can still type any other text.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Rsvp {
+///     #[ibuilder(quick = "yes,no,maybe")]
+///     attending: String,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(choices("dev", "staging", "prod"))]`
+/// Restrict a named `String` field to a fixed list of values, presented as choices instead of the
+/// text input, without defining an enum. With `#[ibuilder(allow_other)]` the text input is kept,
+/// so the user can also type a value that is not in the list.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Deploy {
+///     #[ibuilder(choices("dev", "staging", "prod"))]
+///     environment: String,
+///     #[ibuilder(choices("eu-west-1", "us-east-1"), allow_other)]
+///     region: String,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(secret)]`
+/// Mark a `String` (or `Option<String>`) field as secret, like a password: its menus have
+/// `Options::sensitive` set, so the frontends can hide the text while it's typed, and its value is
+/// masked in the `Node`s and in the `Debug` output of the builder.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Login {
+///     username: String,
+///     #[ibuilder(secret)]
+///     password: String,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(preview)]`
+/// Show the value parsed from the text of the user before saving it, useful for the error-prone
+/// formats like dates. After the text is parsed the user is asked to confirm the value ("Parsed as
+/// ...") or to insert it again, and the field keeps its previous value until it is confirmed. Only
+/// the text inserted in the menu of the field is previewed, not the one of its inner menus.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Meeting {
+///     #[ibuilder(preview, trim)]
+///     duration_minutes: u32,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(max_attempts = 3, fallback_default)]`
+/// Stop asking a field after too many invalid texts in a row, so the user (or a confused bot
+/// conversation) doesn't get stuck in a validation loop. When the limit is reached the field is
+/// reset to its default (see `#[ibuilder(default = something)]`), an `Option` field is left unset,
+/// and the user is moved out of the menu. The attempts are counted in the menu of the field and in
+/// the ones directly inside it, like the value of an `Option`.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Ticket {
+///     #[ibuilder(default = 1, max_attempts = 3, fallback_default)]
+///     quantity: u32,
+///     #[ibuilder(max_attempts = 2, fallback_default)]
+///     seat: Option<u32>,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(invalidates = "other_field")]`
+/// Clear another field of the struct when the value of this one changes, for the fields that
+/// depend on it. The cleared field goes back to its default (or to missing, asking again for its
+/// value), and so do the fields it invalidates. The attribute can be repeated for invalidating
+/// many fields.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Address {
+///     #[ibuilder(invalidates = "state")]
+///     country: String,
+///     #[ibuilder(invalidates = "city")]
+///     state: String,
+///     city: String,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(hostname)]`
+/// Check that the value of a `String` field is a valid hostname, using
+/// `ibuilder::validators::hostname`. With `#[ibuilder(hostname = "resolve")]` the hostname is also
+/// resolved and the user is warned if the resolution fails. Note that the resolution blocks the
+/// builder until it completes.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Server {
+///     #[ibuilder(hostname)]
+///     host: String,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(port)]`
+/// Mark a `u16` field as a port number: the prompt defaults to "Type the port number" and 0 is
+/// rejected. With `#[ibuilder(port(allow_zero))]` 0 is accepted, and with
+/// `#[ibuilder(port(warn_privileged))]` the user is warned about the ports below 1024.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Server {
+///     #[ibuilder(port(warn_privileged))]
+///     port: u16,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(encoding = "base64")]`
+/// Let the user paste the content of a `Vec<u8>` field as text, encoded in base64 or in hex
+/// (`#[ibuilder(encoding = "hex")]`). The text is decoded and validated, and the node of the field
+/// only shows the length of the data.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// struct Keys {
+///     #[ibuilder(encoding = "base64")]
+///     public_key: Vec<u8>,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(builder = "path::to::fn")]`
+/// Use a custom builder for the field instead of the one of its type, useful for the types that
+/// don't implement `NewBuildableValue` (like the enums of other crates, see
+/// `ibuilder::ibuilder_for_fieldless_enum!`). The function must be an `ibuilder::BuilderFn`, and
+/// the builder it returns must build a value of the type of the field.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// use ibuilder::{BuildableValue, BuildableValueConfig};
+/// use ibuilder::builders::FieldlessEnumBuilder;
+///
+/// #[derive(IBuilder)]
+/// struct Config {
+///     #[ibuilder(builder = "ordering_builder")]
+///     ordering: std::cmp::Ordering,
+/// }
+///
+/// fn ordering_builder(config: BuildableValueConfig<()>) -> Box<dyn BuildableValue> {
+///     use std::cmp::Ordering::*;
+///     let variants = vec![("Less", Less), ("Equal", Equal), ("Greater", Greater)];
+///     Box::new(FieldlessEnumBuilder::new(config, variants))
+/// }
+/// ```
+///
+/// ## `#[ibuilder(with = "MyBuilder")]`
+/// Use a custom implementation of `BuildableValue` for the field, made with
+/// `MyBuilder::new(config)`, instead of the one selected from its type. This is useful when the
+/// field has special input semantics, like a `String` that is really a file picker. `new` receives
+/// a `BuildableValueConfig` with the prompt of the field, of the type it expects (e.g.
+/// `BuildableValueConfig<()>`), and the builder must build a value of the type of the field. The
+/// type can have generic arguments (e.g. `with = "LeafBuilder<Version>"`).
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// use ibuilder::*;
+///
+/// #[derive(IBuilder)]
+/// struct Upload {
+///     #[ibuilder(with = "FilePicker")]
+///     file: String,
+/// }
+///
+/// #[derive(Debug)]
+/// struct FilePicker {
+///     file: Option<String>,
+/// }
+///
+/// impl FilePicker {
+///     fn new(_config: BuildableValueConfig<()>) -> FilePicker {
+///         FilePicker { file: None }
+///     }
+/// }
+///
+/// impl BuildableValue for FilePicker {
+///     // ...
+/// #     fn apply(&mut self, _: &Input, _: &[Id]) -> Result<(), ChooseError> { Ok(()) }
+/// #     fn get_options(&self, _: &[Id]) -> Options { Default::default() }
+/// #     fn get_subfields(&self, _: &[Id]) -> Vec<Id> { vec![] }
+/// #     fn to_node(&self) -> nodes::Node { nodes::Node::Leaf(nodes::Field::Missing) }
+/// #     fn get_value_any(&self) -> Option<Box<dyn std::any::Any>> { None }
+/// }
+/// ```
+///
+/// ## `#[ibuilder(action(id = "...", text = "...", handler = "path::to::fn"))]`
+/// Add a custom choice to the menu of a struct with named fields. When the user selects it the
+/// handler is called with the current partial state of the struct and the message it returns (if
+/// any) is shown to the user in the `notice` of the options, and the user can then go back to the
+/// menu of the struct.
+///
+/// The handler must have the signature `fn(&ibuilder::PartialState) -> Option<String>`.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// #[ibuilder(action(id = "ping", text = "Test connection", handler = "ping"))]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// fn ping(state: &ibuilder::PartialState) -> Option<String> {
+///     let host: String = state.get("host")?;
+///     Some(format!("{} is reachable", host))
+/// }
+/// ```
+///
+/// ## `#[ibuilder(constructor = "Type::new")]`
+/// When applied to a struct the final value is built calling the provided function instead of
+/// using the struct literal syntax. The function receives all the fields (including the hidden
+/// ones) in the order they are declared and must return the struct. This is useful for keeping the
+/// invariants enforced by the constructor of the type.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// #[ibuilder(constructor = "Range::new")]
+/// struct Range {
+///     start: i64,
+///     end: i64,
+/// }
+///
+/// impl Range {
+///     fn new(start: i64, end: i64) -> Range {
+///         Range {
+///             start: start.min(end),
+///             end: start.max(end),
+///         }
+///     }
+/// }
+/// ```
+///
+/// ## `#[ibuilder(vis = "pub(crate)")]`
+/// When applied to a struct or an enum it sets the visibility of the generated builder (the type
+/// named `__Name_BuildableValueImpl` and its `new` function), that by default is private to the
+/// module of the type. This allows the code in other modules (e.g. the companion crates of
+/// `ibuilder_derive`) to use the builder directly. The generated types are always hidden from the
+/// documentation, so they don't appear in the public surface of a library even when they are
+/// `pub`.
+///
+/// ```
+/// mod shapes {
+///     # use ibuilder_derive::IBuilder;
+///     #[derive(IBuilder)]
+///     #[ibuilder(vis = "pub(crate)")]
+///     pub enum Shape {
+///         Empty,
+///         Circle { radius: i32 },
+///     }
+/// }
+///
+/// let builder = shapes::__Shape_BuildableValueImpl::new(Default::default());
+/// ```
+///
+/// ## `#[ibuilder(message_key = "...")]`
+/// Every choice for selecting a field, a variant or an action has a `message_key` that frontends
+/// using a translation framework can use for looking up the localized label, instead of the name
+/// baked into the binary. The key is made of the name of the type and the identifier of the field
+/// (e.g. `person.full_name`), or of the variant (e.g. `shape.circle`), in snake case. The keys of
+/// the fields of a variant are prefixed by the key of the variant (e.g. `shape.circle.radius`).
+///
+/// When applied to a struct or an enum this attribute replaces the name of the type in the keys.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// #[derive(IBuilder)]
+/// #[ibuilder(message_key = "account.person")]
+/// struct Person {
+///     full_name: String,
+/// }
+/// ```
+///
+/// ## `#[ibuilder(draft)]`
+/// When applied to a struct with named fields it also generates a `NameDraft` struct with the
+/// visible fields wrapped in an `Option`, convertible from a `&Builder<Name>` (taking the fields
+/// set so far) and into `Name` with `TryFrom` (failing with `FinalizeError::MissingField`). The
+/// nested values are included only when they are complete. The draft is decoupled from the
+/// internal state of the builder, so it can be stored and restored, and with
+/// `#[ibuilder(draft(derive(Serialize, Deserialize)))]` the traits to derive on it can be chosen.
+///
+/// ```
+/// # use ibuilder_derive::IBuilder;
+/// use std::convert::TryFrom;
+/// use ibuilder::{Buildable, Input};
+///
+/// #[derive(IBuilder)]
+/// #[ibuilder(draft(derive(Debug, Clone)))]
+/// struct Person {
+///     name: String,
+///     age: u8,
+/// }
+///
+/// let mut builder = Person::builder();
+/// builder.choose(Input::choice("name")).unwrap();
+/// builder.choose(Input::text("Edoardo")).unwrap();
+/// let mut draft = PersonDraft::from(&builder);
+/// assert_eq!(draft.age, None);
+/// draft.age = Some(24);
+/// let person = Person::try_from(draft).unwrap();
+/// assert_eq!(person.name, "Edoardo");
+/// ```
 #[proc_macro_error]
 #[proc_macro_derive(IBuilder, attributes(ibuilder))]
 pub fn ibuilder_derive(input: TokenStream) -> TokenStream {
@@ -171,7 +807,7 @@ pub fn ibuilder_derive(input: TokenStream) -> TokenStream {
 /// - Some private structure and enums for keeping the state of the builder for `Name`, all those
 ///   types have the name starting with `__`.
 fn ibuilder_macro(ast: &syn::DeriveInput) -> TokenStream {
-    fix_double_error(&ast.ident);
+    fix_double_error(ast);
     match &ast.data {
         syn::Data::Struct(_) => StructGenerator::from_struct(ast).to_token_stream().into(),
         syn::Data::Enum(_) => EnumGenerator::from_enum(ast).to_token_stream().into(),
@@ -179,23 +815,13 @@ fn ibuilder_macro(ast: &syn::DeriveInput) -> TokenStream {
     }
 }
 
-/// Parse the string attribute into the `Option<String>`. In case of duplicate or not string a
-/// compile error is raised.
-fn parse_string_meta(out: &mut Option<String>, lit: syn::Lit) {
-    if out.is_none() {
-        match lit {
-            syn::Lit::Str(content) => *out = Some(content.value()),
-            _ => abort!(lit, "expecting a string"),
-        }
-    } else {
-        abort!(lit, "duplicated attribute");
-    }
-}
-
-fn fix_double_error(ident: &syn::Ident) {
+fn fix_double_error(ast: &syn::DeriveInput) {
+    let ident = &ast.ident;
+    let (impl_generics, ty_generics, where_clause) = ast.generics.split_for_impl();
     set_dummy(quote! {
-        impl ibuilder::Buildable<#ident> for #ident {
-            fn builder() -> ibuilder::Builder<#ident> { unimplemented!() }
+        impl #impl_generics ibuilder::Buildable<#ident #ty_generics> for #ident #ty_generics #where_clause {
+            fn builder() -> ibuilder::Builder<#ident #ty_generics> { unimplemented!() }
+            fn builder_from(self) -> ibuilder::Builder<#ident #ty_generics> { unimplemented!() }
         }
     });
 }
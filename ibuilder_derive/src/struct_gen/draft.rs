@@ -0,0 +1,77 @@
+use proc_macro2::TokenStream;
+use proc_macro_error::abort;
+
+use quote::{format_ident, quote};
+
+use crate::struct_gen::StructGenerator;
+
+/// Generate the draft struct of a struct with `#[ibuilder(draft)]`, with all the visible fields
+/// wrapped in an `Option`, together with the conversions from the `Builder` and into the struct.
+pub fn gen_draft(gen: &StructGenerator) -> TokenStream {
+    if !gen.is_named() {
+        abort!(
+            gen.span,
+            "draft is supported only on structs with named fields"
+        );
+    }
+    if !gen.generics.params.is_empty() {
+        abort!(gen.span, "draft is not supported on generic structs");
+    }
+    let ident = &gen.ident;
+    let vis = &gen.vis;
+    let draft_ident = format_ident!("{}Draft", ident);
+    let derives = gen.metadata.draft.as_deref().unwrap_or_default();
+    let visible: Vec<_> = gen.fields.iter().filter(|f| !f.metadata.hidden).collect();
+    let field_defs = visible.iter().map(|f| {
+        let field_vis = &f.field.vis;
+        let field_ident = &f.ident;
+        let ty = &f.ty;
+        quote! { #field_vis #field_ident: Option<#ty> }
+    });
+    let field_idents: Vec<_> = visible.iter().map(|f| &f.ident).collect();
+    let field_types = visible.iter().map(|f| &f.ty);
+    let field_values = gen.fields.iter().map(|f| {
+        let field_ident = &f.ident;
+        if f.metadata.hidden {
+            f.builder_new()
+        } else {
+            quote! { draft.#field_ident.ok_or(ibuilder::FinalizeError::MissingField)? }
+        }
+    });
+    let value = if let Some(constructor) = &gen.metadata.constructor {
+        quote! { #constructor(#(#field_values),*) }
+    } else {
+        let field_names = gen.fields.iter().map(|f| &f.ident);
+        quote! { #ident { #(#field_names: #field_values),* } }
+    };
+    let doc = format!(
+        "The work-in-progress data of a `{}`, with all the fields optional.",
+        ident
+    );
+    quote! {
+        #[doc = #doc]
+        #[derive(Default, #(#derives),*)]
+        #vis struct #draft_ident {
+            #(#field_defs,)*
+        }
+
+        #[automatically_derived]
+        impl From<&ibuilder::Builder<#ident>> for #draft_ident {
+            fn from(builder: &ibuilder::Builder<#ident>) -> #draft_ident {
+                let state = builder.partial_state();
+                #draft_ident {
+                    #(#field_idents: state.get::<#field_types>(stringify!(#field_idents)),)*
+                }
+            }
+        }
+
+        #[automatically_derived]
+        impl ::std::convert::TryFrom<#draft_ident> for #ident {
+            type Error = ibuilder::FinalizeError;
+
+            fn try_from(draft: #draft_ident) -> Result<#ident, ibuilder::FinalizeError> {
+                Ok(#value)
+            }
+        }
+    }
+}
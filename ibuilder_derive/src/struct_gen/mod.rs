@@ -1,13 +1,17 @@
+use ibuilder_derive_internals::__private::{
+    builder_ident, option_inner, snake_case, CustomBuilder, FieldMetadata, StructMetadata,
+};
 use proc_macro2::{Span, TokenStream};
-use proc_macro_error::{abort, emit_warning, ResultExt};
-use syn::punctuated::Punctuated;
-use syn::{Field, Fields, Ident, Meta, MetaNameValue, Token, Type};
+use proc_macro_error::abort;
+use syn::{Field, Fields, Ident, Type};
 
 use quote::{format_ident, quote, ToTokens, TokenStreamExt};
 
-use crate::parse_string_meta;
+use crate::generics::{bounded_generics, phantom_type};
+use crate::struct_gen::draft::gen_draft;
 use crate::struct_gen::struct_buildable_value_gen::gen_impl_buildable_value;
 
+mod draft;
 mod named_fields;
 mod struct_buildable_value_gen;
 mod unnamed_fields;
@@ -20,6 +24,8 @@ pub struct StructGenerator {
     ident: Ident,
     /// The `Ident` of the newly created struct.
     builder_ident: Ident,
+    /// The visibility of the original struct.
+    vis: syn::Visibility,
     /// The list of fields in the original struct.
     fields: Vec<StructField>,
     /// The span of this structure.
@@ -28,15 +34,9 @@ pub struct StructGenerator {
     named_fields: bool,
     /// The metadata associated with the struct.
     metadata: StructMetadata,
-}
-
-/// The metadata of the struct, it's taken from the attributes of the `struct`.
-#[derive(Debug)]
-pub struct StructMetadata {
-    /// The prompt to use for this struct's main menu.
-    prompt: Option<String>,
-    /// Different name to use in the tree structure.
-    rename: Option<String>,
+    /// The generic parameters of the struct, with the bounds required for building it. The
+    /// builder has the same parameters.
+    generics: syn::Generics,
 }
 
 /// The information about a field of a struct.
@@ -52,19 +52,6 @@ pub struct StructField {
     pub metadata: FieldMetadata,
 }
 
-/// The metadata of the field, it's taken from the attributes of the `Field`.
-#[derive(Debug)]
-pub struct FieldMetadata {
-    /// The default value for this field.
-    pub default: Option<TokenStream>,
-    /// The prompt to use for this field.
-    pub prompt: Option<String>,
-    /// Different name to use in the tree structure.
-    pub rename: Option<String>,
-    /// Whether this field is hidden.
-    pub hidden: bool,
-}
-
 /// Generator for the list of field definition of a struct. It will generate either:
 /// - `{ name: Type, name: Type, ... }`
 /// - `(Type, Type, ...)`
@@ -77,6 +64,8 @@ struct FieldDefList<'s> {
     fields: &'s [StructField],
     /// Whether the struct has named fields or not.
     named: bool,
+    /// The generic parameters of the builder.
+    generics: &'s syn::Generics,
 }
 
 /// Generator for the list of field creation of a struct. It will generate either:
@@ -100,7 +89,7 @@ struct ImplDebug<'s> {
 impl StructGenerator {
     /// Generate the `Ident` to use as the implementation of `BuildableValue` for a struct.
     pub fn gen_builder_ident(ident: &Ident) -> Ident {
-        format_ident!("__{}_BuildableValueImpl", ident)
+        builder_ident(ident)
     }
 
     /// Construct a new `StructGenerator` from the AST of a struct. Will fail if the AST is not
@@ -113,6 +102,7 @@ impl StructGenerator {
                 StructGenerator {
                     ident: ast.ident.clone(),
                     builder_ident: StructGenerator::gen_builder_ident(&ast.ident),
+                    vis: ast.vis.clone(),
                     fields: match &data.fields {
                         syn::Fields::Named(fields) => {
                             fields.named.iter().map(StructField::from).collect()
@@ -129,6 +119,13 @@ impl StructGenerator {
                                     }
                                 }
                             }
+                            if let Some(prompt_key) = &metadata.prompt_key {
+                                for field in fields.iter_mut() {
+                                    if field.metadata.prompt_key.is_none() {
+                                        field.metadata.prompt_key = Some(prompt_key.clone());
+                                    }
+                                }
+                            }
                             fields
                         }
                         syn::Fields::Unit => vec![],
@@ -136,6 +133,7 @@ impl StructGenerator {
                     span: ast.ident.span(),
                     named_fields,
                     metadata,
+                    generics: bounded_generics(&ast.generics),
                 }
             }
             _ => panic!("expecting a struct"),
@@ -153,21 +151,33 @@ impl StructGenerator {
         }
     }
 
+    /// The message key of the choice with this identifier in the menu of the struct (e.g.
+    /// `person.full_name`), prefixed by the name of the struct in snake case unless it's
+    /// overridden with `#[ibuilder(message_key = "...")]`.
+    fn message_key(&self, id: &str) -> String {
+        let prefix = match &self.metadata.message_key {
+            Some(prefix) => prefix.clone(),
+            None => snake_case(&self.ident.to_string()),
+        };
+        format!("{}.{}", prefix, id)
+    }
+
     /// Whether the fields of the original struct are named or unnamed (`struct Foo(i64)`).
     fn is_named(&self) -> bool {
         self.named_fields
     }
 
     /// Make a new `FieldDefList` relative to this struct.
-    fn fields_def_list(&self) -> FieldDefList {
+    fn fields_def_list(&self) -> FieldDefList<'_> {
         FieldDefList {
             fields: &self.fields,
             named: self.is_named(),
+            generics: &self.generics,
         }
     }
 
     /// Make a new `FieldNewList` relative to this struct.
-    fn fields_new_list(&self) -> FieldNewList {
+    fn fields_new_list(&self) -> FieldNewList<'_> {
         FieldNewList { gen: self }
     }
 
@@ -175,87 +185,163 @@ impl StructGenerator {
     ///
     /// This implements the `Debug` trait without requiring any field to be `Debug`. The basic field
     /// must be `Debug`, but the hidden ones don't have to.
-    fn impl_debug(&self) -> ImplDebug {
+    fn impl_debug(&self) -> ImplDebug<'_> {
         ImplDebug { gen: self }
     }
-}
 
-impl From<&syn::DeriveInput> for StructMetadata {
-    fn from(data: &syn::DeriveInput) -> StructMetadata {
-        let mut metadata = StructMetadata {
-            prompt: None,
-            rename: None,
-        };
-        for attr in &data.attrs {
-            if attr.path.is_ident("ibuilder") {
-                let meta = attr
-                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-                    .unwrap_or_abort();
-                for meta in meta {
-                    parse_struct_meta(meta, &mut metadata);
-                }
-            }
-        }
-        metadata
-    }
-}
-
-/// Extract the `StructMetadata` from a `Meta` entry in an attribute. `meta` comes from
-/// `#[ibuilder(HERE)]`.
-fn parse_struct_meta(meta: Meta, metadata: &mut StructMetadata) {
-    match meta {
-        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
-            if path.is_ident("prompt") {
-                parse_string_meta(&mut metadata.prompt, lit);
-            } else if path.is_ident("rename") {
-                parse_string_meta(&mut metadata.rename, lit);
-            } else {
-                abort!(path, "unknown attribute");
-            }
-        }
-        _ => abort!(meta, "unknown attribute"),
+    /// The type of the original struct, with its generic parameters.
+    fn self_type(&self) -> TokenStream {
+        let ident = &self.ident;
+        let (_, ty_generics, _) = self.generics.split_for_impl();
+        quote! { #ident #ty_generics }
     }
 }
 
 impl StructField {
-    /// The type of the builder for the type of this field. It's either one of the builtin types, a
-    /// generic boxed one, or the actual type if the field is hidden.
+    /// The type of the builder for the type of this field. It's a generic boxed builder, so that it
+    /// can be replaced at runtime (see `Builder::replace_field`), or the actual type if the field is
+    /// hidden.
     fn builder_type(&self) -> TokenStream {
         if self.metadata.hidden {
             let ty = &self.ty;
             quote! { #ty }
-        } else if let Some(builtin) = self.builtin_type() {
-            quote! { #builtin }
         } else {
             quote! { Box<dyn ibuilder::BuildableValue> }
         }
     }
 
     /// The initializer of the builder for the current field. It will forward the `FieldMetadata`
-    /// to the builder.
+    /// to the builder, wrapping it in a `PreviewBuilder` if the field is previewed, in a
+    /// `FallbackBuilder` if it has a maximum number of attempts and in a `SecretBuilder` if it's
+    /// secret.
     fn builder_new(&self) -> TokenStream {
+        let mut init = self.inner_builder_new();
+        if self.metadata.preview {
+            init = quote! {
+                Box::new(ibuilder::builders::PreviewBuilder::new(Box::new(|| #init)))
+            };
+        }
+        if let Some(max_attempts) = self.metadata.max_attempts {
+            init = quote! {
+                Box::new(ibuilder::builders::FallbackBuilder::new(Box::new(|| #init), #max_attempts))
+            };
+        }
+        if self.metadata.secret {
+            init = quote! { Box::new(ibuilder::builders::SecretBuilder::new(#init)) };
+        }
+        init
+    }
+
+    /// The initializer of the builder for the current field, without the preview.
+    fn inner_builder_new(&self) -> TokenStream {
         let prompt = match &self.metadata.prompt {
             Some(prompt) => quote!(Some(#prompt.to_string())),
             None => quote! {None},
         };
         if self.metadata.hidden {
             return if let Some(default) = &self.metadata.default {
-                quote! { #default }
+                match option_inner(&self.ty) {
+                    Some(_) => quote! { Some(#default) },
+                    None => quote! { #default },
+                }
             } else {
                 quote! { ::std::default::Default::default() }
             };
         }
+        match &self.metadata.builder {
+            Some(CustomBuilder::Fn(builder)) => {
+                return quote! {
+                    (#builder as ibuilder::BuilderFn)(ibuilder::BuildableValueConfig {
+                        default: None,
+                        prompt: #prompt,
+                        ..::std::default::Default::default()
+                    })
+                };
+            }
+            // the type of the config is the one expected by `new`
+            Some(CustomBuilder::Type(builder)) => {
+                return quote! {
+                    Box::new(<#builder>::new(ibuilder::BuildableValueConfig {
+                        default: None,
+                        prompt: #prompt,
+                        ..::std::default::Default::default()
+                    }))
+                };
+            }
+            None => {}
+        }
+        if let Some(inner_ty) = option_inner(&self.ty) {
+            if self.metadata.default.is_some() || self.metadata.default_none {
+                return self.option_builder_new(inner_ty, prompt);
+            }
+        }
         if let Some(builtin) = self.builtin_type() {
             let default = if let Some(default) = self.metadata.default.clone() {
                 quote! { Some(#default) }
             } else {
                 quote! { None }
             };
+            let ty = &self.ty;
+            let validators = self.metadata.validators.iter().map(|path| {
+                quote! {
+                    ::std::rc::Rc::new(|v: &#ty| -> ibuilder::Validation { #path(v).into() })
+                        as ibuilder::Validator<#ty>
+                }
+            });
+            let bound = |bound: &Option<TokenStream>| match bound {
+                Some(bound) => quote! { Some(#bound) },
+                None => quote! { None },
+            };
+            let (min, max) = (bound(&self.metadata.min), bound(&self.metadata.max));
+            let (range, hint) = if self.metadata.min.is_some() || self.metadata.max.is_some() {
+                (
+                    Some(quote! {
+                        ::std::rc::Rc::new(ibuilder::validators::range::<#ty>(#min, #max))
+                            as ibuilder::Validator<#ty>,
+                    }),
+                    quote! { ibuilder::validators::range_hint::<#ty>(#min, #max) },
+                )
+            } else {
+                (None, quote! { None })
+            };
+            let pattern = self.metadata.pattern.as_ref().map(|pattern| {
+                let message = match &self.metadata.pattern_message {
+                    Some(message) => quote! { Some(#message) },
+                    None => quote! { None },
+                };
+                // the pattern is a literal, compile it once for all the builders
+                quote! {
+                    {
+                        static REGEX: ::std::sync::OnceLock<ibuilder::validators::Regex> =
+                            ::std::sync::OnceLock::new();
+                        let regex = REGEX.get_or_init(|| {
+                            ibuilder::validators::Regex::new(#pattern)
+                                .unwrap_or_else(|e| panic!("Invalid pattern {:?}: {}", #pattern, e))
+                        });
+                        ::std::rc::Rc::new(ibuilder::validators::matches_regex(regex.clone(), #message))
+                            as ibuilder::Validator<#ty>
+                    },
+                }
+            });
+            let preprocessors = self.metadata.preprocessors.iter().map(|path| {
+                quote! {
+                    ::std::rc::Rc::new(|text: &str| -> String { #path(text) })
+                        as ibuilder::Preprocessor
+                }
+            });
+            let charset = match &self.metadata.charset {
+                Some(charset) => quote! { Some(ibuilder::charset::Charset::new(#charset)) },
+                None => quote! { None },
+            };
             quote! {
-                <#builtin>::new(ibuilder::BuildableValueConfig {
+                Box::new(<#builtin>::new(ibuilder::BuildableValueConfig {
                     default: #default,
                     prompt: #prompt,
-                })
+                    validators: vec![ #range #pattern #(#validators,)* ],
+                    preprocessors: vec![ #(#preprocessors,)* ],
+                    hint: #hint,
+                    charset: #charset,
+                }))
             }
         } else {
             let ty = &self.ty;
@@ -263,34 +349,115 @@ impl StructField {
                 <#ty as ibuilder::NewBuildableValue>::new_buildable_value(ibuilder::BuildableValueConfig {
                     default: None,
                     prompt: #prompt,
+                    ..::std::default::Default::default()
                 })
             }
         }
     }
 
+    /// The initializer of the builder of an optional field with `#[ibuilder(default = ...)]` or
+    /// `#[ibuilder(default_none)]`. The default is the value inside the `Some`, so the type inside
+    /// the `Option` must be a builtin type.
+    fn option_builder_new(&self, inner_ty: &Type, prompt: TokenStream) -> TokenStream {
+        let mut builder = quote! {
+            ibuilder::builders::OptionBuilder::<#inner_ty>::new(ibuilder::BuildableValueConfig {
+                default: None,
+                prompt: #prompt,
+                ..::std::default::Default::default()
+            })
+        };
+        if let Some(default) = &self.metadata.default {
+            let inner_builtin =
+                builtin_builder(inner_ty).expect("Option default on a non-plain type");
+            builder = quote! {
+                #builder.with_default(|| Box::new(<#inner_builtin>::new(ibuilder::BuildableValueConfig {
+                    default: Some(#default),
+                    ..::std::default::Default::default()
+                })))
+            };
+        }
+        if self.metadata.default_none {
+            builder = quote! { #builder.with_default_none() };
+        }
+        quote! { Box::new(#builder) }
+    }
+
+    /// The statements that change the options of the menu of this field: the field replaces the
+    /// prompt of its type, so it also replaces its key, and it adds its help. `None` if the menu of
+    /// the type is kept as is.
+    fn menu_overrides(&self) -> Option<TokenStream> {
+        let prompt_key = match (&self.metadata.prompt_key, &self.metadata.prompt) {
+            (Some(key), _) => Some(quote! { options.prompt_key = Some(#key.to_string()); }),
+            (None, Some(_)) => Some(quote! { options.prompt_key = None; }),
+            (None, None) => None,
+        };
+        let help = self
+            .metadata
+            .help
+            .as_ref()
+            .map(|help| quote! { options.help = Some(#help.to_string()); });
+        if prompt_key.is_none() && help.is_none() {
+            return None;
+        }
+        Some(quote! {
+            #prompt_key
+            #help
+        })
+    }
+
+    /// The `help` of the choice of this field.
+    fn help(&self) -> TokenStream {
+        match &self.metadata.help {
+            Some(help) => quote! { Some(#help.to_string()) },
+            None => quote! { None },
+        }
+    }
+
     /// Check if the type of the field is a builtin type, and in this case it will return the
-    /// corresponding builder. It returns `None` if it's not a builtin type.
+    /// corresponding builder. It returns `None` if it's not a builtin type. `Vec<u8>` fields with
+    /// an encoding are considered builtin, the fields with a custom builder are not.
     fn builtin_type(&self) -> Option<TokenStream> {
-        match &self.ty {
-            Type::Path(path) => {
-                let segments = &path.path.segments;
-                if segments.len() != 1 {
-                    return None;
-                }
-                let ty = segments[0].ident.to_string();
-                let ty = ty.as_str();
-                match ty {
-                    "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "isize"
-                    | "usize" | "f32" | "f64" | "String" | "char" | "bool" => {
-                        let builder =
-                            format_ident!("{}", ty[0..1].to_uppercase() + &ty[1..] + "Builder");
-                        Some(quote! { ibuilder::builders::#builder })
-                    }
-                    _ => None,
-                }
-            }
-            _ => None,
+        if self.metadata.builder.is_some() {
+            return None;
+        }
+        if let Some(encoding) = &self.metadata.encoding {
+            return Some(
+                quote! { ibuilder::builders::BinaryBuilder<ibuilder::builders::#encoding> },
+            );
         }
+        builtin_builder(&self.ty)
+    }
+
+    /// Whether the field has a default value that is supported: on the builtin types, or on the
+    /// `Option`s of builtin types.
+    fn supports_default(&self) -> bool {
+        if self.builtin_type().is_some() {
+            return true;
+        }
+        let plain = self.metadata.builder.is_none() && self.metadata.encoding.is_none();
+        plain && option_inner(&self.ty).and_then(builtin_builder).is_some()
+    }
+
+    /// Check if the field is of a builtin numeric type, the ones that support a range.
+    fn is_numeric(&self) -> bool {
+        if self.builtin_type().is_none() || self.metadata.encoding.is_some() {
+            return false;
+        }
+        let ty = &self.ty;
+        matches!(
+            quote!(#ty).to_string().as_str(),
+            "i8" | "i16"
+                | "i32"
+                | "i64"
+                | "u8"
+                | "u16"
+                | "u32"
+                | "u64"
+                | "isize"
+                | "usize"
+                | "f32"
+                | "f64"
+        )
     }
 
     /// Return the actual name of the field, which is the defined name or the renamed one. The
@@ -310,14 +477,22 @@ impl ToTokens for StructGenerator {
         tokens.append_all(gen_struct_builder(self));
         tokens.append_all(gen_impl_new_buildable_value(self));
         tokens.append_all(gen_impl_buildable_value(self));
+        if self.is_named() {
+            tokens.append_all(gen_impl_field_list(self));
+        }
+        if self.metadata.draft.is_some() {
+            tokens.append_all(gen_draft(self));
+        }
     }
 }
 
 impl<'s> ToTokens for FieldDefList<'s> {
     fn to_tokens(&self, tokens: &mut TokenStream) {
+        let generics = self.generics;
+        let where_clause = &self.generics.where_clause;
         // an empty struct is declared like `struct Foo;`
         if self.fields.is_empty() {
-            tokens.append_all(quote! { ; });
+            tokens.append_all(quote! { #generics #where_clause; });
             return;
         }
         let mut inner = TokenStream::new();
@@ -329,12 +504,24 @@ impl<'s> ToTokens for FieldDefList<'s> {
             let ty = field.builder_type();
             inner.append_all(quote! {#ty,})
         }
+        let phantom = phantom_type(self.generics);
         if self.named {
-            inner.append_all(quote! { __prompt: String, });
-            tokens.append_all(quote! { { #inner } });
+            inner.append_all(quote! {
+                __prompt: String,
+                __action_message: Option<String>,
+                __incomplete: usize,
+            });
+            if let Some(phantom) = phantom {
+                inner.append_all(quote! { __phantom: #phantom, });
+            }
+            tokens.append_all(quote! { #generics #where_clause { #inner } });
         } else {
+            // the marker is the last field, after the ones of the original struct
+            if let Some(phantom) = phantom {
+                inner.append_all(quote! { #phantom, });
+            }
             // unnamed struct has the prompt directly forwarded to the inner type
-            tokens.append_all(quote! { ( #inner ); });
+            tokens.append_all(quote! { #generics ( #inner ) #where_clause; });
         }
     }
 }
@@ -357,10 +544,19 @@ impl<'s> ToTokens for FieldNewList<'s> {
             let init = field.builder_new();
             inner.append_all(quote! {#init,})
         }
+        let phantom =
+            phantom_type(&self.gen.generics).map(|_| quote! { ::std::marker::PhantomData });
         if self.gen.is_named() {
-            inner.append_all(quote! { __prompt: #prompt, });
+            inner
+                .append_all(quote! { __prompt: #prompt, __action_message: None, __incomplete: 0, });
+            if let Some(phantom) = phantom {
+                inner.append_all(quote! { __phantom: #phantom, });
+            }
             tokens.append_all(quote! { { #inner } });
         } else {
+            if let Some(phantom) = phantom {
+                inner.append_all(quote! { #phantom, });
+            }
             tokens.append_all(quote! { ( #inner ) });
         }
     }
@@ -387,9 +583,10 @@ impl<'s> ToTokens for ImplDebug<'s> {
                 }
             }
         }
+        let (impl_generics, ty_generics, where_clause) = self.gen.generics.split_for_impl();
         tokens.append_all(quote! {
             #[automatically_derived]
-            impl std::fmt::Debug for #builder_ident {
+            impl #impl_generics std::fmt::Debug for #builder_ident #ty_generics #where_clause {
                 fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                     f.debug_struct(stringify!(#builder_ident))
                         #fields
@@ -406,75 +603,52 @@ impl From<&Field> for StructField {
             ident: field.ident.clone(),
             ty: field.ty.clone(),
             field: field.clone(),
-            metadata: get_field_metadata(field),
+            metadata: FieldMetadata::from(field),
         };
-        if res.metadata.default.is_some() && res.builtin_type().is_none() {
-            abort!(field, "default value is supported only on plain types");
+        if res.metadata.default.is_some() && !res.supports_default() {
+            abort!(
+                field,
+                "default value is supported only on plain types and their Options"
+            );
+        }
+        if !res.metadata.validators.is_empty() && res.builtin_type().is_none() {
+            abort!(field, "validation is supported only on plain types");
+        }
+        if (res.metadata.min.is_some() || res.metadata.max.is_some()) && !res.is_numeric() {
+            abort!(field, "min and max are supported only on numeric types");
+        }
+        if !res.metadata.preprocessors.is_empty() && res.builtin_type().is_none() {
+            abort!(field, "preprocessing is supported only on plain types");
+        }
+        if res.metadata.charset.is_some() && res.builtin_type().is_none() {
+            abort!(field, "charset is supported only on plain types");
         }
         res
     }
 }
 
-/// Extract the `FieldMetadata` from the attribute list of a field.
-fn get_field_metadata(field: &Field) -> FieldMetadata {
-    let mut metadata = FieldMetadata {
-        default: None,
-        prompt: None,
-        rename: None,
-        hidden: false,
-    };
-    for attr in &field.attrs {
-        if attr.path.is_ident("ibuilder") {
-            let meta = attr
-                .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
-                .unwrap_or_abort();
-            for meta in meta {
-                parse_field_meta(meta, &mut metadata, &field.ty);
+/// The builder of a builtin type (e.g. `ibuilder::builders::StringBuilder` for `String`), or
+/// `None` if the type is not builtin.
+fn builtin_builder(ty: &Type) -> Option<TokenStream> {
+    match ty {
+        Type::Path(path) => {
+            let segments = &path.path.segments;
+            if segments.len() != 1 {
+                return None;
             }
-        }
-    }
-    if metadata.hidden && field.ident.is_none() {
-        abort!(field, "unnamed fields cannot be hidden");
-    }
-    metadata
-}
-
-/// Extract the `FieldMetadata` from a `Meta` entry in a field attribute. `meta` comes from
-/// `#[ibuilder(HERE)]`.
-fn parse_field_meta(meta: Meta, metadata: &mut FieldMetadata, ty: &Type) {
-    match meta {
-        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
-            if path.is_ident("default") {
-                if metadata.default.is_none() {
-                    match lit {
-                        syn::Lit::Str(_) => {
-                            metadata.default =
-                                Some(quote! { <#ty as std::str::FromStr>::from_str(#lit).unwrap() })
-                        }
-                        _ => metadata.default = Some(quote! { #lit }),
-                    }
-                } else {
-                    abort!(path, "duplicated default");
+            let ty = segments[0].ident.to_string();
+            let ty = ty.as_str();
+            match ty {
+                "i8" | "i16" | "i32" | "i64" | "u8" | "u16" | "u32" | "u64" | "isize" | "usize"
+                | "f32" | "f64" | "String" | "char" | "bool" => {
+                    let builder =
+                        format_ident!("{}", ty[0..1].to_uppercase() + &ty[1..] + "Builder");
+                    Some(quote! { ibuilder::builders::#builder })
                 }
-            } else if path.is_ident("prompt") {
-                parse_string_meta(&mut metadata.prompt, lit);
-            } else if path.is_ident("rename") {
-                parse_string_meta(&mut metadata.rename, lit);
-            } else {
-                abort!(path, "unknown attribute");
+                _ => None,
             }
         }
-        Meta::Path(path) => {
-            if path.is_ident("hidden") {
-                if metadata.hidden {
-                    emit_warning!(path, "duplicated attribute");
-                }
-                metadata.hidden = true;
-            } else {
-                abort!(path, "unknown attribute");
-            }
-        }
-        _ => abort!(meta, "unknown attribute"),
+        _ => None,
     }
 }
 
@@ -485,19 +659,31 @@ fn gen_struct_builder(gen: &StructGenerator) -> TokenStream {
     let fields_gen = gen.fields_def_list();
     let fields_new = gen.fields_new_list();
     let impl_debug = gen.impl_debug();
+    // the named structs cache the number of incomplete fields, which is computed after building
+    let new = if gen.is_named() {
+        quote! {
+            let mut builder = #builder_ident #fields_new;
+            ibuilder::BuildableValue::refresh(&mut builder);
+            builder
+        }
+    } else {
+        quote! { #builder_ident #fields_new }
+    };
+    let vis = &gen.metadata.vis;
+    let (impl_generics, ty_generics, where_clause) = gen.generics.split_for_impl();
     quote! {
         #[automatically_derived]
         #[allow(non_camel_case_types)]
         #[doc(hidden)]
-        struct #builder_ident #fields_gen
+        #vis struct #builder_ident #fields_gen
 
         #impl_debug
 
         #[automatically_derived]
         #[allow(clippy::unnecessary_cast)]
-        impl #builder_ident {
-            fn new(config: ibuilder::BuildableValueConfig<()>) -> #builder_ident {
-                #builder_ident #fields_new
+        impl #impl_generics #builder_ident #ty_generics #where_clause {
+            #vis fn new(config: ibuilder::BuildableValueConfig<()>) -> Self {
+                #new
             }
         }
     }
@@ -505,14 +691,37 @@ fn gen_struct_builder(gen: &StructGenerator) -> TokenStream {
 
 /// Generate the implementation of `NewBuildableValue` for the struct.
 fn gen_impl_new_buildable_value(gen: &StructGenerator) -> TokenStream {
-    let ident = &gen.ident;
+    let self_type = gen.self_type();
     let builder_ident = &gen.builder_ident;
+    let (impl_generics, ty_generics, where_clause) = gen.generics.split_for_impl();
+    let turbofish = ty_generics.as_turbofish();
     quote! {
         #[automatically_derived]
-        impl ibuilder::NewBuildableValue for #ident {
+        impl #impl_generics ibuilder::NewBuildableValue for #self_type #where_clause {
             fn new_buildable_value(config: ibuilder::BuildableValueConfig<()>) -> Box<dyn ibuilder::BuildableValue> {
-                Box::new(#builder_ident::new(config))
+                Box::new(#builder_ident #turbofish::new(config))
             }
         }
     }
 }
+
+/// Generate the implementation of `FieldList` for a struct with named fields, listing the visible
+/// fields.
+fn gen_impl_field_list(gen: &StructGenerator) -> TokenStream {
+    let self_type = gen.self_type();
+    let (impl_generics, _, where_clause) = gen.generics.split_for_impl();
+    let names: Vec<_> = gen
+        .fields
+        .iter()
+        .filter(|f| !f.metadata.hidden)
+        .map(|f| f.ident.as_ref().unwrap().to_string())
+        .collect();
+    let count = names.len();
+    quote! {
+        #[automatically_derived]
+        impl #impl_generics ibuilder::FieldList for #self_type #where_clause {
+            const FIELD_COUNT: usize = #count;
+            const FIELD_NAMES: &'static [&'static str] = &[#(#names),*];
+        }
+    }
+}
@@ -2,9 +2,10 @@ use proc_macro2::TokenStream;
 use proc_macro_error::abort;
 use syn::Ident;
 
-use quote::{quote, TokenStreamExt};
+use quote::quote;
 
-use crate::struct_gen::StructGenerator;
+use crate::generics::phantom_type;
+use crate::struct_gen::{StructField, StructGenerator};
 
 /// The generator of the implementation of `BuildableValue` for a struct with named fields.
 pub struct StructWithNamedFields<'s> {
@@ -20,6 +21,43 @@ impl<'s> StructWithNamedFields<'s> {
         if gen.fields.is_empty() {
             abort!(gen.span, "the struct must have at least one field");
         }
+        let visible: Vec<_> = gen.fields.iter().filter(|f| !f.metadata.hidden).collect();
+        for (index, field) in visible.iter().enumerate() {
+            for rule in &field.metadata.next_if {
+                if !gen.metadata.wizard {
+                    abort!(rule.goto, "next_if is supported only in wizard mode");
+                }
+                let target = visible
+                    .iter()
+                    .position(|f| f.ident.as_ref().unwrap() == &rule.goto.value())
+                    .unwrap_or_else(|| abort!(rule.goto, "unknown field"));
+                if target <= index {
+                    abort!(rule.goto, "next_if can only jump to a following field");
+                }
+            }
+        }
+        for field in &visible {
+            for target in &field.metadata.invalidates {
+                if field.ident.as_ref().unwrap() == &target.value() {
+                    abort!(target, "a field cannot invalidate itself");
+                }
+                if !visible
+                    .iter()
+                    .any(|f| f.ident.as_ref().unwrap() == &target.value())
+                {
+                    abort!(target, "unknown field");
+                }
+            }
+        }
+        for action in &gen.metadata.actions {
+            if gen
+                .fields
+                .iter()
+                .any(|f| f.ident.as_ref().map(|i| i == &action.id).unwrap_or(false))
+            {
+                abort!(gen.span, "the action id `{}` is also a field", action.id);
+            }
+        }
         Self {
             fields: gen
                 .fields
@@ -36,27 +74,140 @@ impl<'s> StructWithNamedFields<'s> {
         let fn_apply = self.gen_fn_apply();
         let fn_get_options = self.gen_fn_get_options();
         let fn_get_subfields = self.gen_fn_get_subfields();
+        let fn_subfield = self.gen_fn_subfield();
         let fn_to_node = self.gen_fn_to_node();
         let fn_get_value_any = self.gen_fn_get_value_any();
+        let fn_get_child = self.gen_fn_get_child();
+        let fn_stay_after_apply = self.gen_fn_stay_after_apply();
+        let fn_auto_enter = self.gen_fn_auto_enter();
+        let fn_schema = self.gen_fn_schema();
         quote! {
             #fn_apply
             #fn_get_options
             #fn_get_subfields
+            #fn_subfield
             #fn_to_node
             #fn_get_value_any
+            #fn_get_child
+            #fn_stay_after_apply
+            #fn_auto_enter
+            #fn_schema
         }
     }
 
+    /// The fields cleared when the value of `field` changes: the ones it invalidates, and the ones
+    /// invalidated by them, recursively.
+    fn invalidated_fields(&self, field: &Ident) -> Vec<&StructField> {
+        let mut invalidated: Vec<&StructField> = vec![];
+        let mut queue = vec![self.field(&field.to_string())];
+        while let Some(current) = queue.pop() {
+            for target in &current.metadata.invalidates {
+                let target = self.field(&target.value());
+                if target.ident.as_ref() != Some(field)
+                    && !invalidated.iter().any(|f| f.ident == target.ident)
+                {
+                    invalidated.push(target);
+                    queue.push(target);
+                }
+            }
+        }
+        invalidated
+    }
+
+    /// The field of the struct with this name.
+    fn field(&self, name: &str) -> &StructField {
+        self.gen
+            .fields
+            .iter()
+            .find(|f| f.ident.as_ref().map(|i| i == name).unwrap_or(false))
+            .expect("unknown field")
+    }
+
     /// Generate the implementation of the `apply` method.
     fn gen_fn_apply(&self) -> TokenStream {
         let field_names = &self.fields;
+        let field_arms = self.fields.iter().map(|ident| {
+            // the values listed by options_from, the quick replies and the fixed choices are
+            // choices, but the field expects them as text
+            let metadata = &self.field(&ident.to_string()).metadata;
+            let quick = &metadata.quick;
+            let choices = &metadata.choices;
+            let is_value = if metadata.options_from.is_some() {
+                Some(quote! { self.#ident.get_options(rest).text_input })
+            } else if !quick.is_empty() {
+                Some(quote! { [#(#quick),*].contains(&value.as_str()) })
+            } else if !choices.is_empty() {
+                Some(quote! { [#(#choices),*].contains(&value.as_str()) })
+            } else {
+                None
+            };
+            let convert = is_value.map(|is_value| {
+                quote! {
+                    let text;
+                    let data = match data {
+                        ibuilder::Input::Choice(value) if rest.is_empty() && #is_value => {
+                            text = ibuilder::Input::Text(value.clone());
+                            &text
+                        }
+                        _ => data,
+                    };
+                }
+            });
+            let invalidated = self.invalidated_fields(ident);
+            if invalidated.is_empty() {
+                return quote! {
+                    stringify!(#ident) => {
+                        #convert
+                        let was_complete = self.#ident.is_complete();
+                        let res = self.#ident.apply(data, rest);
+                        self.__incomplete = self.__incomplete + was_complete as usize
+                            - self.#ident.is_complete() as usize;
+                        res?
+                    }
+                };
+            }
+            let targets = invalidated.iter().map(|f| f.ident.as_ref().unwrap());
+            let inits = invalidated.iter().map(|f| f.builder_new());
+            quote! {
+                stringify!(#ident) => {
+                    #convert
+                    let was_complete = self.#ident.is_complete();
+                    let before = self.#ident.to_node();
+                    let res = self.#ident.apply(data, rest);
+                    self.__incomplete = self.__incomplete + was_complete as usize
+                        - self.#ident.is_complete() as usize;
+                    res?;
+                    // the fields that depend on this one are not valid anymore
+                    if self.#ident.to_node() != before {
+                        #(
+                            let was_complete = self.#targets.is_complete();
+                            self.#targets = #inits;
+                            self.__incomplete = self.__incomplete + was_complete as usize
+                                - self.#targets.is_complete() as usize;
+                        )*
+                    }
+                }
+            }
+        });
+        let action_ids = self.action_ids();
+        let action_handlers: Vec<_> = self
+            .gen
+            .metadata
+            .actions
+            .iter()
+            .map(|a| &a.handler)
+            .collect();
         quote! {
-            fn apply(&mut self, data: ibuilder::Input, current_fields: &[String]) -> Result<(), ibuilder::ChooseError> {
+            fn apply(&mut self, data: &ibuilder::Input, current_fields: &[ibuilder::Id]) -> Result<(), ibuilder::ChooseError> {
                 if current_fields.is_empty() {
                     match data {
                         ibuilder::Input::Choice(data) => {
                             match data.as_str() {
                                 #(stringify!(#field_names) => {},)*
+                                #(#action_ids => {
+                                    let message = #action_handlers(&ibuilder::PartialState::new(self));
+                                    self.__action_message = message;
+                                },)*
                                 _ => return Err(ibuilder::ChooseError::UnexpectedChoice),
                             }
                         }
@@ -66,7 +217,12 @@ impl<'s> StructWithNamedFields<'s> {
                     let field = &current_fields[0];
                     let rest = &current_fields[1..];
                     match field.as_str() {
-                        #(stringify!(#field_names) => self.#field_names.apply(data, rest)?,)*
+                        #(#field_arms,)*
+                        // the result of an action has no options
+                        #(#action_ids => match data {
+                            ibuilder::Input::Choice(_) => Err(ibuilder::ChooseError::UnexpectedChoice)?,
+                            ibuilder::Input::Text(_) => Err(ibuilder::ChooseError::UnexpectedText)?,
+                        },)*
                         _ => Err(ibuilder::ChooseError::UnexpectedChoice)?,
                     }
                 }
@@ -77,7 +233,106 @@ impl<'s> StructWithNamedFields<'s> {
 
     /// Generate the implementation of the `get_options` method.
     fn gen_fn_get_options(&self) -> TokenStream {
-        let field_names = &self.fields;
+        let field_options = self
+            .gen
+            .fields
+            .iter()
+            .filter(|f| !f.metadata.hidden)
+            .map(|f| {
+                let ident = f.ident.as_ref().unwrap();
+                // the suggestions are added to all the text inputs inside the field
+                let suggest = f.metadata.suggest_fn.as_ref().map(|suggest_fn| {
+                    quote! {
+                        if options.text_input {
+                            options.suggestions = #suggest_fn(&ibuilder::PartialState::new(self));
+                        }
+                    }
+                });
+                // the text input is replaced by the listed values, unless there are none
+                let choices = f.metadata.options_from.as_ref().map(|options_from| {
+                    quote! {
+                        if options.text_input && rest.is_empty() {
+                            let values = #options_from(&ibuilder::PartialState::new(self));
+                            if !values.is_empty() {
+                                options.text_input = false;
+                                options.suggestions = vec![];
+                                options.choices = values
+                                    .into_iter()
+                                    .map(|value| ibuilder::Choice {
+                                        choice_id: ibuilder::Id::from(value.clone()),
+                                        text: value,
+                                        ..::std::default::Default::default()
+                                    })
+                                    .collect();
+                            }
+                        }
+                    }
+                });
+                // the quick replies are offered next to the text input
+                let quick = &f.metadata.quick;
+                let quick = (!quick.is_empty()).then(|| {
+                    quote! {
+                        if options.text_input && rest.is_empty() {
+                            options.choices.extend([#(#quick),*].iter().map(|answer| {
+                                ibuilder::Choice {
+                                    choice_id: ibuilder::Id::from_static(*answer),
+                                    text: answer.to_string(),
+                                    ..::std::default::Default::default()
+                                }
+                            }));
+                        }
+                    }
+                });
+                // the fixed choices replace the text input, unless other values are allowed
+                let fixed = &f.metadata.choices;
+                let allow_other = f.metadata.allow_other;
+                let fixed = (!fixed.is_empty()).then(|| {
+                    let choices_only = (!allow_other).then(|| {
+                        quote! {
+                            options.text_input = false;
+                            options.suggestions = vec![];
+                        }
+                    });
+                    quote! {
+                        if options.text_input && rest.is_empty() {
+                            #choices_only
+                            options.choices.extend([#(#fixed),*].iter().map(|value| {
+                                ibuilder::Choice {
+                                    choice_id: ibuilder::Id::from_static(*value),
+                                    text: value.to_string(),
+                                    ..::std::default::Default::default()
+                                }
+                            }));
+                        }
+                    }
+                });
+                let overrides = f.menu_overrides().map(|set| {
+                    quote! {
+                        if rest.is_empty() {
+                            #set
+                        }
+                    }
+                });
+                if suggest.is_none()
+                    && choices.is_none()
+                    && quick.is_none()
+                    && fixed.is_none()
+                    && overrides.is_none()
+                {
+                    return quote! { stringify!(#ident) => self.#ident.get_options(rest) };
+                }
+                quote! {
+                    stringify!(#ident) => {
+                        let mut options = self.#ident.get_options(rest);
+                        #suggest
+                        #choices
+                        #quick
+                        #fixed
+                        #overrides
+                        options
+                    }
+                }
+            });
         let choices = self
             .gen
             .fields
@@ -86,27 +341,61 @@ impl<'s> StructWithNamedFields<'s> {
             .map(|f| {
                 let ident = f.ident.as_ref().unwrap();
                 let name = f.actual_name();
+                let message_key = self.gen.message_key(&ident.to_string());
+                let help = f.help();
                 quote! {
                     ibuilder::Choice {
-                        choice_id: stringify!(#ident).to_string(),
+                        choice_id: ibuilder::Id::from_static(stringify!(#ident)),
                         text: "Edit ".to_string() + #name,
-                        needs_action: self.#ident.get_value_any().is_none(),
+                        needs_action: !self.#ident.is_complete(),
+                        message_key: Some(#message_key.to_string()),
+                        help: #help,
+                        ..::std::default::Default::default()
                     }
                 }
             });
+        let action_ids = self.action_ids();
+        let action_texts: Vec<_> = self.gen.metadata.actions.iter().map(|a| &a.text).collect();
+        let actions = self.gen.metadata.actions.iter().map(|a| {
+            let id = &a.id;
+            let text = &a.text;
+            let message_key = self.gen.message_key(id);
+            quote! {
+                ibuilder::Choice {
+                    choice_id: ibuilder::Id::from_static(#id),
+                    text: #text.to_string(),
+                    needs_action: false,
+                    message_key: Some(#message_key.to_string()),
+                    ..::std::default::Default::default()
+                }
+            }
+        });
+        let prompt_key = match &self.gen.metadata.prompt_key {
+            Some(key) => quote! { Some(#key.to_string()) },
+            None => quote! { None },
+        };
         quote! {
-            fn get_options(&self, current_fields: &[String]) -> ibuilder::Options {
+            fn get_options(&self, current_fields: &[ibuilder::Id]) -> ibuilder::Options {
                 if current_fields.is_empty() {
                     ibuilder::Options {
                         query: self.__prompt.clone(),
                         text_input: false,
-                        choices: vec![ #(#choices),* ],
+                        choices: vec![ #(#choices,)* #(#actions,)* ],
+                        prompt_key: #prompt_key,
+                        ..::std::default::Default::default()
                     }
                 } else {
                     let field = &current_fields[0];
                     let rest = &current_fields[1..];
                     match field.as_str() {
-                        #(stringify!(#field_names) => self.#field_names.get_options(rest),)*
+                        #(#field_options,)*
+                        #(#action_ids => ibuilder::Options {
+                            query: #action_texts.to_string(),
+                            text_input: false,
+                            choices: vec![],
+                            notice: self.__action_message.clone(),
+                            ..::std::default::Default::default()
+                        },)*
                         _ => unreachable!("Invalid current field: {} (the rest is {:?})", field, rest),
                     }
                 }
@@ -117,15 +406,45 @@ impl<'s> StructWithNamedFields<'s> {
     /// Generate the implementation of the `get_subfields` method.
     fn gen_fn_get_subfields(&self) -> TokenStream {
         let field_names = &self.fields;
+        let action_ids = self.action_ids();
         quote! {
-            fn get_subfields(&self, current_fields: &[String]) -> Vec<String> {
+            fn get_subfields(&self, current_fields: &[ibuilder::Id]) -> Vec<ibuilder::Id> {
                 if current_fields.is_empty() {
-                    vec![ #(stringify!(#field_names).to_string(),)* ]
+                    vec![
+                        #(ibuilder::Id::from_static(stringify!(#field_names)),)*
+                        #(ibuilder::Id::from_static(#action_ids),)*
+                    ]
                 } else {
                     let field = &current_fields[0];
                     let rest = &current_fields[1..];
                     match field.as_str() {
                         #(stringify!(#field_names) => self.#field_names.get_subfields(rest),)*
+                        #(#action_ids => vec![],)*
+                        _ => unreachable!("Invalid current field: {} (the rest is {:?})", field, rest),
+                    }
+                }
+            }
+        }
+    }
+
+    /// Generate the implementation of the `subfield` method, which compares the name with the
+    /// ones of the fields without listing them.
+    fn gen_fn_subfield(&self) -> TokenStream {
+        let field_names = &self.fields;
+        let action_ids = self.action_ids();
+        quote! {
+            fn subfield(&self, current_fields: &[ibuilder::Id], name: &str) -> Option<ibuilder::Id> {
+                if current_fields.is_empty() {
+                    [#(stringify!(#field_names),)* #(#action_ids,)*]
+                        .iter()
+                        .find(|field| **field == name)
+                        .map(|field| ibuilder::Id::from_static(*field))
+                } else {
+                    let field = &current_fields[0];
+                    let rest = &current_fields[1..];
+                    match field.as_str() {
+                        #(stringify!(#field_names) => self.#field_names.subfield(rest, name),)*
+                        #(#action_ids => None,)*
                         _ => unreachable!("Invalid current field: {} (the rest is {:?})", field, rest),
                     }
                 }
@@ -133,7 +452,7 @@ impl<'s> StructWithNamedFields<'s> {
         }
     }
 
-    /// Generate the implementation of the `to_node` method.
+    /// Generate the implementation of the `to_node`, `to_node_pruned` and `visit_nodes` methods.
     fn gen_fn_to_node(&self) -> TokenStream {
         let ident = &self.gen.ident;
         let fields: Vec<_> = self
@@ -145,7 +464,22 @@ impl<'s> StructWithNamedFields<'s> {
                 let ident = f.ident.as_ref().unwrap();
                 let name = f.actual_name();
                 quote! {
-                    ibuilder::nodes::FieldKind::Named(#name.into(), self.#ident.to_node())
+                    ibuilder::nodes::FieldKind::Named(#name.into(), self.#ident.to_node_pruned(depth - 1))
+                }
+            })
+            .collect();
+        let visits: Vec<_> = self
+            .gen
+            .fields
+            .iter()
+            .filter(|f| !f.metadata.hidden)
+            .map(|f| {
+                let ident = f.ident.as_ref().unwrap();
+                let name = f.actual_name();
+                quote! {
+                    if visitor.enter_field(Some(#name)) {
+                        self.#ident.visit_nodes(visitor);
+                    }
                 }
             })
             .collect();
@@ -156,31 +490,345 @@ impl<'s> StructWithNamedFields<'s> {
         };
         quote! {
             fn to_node(&self) -> ibuilder::nodes::Node {
+                self.to_node_pruned(usize::MAX)
+            }
+
+            fn to_node_pruned(&self, depth: usize) -> ibuilder::nodes::Node {
+                if depth == 0 {
+                    return ibuilder::nodes::Node::Leaf(ibuilder::nodes::Field::Elided);
+                }
                 ibuilder::nodes::Node::Composite(
                     #name.into(),
                     vec![ #(#fields,)* ]
                 )
             }
+
+            fn visit_nodes(&self, visitor: &mut dyn ibuilder::nodes::NodeVisitor) {
+                if !visitor.enter_composite(#name) {
+                    return;
+                }
+                #(#visits)*
+                visitor.leave_composite();
+            }
         }
     }
 
-    /// Generate the implementation of the `get_value_any` method.
-    fn gen_fn_get_value_any(&self) -> TokenStream {
+    /// Generate the implementation of the `schema` method: an object with a property for each
+    /// visible field, titled with its name if renamed and described by its help.
+    fn gen_fn_schema(&self) -> TokenStream {
         let ident = &self.gen.ident;
-        let mut field_list = TokenStream::new();
-        for field in self.gen.fields.iter() {
+        let properties: Vec<_> = self
+            .gen
+            .fields
+            .iter()
+            .filter(|f| !f.metadata.hidden)
+            .map(|f| {
+                let ident = f.ident.as_ref().unwrap();
+                let title = match &f.metadata.rename {
+                    Some(rename) => quote! { Some(#rename.to_string()) },
+                    None => quote! { None },
+                };
+                let help = f.help();
+                quote! {
+                    ibuilder::schema::Property {
+                        name: stringify!(#ident).to_string(),
+                        schema: ibuilder::schema::Schema {
+                            title: #title,
+                            description: #help,
+                            ..self.#ident.schema()
+                        },
+                        required: !self.#ident.is_complete(),
+                    }
+                }
+            })
+            .collect();
+        let name = if let Some(name) = &self.gen.metadata.rename {
+            quote! { #name }
+        } else {
+            quote! { stringify!(#ident) }
+        };
+        quote! {
+            fn schema(&self) -> ibuilder::schema::Schema {
+                ibuilder::schema::Schema {
+                    title: Some(#name.to_string()),
+                    ..ibuilder::schema::Schema::new(ibuilder::schema::SchemaKind::Object(vec![
+                        #(#properties,)*
+                    ]))
+                }
+            }
+        }
+    }
+
+    /// Generate the implementation of the `get_child` and `get_child_mut` methods.
+    fn gen_fn_get_child(&self) -> TokenStream {
+        let field_names = &self.fields;
+        quote! {
+            fn get_child(&self, name: &str) -> Option<&dyn ibuilder::BuildableValue> {
+                match name {
+                    #(stringify!(#field_names) => Some(&*self.#field_names),)*
+                    _ => None,
+                }
+            }
+
+            fn get_child_mut(&mut self, name: &str) -> Option<&mut Box<dyn ibuilder::BuildableValue>> {
+                match name {
+                    #(stringify!(#field_names) => Some(&mut self.#field_names),)*
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Generate the implementation of the `stay_after_apply` and `recorded_input` methods.
+    fn gen_fn_stay_after_apply(&self) -> TokenStream {
+        let field_names = &self.fields;
+        quote! {
+            fn stay_after_apply(&self, current_fields: &[ibuilder::Id]) -> bool {
+                if current_fields.is_empty() {
+                    false
+                } else {
+                    let field = &current_fields[0];
+                    let rest = &current_fields[1..];
+                    match field.as_str() {
+                        #(stringify!(#field_names) => self.#field_names.stay_after_apply(rest),)*
+                        _ => false,
+                    }
+                }
+            }
+
+            fn recorded_input(&self, data: &ibuilder::Input, current_fields: &[ibuilder::Id]) -> Option<ibuilder::Input> {
+                let field = current_fields.first()?;
+                let rest = &current_fields[1..];
+                match field.as_str() {
+                    #(stringify!(#field_names) => self.#field_names.recorded_input(data, rest),)*
+                    _ => None,
+                }
+            }
+        }
+    }
+
+    /// Generate the implementation of the `auto_enter` method.
+    ///
+    /// In the menu of the struct the first `ask_first` field without a value is entered. In wizard
+    /// mode the fields are instead entered one after the other.
+    fn gen_fn_auto_enter(&self) -> TokenStream {
+        let field_names = &self.fields;
+        let menu = if self.gen.metadata.wizard {
+            let visible: Vec<_> = self
+                .gen
+                .fields
+                .iter()
+                .filter(|f| !f.metadata.hidden)
+                .collect();
+            let rules = visible
+                .iter()
+                .enumerate()
+                .filter(|(_, f)| !f.metadata.next_if.is_empty());
+            let mut steps = vec![];
+            let mut conds = vec![];
+            let mut targets = vec![];
+            for (index, field) in rules {
+                steps.push(index);
+                conds.push(
+                    field
+                        .metadata
+                        .next_if
+                        .iter()
+                        .map(|r| &r.cond)
+                        .collect::<Vec<_>>(),
+                );
+                targets.push(
+                    field
+                        .metadata
+                        .next_if
+                        .iter()
+                        .map(|r| {
+                            visible
+                                .iter()
+                                .position(|f| f.ident.as_ref().unwrap() == &r.goto.value())
+                                .unwrap()
+                        })
+                        .collect::<Vec<_>>(),
+                );
+            }
+            quote! {
+                const STEPS: &[&str] = &[#(stringify!(#field_names),)*];
+                let position = |name: &str| STEPS.iter().position(|s| *s == name);
+                let state = ibuilder::PartialState::new(self);
+                let next_step = |index: usize| -> usize {
+                    match index {
+                        #(#steps => { #(if #conds(&state) { return #targets; })* })*
+                        _ => {}
+                    }
+                    index + 1
+                };
+                let next = match entry {
+                    ibuilder::MenuEntry::Entered => {
+                        if self.is_complete() {
+                            return None;
+                        }
+                        0
+                    }
+                    // a correction made from the menu of a complete struct goes back to it
+                    ibuilder::MenuEntry::Returned(field) => {
+                        if self.is_complete() {
+                            return None;
+                        }
+                        next_step(position(field)?)
+                    }
+                    ibuilder::MenuEntry::WentBack(field) => {
+                        let index = position(field)?;
+                        let is_complete_section = self.get_child(field).map_or(false, |f| {
+                            f.is_complete()
+                                && matches!(f.to_node(), ibuilder::nodes::Node::Composite(..))
+                        });
+                        // leaving a complete section moves forward, leaving a question moves
+                        // to the previous one in the flow
+                        if is_complete_section {
+                            next_step(index)
+                        } else {
+                            let mut current = 0;
+                            let mut previous = None;
+                            while current < index {
+                                previous = Some(current);
+                                current = next_step(current);
+                            }
+                            previous?
+                        }
+                    }
+                };
+                STEPS.get(next).map(|s| ibuilder::Id::from_static(*s))
+            }
+        } else {
+            let ask_first = self
+                .gen
+                .fields
+                .iter()
+                .filter(|f| f.metadata.ask_first)
+                .map(|f| f.ident.as_ref().unwrap());
+            quote! {
+                if let ibuilder::MenuEntry::WentBack(_) = entry {
+                    return None;
+                }
+                #(
+                    if !self.#ask_first.is_complete() {
+                        return Some(ibuilder::Id::from_static(stringify!(#ask_first)));
+                    }
+                )*
+                None
+            }
+        };
+        quote! {
+            fn auto_enter(&self, current_fields: &[ibuilder::Id], entry: ibuilder::MenuEntry) -> Option<ibuilder::Id> {
+                if current_fields.is_empty() {
+                    #menu
+                } else {
+                    let field = &current_fields[0];
+                    let rest = &current_fields[1..];
+                    match field.as_str() {
+                        #(stringify!(#field_names) => self.#field_names.auto_enter(rest, entry),)*
+                        _ => None,
+                    }
+                }
+            }
+        }
+    }
+
+    /// The list of the identifiers of the custom actions of the struct.
+    fn action_ids(&self) -> Vec<&String> {
+        self.gen.metadata.actions.iter().map(|a| &a.id).collect()
+    }
+
+    /// Generate the implementation of `TypedBuildableValue::get_value`.
+    pub fn gen_fn_get_value(&self) -> TokenStream {
+        let ident = &self.gen.ident;
+        let field_values = self.gen.fields.iter().map(|field| {
             let field_name = field.ident.as_ref().unwrap();
-            field_list.append_all(if field.metadata.hidden {
-                quote! { #field_name: self.#field_name.clone(), }
+            if field.metadata.hidden {
+                quote! { self.#field_name.clone() }
             } else {
-                quote! { #field_name: *self.#field_name.get_value_any()?.downcast().unwrap(), }
-            });
+                quote! { *self.#field_name.get_value_any()?.downcast().unwrap() }
+            }
+        });
+        let value = if let Some(constructor) = &self.gen.metadata.constructor {
+            quote! { #constructor(#(#field_values),*) }
+        } else {
+            let field_names = self.gen.fields.iter().map(|f| f.ident.as_ref().unwrap());
+            quote! { #ident { #(#field_names: #field_values),* } }
+        };
+        let self_type = self.gen.self_type();
+        quote! {
+            fn get_value(&self) -> Option<#self_type> {
+                Some(#value)
+            }
+        }
+    }
+
+    /// Generate the implementation of the `get_value_any`, `set_value_any`, `is_complete` and
+    /// `refresh` methods.
+    fn gen_fn_get_value_any(&self) -> TokenStream {
+        let visible: Vec<_> = self
+            .gen
+            .fields
+            .iter()
+            .filter(|f| !f.metadata.hidden)
+            .map(|f| f.ident.as_ref().unwrap())
+            .collect();
+        let hidden = self
+            .gen
+            .fields
+            .iter()
+            .filter(|f| f.metadata.hidden)
+            .map(|f| f.ident.as_ref().unwrap());
+        let self_type = self.gen.self_type();
+        quote! {
+            ibuilder::typed_get_value_any!(#self_type);
+
+            fn set_value_any(&mut self, value: Box<dyn std::any::Any>) -> bool {
+                let value: #self_type = match value.downcast() {
+                    Ok(value) => *value,
+                    Err(_) => return false,
+                };
+                let mut res = true;
+                #(res &= self.#visible.set_value_any(Box::new(value.#visible));)*
+                #(self.#hidden = value.#hidden;)*
+                self.refresh();
+                res
+            }
+
+            fn is_complete(&self) -> bool {
+                self.__incomplete == 0
+            }
+
+            fn refresh(&mut self) {
+                let complete: &[bool] = &[#(self.#visible.is_complete()),*];
+                self.__incomplete = complete.iter().filter(|c| !**c).count();
+            }
         }
+    }
+
+    /// Generate the `try_clone` method of the builder, used by `clone_boxed`. The visible fields
+    /// are copied with their builders, the hidden ones with `Clone`.
+    pub fn gen_fn_try_clone(&self) -> TokenStream {
+        let visible = &self.fields;
+        let hidden = self
+            .gen
+            .fields
+            .iter()
+            .filter(|f| f.metadata.hidden)
+            .map(|f| f.ident.as_ref().unwrap());
+        let phantom = phantom_type(&self.gen.generics)
+            .map(|_| quote! { __phantom: ::std::marker::PhantomData, });
         quote! {
-            fn get_value_any(&self) -> Option<Box<dyn std::any::Any>> {
-                Some(Box::new(#ident {
-                    #field_list
-                }))
+            fn try_clone(&self) -> Option<Self> {
+                Some(Self {
+                    #(#visible: self.#visible.clone_boxed()?,)*
+                    #(#hidden: self.#hidden.clone(),)*
+                    __prompt: self.__prompt.clone(),
+                    __action_message: self.__action_message.clone(),
+                    __incomplete: self.__incomplete,
+                    #phantom
+                })
             }
         }
     }
@@ -6,19 +6,47 @@ use crate::struct_gen::named_fields::StructWithNamedFields;
 use crate::struct_gen::unnamed_fields::StructWithUnnamedFields;
 use crate::struct_gen::StructGenerator;
 
-/// Generate the implementation of `BuildableValue` for the builder struct.
+/// Generate the implementation of `BuildableValue` and `TypedBuildableValue` for the builder
+/// struct, and its `try_clone` method.
 pub fn gen_impl_buildable_value(gen: &StructGenerator) -> TokenStream {
     let builder_ident = &gen.builder_ident;
-    let content = if gen.is_named() {
-        StructWithNamedFields::new(gen).gen()
+    let (impl_generics, ty_generics, where_clause) = gen.generics.split_for_impl();
+    let self_type = gen.self_type();
+    let (content, fn_get_value, fn_try_clone) = if gen.is_named() {
+        let named = StructWithNamedFields::new(gen);
+        (
+            named.gen(),
+            named.gen_fn_get_value(),
+            named.gen_fn_try_clone(),
+        )
     } else {
-        StructWithUnnamedFields::new(gen).gen()
+        let unnamed = StructWithUnnamedFields::new(gen);
+        (
+            unnamed.gen(),
+            unnamed.gen_fn_get_value(),
+            unnamed.gen_fn_try_clone(),
+        )
     };
     quote! {
         #[automatically_derived]
         #[allow(unreachable_code)]
-        impl ibuilder::BuildableValue for #builder_ident {
+        impl #impl_generics ibuilder::BuildableValue for #builder_ident #ty_generics #where_clause {
             #content
+
+            fn clone_boxed(&self) -> Option<Box<dyn ibuilder::BuildableValue>> {
+                Some(Box::new(self.try_clone()?))
+            }
+        }
+
+        #[automatically_derived]
+        impl #impl_generics ibuilder::TypedBuildableValue<#self_type> for #builder_ident #ty_generics #where_clause {
+            #fn_get_value
+        }
+
+        #[automatically_derived]
+        impl #impl_generics #builder_ident #ty_generics #where_clause {
+            /// A copy of the builder with all its state, `None` if a field cannot be copied.
+            #fn_try_clone
         }
     }
 }
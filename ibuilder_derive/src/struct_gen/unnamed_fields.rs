@@ -3,24 +3,49 @@ use proc_macro_error::abort;
 
 use quote::quote;
 
+use crate::generics::phantom_type;
 use crate::struct_gen::StructGenerator;
 
 /// The generator of the implementation of `BuildableValue` for a struct with named fields.
 pub struct StructWithUnnamedFields<'s> {
     /// The base struct generator.
     gen: &'s StructGenerator,
+    /// The index of the only visible field, the others are markers without data.
+    index: syn::Index,
 }
 
 impl<'s> StructWithUnnamedFields<'s> {
     /// Make a new `StructWithNamedFields` from a `StructGenerator`.
     pub fn new(gen: &'s StructGenerator) -> Self {
-        if gen.fields.len() != 1 {
+        let visible: Vec<_> = gen
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| !f.metadata.hidden)
+            .map(|(i, _)| i)
+            .collect();
+        if visible.len() != 1 {
             abort!(
                 gen.span,
                 "structs with unnamed fields must have only one field"
             );
         }
-        Self { gen }
+        if gen.metadata.wizard {
+            abort!(
+                gen.span,
+                "wizard mode is supported only on structs with named fields"
+            );
+        }
+        if !gen.metadata.actions.is_empty() {
+            abort!(
+                gen.span,
+                "actions are supported only on structs with named fields"
+            );
+        }
+        Self {
+            gen,
+            index: syn::Index::from(visible[0]),
+        }
     }
 
     /// Generate the implementation of the trait methods.
@@ -30,61 +55,196 @@ impl<'s> StructWithUnnamedFields<'s> {
         let fn_get_subfields = self.gen_fn_get_subfields();
         let fn_to_node = self.gen_fn_to_node();
         let fn_get_value_any = self.gen_fn_get_value_any();
+        let fn_schema = self.gen_fn_schema();
         quote! {
             #fn_apply
             #fn_get_options
             #fn_get_subfields
             #fn_to_node
             #fn_get_value_any
+            #fn_schema
         }
     }
 
     /// Generate the implementation of the `apply` method.
     fn gen_fn_apply(&self) -> TokenStream {
+        let index = &self.index;
         quote! {
-            fn apply(&mut self, data: ibuilder::Input, current_fields: &[String]) -> Result<(), ibuilder::ChooseError> {
-                self.0.apply(data, current_fields)
+            fn apply(&mut self, data: &ibuilder::Input, current_fields: &[ibuilder::Id]) -> Result<(), ibuilder::ChooseError> {
+                self.#index.apply(data, current_fields)
             }
         }
     }
 
     /// Generate the implementation of the `get_options` method.
     fn gen_fn_get_options(&self) -> TokenStream {
-        quote! {
-            fn get_options(&self, current_fields: &[String]) -> ibuilder::Options {
-                self.0.get_options(current_fields)
-            }
+        let index = &self.index;
+        let field = &self.gen.fields[index.index as usize];
+        match field.menu_overrides() {
+            Some(set) => quote! {
+                fn get_options(&self, current_fields: &[ibuilder::Id]) -> ibuilder::Options {
+                    let mut options = self.#index.get_options(current_fields);
+                    if current_fields.is_empty() {
+                        #set
+                    }
+                    options
+                }
+            },
+            None => quote! {
+                fn get_options(&self, current_fields: &[ibuilder::Id]) -> ibuilder::Options {
+                    self.#index.get_options(current_fields)
+                }
+            },
         }
     }
 
     /// Generate the implementation of the `get_subfields` method.
     fn gen_fn_get_subfields(&self) -> TokenStream {
+        let index = &self.index;
         quote! {
-            fn get_subfields(&self, current_fields: &[String]) -> Vec<String> {
-                self.0.get_subfields(current_fields)
+            fn get_subfields(&self, current_fields: &[ibuilder::Id]) -> Vec<ibuilder::Id> {
+                self.#index.get_subfields(current_fields)
             }
+
+            fn subfield(&self, current_fields: &[ibuilder::Id], name: &str) -> Option<ibuilder::Id> {
+                self.#index.subfield(current_fields, name)
+            }
+
+            fn stay_after_apply(&self, current_fields: &[ibuilder::Id]) -> bool {
+                self.#index.stay_after_apply(current_fields)
+            }
+
+            fn recorded_input(&self, data: &ibuilder::Input, current_fields: &[ibuilder::Id]) -> Option<ibuilder::Input> {
+                self.#index.recorded_input(data, current_fields)
+            }
+
+            fn auto_enter(&self, current_fields: &[ibuilder::Id], entry: ibuilder::MenuEntry) -> Option<ibuilder::Id> {
+                self.#index.auto_enter(current_fields, entry)
+            }
+        }
+    }
+
+    /// Generate the implementation of the `schema` method, the one of the field.
+    fn gen_fn_schema(&self) -> TokenStream {
+        let index = &self.index;
+        let field = &self.gen.fields[index.index as usize];
+        match &field.metadata.help {
+            Some(help) => quote! {
+                fn schema(&self) -> ibuilder::schema::Schema {
+                    ibuilder::schema::Schema {
+                        description: Some(#help.to_string()),
+                        ..self.#index.schema()
+                    }
+                }
+            },
+            None => quote! {
+                fn schema(&self) -> ibuilder::schema::Schema {
+                    self.#index.schema()
+                }
+            },
         }
     }
 
-    /// Generate the implementation of the `to_node` method.
+    /// Generate the implementation of the `to_node`, `to_node_pruned` and `visit_nodes` methods.
     fn gen_fn_to_node(&self) -> TokenStream {
         let name = self.gen.actual_name();
+        let index = &self.index;
         quote! {
             fn to_node(&self) -> ibuilder::nodes::Node {
+                self.to_node_pruned(usize::MAX)
+            }
+
+            fn to_node_pruned(&self, depth: usize) -> ibuilder::nodes::Node {
+                if depth == 0 {
+                    return ibuilder::nodes::Node::Leaf(ibuilder::nodes::Field::Elided);
+                }
                 ibuilder::nodes::Node::Composite(
                     #name.to_string(),
-                    vec![ibuilder::nodes::FieldKind::Unnamed(self.0.to_node())],
+                    vec![ibuilder::nodes::FieldKind::Unnamed(self.#index.to_node_pruned(depth - 1))],
                 )
             }
+
+            fn visit_nodes(&self, visitor: &mut dyn ibuilder::nodes::NodeVisitor) {
+                if !visitor.enter_composite(#name) {
+                    return;
+                }
+                if visitor.enter_field(None) {
+                    self.#index.visit_nodes(visitor);
+                }
+                visitor.leave_composite();
+            }
         }
     }
 
-    /// Generate the implementation of the `get_value_any` method.
-    fn gen_fn_get_value_any(&self) -> TokenStream {
+    /// Generate the implementation of `TypedBuildableValue::get_value`.
+    pub fn gen_fn_get_value(&self) -> TokenStream {
         let ident = &self.gen.ident;
+        let constructor = match &self.gen.metadata.constructor {
+            Some(constructor) => quote! { #constructor },
+            None => quote! { #ident },
+        };
+        let self_type = self.gen.self_type();
+        let field_values = self.gen.fields.iter().enumerate().map(|(i, field)| {
+            let i = syn::Index::from(i);
+            if field.metadata.hidden {
+                quote! { self.#i.clone() }
+            } else {
+                quote! { *self.#i.get_value_any()?.downcast().unwrap() }
+            }
+        });
+        quote! {
+            fn get_value(&self) -> Option<#self_type> {
+                Some(#constructor(#(#field_values),*))
+            }
+        }
+    }
+
+    /// Generate the implementation of the `get_value_any`, `set_value_any` and `is_complete`
+    /// methods.
+    fn gen_fn_get_value_any(&self) -> TokenStream {
+        let index = &self.index;
+        let self_type = self.gen.self_type();
+        let hidden = self
+            .gen
+            .fields
+            .iter()
+            .enumerate()
+            .filter(|(_, f)| f.metadata.hidden)
+            .map(|(i, _)| syn::Index::from(i));
+        quote! {
+            ibuilder::typed_get_value_any!(#self_type);
+
+            fn set_value_any(&mut self, value: Box<dyn std::any::Any>) -> bool {
+                let value: #self_type = match value.downcast() {
+                    Ok(value) => *value,
+                    Err(_) => return false,
+                };
+                #(self.#hidden = value.#hidden;)*
+                self.#index.set_value_any(Box::new(value.#index))
+            }
+
+            fn is_complete(&self) -> bool {
+                self.#index.is_complete()
+            }
+        }
+    }
+
+    /// Generate the `try_clone` method of the builder, used by `clone_boxed`. The visible field
+    /// is copied with its builder, the hidden ones with `Clone`.
+    pub fn gen_fn_try_clone(&self) -> TokenStream {
+        let fields = self.gen.fields.iter().enumerate().map(|(i, field)| {
+            let i = syn::Index::from(i);
+            if field.metadata.hidden {
+                quote! { self.#i.clone() }
+            } else {
+                quote! { self.#i.clone_boxed()? }
+            }
+        });
+        let phantom =
+            phantom_type(&self.gen.generics).map(|_| quote! { ::std::marker::PhantomData, });
         quote! {
-            fn get_value_any(&self) -> Option<Box<dyn std::any::Any>> {
-                Some(Box::new(#ident(*self.0.get_value_any()?.downcast().unwrap())))
+            fn try_clone(&self) -> Option<Self> {
+                Some(Self(#(#fields,)* #phantom))
             }
         }
     }
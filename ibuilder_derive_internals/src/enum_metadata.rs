@@ -0,0 +1,173 @@
+//! The metadata of the enums and of their variants.
+
+use proc_macro_error::{abort, emit_warning, ResultExt};
+use syn::punctuated::Punctuated;
+use syn::{Meta, MetaNameValue, Token, Variant};
+
+use crate::helpers::{parse_string_meta, parse_vis_meta};
+
+/// The metadata of the enum, it's taken from the attributes of the `enum`.
+#[derive(Debug)]
+pub struct EnumMetadata {
+    /// The prompt to use for this enum's main menu.
+    pub prompt: Option<String>,
+    /// The key of the prompt in the translations, from `#[ibuilder(prompt_key = "...")]`.
+    pub prompt_key: Option<String>,
+    /// The visibility of the generated builder, from `#[ibuilder(vis = "...")]`.
+    pub vis: Option<syn::Visibility>,
+    /// The prefix of the message keys of the variants of this enum, from
+    /// `#[ibuilder(message_key = "...")]`. By default it's the name of the enum in snake case.
+    pub message_key: Option<String>,
+    /// Whether the fields of the selected variant are shown in the menu of the enum, from
+    /// `#[ibuilder(inline_variant_fields)]`.
+    pub inline_variant_fields: bool,
+}
+
+/// The metadata of the variant, it's taken from the attributes of the `Variant`.
+#[derive(Debug)]
+pub struct VariantMetadata {
+    /// The prompt to use for this variant.
+    pub prompt: Option<String>,
+    /// The key of the prompt in the translations, from `#[ibuilder(prompt_key = "...")]`.
+    pub prompt_key: Option<String>,
+    /// Different name to use in the tree structure.
+    pub rename: Option<String>,
+    /// The secondary explanation of this variant, from `#[ibuilder(help = "...")]`.
+    pub help: Option<String>,
+    /// Whether this variant is hidden.
+    pub hidden: bool,
+    /// Whether this is the default variant.
+    pub default: bool,
+    /// The machine value associated with the variant, from `#[ibuilder(value = ...)]` or from the
+    /// explicit discriminant of the variant.
+    pub value: Option<String>,
+}
+
+impl From<&syn::DeriveInput> for EnumMetadata {
+    fn from(data: &syn::DeriveInput) -> EnumMetadata {
+        let mut metadata = EnumMetadata {
+            prompt: None,
+            prompt_key: None,
+            vis: None,
+            message_key: None,
+            inline_variant_fields: false,
+        };
+        for attr in &data.attrs {
+            if attr.path.is_ident("ibuilder") {
+                let meta = attr
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .unwrap_or_abort();
+                for meta in meta {
+                    parse_enum_meta(meta, &mut metadata);
+                }
+            }
+        }
+        metadata
+    }
+}
+
+/// Extract the `EnumMetadata` from a `Meta` entry in an attribute. `meta` comes from
+/// `#[ibuilder(HERE)]`.
+fn parse_enum_meta(meta: Meta, metadata: &mut EnumMetadata) {
+    match meta {
+        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
+            if path.is_ident("prompt") {
+                parse_string_meta(&mut metadata.prompt, lit);
+            } else if path.is_ident("prompt_key") {
+                parse_string_meta(&mut metadata.prompt_key, lit);
+            } else if path.is_ident("rename") {
+                abort!(
+                    path,
+                    "renaming an enum is not supported since the name is not exposed"
+                );
+            } else if path.is_ident("vis") {
+                parse_vis_meta(&mut metadata.vis, lit);
+            } else if path.is_ident("message_key") {
+                parse_string_meta(&mut metadata.message_key, lit);
+            } else {
+                abort!(path, "unknown attribute");
+            }
+        }
+        Meta::Path(path) => {
+            if path.is_ident("inline_variant_fields") {
+                if metadata.inline_variant_fields {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.inline_variant_fields = true;
+            } else {
+                abort!(path, "unknown attribute");
+            }
+        }
+        _ => abort!(meta, "unknown attribute"),
+    }
+}
+
+impl From<&Variant> for VariantMetadata {
+    fn from(var: &Variant) -> Self {
+        let mut metadata = VariantMetadata {
+            prompt: None,
+            prompt_key: None,
+            rename: None,
+            help: None,
+            hidden: false,
+            default: false,
+            value: None,
+        };
+        for attr in &var.attrs {
+            if attr.path.is_ident("ibuilder") {
+                let meta = attr
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .unwrap_or_abort();
+                for meta in meta {
+                    parse_variant_meta(meta, &mut metadata);
+                }
+            }
+        }
+        metadata
+    }
+}
+
+/// Extract the `VariantMetadata` from a `Meta` entry in a variant attribute. `meta` comes from
+/// `#[ibuilder(HERE)]`.
+fn parse_variant_meta(meta: Meta, metadata: &mut VariantMetadata) {
+    match meta {
+        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
+            if path.is_ident("prompt") {
+                parse_string_meta(&mut metadata.prompt, lit);
+            } else if path.is_ident("prompt_key") {
+                parse_string_meta(&mut metadata.prompt_key, lit);
+            } else if path.is_ident("rename") {
+                parse_string_meta(&mut metadata.rename, lit);
+            } else if path.is_ident("help") {
+                parse_string_meta(&mut metadata.help, lit);
+            } else if path.is_ident("value") {
+                if metadata.value.is_some() {
+                    abort!(lit, "duplicated attribute");
+                }
+                metadata.value = Some(match &lit {
+                    syn::Lit::Str(value) => value.value(),
+                    syn::Lit::Int(value) => value.base10_digits().to_string(),
+                    _ => abort!(lit, "expecting a string or an integer"),
+                });
+            } else {
+                abort!(path, "unknown attribute");
+            }
+        }
+        Meta::Path(path) => {
+            if path.is_ident("hidden") {
+                if metadata.hidden {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.hidden = true;
+            } else if path.is_ident("default") {
+                if metadata.default {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.default = true;
+            } else {
+                abort!(path, "unknown attribute");
+            }
+        }
+        _ => abort!(meta, "unknown attribute"),
+    }
+}
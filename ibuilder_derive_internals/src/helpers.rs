@@ -0,0 +1,90 @@
+//! The helpers for parsing the attributes and generating the names of the derived types.
+
+use proc_macro_error::abort;
+use quote::format_ident;
+use syn::{GenericArgument, Ident, PathArguments, Type};
+
+/// Generate the `Ident` of the type that implements `BuildableValue` for a derived struct or enum,
+/// for referring to it from the code generated by the companion crates.
+pub fn builder_ident(ident: &Ident) -> Ident {
+    format_ident!("__{}_BuildableValueImpl", ident)
+}
+
+/// Convert the identifier of a type (e.g. `HttpServer`) to snake case (e.g. `http_server`), for the
+/// default message keys of the choices.
+pub fn snake_case(ident: &str) -> String {
+    let chars: Vec<char> = ident.chars().collect();
+    let mut res = String::with_capacity(ident.len());
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            if prev.is_lowercase() || prev.is_numeric() || (prev.is_uppercase() && next_lower) {
+                res.push('_');
+            }
+        }
+        res.extend(c.to_lowercase());
+    }
+    res
+}
+
+/// Parse the string attribute into the `Option<String>`. In case of duplicate or not string a
+/// compile error is raised.
+pub fn parse_string_meta(out: &mut Option<String>, lit: syn::Lit) {
+    if out.is_none() {
+        match lit {
+            syn::Lit::Str(content) => *out = Some(content.value()),
+            _ => abort!(lit, "expecting a string"),
+        }
+    } else {
+        abort!(lit, "duplicated attribute");
+    }
+}
+
+/// Parse the string attribute containing a path (e.g. to a function) into a `syn::Path`. In case
+/// of invalid path a compile error is raised.
+pub fn parse_path_meta(lit: syn::Lit) -> syn::Path {
+    match &lit {
+        syn::Lit::Str(content) => content
+            .parse()
+            .unwrap_or_else(|_| abort!(lit, "expecting a path")),
+        _ => abort!(lit, "expecting a string"),
+    }
+}
+
+/// Parse the string attribute containing a visibility (e.g. `pub(crate)`) into the
+/// `Option<syn::Visibility>`. In case of duplicate or invalid visibility a compile error is raised.
+pub fn parse_vis_meta(out: &mut Option<syn::Visibility>, lit: syn::Lit) {
+    if out.is_some() {
+        abort!(lit, "duplicated attribute");
+    }
+    match &lit {
+        syn::Lit::Str(content) => {
+            *out = Some(
+                content
+                    .parse()
+                    .unwrap_or_else(|_| abort!(lit, "expecting a visibility")),
+            )
+        }
+        _ => abort!(lit, "expecting a string"),
+    }
+}
+
+/// The type `T` of an `Option<T>`, or `None` if the type is not an `Option`.
+pub fn option_inner(ty: &Type) -> Option<&Type> {
+    let path = match ty {
+        Type::Path(path) if path.qself.is_none() => &path.path,
+        _ => return None,
+    };
+    let segment = path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => match &args.args[0] {
+            GenericArgument::Type(inner) => Some(inner),
+            _ => None,
+        },
+        _ => None,
+    }
+}
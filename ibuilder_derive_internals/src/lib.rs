@@ -0,0 +1,31 @@
+//! [![crates.io](https://img.shields.io/crates/v/ibuilder_derive_internals.svg)](https://crates.io/crates/ibuilder_derive_internals)
+//! [![Docs](https://docs.rs/ibuilder_derive_internals/badge.svg)](https://docs.rs/ibuilder_derive_internals)
+//!
+//! The parsing of the `#[ibuilder(...)]` attributes used by
+//! [`ibuilder_derive`](https://crates.io/crates/ibuilder_derive).
+//!
+//! A proc-macro crate cannot export anything but its macros, so the parsing lives in this crate to
+//! be shared with the companion crates that extend the code generated by `#[derive(IBuilder)]`
+//! (e.g. adding their own validation attributes). This way they read the same metadata of the
+//! derive, with the same errors.
+//!
+//! Everything is inside the `__private` module: it's not covered by semver and may change with any
+//! version of `ibuilder_derive`, so depend on the exact version (`=x.y.z`) of this crate. The
+//! parsing reports the errors with `proc_macro_error`, so it must be called from a macro marked
+//! with `#[proc_macro_error]`.
+
+mod enum_metadata;
+mod helpers;
+mod struct_metadata;
+
+/// The parsing of the attributes used by `ibuilder_derive`, not covered by semver.
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::enum_metadata::{EnumMetadata, VariantMetadata};
+    pub use crate::helpers::{
+        builder_ident, option_inner, parse_path_meta, parse_string_meta, parse_vis_meta, snake_case,
+    };
+    pub use crate::struct_metadata::{
+        CustomBuilder, FieldMetadata, NextIf, StructAction, StructMetadata,
+    };
+}
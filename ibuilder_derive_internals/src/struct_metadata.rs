@@ -0,0 +1,808 @@
+//! The metadata of the structs and of their fields.
+
+use proc_macro2::TokenStream;
+use proc_macro_error::{abort, emit_warning, ResultExt};
+use quote::quote;
+use syn::punctuated::Punctuated;
+use syn::{Field, Ident, Meta, MetaList, MetaNameValue, NestedMeta, Token, Type};
+
+use crate::helpers::{option_inner, parse_path_meta, parse_string_meta, parse_vis_meta};
+
+/// The metadata of the struct, it's taken from the attributes of the `struct`.
+#[derive(Debug)]
+pub struct StructMetadata {
+    /// The prompt to use for this struct's main menu.
+    pub prompt: Option<String>,
+    /// The key of the prompt in the translations, from `#[ibuilder(prompt_key = "...")]`.
+    pub prompt_key: Option<String>,
+    /// Different name to use in the tree structure.
+    pub rename: Option<String>,
+    /// The custom actions to add to the menu of this struct.
+    pub actions: Vec<StructAction>,
+    /// The path to the function to use for building the struct instead of the struct literal.
+    pub constructor: Option<syn::Path>,
+    /// Whether the fields are asked one after the other instead of showing a menu.
+    pub wizard: bool,
+    /// The traits to derive on the draft struct, if it has to be generated.
+    pub draft: Option<Vec<syn::Path>>,
+    /// The visibility of the generated builder, from `#[ibuilder(vis = "...")]`.
+    pub vis: Option<syn::Visibility>,
+    /// The prefix of the message keys of the choices of this struct, from
+    /// `#[ibuilder(message_key = "...")]`. By default it's the name of the struct in snake case.
+    pub message_key: Option<String>,
+}
+
+/// A custom action shown in the menu of a struct, taken from `#[ibuilder(action(...))]`.
+#[derive(Debug)]
+pub struct StructAction {
+    /// The identifier of the choice of this action.
+    pub id: String,
+    /// The text to show to the user.
+    pub text: String,
+    /// The path to the function to call when the action is selected.
+    pub handler: syn::Path,
+}
+
+/// The metadata of the field, it's taken from the attributes of the `Field`.
+#[derive(Debug)]
+pub struct FieldMetadata {
+    /// The default value for this field. For the `Option<T>` fields it's the default `T`.
+    pub default: Option<TokenStream>,
+    /// Whether an optional field is `None` when the user has set its value without completing it,
+    /// from `#[ibuilder(default_none)]`.
+    pub default_none: bool,
+    /// The prompt to use for this field.
+    pub prompt: Option<String>,
+    /// The key of the prompt in the translations, from `#[ibuilder(prompt_key = "...")]`.
+    pub prompt_key: Option<String>,
+    /// Different name to use in the tree structure.
+    pub rename: Option<String>,
+    /// The secondary explanation of this field, from `#[ibuilder(help = "...")]`.
+    pub help: Option<String>,
+    /// Whether this field is hidden.
+    pub hidden: bool,
+    /// The paths to the functions that validate the value of this field.
+    pub validators: Vec<syn::Path>,
+    /// The inclusive lower bound of the value of a numeric field, from `#[ibuilder(min = N)]`.
+    pub min: Option<TokenStream>,
+    /// The inclusive upper bound of the value of a numeric field, from `#[ibuilder(max = N)]`.
+    pub max: Option<TokenStream>,
+    /// The paths to the functions applied to the text inserted by the user, in order, from
+    /// `#[ibuilder(preprocess = "...")]`, `#[ibuilder(trim)]` and `#[ibuilder(lowercase)]`.
+    pub preprocessors: Vec<syn::Path>,
+    /// The characters accepted in the text inserted by the user, from
+    /// `#[ibuilder(charset = "a-z0-9")]`.
+    pub charset: Option<syn::LitStr>,
+    /// The regular expression the text of a `String` field must match, from
+    /// `#[ibuilder(pattern = "...")]`.
+    pub pattern: Option<syn::LitStr>,
+    /// The error shown when the text doesn't match the pattern, from
+    /// `#[ibuilder(pattern_message = "...")]`.
+    pub pattern_message: Option<String>,
+    /// The path to the function that suggests the values of the text inputs of this field, from
+    /// `#[ibuilder(suggest_fn = "path::to::fn")]`.
+    pub suggest_fn: Option<syn::Path>,
+    /// The path to the function that lists the values the user can choose for this field, from
+    /// `#[ibuilder(options_from = "path::to::fn")]`.
+    pub options_from: Option<syn::Path>,
+    /// The answers offered as choices next to the text input of a `String` field, from
+    /// `#[ibuilder(quick = "yes,no")]`.
+    pub quick: Vec<String>,
+    /// The values the user can choose for a `String` field, from
+    /// `#[ibuilder(choices("dev", "prod"))]`.
+    pub choices: Vec<String>,
+    /// Whether the user can also type a value not in `choices`, from `#[ibuilder(allow_other)]`.
+    pub allow_other: bool,
+    /// Whether the value of this field is secret, from `#[ibuilder(secret)]`.
+    pub secret: bool,
+    /// Whether the value parsed from the text of the user is shown for confirmation before saving
+    /// it, from `#[ibuilder(preview)]`.
+    pub preview: bool,
+    /// The number of invalid texts in a row after which the field falls back to its default, from
+    /// `#[ibuilder(max_attempts = N, fallback_default)]`.
+    pub max_attempts: Option<usize>,
+    /// Whether `#[ibuilder(fallback_default)]` is present.
+    pub fallback_default: bool,
+    /// The names of the fields cleared when the value of this field changes, from
+    /// `#[ibuilder(invalidates = "other_field")]`.
+    pub invalidates: Vec<syn::LitStr>,
+    /// Whether the user should be moved into this field before showing the menu of the struct.
+    pub ask_first: bool,
+    /// The rules for choosing the field to ask after this one in wizard mode.
+    pub next_if: Vec<NextIf>,
+    /// Whether the field is a port number, from `#[ibuilder(port)]`.
+    pub port: bool,
+    /// The encoding of the binary data of a `Vec<u8>` field (e.g. `Base64`), from
+    /// `#[ibuilder(encoding = "base64")]`.
+    pub encoding: Option<Ident>,
+    /// The custom builder of this field, from `#[ibuilder(builder = "path::to::fn")]` or
+    /// `#[ibuilder(with = "MyBuilder")]`.
+    pub builder: Option<CustomBuilder>,
+}
+
+/// How the custom builder of a field is made.
+#[derive(Debug)]
+pub enum CustomBuilder {
+    /// Calling the function, an `ibuilder::BuilderFn`, from `#[ibuilder(builder = "...")]`.
+    Fn(syn::Path),
+    /// Calling `new(config)` of the type, from `#[ibuilder(with = "...")]`.
+    Type(syn::Path),
+}
+
+/// A rule for branching the flow of a wizard, taken from `#[ibuilder(next_if(...))]`.
+#[derive(Debug)]
+pub struct NextIf {
+    /// The path to the function that tells if the rule applies.
+    pub cond: syn::Path,
+    /// The name of the field to jump to.
+    pub goto: syn::LitStr,
+}
+
+impl From<&syn::DeriveInput> for StructMetadata {
+    fn from(data: &syn::DeriveInput) -> StructMetadata {
+        let mut metadata = StructMetadata {
+            prompt: None,
+            prompt_key: None,
+            rename: None,
+            actions: vec![],
+            constructor: None,
+            wizard: false,
+            draft: None,
+            vis: None,
+            message_key: None,
+        };
+        for attr in &data.attrs {
+            if attr.path.is_ident("ibuilder") {
+                let meta = attr
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .unwrap_or_abort();
+                for meta in meta {
+                    parse_struct_meta(meta, &mut metadata);
+                }
+            }
+        }
+        metadata
+    }
+}
+
+/// Extract the `StructMetadata` from a `Meta` entry in an attribute. `meta` comes from
+/// `#[ibuilder(HERE)]`.
+fn parse_struct_meta(meta: Meta, metadata: &mut StructMetadata) {
+    match meta {
+        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
+            if path.is_ident("prompt") {
+                parse_string_meta(&mut metadata.prompt, lit);
+            } else if path.is_ident("prompt_key") {
+                parse_string_meta(&mut metadata.prompt_key, lit);
+            } else if path.is_ident("rename") {
+                parse_string_meta(&mut metadata.rename, lit);
+            } else if path.is_ident("constructor") {
+                if metadata.constructor.is_some() {
+                    abort!(lit, "duplicated attribute");
+                }
+                metadata.constructor = Some(parse_path_meta(lit));
+            } else if path.is_ident("mode") {
+                let mut mode = None;
+                parse_string_meta(&mut mode, lit.clone());
+                match mode.as_deref() {
+                    Some("menu") => metadata.wizard = false,
+                    Some("wizard") => metadata.wizard = true,
+                    _ => abort!(lit, "unknown mode, expecting \"menu\" or \"wizard\""),
+                }
+            } else if path.is_ident("vis") {
+                parse_vis_meta(&mut metadata.vis, lit);
+            } else if path.is_ident("message_key") {
+                parse_string_meta(&mut metadata.message_key, lit);
+            } else {
+                abort!(path, "unknown attribute");
+            }
+        }
+        Meta::Path(path) if path.is_ident("draft") => {
+            if metadata.draft.is_some() {
+                abort!(path, "duplicated attribute");
+            }
+            metadata.draft = Some(vec![]);
+        }
+        Meta::List(list) if list.path.is_ident("draft") => {
+            if metadata.draft.is_some() {
+                abort!(list, "duplicated attribute");
+            }
+            metadata.draft = Some(parse_draft_meta(&list));
+        }
+        Meta::List(list) if list.path.is_ident("action") => {
+            let action = StructAction::from(&list);
+            if metadata.actions.iter().any(|a| a.id == action.id) {
+                abort!(list, "duplicated action id");
+            }
+            metadata.actions.push(action);
+        }
+        _ => abort!(meta, "unknown attribute"),
+    }
+}
+
+/// Extract the traits to derive on the draft struct from `#[ibuilder(draft(derive(...)))]`.
+fn parse_draft_meta(list: &MetaList) -> Vec<syn::Path> {
+    let mut derives = vec![];
+    for nested in &list.nested {
+        match nested {
+            NestedMeta::Meta(Meta::List(derive)) if derive.path.is_ident("derive") => {
+                for path in &derive.nested {
+                    match path {
+                        NestedMeta::Meta(Meta::Path(path)) => derives.push(path.clone()),
+                        _ => abort!(path, "expecting the path of a trait"),
+                    }
+                }
+            }
+            _ => abort!(nested, "unknown attribute"),
+        }
+    }
+    derives
+}
+
+impl From<&MetaList> for StructAction {
+    fn from(list: &MetaList) -> StructAction {
+        let mut id = None;
+        let mut text = None;
+        let mut handler = None;
+        for nested in &list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                    if path.is_ident("id") {
+                        parse_string_meta(&mut id, lit.clone());
+                    } else if path.is_ident("text") {
+                        parse_string_meta(&mut text, lit.clone());
+                    } else if path.is_ident("handler") {
+                        parse_string_meta(&mut handler, lit.clone());
+                    } else {
+                        abort!(path, "unknown attribute");
+                    }
+                }
+                _ => abort!(nested, "unknown attribute"),
+            }
+        }
+        let id = id.unwrap_or_else(|| abort!(list, "missing action id"));
+        let text = text.unwrap_or_else(|| id.clone());
+        let handler = handler.unwrap_or_else(|| abort!(list, "missing action handler"));
+        let handler = syn::parse_str(&handler)
+            .unwrap_or_else(|_| abort!(list, "the handler must be a path to a function"));
+        StructAction { id, text, handler }
+    }
+}
+
+impl From<&Field> for FieldMetadata {
+    /// Extract the `FieldMetadata` from the attribute list of a field.
+    fn from(field: &Field) -> FieldMetadata {
+        let mut metadata = FieldMetadata {
+            default: None,
+            default_none: false,
+            prompt: None,
+            prompt_key: None,
+            rename: None,
+            help: None,
+            hidden: false,
+            validators: vec![],
+            min: None,
+            max: None,
+            preprocessors: vec![],
+            charset: None,
+            pattern: None,
+            pattern_message: None,
+            suggest_fn: None,
+            options_from: None,
+            quick: vec![],
+            choices: vec![],
+            allow_other: false,
+            secret: false,
+            preview: false,
+            max_attempts: None,
+            fallback_default: false,
+            invalidates: vec![],
+            ask_first: false,
+            next_if: vec![],
+            port: false,
+            encoding: None,
+            builder: None,
+        };
+        for attr in &field.attrs {
+            if attr.path.is_ident("ibuilder") {
+                let meta = attr
+                    .parse_args_with(Punctuated::<Meta, Token![,]>::parse_terminated)
+                    .unwrap_or_abort();
+                for meta in meta {
+                    parse_field_meta(meta, &mut metadata, &field.ty);
+                }
+            }
+        }
+        if metadata.hidden && field.ident.is_none() {
+            abort!(field, "unnamed fields cannot be hidden");
+        }
+        // the markers without data have nothing to ask, they are built with `Default`
+        if is_zero_sized(&field.ty) {
+            metadata.hidden = true;
+        }
+        if metadata.ask_first && field.ident.is_none() {
+            abort!(field, "unnamed fields cannot be asked first");
+        }
+        if metadata.ask_first && metadata.hidden {
+            abort!(field, "hidden fields cannot be asked first");
+        }
+        if metadata.suggest_fn.is_some() && (metadata.hidden || field.ident.is_none()) {
+            abort!(
+                field,
+                "suggest_fn is supported only on visible named fields"
+            );
+        }
+        if metadata.options_from.is_some() && (metadata.hidden || field.ident.is_none()) {
+            abort!(
+                field,
+                "options_from is supported only on visible named fields"
+            );
+        }
+        if !metadata.quick.is_empty() && (metadata.hidden || field.ident.is_none()) {
+            abort!(field, "quick is supported only on visible named fields");
+        }
+        if !metadata.quick.is_empty() && metadata.options_from.is_some() {
+            abort!(field, "quick cannot be used together with options_from");
+        }
+        if !metadata.choices.is_empty() && (metadata.hidden || field.ident.is_none()) {
+            abort!(field, "choices is supported only on visible named fields");
+        }
+        if !metadata.choices.is_empty()
+            && (metadata.options_from.is_some() || !metadata.quick.is_empty())
+        {
+            abort!(
+                field,
+                "choices cannot be used together with options_from or quick"
+            );
+        }
+        if metadata.pattern_message.is_some() && metadata.pattern.is_none() {
+            abort!(field, "pattern_message requires pattern");
+        }
+        if metadata.allow_other && metadata.choices.is_empty() {
+            abort!(field, "allow_other requires choices");
+        }
+        if !metadata.invalidates.is_empty() && (metadata.hidden || field.ident.is_none()) {
+            abort!(
+                field,
+                "invalidates is supported only on visible named fields"
+            );
+        }
+        if (metadata.min.is_some() || metadata.max.is_some()) && metadata.hidden {
+            abort!(field, "hidden fields cannot have a range");
+        }
+        if metadata.secret && metadata.hidden {
+            abort!(field, "hidden fields cannot be secret");
+        }
+        if metadata.preview && metadata.hidden {
+            abort!(field, "hidden fields cannot be previewed");
+        }
+        if metadata.max_attempts.is_some() != metadata.fallback_default {
+            abort!(
+                field,
+                "max_attempts and fallback_default must be used together"
+            );
+        }
+        if metadata.max_attempts.is_some() && metadata.hidden {
+            abort!(field, "hidden fields cannot fall back to the default");
+        }
+        if metadata.builder.is_some() && (metadata.hidden || metadata.encoding.is_some()) {
+            abort!(
+                field,
+                "the builder of hidden or encoded fields cannot be customized"
+            );
+        }
+        if metadata.port && metadata.prompt.is_none() {
+            metadata.prompt = Some("Type the port number".into());
+        }
+        metadata
+    }
+}
+
+/// Whether the type is a well-known zero-sized marker: `PhantomData<T>`, `PhantomPinned` or `()`.
+fn is_zero_sized(ty: &Type) -> bool {
+    match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|s| s.ident == "PhantomData" || s.ident == "PhantomPinned")
+            .unwrap_or(false),
+        Type::Tuple(tuple) => tuple.elems.is_empty(),
+        Type::Paren(inner) => is_zero_sized(&inner.elem),
+        _ => false,
+    }
+}
+
+/// Whether the type is `f32` or `f64`.
+fn is_float(ty: &Type) -> bool {
+    matches!(ty, Type::Path(path) if path.path.is_ident("f32") || path.path.is_ident("f64"))
+}
+
+/// Extract the `FieldMetadata` from a `Meta` entry in a field attribute. `meta` comes from
+/// `#[ibuilder(HERE)]`.
+fn parse_field_meta(meta: Meta, metadata: &mut FieldMetadata, ty: &Type) {
+    match meta {
+        Meta::NameValue(MetaNameValue { path, lit, .. }) => {
+            if path.is_ident("default") {
+                if metadata.default.is_none() {
+                    // the default of an optional field is the value inside the `Some`
+                    let ty = option_inner(ty).unwrap_or(ty);
+                    match lit {
+                        syn::Lit::Str(_) => {
+                            metadata.default =
+                                Some(quote! { <#ty as std::str::FromStr>::from_str(#lit).unwrap() })
+                        }
+                        _ => metadata.default = Some(quote! { #lit }),
+                    }
+                } else {
+                    abort!(path, "duplicated default");
+                }
+            } else if path.is_ident("prompt") {
+                parse_string_meta(&mut metadata.prompt, lit);
+            } else if path.is_ident("prompt_key") {
+                parse_string_meta(&mut metadata.prompt_key, lit);
+            } else if path.is_ident("rename") {
+                parse_string_meta(&mut metadata.rename, lit);
+            } else if path.is_ident("help") {
+                parse_string_meta(&mut metadata.help, lit);
+            } else if path.is_ident("validate") {
+                metadata.validators.push(parse_path_meta(lit));
+            } else if path.is_ident("min") || path.is_ident("max") {
+                let bound = match lit {
+                    // `min = 0` on a float field, the literal would be inferred as an integer
+                    syn::Lit::Int(int) if int.suffix().is_empty() && is_float(ty) => {
+                        let float =
+                            syn::LitFloat::new(&format!("{}.0", int.base10_digits()), int.span());
+                        quote! { #float }
+                    }
+                    syn::Lit::Int(_) | syn::Lit::Float(_) => quote! { #lit },
+                    // negative numbers are not literals, they can be written as strings
+                    syn::Lit::Str(_) => {
+                        quote! { <#ty as std::str::FromStr>::from_str(#lit).unwrap() }
+                    }
+                    _ => abort!(lit, "expecting a number"),
+                };
+                let target = if path.is_ident("min") {
+                    &mut metadata.min
+                } else {
+                    &mut metadata.max
+                };
+                if target.is_some() {
+                    abort!(path, "duplicated attribute");
+                }
+                *target = Some(bound);
+            } else if path.is_ident("preprocess") {
+                metadata.preprocessors.push(parse_path_meta(lit));
+            } else if path.is_ident("charset") {
+                if metadata.charset.is_some() {
+                    abort!(path, "duplicated attribute");
+                }
+                match lit {
+                    syn::Lit::Str(charset) => {
+                        check_charset(&charset);
+                        metadata.charset = Some(charset);
+                    }
+                    _ => abort!(lit, "expecting a string"),
+                }
+            } else if path.is_ident("pattern") {
+                check_plain_type(&path, ty, "pattern", "String");
+                if metadata.pattern.is_some() {
+                    abort!(path, "duplicated attribute");
+                }
+                match lit {
+                    syn::Lit::Str(pattern) => {
+                        check_pattern(&pattern);
+                        metadata.pattern = Some(pattern);
+                    }
+                    _ => abort!(lit, "expecting a string"),
+                }
+            } else if path.is_ident("pattern_message") {
+                parse_string_meta(&mut metadata.pattern_message, lit);
+            } else if path.is_ident("suggest_fn") {
+                if metadata.suggest_fn.is_some() {
+                    abort!(path, "duplicated attribute");
+                }
+                metadata.suggest_fn = Some(parse_path_meta(lit));
+            } else if path.is_ident("options_from") {
+                if metadata.options_from.is_some() {
+                    abort!(path, "duplicated attribute");
+                }
+                if quote!(#ty).to_string() != "String" {
+                    abort!(path, "options_from is supported only on String fields");
+                }
+                metadata.options_from = Some(parse_path_meta(lit));
+            } else if path.is_ident("quick") {
+                if !metadata.quick.is_empty() {
+                    abort!(path, "duplicated attribute");
+                }
+                if quote!(#ty).to_string() != "String" {
+                    abort!(path, "quick is supported only on String fields");
+                }
+                match lit {
+                    syn::Lit::Str(quick) => {
+                        metadata.quick = quick
+                            .value()
+                            .split(',')
+                            .map(|answer| answer.trim().to_string())
+                            .filter(|answer| !answer.is_empty())
+                            .collect();
+                        if metadata.quick.is_empty() {
+                            abort!(quick, "empty list of quick replies");
+                        }
+                    }
+                    _ => abort!(lit, "expecting a string"),
+                }
+            } else if path.is_ident("max_attempts") {
+                if metadata.max_attempts.is_some() {
+                    abort!(path, "duplicated attribute");
+                }
+                let max_attempts = match &lit {
+                    syn::Lit::Int(value) => value.base10_parse::<usize>().ok(),
+                    _ => None,
+                };
+                match max_attempts {
+                    Some(max_attempts) if max_attempts > 0 => {
+                        metadata.max_attempts = Some(max_attempts)
+                    }
+                    _ => abort!(lit, "expecting a positive integer"),
+                }
+            } else if path.is_ident("invalidates") {
+                match lit {
+                    syn::Lit::Str(field) => metadata.invalidates.push(field),
+                    _ => abort!(lit, "expecting a string"),
+                }
+            } else if path.is_ident("builder") || path.is_ident("with") {
+                match (&metadata.builder, path.is_ident("builder")) {
+                    (Some(CustomBuilder::Fn(_)), true) | (Some(CustomBuilder::Type(_)), false) => {
+                        abort!(path, "duplicated attribute")
+                    }
+                    (Some(_), _) => abort!(path, "builder and with cannot be used together"),
+                    (None, true) => {
+                        metadata.builder = Some(CustomBuilder::Fn(parse_path_meta(lit)))
+                    }
+                    (None, false) => {
+                        metadata.builder = Some(CustomBuilder::Type(parse_path_meta(lit)))
+                    }
+                }
+            } else if path.is_ident("encoding") {
+                if quote!(#ty).to_string().replace(' ', "") != "Vec<u8>" {
+                    abort!(path, "encoding is supported only on Vec<u8> fields");
+                }
+                if metadata.encoding.is_some() {
+                    abort!(path, "duplicated attribute");
+                }
+                let encoding = match &lit {
+                    syn::Lit::Str(s) if s.value() == "base64" => "Base64",
+                    syn::Lit::Str(s) if s.value() == "hex" => "Hex",
+                    _ => abort!(lit, "unknown encoding, expecting \"base64\" or \"hex\""),
+                };
+                metadata.encoding = Some(Ident::new(encoding, lit.span()));
+            } else if path.is_ident("hostname") {
+                check_plain_type(&path, ty, "hostname", "String");
+                match &lit {
+                    syn::Lit::Str(s) if s.value() == "resolve" => {}
+                    _ => abort!(lit, "expecting \"resolve\""),
+                }
+                metadata
+                    .validators
+                    .push(syn::parse_quote! { ibuilder::validators::hostname });
+                metadata
+                    .validators
+                    .push(syn::parse_quote! { ibuilder::validators::resolvable_hostname });
+            } else {
+                abort!(path, "unknown attribute");
+            }
+        }
+        Meta::Path(path) => {
+            if path.is_ident("hidden") {
+                if metadata.hidden {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.hidden = true;
+            } else if path.is_ident("default_none") {
+                if option_inner(ty).is_none() {
+                    abort!(path, "default_none is supported only on Option fields");
+                }
+                if metadata.default_none {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.default_none = true;
+            } else if path.is_ident("ask_first") {
+                if metadata.ask_first {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.ask_first = true;
+            } else if path.is_ident("hostname") {
+                check_plain_type(&path, ty, "hostname", "String");
+                metadata
+                    .validators
+                    .push(syn::parse_quote! { ibuilder::validators::hostname });
+            } else if path.is_ident("port") {
+                parse_port_meta(&path, &[], metadata, ty);
+            } else if path.is_ident("preview") {
+                if metadata.preview {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.preview = true;
+            } else if path.is_ident("secret") {
+                if metadata.secret {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                let ty = quote!(#ty).to_string();
+                if ty != "String" && ty != "Option < String >" {
+                    abort!(
+                        path,
+                        "secret is supported only on String and Option<String> fields"
+                    );
+                }
+                metadata.secret = true;
+            } else if path.is_ident("allow_other") {
+                if metadata.allow_other {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.allow_other = true;
+            } else if path.is_ident("fallback_default") {
+                if metadata.fallback_default {
+                    emit_warning!(path, "duplicated attribute");
+                }
+                metadata.fallback_default = true;
+            } else if path.is_ident("trim") {
+                metadata
+                    .preprocessors
+                    .push(syn::parse_quote! { ibuilder::preprocessors::trim });
+            } else if path.is_ident("lowercase") {
+                metadata
+                    .preprocessors
+                    .push(syn::parse_quote! { ibuilder::preprocessors::lowercase });
+            } else {
+                abort!(path, "unknown attribute");
+            }
+        }
+        Meta::List(list) if list.path.is_ident("next_if") => {
+            metadata.next_if.push(NextIf::from(&list));
+        }
+        Meta::List(list) if list.path.is_ident("choices") => {
+            check_plain_type(&list.path, ty, "choices", "String");
+            if !metadata.choices.is_empty() {
+                abort!(list, "duplicated attribute");
+            }
+            for nested in &list.nested {
+                match nested {
+                    NestedMeta::Lit(syn::Lit::Str(value)) => {
+                        if metadata.choices.contains(&value.value()) {
+                            abort!(value, "duplicated choice");
+                        }
+                        metadata.choices.push(value.value());
+                    }
+                    _ => abort!(nested, "expecting a string"),
+                }
+            }
+            if metadata.choices.is_empty() {
+                abort!(list, "empty list of choices");
+            }
+        }
+        Meta::List(list) if list.path.is_ident("port") => {
+            let options: Vec<_> = list.nested.iter().collect();
+            parse_port_meta(&list.path, &options, metadata, ty);
+        }
+        _ => abort!(meta, "unknown attribute"),
+    }
+}
+
+/// Parse `#[ibuilder(port)]` and `#[ibuilder(port(allow_zero, warn_privileged))]`.
+fn parse_port_meta(
+    path: &syn::Path,
+    options: &[&NestedMeta],
+    metadata: &mut FieldMetadata,
+    ty: &Type,
+) {
+    check_plain_type(path, ty, "port", "u16");
+    if metadata.port {
+        abort!(path, "duplicated attribute");
+    }
+    metadata.port = true;
+    let mut allow_zero = false;
+    let mut warn_privileged = false;
+    for option in options {
+        match option {
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("allow_zero") => allow_zero = true,
+            NestedMeta::Meta(Meta::Path(path)) if path.is_ident("warn_privileged") => {
+                warn_privileged = true
+            }
+            _ => abort!(option, "unknown attribute"),
+        }
+    }
+    if !allow_zero {
+        metadata
+            .validators
+            .push(syn::parse_quote! { ibuilder::validators::non_zero_port });
+    }
+    if warn_privileged {
+        metadata
+            .validators
+            .push(syn::parse_quote! { ibuilder::validators::unprivileged_port });
+    }
+}
+
+/// Abort if the type of the field is not the plain type required by the attribute.
+fn check_plain_type(path: &syn::Path, ty: &Type, attribute: &str, expected: &str) {
+    let is_expected = match ty {
+        Type::Path(ty) => ty.qself.is_none() && ty.path.is_ident(expected),
+        _ => false,
+    };
+    if !is_expected {
+        abort!(
+            path,
+            "{} is supported only on {} fields",
+            attribute,
+            expected
+        );
+    }
+}
+
+/// Check that the set of `#[ibuilder(charset = "...")]` is valid, with the same rules of
+/// `ibuilder::charset::Charset::parse`.
+/// Check that the pattern is a valid regular expression, with the same syntax of the regex crate,
+/// so that the compiled `Regex` is never invalid at runtime.
+#[cfg(feature = "regex")]
+fn check_pattern(pattern: &syn::LitStr) {
+    if let Err(e) = regex_syntax::Parser::new().parse(&pattern.value()) {
+        abort!(pattern, "invalid pattern: {}", e);
+    }
+}
+
+/// Without the `regex` feature the pattern is checked only when the builder is made.
+#[cfg(not(feature = "regex"))]
+fn check_pattern(_pattern: &syn::LitStr) {}
+
+fn check_charset(charset: &syn::LitStr) {
+    let chars: Vec<char> = charset.value().chars().collect();
+    if chars.is_empty() {
+        abort!(charset, "empty charset");
+    }
+    let mut i = 0;
+    while i < chars.len() {
+        if i + 2 < chars.len() && chars[i + 1] == '-' {
+            if chars[i] > chars[i + 2] {
+                abort!(charset, "invalid range {}-{}", chars[i], chars[i + 2]);
+            }
+            i += 3;
+        } else {
+            i += 1;
+        }
+    }
+}
+
+impl From<&MetaList> for NextIf {
+    fn from(list: &MetaList) -> NextIf {
+        let mut cond = None;
+        let mut goto = None;
+        for nested in &list.nested {
+            match nested {
+                NestedMeta::Meta(Meta::NameValue(MetaNameValue { path, lit, .. })) => {
+                    if path.is_ident("cond") {
+                        if cond.is_some() {
+                            abort!(lit, "duplicated attribute");
+                        }
+                        cond = Some(parse_path_meta(lit.clone()));
+                    } else if path.is_ident("goto") {
+                        if goto.is_some() {
+                            abort!(lit, "duplicated attribute");
+                        }
+                        match lit {
+                            syn::Lit::Str(lit) => goto = Some(lit.clone()),
+                            _ => abort!(lit, "expecting a string"),
+                        }
+                    } else {
+                        abort!(path, "unknown attribute");
+                    }
+                }
+                _ => abort!(nested, "unknown attribute"),
+            }
+        }
+        NextIf {
+            cond: cond.unwrap_or_else(|| abort!(list, "missing next_if cond")),
+            goto: goto.unwrap_or_else(|| abort!(list, "missing next_if goto")),
+        }
+    }
+}
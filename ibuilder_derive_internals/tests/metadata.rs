@@ -0,0 +1,89 @@
+use ibuilder_derive_internals::__private::*;
+use quote::quote;
+use syn::{parse_quote, DeriveInput};
+
+#[test]
+fn test_struct_metadata() {
+    let ast: DeriveInput = parse_quote! {
+        #[ibuilder(prompt = "Edit the server", mode = "wizard", vis = "pub(crate)")]
+        #[ibuilder(action(id = "test", handler = "test_connection"))]
+        struct Server {
+            #[ibuilder(rename = "host name", hostname)]
+            host: String,
+            #[ibuilder(port, default = 8080)]
+            port: u16,
+            #[serde(skip)]
+            #[ibuilder(hidden)]
+            secret: String,
+        }
+    };
+    let metadata = StructMetadata::from(&ast);
+    assert_eq!(metadata.prompt.as_deref(), Some("Edit the server"));
+    assert!(metadata.wizard);
+    let vis = metadata.vis.as_ref().map(|v| quote!(#v).to_string());
+    assert_eq!(vis, Some(quote!(pub(crate)).to_string()));
+    assert_eq!(metadata.actions.len(), 1);
+    assert_eq!(metadata.actions[0].id, "test");
+    assert_eq!(metadata.actions[0].text, "test");
+
+    let fields = match &ast.data {
+        syn::Data::Struct(data) => data
+            .fields
+            .iter()
+            .map(FieldMetadata::from)
+            .collect::<Vec<_>>(),
+        _ => unreachable!(),
+    };
+    assert_eq!(fields[0].rename.as_deref(), Some("host name"));
+    assert_eq!(fields[0].validators.len(), 1);
+    assert!(fields[1].port);
+    assert_eq!(fields[1].prompt.as_deref(), Some("Type the port number"));
+    assert_eq!(
+        fields[1].default.as_ref().map(|d| d.to_string()),
+        Some(quote!(8080).to_string())
+    );
+    assert!(fields[2].hidden);
+    assert_eq!(
+        builder_ident(&ast.ident).to_string(),
+        "__Server_BuildableValueImpl"
+    );
+}
+
+#[test]
+fn test_enum_metadata() {
+    let ast: DeriveInput = parse_quote! {
+        #[ibuilder(prompt = "Pick a shape")]
+        enum Shape {
+            #[ibuilder(default, value = 1)]
+            Empty,
+            #[ibuilder(rename = "circle", prompt = "Radius?")]
+            Circle(i32),
+            #[ibuilder(hidden)]
+            Unknown,
+        }
+    };
+    let metadata = EnumMetadata::from(&ast);
+    assert_eq!(metadata.prompt.as_deref(), Some("Pick a shape"));
+
+    let variants = match &ast.data {
+        syn::Data::Enum(data) => data
+            .variants
+            .iter()
+            .map(VariantMetadata::from)
+            .collect::<Vec<_>>(),
+        _ => unreachable!(),
+    };
+    assert!(variants[0].default);
+    assert_eq!(variants[0].value.as_deref(), Some("1"));
+    assert_eq!(variants[1].rename.as_deref(), Some("circle"));
+    assert_eq!(variants[1].prompt.as_deref(), Some("Radius?"));
+    assert!(variants[2].hidden);
+}
+
+#[test]
+fn test_snake_case() {
+    assert_eq!(snake_case("Person"), "person");
+    assert_eq!(snake_case("HttpServer"), "http_server");
+    assert_eq!(snake_case("HTTPServer"), "http_server");
+    assert_eq!(snake_case("Ipv4Addr"), "ipv4_addr");
+}
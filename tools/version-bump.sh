@@ -9,8 +9,12 @@ fi
 
 version="$1"
 
+echo "Updating version of ibuilder_derive_internals to $version"
+sed -i "s/^version =.*/version = \"${version}\"/" "ibuilder_derive_internals/Cargo.toml"
+
 echo "Updating version of ibuilder_derive to $version"
 sed -i "s/^version =.*/version = \"${version}\"/" "ibuilder_derive/Cargo.toml"
+sed -Ei "s/(ibuilder_derive_internals.+version = )\"=[^\"]+\"(.*)/\\1\"=${version}\"\\2/" "ibuilder_derive/Cargo.toml"
 
 echo "Updating version of ibuilder to $version"
 sed -i "s/^version =.*/version = \"${version}\"/" "ibuilder/Cargo.toml"
@@ -20,28 +24,35 @@ echo "Running cargo test"
 cargo test --all
 
 echo "Committing changes"
-git add ibuilder/Cargo.toml ibuilder_derive/Cargo.toml
+git add ibuilder/Cargo.toml ibuilder_derive/Cargo.toml ibuilder_derive_internals/Cargo.toml
 git commit -m "Version v${version}"
 
+wait_crates_io() {
+  echo "Waiting a bit for crates.io"
+  found=no
+  for i in {1..20}; do
+    echo "Attempt $i"
+    actual=$(cargo search "$1" | grep "^$1 " | cut -d'"' -f 2)
+    echo "crates.io reports version ${actual}"
+    if [[ $actual == "${version}" ]]; then
+      found=yes
+      break
+    fi
+    echo "attempting again in 5s"
+    sleep 5s
+  done
+  if [[ $found == no ]]; then
+    echo "crates.io hasn't updated the version :c"
+  fi
+}
+
+echo "Publishing ibuilder_derive_internals"
+(cd ibuilder_derive_internals && cargo publish)
+wait_crates_io ibuilder_derive_internals
+
 echo "Publishing ibuilder_derive"
 (cd ibuilder_derive && cargo publish)
-
-echo "Waiting a bit for crates.io"
-found=no
-for i in {1..20}; do
-  echo "Attempt $i"
-  actual=$(cargo search ibuilder_derive | head -n 1 | cut -d'"' -f 2)
-  echo "crates.io reports version ${actual}"
-  if [[ $actual == "${version}" ]]; then
-    found=yes
-    break
-  fi
-  echo "attempting again in 5s"
-  sleep 5s
-done
-if [[ $found == no ]]; then
-  echo "crates.io hasn't updated the version :c"
-fi
+wait_crates_io ibuilder_derive
 
 echo "Publishing ibuilder"
 (cd ibuilder && cargo publish)